@@ -39,6 +39,17 @@ cat > "$APP_BUNDLE/Contents/Info.plist" <<EOF
     <string>10.14</string>
     <key>NSHighResolutionCapable</key>
     <true/>
+    <key>CFBundleURLTypes</key>
+    <array>
+        <dict>
+            <key>CFBundleURLName</key>
+            <string>com.z1529.nativehub</string>
+            <key>CFBundleURLSchemes</key>
+            <array>
+                <string>nativehub</string>
+            </array>
+        </dict>
+    </array>
 </dict>
 </plist>
 EOF
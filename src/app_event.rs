@@ -1,69 +1,513 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 // Re-export types for convenience
 pub use crate::engine::api_client::FileNode;
 pub use crate::engine::api_client::RepoInfo;
 pub use crate::engine::api_client::SearchRepoItem;
+pub use crate::engine::api_client::Tag;
+pub use crate::engine::api_client::Branch;
+pub use crate::engine::api_client::WatchState;
+pub use crate::engine::api_client::Notification;
 pub use crate::engine::api_client::Issue;
 pub use crate::engine::api_client::IssueComment;
 pub use crate::engine::api_client::IssueLabel;
+pub use crate::engine::api_client::Milestone;
 pub use crate::engine::api_client::PullRequest;
 pub use crate::engine::api_client::MergeResult;
+pub use crate::engine::api_client::CheckRun;
+pub use crate::engine::api_client::Commit;
+pub use crate::engine::api_client::CommitDetail;
+pub use crate::engine::api_client::ChangedFile;
+pub use crate::engine::api_client::CombinedStatus;
+pub use crate::engine::api_client::Discussion;
+pub use crate::engine::api_client::DiscussionCategory;
+pub use crate::engine::api_client::DiscussionThread;
 
 /// Actions sent from the UI to the Backend
 #[derive(Debug, Clone)]
 pub enum AppAction {
     Login,
-    Cancel,
+    Cancel,                   // Cancel every tracked operation
+    CancelOperation(Operation), // Cancel one kind of in-flight operation
     FetchRepos,
-    SelectRepo(String),      // Repo name/full_name to browse
-    FetchDir(String, String), // (full_name, path) - fetch directory contents
-    ReadFile(String),         // (download_url) - fetch file content
+    FetchOrgs,                // Organizations of the authenticated user
+    FetchStarred,             // Repos starred by the authenticated user
+    FetchOrgRepos(String),    // (org) - repos owned by an organization
+    SelectRepo(String),      // Repo name/full_name to browse (also starts polling it)
+    StopPolling,             // Stop refreshing the repo left by the user
+    SetPollInterval(u64),    // Seconds between background refreshes, 0 = off
+    SetEngine(Option<crate::engine::EngineKind>), // None = probe for gh again
+    FetchDir(String, String, String), // (full_name, path, ref) - fetch directory contents ("" ref = default branch)
+    FetchTreeDir(String, String, String), // (full_name, path, ref) - children of a folder expanded in the tree
+    PrefetchTreeDir(String, String, String), // (full_name, path, ref) - children of a folder the pointer rests on
+    FetchTags(String),        // (full_name) - list tags for the ref selector
+    FetchBranches(String),    // (full_name) - list branches
+    FetchLanguages(String),   // (full_name) - language byte counts
+    FetchTopics(String),      // (full_name) - current topic list
+    SetTopics(String, Vec<String>), // (full_name, topics) - replace all topics
+    CheckStarred(String),     // (full_name) - query star state
+    SetStarred(String, bool), // (full_name, starred) - star or unstar
+    ForkRepo(String),         // (full_name) - fork into my account
+    DownloadZipball(String, String, String), // (full_name, ref ("" = default branch), target directory)
+    CloneRepo(String, String, String, CloneTool), // (full_name, clone URL, parent directory, tool)
+    RunLocalGit(String, String, LocalGitOp), // (full_name, checkout directory, operation)
+    SaveFile(String, FileNode, String), // (full_name, file, target path) - write the raw bytes to disk
+    CreateRepo(String, String, bool, bool), // (name, description, private, init with README)
+    SetArchived(String, bool), // (full_name, archived) - archive or unarchive
+    DeleteRepo(String),       // (full_name) - permanently delete
+    CheckWatchState(String),  // (full_name) - query subscription
+    SetWatchState(String, WatchState), // (full_name, state) - change subscription
+    ReadFile(String, FileNode), // (full_name, file) - fetch file content
+    ReadFileMore(String, FileNode, u64), // (full_name, file, byte offset) - next part of a file opened in parts
     SearchRepos(String),      // Search query
+    FetchTrending(TrendingRange, String), // (period, language filter ("" = any)) - most starred new repos
     
     // Issue actions
-    FetchIssues(String, String),                    // (full_name, state: "open"/"closed"/"all")
+    FetchIssues(RequestId, String, String, String, Vec<String>), // (request id, full_name, state: "open"/"closed"/"all", milestone filter, label names)
+    FetchIssue(String, u32),                        // (full_name, issue_number) - open a referenced issue
     FetchIssueComments(String, u32),                // (full_name, issue_number)
+    PrefetchComments(String, u32),                  // (full_name, issue_number) - warm the cache for a hovered issue
     CreateComment(String, u32, String),             // (full_name, issue_number, body)
+    UploadAttachment(RequestId, String, PathBuf),   // (upload id, file name, local path) - host a file for a comment
     UpdateIssueState(String, u32, String),          // (full_name, issue_number, state)
+    FetchMilestones(String),                        // (full_name)
+    FetchMentionCandidates(String),                 // (full_name) - collaborators and recent issues for @/# completion
+    SetIssueMilestone(String, u32, Option<u32>),    // (full_name, issue_number, milestone number)
+    BulkIssues(String, Vec<u32>, BulkIssueOp),      // (full_name, issue numbers, operation) - one at a time, cancellable
+    
+    // Label actions
+    FetchLabels(RequestId, String),                 // (request id, full_name)
+    CreateLabel(String, IssueLabel),                // (full_name, label)
+    UpdateLabel(String, String, IssueLabel),        // (full_name, current name, new values)
+    DeleteLabel(String, String),                    // (full_name, name)
     
     // Pull Request actions
-    FetchPullRequests(String, String),              // (full_name, state: "open"/"closed"/"all")
+    FetchPullRequests(RequestId, String, String),   // (request id, full_name, state: "open"/"closed"/"all")
     MergePullRequest(String, u32, String),          // (full_name, pr_number, merge_method)
     ClosePullRequest(String, u32),                  // (full_name, pr_number)
+    CreatePullRequest(String, String, String, String, String), // (full_name, title, body, head, base)
+    MarkPullRequestReady(String, u32, String),      // (full_name, pr_number, node_id)
+    FetchPullRequest(String, u32),                  // (full_name, pr_number) - full detail incl. mergeability
+    UpdatePullRequestBranch(String, u32, String),   // (full_name, pr_number, current head sha) - merge base into head
+    FetchPullRequestChecks(String, String),         // (full_name, head_sha)
+    FetchPullRequestFiles(String, u32),             // (full_name, pr_number)
+    
+    // Commit actions
+    FetchCommits(RequestId, String, String, u32),   // (request id, full_name, branch ("" = default), page)
+    FetchCommitDetail(String, String),              // (full_name, sha)
+    
+    // Discussion actions
+    FetchDiscussionCategories(String),              // (full_name)
+    FetchDiscussions(RequestId, String, Option<String>), // (request id, full_name, category id (None = all))
+    FetchDiscussion(String, u32),                   // (full_name, discussion number)
 }
 
-#[derive(Debug, Clone)]
+/// Identifies a list request so its response can be matched to it.
+/// Panels remember the id of their latest request and drop any other response.
+pub type RequestId = u64;
+
+/// A fresh, process-unique request id
+pub fn next_request_id() -> RequestId {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Error text translated when it is shown: the `key` message with `args`
+/// filled in, followed by the underlying cause as reported (GitHub's own
+/// message, an OS error), which stays untranslated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorMessage {
+    pub key: &'static str,
+    pub args: Vec<String>,
+    pub cause: Option<String>,
+}
+
+impl ErrorMessage {
+    /// The message in the UI language
+    pub fn render(&self, i18n: &crate::i18n::I18n) -> String {
+        let args: Vec<&dyn std::fmt::Display> = self.args.iter().map(|arg| arg as &dyn std::fmt::Display).collect();
+        let text = i18n.tf(self.key, &args);
+        match &self.cause {
+            Some(cause) => format!("{}: {}", text, cause),
+            None => text,
+        }
+    }
+    
+    pub fn with_cause(mut self, cause: String) -> Self {
+        self.cause = Some(cause);
+        self
+    }
+}
+
+impl From<&'static str> for ErrorMessage {
+    fn from(key: &'static str) -> Self {
+        Self { key, args: Vec::new(), cause: None }
+    }
+}
+
+impl From<(&'static str, Vec<String>)> for ErrorMessage {
+    fn from((key, args): (&'static str, Vec<String>)) -> Self {
+        Self { key, args, cause: None }
+    }
+}
+
+/// English text, for logs written outside the UI
+impl std::fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(&crate::i18n::I18n::new(crate::i18n::Lang::En)))
+    }
+}
+
+/// Structured backend error, so the UI can offer a matching recovery action
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AppError {
+    /// Missing or rejected credentials - offer to log in again
+    #[error("{0}")]
+    Auth(ErrorMessage),
+    /// Primary or secondary rate limit hit; `reset` is the unix time it lifts, if known
+    #[error("{message}")]
+    RateLimited { reset: Option<u64>, message: ErrorMessage },
+    #[error("{0}")]
+    NotFound(ErrorMessage),
+    /// Request never got a response (offline, DNS, timeout) - offer a retry
+    #[error("{0}")]
+    Network(ErrorMessage),
+    #[error("{0}")]
+    Permission(ErrorMessage),
+    /// Rejected input (bad repo name, 422 from the API)
+    #[error("{0}")]
+    Validation(ErrorMessage),
+    #[error("{0}")]
+    Other(ErrorMessage),
+}
+
+impl AppError {
+    /// Classify an engine error by its cause and HTTP status; `context` says what failed
+    pub fn from_error(context: impl Into<ErrorMessage>, err: &anyhow::Error) -> Self {
+        let context = context.into();
+        let message = context.clone().with_cause(err.to_string());
+        
+        if let Some(e) = err.chain().find_map(|e| e.downcast_ref::<reqwest::Error>())
+            && (e.is_connect() || e.is_timeout() || e.is_request())
+        {
+            return AppError::Network(message);
+        }
+        
+        // REST errors carry GitHub's own explanation; show that rather than the status line
+        if let Some(e) = err.chain().find_map(|e| e.downcast_ref::<crate::engine::api_client::GitHubError>()) {
+            let message = context.with_cause(e.summary());
+            if e.is_rate_limit() {
+                return AppError::RateLimited { reset: e.retry_at, message };
+            }
+            return match e.status.as_u16() {
+                401 => AppError::Auth(message),
+                403 | 405 => AppError::Permission(message), // 405: a rule such as branch protection refuses it
+                404 => AppError::NotFound(message),
+                409 | 422 => AppError::Validation(message),
+                429 => AppError::RateLimited { reset: None, message },
+                _ => AppError::Other(message),
+            };
+        }
+        
+        // Other engines' status codes only survive in the text, e.g. "Failed to fetch issues: 404 Not Found"
+        let text = format!("{:#}", err);
+        if text.to_lowercase().contains("rate limit") {
+            return AppError::RateLimited { reset: None, message };
+        }
+        match http_status(&text) {
+            Some(401) => AppError::Auth(message),
+            Some(403) => AppError::Permission(message),
+            Some(404) => AppError::NotFound(message),
+            Some(422) => AppError::Validation(message),
+            Some(429) => AppError::RateLimited { reset: None, message },
+            _ => AppError::Other(message),
+        }
+    }
+    
+    pub fn message(&self) -> &ErrorMessage {
+        match self {
+            AppError::Auth(message)
+            | AppError::RateLimited { message, .. }
+            | AppError::NotFound(message)
+            | AppError::Network(message)
+            | AppError::Permission(message)
+            | AppError::Validation(message)
+            | AppError::Other(message) => message,
+        }
+    }
+}
+
+/// First HTTP error status (4xx/5xx) appearing as a standalone number in `text`
+fn http_status(text: &str) -> Option<u16> {
+    let bytes = text.as_bytes();
+    (0..bytes.len().saturating_sub(2)).find_map(|i| {
+        let starts_word = i == 0 || bytes[i - 1] == b' ';
+        let ends_word = matches!(bytes.get(i + 3), None | Some(b' ') | Some(b':'));
+        if !starts_word || !ends_word || !bytes[i..i + 3].iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let code: u16 = text[i..i + 3].parse().ok()?;
+        (400..600).contains(&code).then_some(code)
+    })
+}
+
+/// Change applied to every selected issue by a bulk action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkIssueOp {
+    Close,
+    AddLabel(String),
+    SetMilestone(Option<u32>), // None clears it
+}
+
+impl BulkIssueOp {
+    /// Message key of the action, or the label / milestone it sets
+    pub fn label(&self) -> String {
+        match self {
+            BulkIssueOp::Close => "bulk.close".to_string(),
+            BulkIssueOp::AddLabel(name) => format!("🏷 {}", name),
+            BulkIssueOp::SetMilestone(Some(number)) => format!("🎯 #{}", number),
+            BulkIssueOp::SetMilestone(None) => "bulk.clear_milestone".to_string(),
+        }
+    }
+}
+
+/// How far back the trending list looks for newly created repos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrendingRange {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+impl TrendingRange {
+    /// Message key of the range's name
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrendingRange::Day => "trending.day",
+            TrendingRange::Week => "trending.week",
+            TrendingRange::Month => "trending.month",
+        }
+    }
+    
+    pub fn days(&self) -> u64 {
+        match self {
+            TrendingRange::Day => 1,
+            TrendingRange::Week => 7,
+            TrendingRange::Month => 30,
+        }
+    }
+    
+    pub fn all() -> &'static [TrendingRange] {
+        &[TrendingRange::Day, TrendingRange::Week, TrendingRange::Month]
+    }
+}
+
+/// Program used to clone a repository to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneTool {
+    Git, // `git clone <url>`
+    Gh,  // `gh repo clone <full_name>`, using the gh login and its protocol setting
+}
+
+/// Git command run in a repo's local checkout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalGitOp {
+    Pull,
+    Push,
+    Sync,   // Pull with rebase, then push
+    Status,
+}
+
+impl LocalGitOp {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LocalGitOp::Pull => "pull",
+            LocalGitOp::Push => "push",
+            LocalGitOp::Sync => "sync",
+            LocalGitOp::Status => "status",
+        }
+    }
+}
+
+/// Long-running or superseded operations the backend can cancel or report progress of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Login,
+    LoadDir,
+    ReadFile,
+    Search,
+    Download,
+    SaveFile,
+    Clone,
+    Git,
+    Upload,
+    Bulk,
+    LoadRepos,
+    LoadBranches,
+    LoadTags,
+}
+
+impl Operation {
+    /// Message key of the operation's name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Operation::Login => "op.login",
+            Operation::LoadDir => "op.load_dir",
+            Operation::ReadFile => "op.read_file",
+            Operation::Search => "op.search",
+            Operation::Download => "op.download",
+            Operation::SaveFile => "op.save_file",
+            Operation::Clone => "op.clone",
+            Operation::Git => "op.git",
+            Operation::Upload => "op.upload",
+            Operation::Bulk => "op.bulk",
+            Operation::LoadRepos => "op.load_repos",
+            Operation::LoadBranches => "op.load_branches",
+            Operation::LoadTags => "op.load_tags",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RepoData {
     pub name: String,
     pub full_name: String, // owner/repo format for API calls
     pub description: String,
     pub is_private: bool,
-    pub last_updated: String,
+    pub last_updated: String, // RFC 3339, shown relative to now
     pub stars_count: u32,
     pub forks_count: u32,
 }
 
+impl From<SearchRepoItem> for RepoData {
+    fn from(item: SearchRepoItem) -> Self {
+        Self {
+            name: item.name,
+            full_name: item.full_name,
+            description: item.description.unwrap_or_default(),
+            is_private: item.is_private,
+            last_updated: item.updated_at,
+            stars_count: item.stargazers_count,
+            forks_count: item.forks_count,
+        }
+    }
+}
+
 /// Events sent from the Backend to the UI
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     Log(String),
+    LogKey(&'static str, Vec<String>), // (message key, placeholder values) - translated by the UI
+    OpenLink(String),                // nativehub:// or github.com link from the OS or a later launch
+    Revalidating(bool),              // Cached data is on screen while a refresh runs (true) / that refresh ended (false)
+    Throttled(u64),                  // Unix time requests resume after GitHub asked to slow down (rate limit pause)
     DeviceCode(crate::modules::auth::DeviceCodeResponse),
+    BrowserAuth(String),             // Authorize URL of the web flow, opened in the browser
     AuthSuccess(String),
-    Error(String),
+    AuthExpired,                     // GitHub no longer accepts the token (expired or revoked); it has been forgotten
+    Error(AppError),
     RepoList(Vec<RepoData>),
+    OrgList(Vec<String>),            // Organization logins
+    StarredList(Vec<RepoData>),      // Repos I've starred, most recently starred first
+    RepoForked(RepoData),            // Newly created fork
+    RepoCreated(String),             // (full_name) of a newly created repo
+    RepoDeleted(String),             // (full_name) of a deleted repo
+    DownloadProgress(Operation, String, u64, Option<u64>), // (download or save, file label, bytes written, total bytes)
+    DownloadFinished(String),        // Path of the written archive or file
+    Cancelled(Operation),            // An in-flight operation was aborted
+    TokenScopesMissing(Vec<(String, &'static str)>), // Scopes the token lacks, with the message key of what needs them
+    Progress { op: Operation, current: u64, total: Option<u64> }, // Pages, tree levels or bytes done so far
+    TaskStarted(u64, String),        // (task id, name) - a backend task got a permit and is running
+    TaskFinished(u64),               // (task id) - completed, failed or cancelled
     FileTree(String, Vec<FileNode>), // (current_path, file list)
-    FileContent(String, String),      // (filename, content)
+    TreeChildren(String, String, Vec<FileNode>), // (full_name, path, file list) - for the expandable tree only
+    FileContent(String, String, Option<u64>), // (filename, content, byte offset of the rest if only the start was fetched)
+    FileContentMore(String, String, Option<u64>), // (filename, next part, offset of what is left) - for the open file
+    FileTooLarge(String, u64, Option<String>), // (filename, size, download_url) - too big for the viewer
+    BinaryFile(String, u64, Vec<u8>, Option<String>), // (filename, size, first bytes, download_url) - not text
     RepoInfoLoaded(RepoInfo),         // Repo metadata (stars, forks, etc.)
     ReadmeLoaded(String),             // README content
     SearchResults(Vec<SearchRepoItem>), // Search results
+    TrendingLoaded(Vec<SearchRepoItem>), // Trending repos, most stars first
+    TagList(Vec<Tag>),                // Tags of the browsed repo
+    BranchList(Vec<Branch>),          // Branches of the browsed repo
+    LanguagesLoaded(String, Vec<(String, u64)>), // (full_name, [(language, bytes)])
+    TopicsLoaded(String, Vec<String>), // (full_name, topics)
+    StarState(String, bool),          // (full_name, starred)
+    WatchStateLoaded(String, WatchState), // (full_name, subscription)
     
     // Issue events
-    IssueList(Vec<Issue>),            // List of issues
+    IssueList(RequestId, Vec<Issue>), // List of issues
     IssueComments(u32, Vec<IssueComment>), // (issue_number, comments)
     CommentCreated(IssueComment),     // New comment created
+    AttachmentUploaded(RequestId, String), // (upload id, markdown linking the hosted file)
+    AttachmentFailed(RequestId),      // Upload id whose placeholder should be removed
     IssueUpdated(Issue),              // Issue state updated
+    BulkIssuesFinished(String, usize), // (full_name, failed)
+    IssueLoaded(String, Issue),       // (full_name, issue) - referenced issue fetched for opening
+    LocalGitFinished(String),         // full_name - a local git operation ended
+    Cloned(String, String),           // (full_name, checkout directory)
+    MilestoneList(Vec<Milestone>),    // Open milestones of the repo
+    MentionCandidates(String, Vec<String>, Vec<(u32, String)>), // (full_name, logins, (number, title) of recent issues)
+    
+    // Label events
+    LabelList(RequestId, Vec<IssueLabel>), // All labels of the repo
+    LabelCreated(IssueLabel),         // New label created
+    LabelUpdated(String, IssueLabel), // (previous name, updated label)
+    LabelDeleted(String),             // (name)
     
     // Pull Request events
-    PullRequestList(Vec<PullRequest>), // List of PRs
+    PullRequestList(RequestId, Vec<PullRequest>), // List of PRs
+    IssuesUpdated(String, Vec<Issue>), // (full_name, open issues) - polled list changed
+    PullRequestsUpdated(String, Vec<PullRequest>), // (full_name, open PRs) - polled list changed
+    NotificationsUpdated(String, Vec<Notification>), // (full_name, unread notifications) - polled list changed
     PullRequestMerged(MergeResult),   // PR merge result
     PullRequestClosed(PullRequest),   // PR closed
+    PullRequestCreated(PullRequest),  // New PR opened
+    PullRequestUpdated(PullRequest),  // PR changed in place (e.g. ready for review)
+    PullRequestChecks(String, Vec<CheckRun>, CombinedStatus), // (head_sha, check runs, combined status)
+    PullRequestFiles(u32, Vec<ChangedFile>), // (pr_number, changed files)
+    
+    // Commit events
+    CommitList(RequestId, Vec<Commit>), // One page of commits
+    CommitDetailLoaded(CommitDetail), // Single commit with file patches
+    
+    // Discussion events
+    DiscussionCategories(Vec<DiscussionCategory>), // Categories of the repo
+    DiscussionList(RequestId, Vec<Discussion>), // Discussions (filtered by category)
+    DiscussionLoaded(DiscussionThread), // Thread with comments and replies
+}
+
+/// Backend -> UI half of the event channel. The UI only repaints on input, so
+/// every send also wakes it up once the egui context is attached.
+#[derive(Clone)]
+pub struct EventSender {
+    tx: std::sync::mpsc::Sender<AppEvent>,
+    ui: std::sync::Arc<std::sync::OnceLock<eframe::egui::Context>>,
+}
+
+impl EventSender {
+    pub fn channel() -> (EventSender, std::sync::mpsc::Receiver<AppEvent>) {
+        let (tx, rx) = std::sync::mpsc::channel();
+        (EventSender { tx, ui: Default::default() }, rx)
+    }
+
+    /// Repaint `ctx` on every event from now on (events sent earlier are read on the first frame)
+    pub fn attach(&self, ctx: &eframe::egui::Context) {
+        let _ = self.ui.set(ctx.clone());
+    }
+
+    /// False once the UI has gone away
+    pub fn send(&self, event: AppEvent) -> bool {
+        if self.tx.send(event).is_err() {
+            return false;
+        }
+        if let Some(ctx) = self.ui.get() {
+            ctx.request_repaint();
+        }
+        true
+    }
 }
@@ -1,238 +1,1955 @@
 use tokio::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
-use crate::app_event::{AppAction, AppEvent};
+use crate::app_event::{AppAction, AppError, AppEvent, BulkIssueOp, ErrorMessage, EventSender, FileNode, Issue, Operation};
 use crate::context::AppContext;
-use crate::modules::auth;
+use crate::modules::{auth, local_git, token_store};
 use crate::engine::api_client::ApiClient;
+use crate::engine::{self, cache, create_engine};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use serde::Serialize;
+use tokio::sync::{watch, Semaphore, SemaphorePermit};
+use tokio_util::sync::CancellationToken;
 
-/// Helper function to get GitHub token (tries gh CLI first, then keyring)
+/// Helper function to get GitHub token (tries gh CLI first, then the token store).
+/// Tokens GitHub has rejected are skipped: gh keeps handing out a revoked one
+/// until the user runs `gh auth login` again.
 fn get_github_token() -> Option<String> {
     // First try gh CLI (always works if installed)
-    if let Ok(token) = auth::get_token_from_gh_cli() {
+    if let Ok(token) = auth::get_token_from_gh_cli()
+        && !is_rejected(&token)
+    {
         return Some(token);
     }
     
-    // Fallback to keyring
-    if let Ok(entry) = keyring::Entry::new("native_hub", "github_oauth") {
-        if let Ok(token) = entry.get_password() {
-            return Some(token);
-        }
+    // Fallback to the stored token (keyring on desktop, encrypted file on Android)
+    if let Ok(Some(token)) = token_store::global().load()
+        && !is_rejected(&token)
+    {
+        return Some(token);
     }
     
     None
 }
 
+/// Tokens GitHub answered 401 for (expired or revoked) this session
+fn rejected_tokens() -> &'static std::sync::Mutex<std::collections::HashSet<String>> {
+    static REJECTED: std::sync::OnceLock<std::sync::Mutex<std::collections::HashSet<String>>> = std::sync::OnceLock::new();
+    REJECTED.get_or_init(Default::default)
+}
+
+fn is_rejected(token: &str) -> bool {
+    rejected_tokens().lock().unwrap().contains(token)
+}
+
+/// Whether GitHub still accepts `token`. Only a 401 counts as rejection;
+/// network trouble or other errors give it the benefit of the doubt.
+async fn token_accepted(token: &str) -> bool {
+    let Err(e) = ApiClient::new(token.to_string()).fetch_token_scopes().await else { return true };
+    let rejected = e.chain()
+        .find_map(|e| e.downcast_ref::<crate::engine::api_client::GitHubError>())
+        .is_some_and(|e| e.status == reqwest::StatusCode::UNAUTHORIZED);
+    if rejected {
+        rejected_tokens().lock().unwrap().insert(token.to_string());
+    }
+    !rejected
+}
+
+/// Maximum number of API requests in flight at once
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+/// Of those, how many may be taken by background (low priority) requests
+const MAX_BACKGROUND_REQUESTS: usize = 2;
+/// Hover prefetches running at once; more are dropped rather than queued
+const MAX_PREFETCHES: usize = 1;
+/// Bytes of a binary file fetched for its hex preview
+const HEX_PREVIEW_BYTES: u64 = 4096;
+/// Bytes of a text file fetched at a time; the viewer asks for more as needed
+const FILE_PART_BYTES: u64 = 256 * 1024;
+
+/// How urgently a task should run when all permits are taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Priority {
+    /// Direct user actions (open file, navigate, mutations) - may use the reserved slot
+    High,
+    /// Primary content for the current view
+    Normal,
+    /// Secondary data (tags, languages, star state...) - capped so it can't starve the rest
+    Low,
+}
+
+/// Backend task manager
+/// 
+/// Opening a repository fans out into many requests at once (tree, info,
+/// README, issues, PRs, tags...). Firing them all concurrently trips GitHub's
+/// secondary rate limits, so every backend task takes a permit first.
+/// One slot is reserved for high priority work so user actions stay responsive
+/// while background loads are queued.
+/// 
+/// Running tasks are reported to the UI with `TaskStarted`/`TaskFinished`
+/// so it can show an accurate busy indicator.
+#[derive(Clone)]
+struct TaskManager {
+    shared: Arc<Semaphore>,
+    reserved: Arc<Semaphore>,
+    background: Arc<Semaphore>,
+    prefetch: Arc<Semaphore>,
+    next_id: Arc<AtomicU64>,
+    active: Arc<AtomicUsize>, // Shared with AppContext for the status bar
+    event_tx: EventSender,
+}
+
+/// Reports a task as finished when dropped, so cancelled tasks are reported too
+struct TaskGuard {
+    id: u64,
+    active: Arc<AtomicUsize>,
+    event_tx: EventSender,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::Relaxed);
+        let _ = self.event_tx.send(AppEvent::TaskFinished(self.id));
+    }
+}
+
+impl TaskManager {
+    fn new(event_tx: EventSender, active: Arc<AtomicUsize>) -> Self {
+        Self {
+            shared: Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS - 1)),
+            reserved: Arc::new(Semaphore::new(1)),
+            background: Arc::new(Semaphore::new(MAX_BACKGROUND_REQUESTS)),
+            prefetch: Arc::new(Semaphore::new(MAX_PREFETCHES)),
+            next_id: Arc::new(AtomicU64::new(1)),
+            active,
+            event_tx,
+        }
+    }
+    
+    /// Spawn a named backend task that runs once a permit for its priority is available
+    fn spawn<F>(&self, name: &'static str, priority: Priority, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let tasks = self.clone();
+        tokio::spawn(async move {
+            tasks.run(name, priority, task).await;
+        });
+    }
+    
+    /// Like `spawn`, but the task (including its wait for a permit) is aborted
+    /// when `token` is cancelled and the UI is told about it
+    fn spawn_cancellable<F>(&self, name: &'static str, priority: Priority, token: CancellationToken, op: Operation, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let tasks = self.clone();
+        tokio::spawn(cancellable(token, op, self.event_tx.clone(), async move {
+            tasks.run(name, priority, task).await;
+        }));
+    }
+    
+    /// Run speculative work at low priority, unless `MAX_PREFETCHES` are already
+    /// running: then it is dropped, as the pointer has likely moved on. Not
+    /// reported to the UI; a prefetch isn't something the user is waiting for.
+    fn prefetch<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let Ok(slot) = self.prefetch.clone().try_acquire_owned() else { return };
+        let tasks = self.clone();
+        tokio::spawn(async move {
+            let _slot = slot;
+            let _permits = tasks.acquire(Priority::Low).await;
+            task.await;
+        });
+    }
+    
+    async fn run<F: Future<Output = ()>>(&self, name: &'static str, priority: Priority, task: F) {
+        let _permits = self.acquire(priority).await;
+        
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let _ = self.event_tx.send(AppEvent::TaskStarted(id, name.to_string()));
+        self.active.fetch_add(1, Ordering::Relaxed);
+        let _guard = TaskGuard { id, active: self.active.clone(), event_tx: self.event_tx.clone() };
+        
+        task.await;
+    }
+    
+    async fn acquire(&self, priority: Priority) -> Vec<SemaphorePermit<'_>> {
+        // The semaphores are never closed, so acquire() can't fail
+        match priority {
+            Priority::High => {
+                let permit = tokio::select! {
+                    p = self.reserved.acquire() => p,
+                    p = self.shared.acquire() => p,
+                };
+                permit.into_iter().collect()
+            }
+            Priority::Normal => self.shared.acquire().await.into_iter().collect(),
+            Priority::Low => {
+                let background = self.background.acquire().await;
+                let shared = self.shared.acquire().await;
+                background.into_iter().chain(shared).collect()
+            }
+        }
+    }
+}
+
+/// Run `task` until it completes or `token` is cancelled
+async fn cancellable<F: Future<Output = ()>>(token: CancellationToken, op: Operation, event_tx: EventSender, task: F) {
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => {
+            let _ = event_tx.send(AppEvent::LogKey("log.cancelled", vec![op.label().to_string()]));
+            let _ = event_tx.send(AppEvent::Cancelled(op));
+        }
+        _ = task => {}
+    }
+}
+
+/// Register a fresh token for `op`, cancelling the one it supersedes
+fn track(operations: &mut HashMap<Operation, CancellationToken>, op: Operation) -> CancellationToken {
+    let token = CancellationToken::new();
+    if let Some(previous) = operations.insert(op, token.clone()) {
+        previous.cancel();
+    }
+    token
+}
+
+/// Stale-while-revalidate: send the last-known copy stored under `key` (by
+/// `CachedEngine`) through `stale` right away, then return the live result of
+/// `fetch` for the caller to send. The UI shows "updating" in between.
+async fn revalidate<T, F>(tx: &EventSender, key: &str, stale: impl FnOnce(T) -> AppEvent, fetch: F) -> anyhow::Result<T>
+where
+    T: serde::de::DeserializeOwned,
+    F: Future<Output = anyhow::Result<T>>,
+{
+    let _updating = cache::global().and_then(|c| c.get::<T>(key)).map(|cached| {
+        let _ = tx.send(stale(cached));
+        Revalidation::start(tx)
+    });
+    fetch.await
+}
+
+/// Keeps the "updating" indicator up while alive, so a cancelled refresh clears it too
+struct Revalidation(EventSender);
+
+impl Revalidation {
+    fn start(tx: &EventSender) -> Self {
+        let _ = tx.send(AppEvent::Revalidating(true));
+        Self(tx.clone())
+    }
+}
+
+impl Drop for Revalidation {
+    fn drop(&mut self) {
+        let _ = self.0.send(AppEvent::Revalidating(false));
+    }
+}
+
+/// Clears the flag when the task holding it ends, finished or cancelled
+struct RunningFlag(Arc<AtomicBool>);
+
+impl Drop for RunningFlag {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Deletes a partially written file or directory unless `keep` is called
+struct PartialFile(Option<PathBuf>);
+
+impl PartialFile {
+    fn keep(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            if path.is_dir() {
+                let _ = std::fs::remove_dir_all(path);
+            } else {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// Host a comment attachment in a secret gist and return markdown linking it.
+/// GitHub's own attachment upload needs a browser session, so it isn't usable
+/// with a token. Text files go into the gist directly; binary files (images)
+/// are pushed into the gist's git repository.
+async fn upload_attachment(name: &str, path: &std::path::Path, tx: &EventSender) -> Result<String, AppError> {
+    /// Gists hold larger files, but they stop rendering inline well before that
+    const MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+    let failed = || ("error.upload_attachment", vec![name.to_string()]);
+    
+    let bytes = tokio::fs::read(path).await
+        .map_err(|e| AppError::from_error(("error.read_path", vec![path.display().to_string()]), &e.into()))?;
+    if bytes.len() > MAX_ATTACHMENT_SIZE {
+        let limit = (MAX_ATTACHMENT_SIZE / (1024 * 1024)).to_string();
+        return Err(AppError::Validation(("error.attachment_too_large", vec![limit]).into()));
+    }
+    let token = get_github_token().ok_or_else(|| AppError::Auth("error.no_token".into()))?;
+    let api = ApiClient::new(token.clone());
+    
+    let is_image = ["png", "jpg", "jpeg", "gif", "webp", "svg"].iter()
+        .any(|ext| name.to_lowercase().ends_with(&format!(".{}", ext)));
+    let url = match std::str::from_utf8(&bytes) {
+        Ok(text) if !is_image => {
+            let gist = api.create_gist(&format!("Attachment: {}", name), &[(name.to_string(), text.to_string())]).await
+                .map_err(|e| AppError::from_error(failed(), &e))?;
+            gist.files.get(name).map(|file| file.raw_url.clone())
+                .ok_or_else(|| AppError::Other(("error.attachment_missing", vec![name.to_string()]).into()))?
+        }
+        _ => {
+            // The API only takes text, so start the gist with a note and push the file
+            let note = ("README.md".to_string(), format!("Attachment `{}` uploaded from NativeHub.", name));
+            let gist = api.create_gist(&format!("Attachment: {}", name), &[note]).await
+                .map_err(|e| AppError::from_error(failed(), &e))?;
+            local_git::push_to_gist(&gist.git_push_url, &token, name, &bytes, tx).await
+                .map_err(|e| AppError::from_error(failed(), &e))?;
+            format!("{}/raw/{}", gist.html_url, urlencoding::encode(name))
+        }
+    };
+    
+    Ok(if is_image {
+        format!("![{}]({})", name, url)
+    } else {
+        format!("[{}]({})", name, url)
+    })
+}
+
+/// Drop the cached repo list after a change the cache can't see (fork, archive, delete)
+fn forget_repo_list() {
+    if let Some(store) = cache::global() {
+        store.remove(&cache::key("fetch_repos", &[]));
+    }
+}
+
+/// Tell the UI which of the listed repos are starred (search results, trending)
+async fn send_star_states(api: &ApiClient, names: &[String], tx: &EventSender) {
+    match api.star_states(names).await {
+        Ok(states) => {
+            for (full_name, starred) in states {
+                let _ = tx.send(AppEvent::StarState(full_name, starred));
+            }
+        }
+        Err(e) => tracing::debug!("Star state lookup failed: {}", e),
+    }
+}
+
+/// Forward progress reported by engine calls for `op` to the UI
+fn progress_reporter(op: Operation, tx: &EventSender) -> engine::ProgressFn {
+    let tx = tx.clone();
+    Arc::new(move |current, total| {
+        let _ = tx.send(AppEvent::Progress { op, current, total });
+    })
+}
+
+/// Overrides the configured poll interval in seconds (0 disables polling)
+const POLL_INTERVAL_ENV: &str = "NATIVE_HUB_POLL_INTERVAL";
+
+/// What the background poller refreshes and how often
+#[derive(Debug, Clone)]
+struct PollConfig {
+    repo: Option<String>,
+    interval: u64, // Seconds, 0 = off
+    engine: engine::EngineKind,
+}
+
+/// Refresh the API quota shown in the status bar once a minute
+async fn run_rate_limit_monitor(ctx: AppContext) {
+    const RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(60);
+    
+    loop {
+        // The token lookup may shell out to gh; keep it off the runtime threads
+        let token = tokio::task::spawn_blocking(get_github_token).await.ok().flatten();
+        if let Some(token) = token {
+            match ApiClient::new(token).fetch_rate_limit().await {
+                Ok(limit) => *ctx.rate_limit.write().await = Some(limit),
+                Err(e) => tracing::debug!("Rate limit check failed: {}", e),
+            }
+        }
+        tokio::time::sleep(RATE_LIMIT_INTERVAL).await;
+    }
+}
+
+/// When a request comes back 401, check the token once more and, if GitHub
+/// really no longer accepts it (expired or revoked), forget it and send the
+/// UI back to the login screen instead of letting every panel fail on its own
+async fn run_auth_monitor(ctx: AppContext, event_tx: EventSender) {
+    let mut rejections = crate::engine::api_client::watch_auth_rejections();
+    while rejections.changed().await.is_ok() {
+        rejections.mark_unchanged();
+        let token = tokio::task::spawn_blocking(get_github_token).await.ok().flatten();
+        let Some(token) = token else { continue };
+        
+        let accepted = token_accepted(&token).await;
+        // The check's own 401 must not trigger another check
+        rejections.mark_unchanged();
+        if accepted {
+            continue;
+        }
+        
+        tracing::warn!("GitHub rejected the stored token; logging out");
+        if let Err(e) = token_store::global().delete() {
+            tracing::warn!("Failed to delete the stored token: {}", e);
+        }
+        // Nothing fetched with it may outlive the token, offline or not
+        if let Some(store) = cache::global() {
+            store.clear();
+        }
+        *ctx.auth_token.write().await = None;
+        let _ = event_tx.send(AppEvent::AuthExpired);
+    }
+}
+
+/// Tell the UI whenever GitHub pauses requests, so it can count down to the retry
+async fn run_throttle_monitor(event_tx: EventSender) {
+    let mut throttle = crate::engine::api_client::watch_throttle();
+    while throttle.changed().await.is_ok() {
+        let until = *throttle.borrow_and_update();
+        let _ = event_tx.send(AppEvent::Throttled(until));
+    }
+}
+
+/// Periodically re-fetch the open issues, open PRs and notifications of the
+/// repo being viewed, pushing each list to the UI only when it changed
+async fn run_poller(
+    mut config_rx: watch::Receiver<PollConfig>,
+    tasks: TaskManager,
+    event_tx: EventSender,
+) {
+    // Last JSON sent per list, to drop refreshes that changed nothing
+    let mut snapshots: HashMap<&'static str, String> = HashMap::new();
+    let mut polled_repo = None;
+    
+    loop {
+        let config = config_rx.borrow_and_update().clone();
+        if config.repo != polled_repo {
+            snapshots.clear();
+            polled_repo = config.repo.clone();
+        }
+        let active = config.repo.is_some() && config.interval > 0;
+        
+        tokio::select! {
+            changed = config_rx.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+                continue;
+            }
+            _ = tokio::time::sleep(Duration::from_secs(config.interval)), if active => {}
+        }
+        
+        if let Some(full_name) = config.repo {
+            tasks.run("Poll", Priority::Low, poll_repo(&full_name, config.engine, &mut snapshots, &event_tx)).await;
+        }
+    }
+}
+
+async fn poll_repo(
+    full_name: &str,
+    engine_kind: engine::EngineKind,
+    snapshots: &mut HashMap<&'static str, String>,
+    tx: &EventSender,
+) {
+    let Some(token) = get_github_token() else { return };
+    let parts: Vec<&str> = full_name.split('/').collect();
+    if parts.len() != 2 { return; }
+    
+    let engine = create_engine(engine_kind, token.clone());
+    let api = ApiClient::new(token);
+    
+    poll_engine(engine.as_ref(), full_name, snapshots, tx).await;
+    
+    match api.fetch_repo_notifications(parts[0], parts[1]).await {
+        Ok(notifications) if snapshot_changed(snapshots, "notifications", &notifications) => {
+            let _ = tx.send(AppEvent::NotificationsUpdated(full_name.to_string(), notifications));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Notification poll failed: {}", e),
+    }
+}
+
+/// Send the open issues and pull requests of `full_name` if they changed since the last poll
+async fn poll_engine(
+    engine: &dyn engine::Ops,
+    full_name: &str,
+    snapshots: &mut HashMap<&'static str, String>,
+    tx: &EventSender,
+) {
+    let Some((owner, repo)) = full_name.split_once('/') else { return };
+    
+    match engine.fetch_issues(owner, repo, "open", "", &[]).await {
+        Ok(issues) if snapshot_changed(snapshots, "issues", &issues) => {
+            let _ = tx.send(AppEvent::IssuesUpdated(full_name.to_string(), issues));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Issue poll failed: {}", e),
+    }
+    
+    match engine.fetch_pull_requests(owner, repo, "open").await {
+        Ok(prs) if snapshot_changed(snapshots, "pulls", &prs) => {
+            let _ = tx.send(AppEvent::PullRequestsUpdated(full_name.to_string(), prs));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("PR poll failed: {}", e),
+    }
+}
+
+/// Store `value` as the latest snapshot of `key`; true if it differs from the previous one
+fn snapshot_changed<T: Serialize>(snapshots: &mut HashMap<&'static str, String>, key: &'static str, value: &T) -> bool {
+    let Ok(json) = serde_json::to_string(value) else { return true };
+    if snapshots.get(key) == Some(&json) {
+        return false;
+    }
+    snapshots.insert(key, json);
+    true
+}
+
 /// The main backend loop running on the tokio runtime
 pub async fn run_backend(
     mut action_rx: Receiver<AppAction>,
-    event_tx: Sender<AppEvent>,
+    event_tx: EventSender,
     ctx: AppContext,
 ) {
-    let _ = event_tx.send(AppEvent::Log("SYSTEM LINE ONLINE.".to_string()));
-    let _ = event_tx.send(AppEvent::Log("AWAITING INPUT...".to_string()));
+    let _ = event_tx.send(AppEvent::LogKey("log.system_online", Vec::new()));
+    let _ = event_tx.send(AppEvent::LogKey("log.awaiting", Vec::new()));
+
+    // Use the configured engine, or prefer the gh CLI when it is usable and fall back to plain HTTP
+    let mut engine_kind = match crate::config::current().engine {
+        Some(kind) => kind,
+        None => engine::probe_engine().await,
+    };
+    *ctx.engine.write().await = Some(engine_kind);
+    let _ = event_tx.send(AppEvent::LogKey("log.engine", vec![engine_kind.label().to_string()]));
+    
+    let tasks = TaskManager::new(event_tx.clone(), ctx.active_tasks.clone());
+    tokio::spawn(run_rate_limit_monitor(ctx.clone()));
+    tokio::spawn(run_throttle_monitor(event_tx.clone()));
+    tokio::spawn(run_auth_monitor(ctx.clone(), event_tx.clone()));
+    let mut operations: HashMap<Operation, CancellationToken> = HashMap::new();
+    let bulk_running = Arc::new(AtomicBool::new(false));
+
+    let interval = std::env::var(POLL_INTERVAL_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(crate::config::current().poll_interval);
+    let (poll_tx, poll_rx) = watch::channel(PollConfig { repo: None, interval, engine: engine_kind });
+    tokio::spawn(run_poller(poll_rx, tasks.clone(), event_tx.clone()));
 
     while let Some(action) = action_rx.recv().await {
         match action {
             AppAction::Login => {
                 let tx = event_tx.clone();
-                let ctx_clone = ctx.clone();
-                tokio::spawn(async move {
-                    handle_login(ctx_clone, tx).await;
+                let ctx_clone = ctx.clone();
+                let token = track(&mut operations, Operation::Login);
+                // Not scheduled: the device flow polls for minutes and would hold a permit
+                tokio::spawn(cancellable(token, Operation::Login, tx.clone(), async move {
+                    handle_login(ctx_clone, tx).await;
+                }));
+            }
+            AppAction::FetchRepos => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchRepos", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_repos", Vec::new()));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    let engine = create_engine(engine_kind, token);
+                    // Show the last-known list right away; the live one replaces it
+                    let repos = engine::with_progress(progress_reporter(Operation::LoadRepos, &tx), engine.fetch_repos());
+                    match revalidate(&tx, &cache::key("fetch_repos", &[]), AppEvent::RepoList, repos).await {
+                        Ok(repos) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_repos", vec![repos.len().to_string()]));
+                            let _ = tx.send(AppEvent::RepoList(repos));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_repos", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchOrgs => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchOrgs", Priority::Normal, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    let engine = create_engine(engine_kind, token);
+                    match engine.fetch_orgs().await {
+                        Ok(orgs) => {
+                            let _ = tx.send(AppEvent::OrgList(orgs));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.orgs_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchStarred => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchStarred", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.loading_starred", Vec::new()));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    let api = ApiClient::new(token);
+                    match engine::with_progress(progress_reporter(Operation::LoadRepos, &tx), api.fetch_starred()).await {
+                        Ok(repos) => {
+                            let _ = tx.send(AppEvent::LogKey("log.starred_count", vec![repos.len().to_string()]));
+                            // Everything listed is starred; lets the cards show it without extra requests
+                            for repo in &repos {
+                                let _ = tx.send(AppEvent::StarState(repo.full_name.clone(), true));
+                            }
+                            let _ = tx.send(AppEvent::StarredList(repos));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.load_starred", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchOrgRepos(org) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchOrgRepos", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_org_repos", vec![org.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    let engine = create_engine(engine_kind, token);
+                    match engine::with_progress(progress_reporter(Operation::LoadRepos, &tx), engine.fetch_org_repos(&org)).await {
+                        Ok(repos) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_repos", vec![repos.len().to_string()]));
+                            let _ = tx.send(AppEvent::RepoList(repos));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_repos", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::SelectRepo(full_name) => {
+                poll_tx.send_modify(|config| config.repo = Some(full_name.clone()));
+                
+                // Fetch root file tree, repo info, and README for the repo
+                let tx = event_tx.clone();
+                tasks.spawn("SelectRepo", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.opening_repo", vec![full_name.to_string()]));
+                    
+                    // Get token from gh CLI or the token store
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token_gh".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token.clone());
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 {
+                        let _ = tx.send(AppEvent::Error(AppError::Validation("error.invalid_repo_name".into())));
+                        return;
+                    }
+                    
+                    let (owner, repo) = (parts[0], parts[1]);
+                    let readme_key = cache::key("readme", &[owner, repo]);
+                    
+                    // Open instantly with the last-known tree and README, then refresh
+                    let store = cache::global();
+                    let mut updating = None;
+                    if let Some(files) = store.and_then(|c| c.get::<Vec<FileNode>>(&cache::key("fetch_file_tree", &[owner, repo, "", ""]))) {
+                        let _ = tx.send(AppEvent::FileTree("".to_string(), files));
+                        if let Some(readme) = store.and_then(|c| c.get::<String>(&readme_key)) {
+                            let _ = tx.send(AppEvent::ReadmeLoaded(readme));
+                        }
+                        updating = Some(Revalidation::start(&tx));
+                    }
+                    
+                    // Tree, README and repo info don't depend on each other, so they
+                    // are fetched together. The tree is sent as soon as it arrives: it
+                    // switches the UI to the repo, which the other two are shown in.
+                    let tree = async {
+                        // Served from the cache when offline
+                        match engine.fetch_file_tree(owner, repo, "", "").await {
+                            Ok(files) => {
+                                let _ = tx.send(AppEvent::LogKey("log.found_files", vec![files.len().to_string()]));
+                                let _ = tx.send(AppEvent::FileTree("".to_string(), files));
+                                true
+                            }
+                            Err(e) => {
+                                let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_files", &e)));
+                                false
+                            }
+                        }
+                    };
+                    let readme = async {
+                        if let Some(readme) = store.and_then(|c| c.get_fresh::<String>(&readme_key, cache::README_TTL)) {
+                            return Some(readme);
+                        }
+                        let _ = tx.send(AppEvent::LogKey("log.loading_readme", Vec::new()));
+                        match api.fetch_readme(owner, repo).await {
+                            Ok(readme) => {
+                                if let (Some(store), Some(readme)) = (store, &readme) {
+                                    store.put(&readme_key, readme);
+                                }
+                                readme
+                            }
+                            Err(_) => store.and_then(|c| c.get::<String>(&readme_key)),
+                        }
+                    };
+                    let info = api.fetch_repo_info(owner, repo);
+                    
+                    let (tree_loaded, readme, info) = tokio::join!(tree, readme, info);
+                    drop(updating);
+                    if !tree_loaded {
+                        return;
+                    }
+                    if let Some(readme) = readme {
+                        let _ = tx.send(AppEvent::ReadmeLoaded(readme));
+                    }
+                    if let Ok(info) = info {
+                        let _ = tx.send(AppEvent::Log(format!("⭐ {} | 🍴 {}", info.stargazers_count, info.forks_count)));
+                        let _ = tx.send(AppEvent::RepoInfoLoaded(info));
+                    }
+                });
+            }
+            AppAction::FetchDir(full_name, path, git_ref) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::LoadDir);
+                tasks.spawn_cancellable("FetchDir", Priority::High, token, Operation::LoadDir, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.loading_dir", vec![path.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    let listing = engine::with_progress(progress_reporter(Operation::LoadDir, &tx), engine.fetch_file_tree(parts[0], parts[1], &path, &git_ref));
+                    let cache_key = cache::key("fetch_file_tree", &[parts[0], parts[1], &path, &git_ref]);
+                    let stale = |files| AppEvent::FileTree(path.clone(), files);
+                    match revalidate(&tx, &cache_key, stale, listing).await {
+                        Ok(files) => {
+                            let _ = tx.send(AppEvent::FileTree(path, files));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.load_dir", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchTreeDir(full_name, path, git_ref) => {
+                // Not tracked as LoadDir: expanding one folder must not cancel another
+                let tx = event_tx.clone();
+                tasks.spawn("FetchTreeDir", Priority::Normal, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match engine.fetch_file_tree(parts[0], parts[1], &path, &git_ref).await {
+                        Ok(files) => {
+                            let _ = tx.send(AppEvent::TreeChildren(full_name, path, files));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.load_dir", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::PrefetchTreeDir(full_name, path, git_ref) => {
+                let tx = event_tx.clone();
+                tasks.prefetch(async move {
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    // A recent listing needs no request at all
+                    let cache_key = cache::key("fetch_file_tree", &[parts[0], parts[1], &path, &git_ref]);
+                    let store = cache::global();
+                    if store.is_some_and(|c| c.is_fresh(&cache_key, cache::PREFETCH_TTL)) {
+                        if let Some(files) = store.and_then(|c| c.get(&cache_key)) {
+                            let _ = tx.send(AppEvent::TreeChildren(full_name, path, files));
+                        }
+                        return;
+                    }
+                    
+                    let Some(token) = get_github_token() else { return };
+                    let engine = create_engine(engine_kind, token);
+                    if let Ok(files) = engine.fetch_file_tree(parts[0], parts[1], &path, &git_ref).await {
+                        let _ = tx.send(AppEvent::TreeChildren(full_name, path, files));
+                    }
+                });
+            }
+            AppAction::FetchTags(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchTags", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match engine::with_progress(progress_reporter(Operation::LoadTags, &tx), api.fetch_tags(parts[0], parts[1])).await {
+                        Ok(tags) => {
+                            let _ = tx.send(AppEvent::TagList(tags));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.tags_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchLanguages(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchLanguages", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_languages(parts[0], parts[1]).await {
+                        Ok(languages) => {
+                            let _ = tx.send(AppEvent::LanguagesLoaded(full_name, languages));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.languages_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchBranches(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchBranches", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match engine::with_progress(progress_reporter(Operation::LoadBranches, &tx), api.fetch_branches(parts[0], parts[1])).await {
+                        Ok(branches) => {
+                            let _ = tx.send(AppEvent::BranchList(branches));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.branches_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchTopics(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchTopics", Priority::Normal, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_topics(parts[0], parts[1]).await {
+                        Ok(topics) => {
+                            let _ = tx.send(AppEvent::TopicsLoaded(full_name, topics));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.topics_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::SetTopics(full_name, topics) => {
+                let tx = event_tx.clone();
+                tasks.spawn("SetTopics", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.replace_topics(parts[0], parts[1], &topics).await {
+                        Ok(topics) => {
+                            let _ = tx.send(AppEvent::LogKey("log.topics_updated", vec![full_name.to_string(), topics.len().to_string()]));
+                            let _ = tx.send(AppEvent::TopicsLoaded(full_name, topics));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.update_topics", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::CheckStarred(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("CheckStarred", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    if let Ok(starred) = api.is_starred(parts[0], parts[1]).await {
+                        let _ = tx.send(AppEvent::StarState(full_name, starred));
+                    }
+                });
+            }
+            AppAction::SetStarred(full_name, starred) => {
+                let tx = event_tx.clone();
+                tasks.spawn("SetStarred", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    let result = if starred {
+                        api.star_repo(parts[0], parts[1]).await
+                    } else {
+                        api.unstar_repo(parts[0], parts[1]).await
+                    };
+                    
+                    match result {
+                        Ok(()) => {
+                            let _ = tx.send(AppEvent::LogKey(if starred { "log.starred" } else { "log.unstarred" }, vec![full_name.to_string()]));
+                            let _ = tx.send(AppEvent::StarState(full_name, starred));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.star", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::ForkRepo(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("ForkRepo", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.forking", vec![full_name.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fork_repo(parts[0], parts[1]).await {
+                        Ok(fork) => {
+                            forget_repo_list();
+                            let _ = tx.send(AppEvent::LogKey("log.forked", vec![fork.full_name.to_string()]));
+                            let _ = tx.send(AppEvent::RepoForked(fork.into()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fork", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::CreateRepo(name, description, private, auto_init) => {
+                let tx = event_tx.clone();
+                tasks.spawn("CreateRepo", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.creating_repo", vec![name.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    let engine = create_engine(engine_kind, token);
+                    match engine.create_repo(&name, &description, private, auto_init).await {
+                        Ok(full_name) => {
+                            let _ = tx.send(AppEvent::LogKey("log.repo_created", vec![full_name.to_string()]));
+                            let _ = tx.send(AppEvent::RepoCreated(full_name));
+                            
+                            // Refresh my repo list so the new repo shows up
+                            if let Ok(repos) = engine.fetch_repos().await {
+                                let _ = tx.send(AppEvent::RepoList(repos));
+                            }
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.create_repo", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::SetArchived(full_name, archived) => {
+                let tx = event_tx.clone();
+                tasks.spawn("SetArchived", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.set_repo_archived(parts[0], parts[1], archived).await {
+                        Ok(info) => {
+                            forget_repo_list();
+                            let _ = tx.send(AppEvent::LogKey(if archived { "log.archived" } else { "log.unarchived" }, vec![full_name.to_string()]));
+                            let _ = tx.send(AppEvent::RepoInfoLoaded(info));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.archive", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::DeleteRepo(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("DeleteRepo", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.deleting_repo", vec![full_name.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.delete_repo(parts[0], parts[1]).await {
+                        Ok(()) => {
+                            forget_repo_list();
+                            let _ = tx.send(AppEvent::LogKey("log.repo_deleted", vec![full_name.to_string()]));
+                            let _ = tx.send(AppEvent::RepoDeleted(full_name));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.delete_repo", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::DownloadZipball(full_name, git_ref, directory) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::Download);
+                tasks.spawn_cancellable("DownloadZipball", Priority::Normal, token, Operation::Download, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.downloading_archive", vec![full_name.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    // Report progress at most once per 256 KiB to avoid flooding the UI
+                    let mut last_reported = 0u64;
+                    let progress_tx = tx.clone();
+                    let label = full_name.clone();
+                    let on_progress = move |written: u64, total: Option<u64>| {
+                        if written - last_reported >= 256 * 1024 || Some(written) == total {
+                            last_reported = written;
+                            let _ = progress_tx.send(AppEvent::DownloadProgress(Operation::Download, label.clone(), written, total));
+                            let _ = progress_tx.send(AppEvent::Progress { op: Operation::Download, current: written, total });
+                        }
+                    };
+                    
+                    let dest = PathBuf::from(directory);
+                    // Don't leave a truncated archive behind on failure or cancellation; an
+                    // archive already there is only replaced once the new one is complete
+                    let archive = dest.join(crate::engine::api_client::zipball_file_name(parts[1], &git_ref));
+                    let partial = PartialFile(Some(crate::engine::api_client::partial_path(&archive)));
+                    match api.download_zipball(parts[0], parts[1], &git_ref, &dest, on_progress).await {
+                        Ok(path) => {
+                            partial.keep();
+                            let path = path.display().to_string();
+                            let _ = tx.send(AppEvent::LogKey("log.downloaded", vec![path.to_string()]));
+                            let _ = tx.send(AppEvent::DownloadFinished(path));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.download", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::UploadAttachment(id, name, path) => {
+                let tx = event_tx.clone();
+                tasks.spawn("UploadAttachment", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.uploading", vec![name.to_string()]));
+                    match upload_attachment(&name, &path, &tx).await {
+                        Ok(markdown) => {
+                            let _ = tx.send(AppEvent::LogKey("log.uploaded", vec![name.to_string()]));
+                            let _ = tx.send(AppEvent::AttachmentUploaded(id, markdown));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(e));
+                            let _ = tx.send(AppEvent::AttachmentFailed(id));
+                        }
+                    }
+                    let _ = tx.send(AppEvent::Progress { op: Operation::Upload, current: 100, total: Some(100) });
+                });
+            }
+            AppAction::CloneRepo(full_name, url, directory, tool) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::Clone);
+                tasks.spawn_cancellable("CloneRepo", Priority::Normal, token, Operation::Clone, async move {
+                    let name = full_name.rsplit('/').next().unwrap_or(&full_name);
+                    let dest = PathBuf::from(directory).join(name);
+                    if dest.exists() {
+                        let _ = tx.send(AppEvent::Error(AppError::Validation(("error.dest_exists", vec![dest.display().to_string()]).into())));
+                        return;
+                    }
+                    let _ = tx.send(AppEvent::LogKey("log.cloning", vec![full_name.to_string(), dest.display().to_string()]));
+                    
+                    // Don't leave a half-cloned checkout behind on failure or cancellation
+                    let partial = PartialFile(Some(dest.clone()));
+                    match local_git::clone(&full_name, &url, &dest, tool, &tx).await {
+                        Ok(()) => {
+                            partial.keep();
+                            let _ = tx.send(AppEvent::Progress { op: Operation::Clone, current: 100, total: Some(100) });
+                            let _ = tx.send(AppEvent::LogKey("log.cloned", vec![dest.display().to_string()]));
+                            let _ = tx.send(AppEvent::Cloned(full_name, dest.display().to_string()));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Progress { op: Operation::Clone, current: 100, total: Some(100) });
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.clone", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::RunLocalGit(full_name, directory, op) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::Git);
+                tasks.spawn_cancellable("RunLocalGit", Priority::Normal, token, Operation::Git, async move {
+                    let _ = tx.send(AppEvent::Log(format!("git {} @ {}", op.label(), directory)));
+                    let result = local_git::run_op(std::path::Path::new(&directory), op, &tx).await;
+                    let _ = tx.send(AppEvent::Progress { op: Operation::Git, current: 100, total: Some(100) });
+                    match &result {
+                        Ok(()) => {
+                            let _ = tx.send(AppEvent::LogKey("log.git_done", vec![op.label().to_string()]));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error(("error.git", vec![op.label().to_string()]), e)));
+                        }
+                    }
+                    let _ = tx.send(AppEvent::LocalGitFinished(full_name));
+                });
+            }
+            AppAction::CheckWatchState(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("CheckWatchState", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    if let Ok(state) = api.get_subscription(parts[0], parts[1]).await {
+                        let _ = tx.send(AppEvent::WatchStateLoaded(full_name, state));
+                    }
+                });
+            }
+            AppAction::SetWatchState(full_name, state) => {
+                let tx = event_tx.clone();
+                tasks.spawn("SetWatchState", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.set_subscription(parts[0], parts[1], state).await {
+                        Ok(()) => {
+                            let _ = tx.send(AppEvent::LogKey("log.watch_updated", vec![full_name.to_string(), state.label().to_string()]));
+                            let _ = tx.send(AppEvent::WatchStateLoaded(full_name, state));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.update_subscription", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::SaveFile(full_name, file, path) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::SaveFile);
+                tasks.spawn_cancellable("SaveFile", Priority::Normal, token, Operation::SaveFile, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.saving", vec![file.path.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    let mut last_reported = 0u64;
+                    let progress_tx = tx.clone();
+                    let label = file.name.clone();
+                    let on_progress = move |written: u64, total: Option<u64>| {
+                        if written - last_reported >= 256 * 1024 || Some(written) == total {
+                            last_reported = written;
+                            let _ = progress_tx.send(AppEvent::DownloadProgress(Operation::SaveFile, label.clone(), written, total));
+                            let _ = progress_tx.send(AppEvent::Progress { op: Operation::SaveFile, current: written, total });
+                        }
+                    };
+                    
+                    let dest = PathBuf::from(path);
+                    if let Some(parent) = dest.parent().filter(|parent| !parent.as_os_str().is_empty())
+                        && let Err(e) = tokio::fs::create_dir_all(parent).await
+                    {
+                        let _ = tx.send(AppEvent::Error(AppError::from_error("error.save_file", &e.into())));
+                        return;
+                    }
+                    // The file is written next to `dest` and moved over it when complete,
+                    // so a failed or cancelled save leaves an existing file untouched
+                    let partial = PartialFile(Some(crate::engine::api_client::partial_path(&dest)));
+                    match api.download_file(parts[0], parts[1], &file, &dest, on_progress).await {
+                        Ok(()) => {
+                            partial.keep();
+                            let path = dest.display().to_string();
+                            let _ = tx.send(AppEvent::LogKey("log.saved", vec![path.to_string()]));
+                            let _ = tx.send(AppEvent::DownloadFinished(path));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.save_file", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::ReadFile(full_name, file) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::ReadFile);
+                tasks.spawn_cancellable("ReadFile", Priority::High, token, Operation::ReadFile, async move {
+                    use crate::engine::api_client::{has_binary_extension, MAX_INLINE_FILE_SIZE};
+                    
+                    let filename = file.name.clone();
+                    // Binary files are never shown as text, so their first bytes are enough for a preview.
+                    // Text comes in parts so the viewer can show the start while the user reads.
+                    let binary = has_binary_extension(&filename);
+                    let limit = if binary { HEX_PREVIEW_BYTES } else { FILE_PART_BYTES };
+                    
+                    // Don't pull huge blobs into memory; offer a download instead
+                    if file.size > MAX_INLINE_FILE_SIZE && !binary {
+                        let _ = tx.send(AppEvent::LogKey("log.file_too_large", vec![filename.to_string(), file.size.to_string()]));
+                        let _ = tx.send(AppEvent::FileTooLarge(filename, file.size, file.download_url));
+                        return;
+                    }
+                    
+                    let _ = tx.send(AppEvent::LogKey("log.reading_file", Vec::new()));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    let result = api.fetch_file_part(parts[0], parts[1], &file, 0, limit).await;
+                    
+                    match result {
+                        Ok(part) if part.is_binary(&filename) => {
+                            let mut preview = part.data;
+                            preview.truncate(HEX_PREVIEW_BYTES as usize);
+                            let _ = tx.send(AppEvent::LogKey("log.binary_file", vec![filename.to_string()]));
+                            let _ = tx.send(AppEvent::BinaryFile(filename, file.size, preview, file.download_url));
+                        }
+                        Ok(part) => {
+                            let (content, len) = part.text();
+                            let rest = part.truncated.then_some(len as u64);
+                            let _ = tx.send(AppEvent::LogKey("log.file_loaded", vec![filename.to_string()]));
+                            let _ = tx.send(AppEvent::FileContent(filename, content, rest));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.read_file", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::ReadFileMore(full_name, file, offset) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::ReadFile);
+                tasks.spawn_cancellable("ReadFileMore", Priority::High, token, Operation::ReadFile, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_file_part(parts[0], parts[1], &file, offset, FILE_PART_BYTES).await {
+                        Ok(part) => {
+                            let (content, len) = part.text();
+                            let rest = part.truncated.then_some(offset + len as u64);
+                            let _ = tx.send(AppEvent::FileContentMore(file.name, content, rest));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.read_file", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchTrending(range, language) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchTrending", Priority::Normal, async move {
+                    // GitHub has no trending API; approximate it with the most starred repos created recently
+                    let since = chrono::Utc::now().date_naive() - chrono::Days::new(range.days());
+                    let mut query = format!("created:>{}", since.format("%Y-%m-%d"));
+                    if !language.trim().is_empty() {
+                        query.push_str(&format!(" language:{}", language.trim()));
+                    }
+                    let _ = tx.send(AppEvent::LogKey("log.loading_trending", vec![range.label().to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token.clone());
+                    let engine = create_engine(engine_kind, token);
+                    
+                    match engine.search_repos(&query, Some("stars"), 30).await {
+                        Ok(result) => {
+                            let names: Vec<String> = result.items.iter().map(|r| r.full_name.clone()).collect();
+                            let _ = tx.send(AppEvent::TrendingLoaded(result.items));
+                            send_star_states(&api, &names, &tx).await;
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.load_trending", &e)));
+                            let _ = tx.send(AppEvent::TrendingLoaded(Vec::new()));
+                        }
+                    }
+                });
+            }
+            AppAction::SearchRepos(query) => {
+                let tx = event_tx.clone();
+                let token = track(&mut operations, Operation::Search);
+                tasks.spawn_cancellable("SearchRepos", Priority::High, token, Operation::Search, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.searching", vec![query.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let api = ApiClient::new(token.clone());
+                    let engine = create_engine(engine_kind, token);
+                    
+                    match engine.search_repos(&query, Some("stars"), 30).await {
+                        Ok(result) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_repos", vec![result.total_count.to_string()]));
+                            let names: Vec<String> = result.items.iter().map(|r| r.full_name.clone()).collect();
+                            let _ = tx.send(AppEvent::SearchResults(result.items));
+                            
+                            // Resolve star state for the results after the list is shown
+                            send_star_states(&api, &names, &tx).await;
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.search", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchIssues(request, full_name, state, milestone, labels) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchIssues", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_issues", vec![full_name.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => {
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                            return;
+                        }
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 {
+                        let _ = tx.send(AppEvent::Error(AppError::Validation("error.invalid_repo_name".into())));
+                        return;
+                    }
+                    
+                    // Show cached issues while the live list loads
+                    let cache_key = cache::key("fetch_issues", &[parts[0], parts[1], &state, &milestone, &labels.join(",")]);
+                    let stale = |issues: Vec<Issue>| AppEvent::IssueList(request, issues.into_iter().filter(|i| i.pull_request.is_none()).collect());
+                    match revalidate(&tx, &cache_key, stale, engine.fetch_issues(parts[0], parts[1], &state, &milestone, &labels)).await {
+                        Ok(issues) => {
+                            // Filter out PRs (they have pull_request field)
+                            let issues: Vec<_> = issues.into_iter()
+                                .filter(|i| i.pull_request.is_none())
+                                .collect();
+                            let _ = tx.send(AppEvent::LogKey("log.found_issues", vec![issues.len().to_string()]));
+                            let _ = tx.send(AppEvent::IssueList(request, issues));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_issues", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchIssue(full_name, issue_number) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchIssue", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_issue(parts[0], parts[1], issue_number).await {
+                        Ok(issue) => {
+                            let _ = tx.send(AppEvent::IssueLoaded(full_name, issue));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error(("error.fetch_issue", vec![issue_number.to_string()]), &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchIssueComments(full_name, issue_number) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchIssueComments", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_comments", vec![issue_number.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    let cache_key = cache::key("fetch_issue_comments", &[parts[0], parts[1], &issue_number.to_string()]);
+                    let stale = |comments| AppEvent::IssueComments(issue_number, comments);
+                    match revalidate(&tx, &cache_key, stale, engine.fetch_issue_comments(parts[0], parts[1], issue_number)).await {
+                        Ok(comments) => {
+                            let _ = tx.send(AppEvent::IssueComments(issue_number, comments));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_comments", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::PrefetchComments(full_name, issue_number) => {
+                tasks.prefetch(async move {
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    // Only useful through the cache, which FetchIssueComments shows first
+                    let cache_key = cache::key("fetch_issue_comments", &[parts[0], parts[1], &issue_number.to_string()]);
+                    if cache::global().is_none_or(|c| c.is_fresh(&cache_key, cache::PREFETCH_TTL)) {
+                        return;
+                    }
+                    
+                    let Some(token) = get_github_token() else { return };
+                    let engine = create_engine(engine_kind, token);
+                    let _ = engine.fetch_issue_comments(parts[0], parts[1], issue_number).await;
+                });
+            }
+            AppAction::CreateComment(full_name, issue_number, body) => {
+                let tx = event_tx.clone();
+                tasks.spawn("CreateComment", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.posting_comment", Vec::new()));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match engine.create_comment(parts[0], parts[1], issue_number, &body).await {
+                        Ok(comment) => {
+                            let _ = tx.send(AppEvent::LogKey("log.comment_posted", Vec::new()));
+                            let _ = tx.send(AppEvent::CommentCreated(comment));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.post_comment", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::UpdateIssueState(full_name, issue_number, state) => {
+                let tx = event_tx.clone();
+                tasks.spawn("UpdateIssueState", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey(if state == "closed" { "log.closing_issue" } else { "log.reopening_issue" }, vec![issue_number.to_string()]));
+                    
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match engine.update_issue_state(parts[0], parts[1], issue_number, &state).await {
+                        Ok(issue) => {
+                            let _ = tx.send(AppEvent::LogKey(if state == "closed" { "log.issue_closed" } else { "log.issue_reopened" }, vec![issue_number.to_string()]));
+                            let _ = tx.send(AppEvent::IssueUpdated(issue));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.action", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchMilestones(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchMilestones", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_milestones(parts[0], parts[1]).await {
+                        Ok(milestones) => {
+                            let _ = tx.send(AppEvent::MilestoneList(milestones));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.milestones_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchMentionCandidates(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchMentionCandidates", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    // Completion is best effort; an empty list just shows no popup
+                    let (users, issues) = tokio::join!(
+                        api.fetch_collaborators(parts[0], parts[1]),
+                        api.fetch_recent_issues(parts[0], parts[1])
+                    );
+                    let logins = users.map(|u| u.into_iter().map(|u| u.login).collect()).unwrap_or_default();
+                    let issues = issues.map(|i| i.into_iter().map(|i| (i.number, i.title)).collect()).unwrap_or_default();
+                    let _ = tx.send(AppEvent::MentionCandidates(full_name, logins, issues));
+                });
+            }
+            AppAction::SetIssueMilestone(full_name, issue_number, milestone) => {
+                let tx = event_tx.clone();
+                tasks.spawn("SetIssueMilestone", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.set_issue_milestone(parts[0], parts[1], issue_number, milestone).await {
+                        Ok(issue) => {
+                            let _ = tx.send(AppEvent::LogKey("log.milestone_updated", vec![issue_number.to_string()]));
+                            let _ = tx.send(AppEvent::IssueUpdated(issue));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.set_milestone", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::BulkIssues(full_name, numbers, op) => {
+                let tx = event_tx.clone();
+                // A second batch would cancel the first halfway through; turn it away instead
+                if bulk_running.swap(true, Ordering::SeqCst) {
+                    let _ = tx.send(AppEvent::LogKey("log.bulk_busy", vec![numbers.len().to_string()]));
+                    let _ = tx.send(AppEvent::BulkIssuesFinished(full_name, numbers.len()));
+                    continue;
+                }
+                let running = RunningFlag(bulk_running.clone());
+                let token = track(&mut operations, Operation::Bulk);
+                tasks.spawn_cancellable("BulkIssues", Priority::Normal, token, Operation::Bulk, async move {
+                    let _running = running;
+                    let Some(token) = get_github_token() else {
+                        let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
+                        return;
+                    };
+                    let api = ApiClient::new(token);
+                    let Some((owner, repo)) = full_name.split_once('/') else { return };
+                    
+                    let total = numbers.len() as u64;
+                    let _ = tx.send(AppEvent::LogKey("log.bulk_started", vec![op.label().to_string(), total.to_string()]));
+                    let _ = tx.send(AppEvent::Progress { op: Operation::Bulk, current: 0, total: Some(total) });
+                    
+                    // One request at a time: bulk edits are what trips GitHub's secondary rate limit
+                    let (mut succeeded, mut failed) = (0, 0);
+                    for (done, number) in numbers.into_iter().enumerate() {
+                        let result = match &op {
+                            BulkIssueOp::Close => api.update_issue_state(owner, repo, number, "closed").await,
+                            BulkIssueOp::AddLabel(name) => match api.add_issue_labels(owner, repo, number, std::slice::from_ref(name)).await {
+                                Ok(()) => api.fetch_issue(owner, repo, number).await,
+                                Err(e) => Err(e),
+                            },
+                            BulkIssueOp::SetMilestone(milestone) => api.set_issue_milestone(owner, repo, number, *milestone).await,
+                        };
+                        match result {
+                            Ok(issue) => {
+                                succeeded += 1;
+                                let _ = tx.send(AppEvent::IssueUpdated(issue));
+                            }
+                            Err(e) => {
+                                failed += 1;
+                                let _ = tx.send(AppEvent::LogKey("log.bulk_item_failed", vec![number.to_string(), op.label().to_string(), e.to_string()]));
+                            }
+                        }
+                        let _ = tx.send(AppEvent::Progress { op: Operation::Bulk, current: done as u64 + 1, total: Some(total) });
+                    }
+                    
+                    let _ = tx.send(AppEvent::LogKey("log.bulk_finished", vec![op.label().to_string(), succeeded.to_string(), failed.to_string()]));
+                    let _ = tx.send(AppEvent::BulkIssuesFinished(full_name, failed));
                 });
             }
-            AppAction::FetchRepos => {
+            AppAction::FetchLabels(request, full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchLabels", Priority::Normal, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_labels(parts[0], parts[1]).await {
+                        Ok(labels) => {
+                            let _ = tx.send(AppEvent::LabelList(request, labels));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_labels", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::CreateLabel(full_name, label) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    use crate::engine::{GhCliEngine, Ops};
+                tasks.spawn("CreateLabel", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
                     
-                    let _ = tx.send(AppEvent::Log("FETCHING REPOS VIA GH CLI...".to_string()));
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
                     
-                    let engine = GhCliEngine::new();
-                    match engine.fetch_repos().await {
-                        Ok(repos) => {
-                            let _ = tx.send(AppEvent::Log(format!("FOUND {} REPOSITORIES.", repos.len())));
-                            let _ = tx.send(AppEvent::RepoList(repos));
+                    match api.create_label(parts[0], parts[1], &label).await {
+                        Ok(created) => {
+                            let _ = tx.send(AppEvent::LogKey("log.label_created", vec![created.name.to_string()]));
+                            let _ = tx.send(AppEvent::LabelCreated(created));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("FETCH FAILED: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.create_label", &e)));
                         }
                     }
                 });
             }
-            AppAction::SelectRepo(full_name) => {
-                // Fetch root file tree, repo info, and README for the repo
+            AppAction::UpdateLabel(full_name, name, label) => {
+                let tx = event_tx.clone();
+                tasks.spawn("UpdateLabel", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.update_label(parts[0], parts[1], &name, &label).await {
+                        Ok(updated) => {
+                            let _ = tx.send(AppEvent::LogKey("log.label_updated", vec![updated.name.to_string()]));
+                            let _ = tx.send(AppEvent::LabelUpdated(name, updated));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.update_label", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::DeleteLabel(full_name, name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("DeleteLabel", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.delete_label(parts[0], parts[1], &name).await {
+                        Ok(()) => {
+                            let _ = tx.send(AppEvent::LogKey("log.label_deleted", vec![name.to_string()]));
+                            let _ = tx.send(AppEvent::LabelDeleted(name));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.delete_label", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchPullRequests(request, full_name, state) => {
                 let tx = event_tx.clone();
-                let full_name_clone = full_name.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在浏览仓库: {}...", full_name)));
+                tasks.spawn("FetchPullRequests", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_prs", vec![full_name.to_string()]));
                     
-                    // Get token from gh CLI or keyring
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => {
-                            let _ = tx.send(AppEvent::Error("无法获取 Token (请确保已登录 gh CLI)".to_string()));
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
                             return;
                         }
                     };
                     
-                    let api = ApiClient::new(token);
+                    let engine = create_engine(engine_kind, token);
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 {
-                        let _ = tx.send(AppEvent::Error("仓库名格式错误".to_string()));
+                        let _ = tx.send(AppEvent::Error(AppError::Validation("error.invalid_repo_name".into())));
                         return;
                     }
                     
-                    let (owner, repo) = (parts[0], parts[1]);
-                    
-                    // Fetch file tree
-                    match api.fetch_file_tree(owner, repo, "").await {
-                        Ok(files) => {
-                            let _ = tx.send(AppEvent::Log(format!("找到 {} 个文件/目录", files.len())));
-                            
-                            // Send FileTree FIRST so UI transitions to Browsing state
-                            let _ = tx.send(AppEvent::FileTree("".to_string(), files.clone()));
-                            
-                            // NOW load README (after state has transitioned)
-                            for file in &files {
-                                if file.name.to_lowercase().starts_with("readme") {
-                                    if let Some(ref url) = file.download_url {
-                                        let _ = tx.send(AppEvent::Log("正在加载 README...".to_string()));
-                                        if let Ok(readme) = api.fetch_file_content(url).await {
-                                            let _ = tx.send(AppEvent::ReadmeLoaded(readme));
-                                        }
-                                        break;
-                                    }
-                                }
-                            }
+                    let cache_key = cache::key("fetch_pull_requests", &[parts[0], parts[1], &state]);
+                    let stale = |prs| AppEvent::PullRequestList(request, prs);
+                    match revalidate(&tx, &cache_key, stale, engine.fetch_pull_requests(parts[0], parts[1], &state)).await {
+                        Ok(prs) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_prs", vec![prs.len().to_string()]));
+                            let _ = tx.send(AppEvent::PullRequestList(request, prs));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("获取文件列表失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_prs", &e)));
                         }
                     }
-                    
-                    // Fetch repo info
-                    if let Ok(info) = api.fetch_repo_info(owner, repo).await {
-                        let _ = tx.send(AppEvent::Log(format!("⭐ {} | 🍴 {}", info.stargazers_count, info.forks_count)));
-                        let _ = tx.send(AppEvent::RepoInfoLoaded(info));
-                    }
                 });
             }
-            AppAction::FetchDir(full_name, path) => {
+            AppAction::MergePullRequest(full_name, pr_number, merge_method) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在加载目录: /{}", path)));
+                tasks.spawn("MergePullRequest", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.merging_pr", vec![pr_number.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => return,
                     };
                     
-                    let api = ApiClient::new(token);
+                    let engine = create_engine(engine_kind, token);
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.fetch_file_tree(parts[0], parts[1], &path).await {
-                        Ok(files) => {
-                            let _ = tx.send(AppEvent::FileTree(path, files));
+                    match engine.merge_pull_request(parts[0], parts[1], pr_number, &merge_method).await {
+                        Ok(result) => {
+                            let _ = tx.send(AppEvent::LogKey("log.pr_merged", vec![pr_number.to_string(), result.message.to_string()]));
+                            let _ = tx.send(AppEvent::PullRequestMerged(result));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("加载目录失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.merge", &e)));
                         }
                     }
                 });
             }
-            AppAction::ReadFile(download_url) => {
+            AppAction::ClosePullRequest(full_name, pr_number) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log("正在读取文件内容...".to_string()));
+                tasks.spawn("ClosePullRequest", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.closing_pr", vec![pr_number.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => return,
                     };
                     
-                    let api = ApiClient::new(token);
-                    
-                    // Extract filename from URL
-                    let filename = download_url.split('/').last().unwrap_or("file").to_string();
+                    let engine = create_engine(engine_kind, token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
                     
-                    match api.fetch_file_content(&download_url).await {
-                        Ok(content) => {
-                            let _ = tx.send(AppEvent::Log(format!("文件 {} 已加载", filename)));
-                            let _ = tx.send(AppEvent::FileContent(filename, content));
+                    match engine.close_pull_request(parts[0], parts[1], pr_number).await {
+                        Ok(pr) => {
+                            let _ = tx.send(AppEvent::LogKey("log.pr_closed", vec![pr_number.to_string()]));
+                            let _ = tx.send(AppEvent::PullRequestClosed(pr));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("读取文件失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.close", &e)));
                         }
                     }
                 });
             }
-            AppAction::SearchRepos(query) => {
+            AppAction::CreatePullRequest(full_name, title, body, head, base) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在搜索: {}...", query)));
+                tasks.spawn("CreatePullRequest", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.creating_pr", vec![head.to_string(), base.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => {
-                            let _ = tx.send(AppEvent::Error("无法获取 Token".to_string()));
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
                             return;
                         }
                     };
                     
                     let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
                     
-                    match api.search_repos(&query, Some("stars"), 30).await {
-                        Ok(result) => {
-                            let _ = tx.send(AppEvent::Log(format!("找到 {} 个仓库", result.total_count)));
-                            let _ = tx.send(AppEvent::SearchResults(result.items));
+                    match api.create_pull_request(parts[0], parts[1], &title, &body, &head, &base).await {
+                        Ok(pr) => {
+                            let _ = tx.send(AppEvent::LogKey("log.pr_created", vec![pr.number.to_string()]));
+                            let _ = tx.send(AppEvent::PullRequestCreated(pr));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("搜索失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.create_pr", &e)));
                         }
                     }
                 });
             }
-            AppAction::FetchIssues(full_name, state) => {
+            AppAction::MarkPullRequestReady(full_name, pr_number, node_id) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在获取 {} 的 Issues...", full_name)));
-                    
+                tasks.spawn("MarkPullRequestReady", Priority::High, async move {
                     let token = match get_github_token() {
                         Some(t) => t,
-                        None => {
-                            let _ = tx.send(AppEvent::Error("无法获取 Token".to_string()));
-                            return;
-                        }
+                        None => return,
                     };
                     
                     let api = ApiClient::new(token);
                     let parts: Vec<&str> = full_name.split('/').collect();
-                    if parts.len() != 2 {
-                        let _ = tx.send(AppEvent::Error("无效的仓库名".to_string()));
+                    if parts.len() != 2 { return; }
+                    
+                    if let Err(e) = api.mark_ready_for_review(&node_id).await {
+                        let _ = tx.send(AppEvent::Error(AppError::from_error("error.action", &e)));
                         return;
                     }
                     
-                    match api.fetch_issues(parts[0], parts[1], &state).await {
-                        Ok(issues) => {
-                            // Filter out PRs (they have pull_request field)
-                            let issues: Vec<_> = issues.into_iter()
-                                .filter(|i| i.pull_request.is_none())
-                                .collect();
-                            let _ = tx.send(AppEvent::Log(format!("找到 {} 个 Issues", issues.len())));
-                            let _ = tx.send(AppEvent::IssueList(issues));
+                    let _ = tx.send(AppEvent::LogKey("log.pr_ready", vec![pr_number.to_string()]));
+                    if let Ok(pr) = api.fetch_pull_request(parts[0], parts[1], pr_number).await {
+                        let _ = tx.send(AppEvent::PullRequestUpdated(pr));
+                    }
+                });
+            }
+            AppAction::FetchPullRequest(full_name, pr_number) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchPullRequest", Priority::Normal, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_pull_request(parts[0], parts[1], pr_number).await {
+                        Ok(pr) => {
+                            let _ = tx.send(AppEvent::PullRequestUpdated(pr));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("获取 Issues 失败: {}", e)));
+                            let _ = tx.send(AppEvent::LogKey("log.pr_failed", vec![pr_number.to_string(), e.to_string()]));
                         }
                     }
                 });
             }
-            AppAction::FetchIssueComments(full_name, issue_number) => {
+            AppAction::UpdatePullRequestBranch(full_name, pr_number, head_sha) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在获取 Issue #{} 的评论...", issue_number)));
-                    
+                tasks.spawn("UpdatePullRequestBranch", Priority::High, async move {
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => return,
@@ -242,21 +1959,40 @@ pub async fn run_backend(
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.fetch_issue_comments(parts[0], parts[1], issue_number).await {
-                        Ok(comments) => {
-                            let _ = tx.send(AppEvent::IssueComments(issue_number, comments));
+                    if let Err(e) = api.update_pull_request_branch(parts[0], parts[1], pr_number, &head_sha).await {
+                        let _ = tx.send(AppEvent::Error(AppError::from_error("error.update_branch", &e)));
+                        if let Ok(pr) = api.fetch_pull_request(parts[0], parts[1], pr_number).await {
+                            let _ = tx.send(AppEvent::PullRequestUpdated(pr));
                         }
-                        Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("获取评论失败: {}", e)));
+                        return;
+                    }
+                    let _ = tx.send(AppEvent::LogKey("log.updating_branch", vec![pr_number.to_string()]));
+                    
+                    // The merge runs in the background; wait for the new head and
+                    // for GitHub to finish recomputing mergeability
+                    let mut latest = None;
+                    for _ in 0..15 {
+                        tokio::time::sleep(Duration::from_secs(2)).await;
+                        let Ok(pr) = api.fetch_pull_request(parts[0], parts[1], pr_number).await else { continue };
+                        let settled = pr.head.sha != head_sha
+                            && pr.mergeable.is_some()
+                            && pr.mergeable_state.as_deref() != Some("unknown");
+                        latest = Some(pr);
+                        if settled {
+                            break;
                         }
                     }
+                    
+                    if let Some(pr) = latest {
+                        let state = pr.mergeable_state.clone().unwrap_or_default();
+                        let _ = tx.send(AppEvent::LogKey("log.branch_updated", vec![pr_number.to_string(), state.to_string()]));
+                        let _ = tx.send(AppEvent::PullRequestUpdated(pr));
+                    }
                 });
             }
-            AppAction::CreateComment(full_name, issue_number, body) => {
+            AppAction::FetchPullRequestChecks(full_name, sha) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在发表评论...")));
-                    
+                tasks.spawn("FetchPullRequestChecks", Priority::High, async move {
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => return,
@@ -266,22 +2002,23 @@ pub async fn run_backend(
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.create_comment(parts[0], parts[1], issue_number, &body).await {
-                        Ok(comment) => {
-                            let _ = tx.send(AppEvent::Log("评论已发表".to_string()));
-                            let _ = tx.send(AppEvent::CommentCreated(comment));
-                        }
+                    // Check runs and legacy statuses are independent; a repo may use either or both
+                    let check_runs = match api.fetch_check_runs(parts[0], parts[1], &sha).await {
+                        Ok(list) => list.check_runs,
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("发表评论失败: {}", e)));
+                            let _ = tx.send(AppEvent::LogKey("log.checks_failed", vec![e.to_string()]));
+                            Vec::new()
                         }
-                    }
+                    };
+                    let combined = api.fetch_combined_status(parts[0], parts[1], &sha).await.unwrap_or_default();
+                    
+                    let _ = tx.send(AppEvent::PullRequestChecks(sha, check_runs, combined));
                 });
             }
-            AppAction::UpdateIssueState(full_name, issue_number, state) => {
+            AppAction::FetchPullRequestFiles(full_name, pr_number) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let action_text = if state == "closed" { "关闭" } else { "重新打开" };
-                    let _ = tx.send(AppEvent::Log(format!("正在{} Issue #{}...", action_text, issue_number)));
+                tasks.spawn("FetchPullRequestFiles", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_pr_files", vec![pr_number.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
@@ -292,26 +2029,25 @@ pub async fn run_backend(
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.update_issue_state(parts[0], parts[1], issue_number, &state).await {
-                        Ok(issue) => {
-                            let _ = tx.send(AppEvent::Log(format!("Issue #{} 已{}", issue_number, action_text)));
-                            let _ = tx.send(AppEvent::IssueUpdated(issue));
+                    match api.fetch_pr_files(parts[0], parts[1], pr_number).await {
+                        Ok(files) => {
+                            let _ = tx.send(AppEvent::PullRequestFiles(pr_number, files));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("操作失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_pr_files", &e)));
                         }
                     }
                 });
             }
-            AppAction::FetchPullRequests(full_name, state) => {
+            AppAction::FetchCommits(request, full_name, branch, page) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在获取 {} 的 Pull Requests...", full_name)));
+                tasks.spawn("FetchCommits", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_commits", vec![full_name.to_string(), page.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
                         None => {
-                            let _ = tx.send(AppEvent::Error("无法获取 Token".to_string()));
+                            let _ = tx.send(AppEvent::Error(AppError::Auth("error.no_token".into())));
                             return;
                         }
                     };
@@ -319,25 +2055,25 @@ pub async fn run_backend(
                     let api = ApiClient::new(token);
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 {
-                        let _ = tx.send(AppEvent::Error("无效的仓库名".to_string()));
+                        let _ = tx.send(AppEvent::Error(AppError::Validation("error.invalid_repo_name".into())));
                         return;
                     }
                     
-                    match api.fetch_pull_requests(parts[0], parts[1], &state).await {
-                        Ok(prs) => {
-                            let _ = tx.send(AppEvent::Log(format!("找到 {} 个 Pull Requests", prs.len())));
-                            let _ = tx.send(AppEvent::PullRequestList(prs));
+                    match api.fetch_commits(parts[0], parts[1], &branch, page).await {
+                        Ok(commits) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_commits", vec![commits.len().to_string()]));
+                            let _ = tx.send(AppEvent::CommitList(request, commits));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("获取 PRs 失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_commits", &e)));
                         }
                     }
                 });
             }
-            AppAction::MergePullRequest(full_name, pr_number, merge_method) => {
+            AppAction::FetchCommitDetail(full_name, sha) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在合并 PR #{}...", pr_number)));
+                tasks.spawn("FetchCommitDetail", Priority::High, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.loading_commit", vec![sha[..sha.len().min(7)].to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
@@ -348,21 +2084,42 @@ pub async fn run_backend(
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.merge_pull_request(parts[0], parts[1], pr_number, &merge_method).await {
-                        Ok(result) => {
-                            let _ = tx.send(AppEvent::Log(format!("PR #{} 已合并: {}", pr_number, result.message)));
-                            let _ = tx.send(AppEvent::PullRequestMerged(result));
+                    match api.fetch_commit(parts[0], parts[1], &sha).await {
+                        Ok(detail) => {
+                            let _ = tx.send(AppEvent::CommitDetailLoaded(detail));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("合并失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.load_commit", &e)));
                         }
                     }
                 });
             }
-            AppAction::ClosePullRequest(full_name, pr_number) => {
+            AppAction::FetchDiscussionCategories(full_name) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchDiscussionCategories", Priority::Low, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_discussion_categories(parts[0], parts[1]).await {
+                        Ok(categories) => {
+                            let _ = tx.send(AppEvent::DiscussionCategories(categories));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::LogKey("log.categories_failed", vec![e.to_string()]));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchDiscussions(request, full_name, category) => {
                 let tx = event_tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::Log(format!("正在关闭 PR #{}...", pr_number)));
+                tasks.spawn("FetchDiscussions", Priority::Normal, async move {
+                    let _ = tx.send(AppEvent::LogKey("log.fetching_discussions", vec![full_name.to_string()]));
                     
                     let token = match get_github_token() {
                         Some(t) => t,
@@ -373,82 +2130,337 @@ pub async fn run_backend(
                     let parts: Vec<&str> = full_name.split('/').collect();
                     if parts.len() != 2 { return; }
                     
-                    match api.close_pull_request(parts[0], parts[1], pr_number).await {
-                        Ok(pr) => {
-                            let _ = tx.send(AppEvent::Log(format!("PR #{} 已关闭", pr_number)));
-                            let _ = tx.send(AppEvent::PullRequestClosed(pr));
+                    match api.fetch_discussions(parts[0], parts[1], category.as_deref()).await {
+                        Ok(discussions) => {
+                            let _ = tx.send(AppEvent::LogKey("log.found_discussions", vec![discussions.len().to_string()]));
+                            let _ = tx.send(AppEvent::DiscussionList(request, discussions));
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_discussions", &e)));
+                        }
+                    }
+                });
+            }
+            AppAction::FetchDiscussion(full_name, number) => {
+                let tx = event_tx.clone();
+                tasks.spawn("FetchDiscussion", Priority::High, async move {
+                    let token = match get_github_token() {
+                        Some(t) => t,
+                        None => return,
+                    };
+                    
+                    let api = ApiClient::new(token);
+                    let parts: Vec<&str> = full_name.split('/').collect();
+                    if parts.len() != 2 { return; }
+                    
+                    match api.fetch_discussion(parts[0], parts[1], number).await {
+                        Ok(thread) => {
+                            let _ = tx.send(AppEvent::DiscussionLoaded(thread));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(format!("关闭失败: {}", e)));
+                            let _ = tx.send(AppEvent::Error(AppError::from_error("error.fetch_discussion", &e)));
                         }
                     }
                 });
             }
+            AppAction::StopPolling => {
+                poll_tx.send_modify(|config| config.repo = None);
+            }
+            AppAction::SetPollInterval(seconds) => {
+                poll_tx.send_modify(|config| config.interval = seconds);
+            }
+            AppAction::SetEngine(kind) => {
+                engine_kind = match kind {
+                    Some(kind) => kind,
+                    None => engine::probe_engine().await,
+                };
+                *ctx.engine.write().await = Some(engine_kind);
+                poll_tx.send_modify(|config| config.engine = engine_kind);
+                let _ = event_tx.send(AppEvent::LogKey("log.engine", vec![engine_kind.label().to_string()]));
+            }
             AppAction::Cancel => {
+                for (_, token) in operations.drain() {
+                    token.cancel();
+                }
+            }
+            AppAction::CancelOperation(op) => {
+                if let Some(token) = operations.remove(&op) {
+                    token.cancel();
+                }
             }
         }
     }
 }
 
-async fn handle_login(ctx: AppContext, event_tx: Sender<AppEvent>) {
+async fn handle_login(ctx: AppContext, event_tx: EventSender) {
     // Strategy 1: Try to get token from gh CLI (easiest, no registration needed)
-    let _ = event_tx.send(AppEvent::Log("SCANNING FOR GH CLI...".to_string()));
+    let _ = event_tx.send(AppEvent::LogKey("log.scanning_gh", Vec::new()));
     
-    match auth::get_token_from_gh_cli() {
+    // A revoked gh token would log in only to be rejected again, so check it first
+    let gh_token = match auth::get_token_from_gh_cli() {
+        Ok(token) if is_rejected(&token) || !token_accepted(&token).await => Err(anyhow::anyhow!("gh token was rejected by GitHub")),
+        result => result,
+    };
+    match gh_token {
         Ok(token) => {
-            let _ = event_tx.send(AppEvent::Log("GH CLI TOKEN FOUND!".to_string()));
+            let _ = event_tx.send(AppEvent::LogKey("log.gh_found", Vec::new()));
             
-            // Store in keyring for future sessions
-            if let Ok(entry) = keyring::Entry::new("native_hub", "github_oauth") {
-                let _ = entry.set_password(&token);
-            }
+            // Store for future sessions
+            let _ = token_store::global().save(&token);
             
             // Update global context
             *ctx.auth_token.write().await = Some(token.clone());
             
-            let _ = event_tx.send(AppEvent::AuthSuccess(token));
+            let _ = event_tx.send(AppEvent::AuthSuccess(token.clone()));
+            check_token_scopes(&token, &event_tx).await;
             return;
         }
         Err(e) => {
-            let _ = event_tx.send(AppEvent::Log(format!("GH CLI not available: {}", e)));
-            let _ = event_tx.send(AppEvent::Log("FALLING BACK TO OAUTH DEVICE FLOW...".to_string()));
+            let _ = event_tx.send(AppEvent::LogKey("log.gh_unavailable", vec![e.to_string()]));
+        }
+    }
+    
+    // Strategy 2: OAuth Web Flow (desktop, requires GITHUB_CLIENT_ID and GITHUB_CLIENT_SECRET)
+    if auth::web_flow_available() {
+        let _ = event_tx.send(AppEvent::LogKey("log.web_flow", Vec::new()));
+        match auth::start_web_flow().await {
+            Ok(flow) => {
+                let _ = event_tx.send(AppEvent::BrowserAuth(flow.authorize_url.clone()));
+                let _ = event_tx.send(AppEvent::LogKey("log.awaiting_redirect", Vec::new()));
+                
+                match flow.finish(&ctx.http_client).await {
+                    Ok(token) => {
+                        let _ = event_tx.send(AppEvent::LogKey("log.token_acquired", Vec::new()));
+                        
+                        if token_store::global().save(&token).is_ok() {
+                            let _ = event_tx.send(AppEvent::LogKey("log.token_stored", Vec::new()));
+                        }
+                        *ctx.auth_token.write().await = Some(token.clone());
+                        
+                        let _ = event_tx.send(AppEvent::AuthSuccess(token.clone()));
+                        check_token_scopes(&token, &event_tx).await;
+                    }
+                    Err(e) => {
+                        let _ = event_tx.send(AppEvent::Error(AppError::from_error("error.auth_failed", &e)));
+                        let _ = event_tx.send(AppEvent::LogKey("log.oauth_aborted", Vec::new()));
+                    }
+                }
+                return;
+            }
+            Err(e) => {
+                let _ = event_tx.send(AppEvent::LogKey("log.web_flow_unavailable", vec![e.to_string()]));
+            }
         }
     }
     
-    // Strategy 2: OAuth Device Flow (requires GITHUB_CLIENT_ID env var)
-    let _ = event_tx.send(AppEvent::Log("EXECUTING PROTOCOL: OAUTH_DEVICE_FLOW".to_string()));
+    let _ = event_tx.send(AppEvent::LogKey("log.device_fallback", Vec::new()));
+    
+    // Strategy 3: OAuth Device Flow (requires GITHUB_CLIENT_ID env var)
+    let _ = event_tx.send(AppEvent::LogKey("log.device_flow", Vec::new()));
 
     match auth::request_device_code(&ctx.http_client).await {
         Ok(res) => {
-            let _ = event_tx.send(AppEvent::Log("DEVICE CODE RECEIVED.".to_string()));
+            let _ = event_tx.send(AppEvent::LogKey("log.device_code", Vec::new()));
             let _ = event_tx.send(AppEvent::DeviceCode(res.clone()));
             
-            let _ = event_tx.send(AppEvent::Log("POLLING FOR TOKEN...".to_string()));
+            let _ = event_tx.send(AppEvent::LogKey("log.polling_token", Vec::new()));
             
             // Poll for token
             match auth::poll_access_token(&ctx.http_client, &res.device_code, res.interval).await {
                 Ok(token) => {
-                    let _ = event_tx.send(AppEvent::Log("ACCESS TOKEN ACQUIRED.".to_string()));
+                    let _ = event_tx.send(AppEvent::LogKey("log.token_acquired", Vec::new()));
                     
-                    // Store in keyring
-                    if let Ok(entry) = keyring::Entry::new("native_hub", "github_oauth") {
-                         let _ = entry.set_password(&token);
-                         let _ = event_tx.send(AppEvent::Log("TOKEN ENCRYPTED & STORED.".to_string()));
+                    // Store for future sessions
+                    if token_store::global().save(&token).is_ok() {
+                         let _ = event_tx.send(AppEvent::LogKey("log.token_stored", Vec::new()));
                     }
 
                     // Update global context
                     *ctx.auth_token.write().await = Some(token.clone());
                     
-                    let _ = event_tx.send(AppEvent::AuthSuccess(token));
+                    let _ = event_tx.send(AppEvent::AuthSuccess(token.clone()));
+                    check_token_scopes(&token, &event_tx).await;
                 }
                 Err(e) => {
-                    let _ = event_tx.send(AppEvent::Error(format!("AUTH FAILED: {}", e)));
-                    let _ = event_tx.send(AppEvent::Log("ABORTING OAUTH FLOW.".to_string()));
+                    let _ = event_tx.send(AppEvent::Error(AppError::from_error("error.auth_failed", &e)));
+                    let _ = event_tx.send(AppEvent::LogKey("log.oauth_aborted", Vec::new()));
                 }
             }
         }
         Err(e) => {
-            let _ = event_tx.send(AppEvent::Error(format!("NETWORK ERROR: {}", e)));
+            let _ = event_tx.send(AppEvent::Error(AppError::Network(ErrorMessage::from("error.network").with_cause(e.to_string()))));
+        }
+    }
+}
+
+/// Scopes needed by the app, with the message key of what fails without them
+const REQUIRED_SCOPES: &[(&str, &str)] = &[
+    ("repo", "scope.repo"),
+    ("read:org", "scope.read_org"),
+];
+
+/// Warn about missing scopes right after login, instead of opaque 403s on merge/close later
+async fn check_token_scopes(token: &str, tx: &EventSender) {
+    let granted = match ApiClient::new(token.to_string()).fetch_token_scopes().await {
+        Ok(Some(granted)) => granted,
+        Ok(None) => return, // Token type without classic scopes
+        Err(e) => {
+            tracing::warn!("Failed to check token scopes: {}", e);
+            return;
+        }
+    };
+    
+    let missing: Vec<(String, &'static str)> = REQUIRED_SCOPES.iter()
+        .filter(|(scope, _)| !has_scope(&granted, scope))
+        .map(|&(scope, needed_for)| (scope.to_string(), needed_for))
+        .collect();
+    if !missing.is_empty() {
+        let _ = tx.send(AppEvent::TokenScopesMissing(missing));
+    }
+}
+
+/// Whether `granted` covers `scope`; write:/admin: scopes imply the read: one
+fn has_scope(granted: &[String], scope: &str) -> bool {
+    granted.iter().any(|g| {
+        g == scope || scope.strip_prefix("read:").is_some_and(|rest| {
+            g.strip_prefix("write:") == Some(rest) || g.strip_prefix("admin:") == Some(rest)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Ops;
+    use crate::engine::cassette::CassetteEngine;
+    use crate::engine::mock::{MockEngine, MockState};
+    
+    fn issue(number: u32, state: &str) -> crate::engine::api_client::Issue {
+        serde_json::from_value(serde_json::json!({
+            "id": number,
+            "number": number,
+            "title": format!("Issue {}", number),
+            "state": state,
+            "user": { "login": "octocat", "avatar_url": "" },
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "html_url": format!("https://github.com/octocat/hello/issues/{}", number),
+        })).unwrap()
+    }
+    
+    fn pull_request(number: u32) -> crate::engine::api_client::PullRequest {
+        let branch = |name: &str| serde_json::json!({ "label": name, "ref": name, "sha": "abc1234" });
+        serde_json::from_value(serde_json::json!({
+            "id": number,
+            "number": number,
+            "title": format!("PR {}", number),
+            "state": "open",
+            "user": { "login": "octocat", "avatar_url": "" },
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "html_url": format!("https://github.com/octocat/hello/pull/{}", number),
+            "head": branch("feature"),
+            "base": branch("main"),
+        })).unwrap()
+    }
+    
+    fn mock_engine() -> MockEngine {
+        MockEngine::new(MockState {
+            issues: vec![issue(1, "open"), issue(2, "open"), issue(3, "closed")],
+            pull_requests: vec![pull_request(4)],
+            login: "octocat".to_string(),
+            ..Default::default()
+        })
+    }
+    
+    /// Scratch directory for one test's cassette, emptied first
+    fn cassette_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("native_hub_cassette_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+    
+    /// (open issue numbers, open PR numbers) reported by one poll, if it reported them
+    async fn poll(engine: &dyn Ops, snapshots: &mut HashMap<&'static str, String>) -> (Option<Vec<u32>>, Option<Vec<u32>>) {
+        let (tx, rx) = EventSender::channel();
+        poll_engine(engine, "octocat/hello", snapshots, &tx).await;
+        let (mut issues, mut prs) = (None, None);
+        for event in rx.try_iter() {
+            match event {
+                AppEvent::IssuesUpdated(_, list) => issues = Some(list.iter().map(|i| i.number).collect()),
+                AppEvent::PullRequestsUpdated(_, list) => prs = Some(list.iter().map(|pr| pr.number).collect()),
+                _ => {}
+            }
         }
+        (issues, prs)
+    }
+    
+    #[tokio::test]
+    async fn poll_reports_only_changes() {
+        let engine = mock_engine();
+        let mut snapshots = HashMap::new();
+        
+        assert_eq!(poll(&engine, &mut snapshots).await, (Some(vec![1, 2]), Some(vec![4])));
+        assert_eq!(poll(&engine, &mut snapshots).await, (None, None));
+        
+        engine.update_issue_state("octocat", "hello", 1, "closed").await.unwrap();
+        assert_eq!(poll(&engine, &mut snapshots).await, (Some(vec![2]), None));
+        
+        engine.merge_pull_request("octocat", "hello", 4, "squash").await.unwrap();
+        assert_eq!(poll(&engine, &mut snapshots).await, (None, Some(vec![])));
+    }
+    
+    #[tokio::test]
+    async fn mock_applies_mutations() {
+        let engine = mock_engine();
+        
+        let comment = engine.create_comment("octocat", "hello", 2, "Looks good").await.unwrap();
+        assert_eq!(comment.user.login, "octocat");
+        let comments = engine.fetch_issue_comments("octocat", "hello", 2).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        
+        assert!(engine.merge_pull_request("octocat", "hello", 4, "merge").await.unwrap().merged);
+        // Merging twice fails like it does on GitHub
+        assert!(engine.merge_pull_request("octocat", "hello", 4, "merge").await.is_err());
+        assert!(engine.state().pull_requests[0].merged);
+    }
+    
+    #[tokio::test]
+    async fn cassette_replays_recorded_poll() {
+        let dir = cassette_dir("poll");
+        
+        let recorder = CassetteEngine::record(Box::new(mock_engine()), &dir);
+        let recorded = poll(&recorder, &mut HashMap::new()).await;
+        
+        // The replay needs nothing but the fixtures
+        let replay = CassetteEngine::replay(&dir);
+        assert_eq!(poll(&replay, &mut HashMap::new()).await, recorded);
+        assert_eq!(recorded, (Some(vec![1, 2]), Some(vec![4])));
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[tokio::test]
+    async fn cassette_replay_fails_for_unrecorded_calls() {
+        let dir = cassette_dir("missing");
+        
+        let recorder = CassetteEngine::record(Box::new(mock_engine()), &dir);
+        recorder.fetch_issues("octocat", "hello", "open", "", &[]).await.unwrap();
+        
+        let replay = CassetteEngine::replay(&dir);
+        assert_eq!(replay.fetch_issues("octocat", "hello", "open", "", &[]).await.unwrap().len(), 2);
+        assert!(replay.fetch_issues("octocat", "hello", "closed", "", &[]).await.is_err());
+        assert!(replay.fetch_pull_requests("octocat", "hello", "open").await.is_err());
+        
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn snapshot_changes_only_on_new_values() {
+        let mut snapshots = HashMap::new();
+        assert!(snapshot_changed(&mut snapshots, "issues", &vec![1, 2]));
+        assert!(!snapshot_changed(&mut snapshots, "issues", &vec![1, 2]));
+        assert!(snapshot_changed(&mut snapshots, "issues", &vec![2]));
+        assert!(snapshot_changed(&mut snapshots, "pulls", &vec![2]));
     }
 }
@@ -0,0 +1,382 @@
+//! Configuration File
+//!
+//! User settings persisted as TOML in the platform config directory
+//! (`~/.config/native_hub/config.toml` on Linux). Loaded once at startup;
+//! missing or invalid files fall back to the defaults.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use crate::app_event::RepoData;
+use crate::engine::EngineKind;
+use crate::i18n::{I18n, Lang};
+use crate::modules::paths;
+
+/// Public GitHub REST endpoint
+const GITHUB_API: &str = "https://api.github.com";
+
+/// Visual theme of the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Cyberpunk, // Neon HUD theme
+    Terminal,  // Translucent terminal look over the retro grid
+}
+
+impl Theme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Cyberpunk => "Cyberpunk",
+            Theme::Terminal => "Terminal",
+        }
+    }
+    
+    pub fn all() -> &'static [Theme] {
+        &[Theme::Cyberpunk, Theme::Terminal]
+    }
+}
+
+/// Background grid, particles, click ripples and CRT overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effects {
+    Off,
+    #[default]
+    Low,  // Static grid and click ripples
+    Full, // Scrolling grid, particles and the CRT overlay as well
+}
+
+impl Effects {
+    pub fn name(&self, i18n: &I18n) -> &'static str {
+        i18n.t(match self {
+            Effects::Off => "effects.off",
+            Effects::Low => "effects.low",
+            Effects::Full => "effects.full",
+        })
+    }
+    
+    pub fn all() -> &'static [Effects] {
+        &[Effects::Off, Effects::Low, Effects::Full]
+    }
+}
+
+/// Named repo search or issue filter combination, listed in the sidebar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SavedSearch {
+    Repos {
+        name: String,
+        query: String,
+    },
+    Issues {
+        name: String,
+        repo: String,           // full_name
+        state: String,          // "open", "closed", "all"
+        milestone: String,      // "" = any, "none", or milestone number
+        labels: Vec<String>,
+    },
+}
+
+impl SavedSearch {
+    pub fn name(&self) -> &str {
+        match self {
+            SavedSearch::Repos { name, .. } | SavedSearch::Issues { name, .. } => name,
+        }
+    }
+}
+
+/// User settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// UI language; None = follow the system locale
+    pub language: Option<Lang>,
+    pub theme: Theme,
+    pub effects: Effects,
+    /// Engine to use; None = probe for `gh` at startup
+    pub engine: Option<EngineKind>,
+    /// Items per page for issue, PR and commit lists (GitHub allows 1-100)
+    pub page_size: u32,
+    /// Seconds between background refreshes of the open repo, 0 = off
+    pub poll_interval: u64,
+    /// GitHub Enterprise Server URL, e.g. `https://github.example.com`; None = github.com
+    pub enterprise_url: Option<String>,
+    pub network: NetworkConfig,
+    /// Repos pinned to the top of the repo list, in pin order (may include other people's repos)
+    pub pinned_repos: Vec<RepoData>,
+    /// Local working copies by repo full_name, used by the command deck's git operations
+    pub local_checkouts: BTreeMap<String, PathBuf>,
+    /// Saved repo searches and issue filters, in the order they were saved
+    pub saved_searches: Vec<SavedSearch>,
+}
+
+/// Timeouts, proxy and TLS settings shared by every HTTP client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Seconds to wait for the TCP/TLS connection
+    pub connect_timeout: u64,
+    /// Seconds a response may stall between reads before it is abandoned
+    pub read_timeout: u64,
+    /// Proxy for all requests; None = HTTPS_PROXY / HTTP_PROXY / NO_PROXY from the environment
+    pub proxy: Option<String>,
+    /// PEM file with extra root certificates, for corporate TLS inspection
+    pub ca_cert: Option<PathBuf>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: 10,
+            read_timeout: 30,
+            proxy: None,
+            ca_cert: None,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Async client builder with these settings applied
+    pub fn client_builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout))
+            .read_timeout(Duration::from_secs(self.read_timeout));
+        if let Some(proxy) = self.proxy() {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(cert) = self.root_certificate() {
+            builder = builder.add_root_certificate(cert);
+        }
+        builder
+    }
+    
+    /// Async client with these settings. It is shared, so requests reuse its
+    /// pooled connections; a new one is only built when the settings change.
+    pub fn client(&self) -> reqwest::Client {
+        static SHARED: Mutex<Option<(NetworkConfig, reqwest::Client)>> = Mutex::new(None);
+        let mut slot = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+        match &*slot {
+            Some((settings, client)) if settings == self => client.clone(),
+            _ => {
+                let client = self.client_builder().build().unwrap_or_default();
+                *slot = Some((self.clone(), client.clone()));
+                client
+            }
+        }
+    }
+    
+    /// Blocking counterpart of `client`
+    pub fn blocking_client(&self) -> reqwest::Result<reqwest::blocking::Client> {
+        static SHARED: Mutex<Option<(NetworkConfig, reqwest::blocking::Client)>> = Mutex::new(None);
+        let mut slot = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+        match &*slot {
+            Some((settings, client)) if settings == self => Ok(client.clone()),
+            _ => {
+                let client = self.blocking_client_builder().build()?;
+                *slot = Some((self.clone(), client.clone()));
+                Ok(client)
+            }
+        }
+    }
+    
+    /// Blocking client builder; it has no per-read timeout, so the read timeout caps the whole request
+    pub fn blocking_client_builder(&self) -> reqwest::blocking::ClientBuilder {
+        let mut builder = reqwest::blocking::Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout))
+            .timeout(Duration::from_secs(self.read_timeout));
+        if let Some(proxy) = self.proxy() {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(cert) = self.root_certificate() {
+            builder = builder.add_root_certificate(cert);
+        }
+        builder
+    }
+    
+    /// Explicit proxy; without one reqwest falls back to the proxy environment variables
+    fn proxy(&self) -> Option<reqwest::Proxy> {
+        let url = self.proxy.as_deref().map(str::trim).filter(|url| !url.is_empty())?;
+        match reqwest::Proxy::all(url) {
+            Ok(proxy) => Some(proxy),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid proxy {}: {}", url, e);
+                None
+            }
+        }
+    }
+    
+    fn root_certificate(&self) -> Option<reqwest::Certificate> {
+        let path = self.ca_cert.as_ref()?;
+        let cert = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|pem| reqwest::Certificate::from_pem(&pem).map_err(anyhow::Error::from));
+        match cert {
+            Ok(cert) => Some(cert),
+            Err(e) => {
+                tracing::warn!("Ignoring CA certificate {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: None,
+            theme: Theme::default(),
+            effects: Effects::default(),
+            engine: None,
+            page_size: 30,
+            poll_interval: 60,
+            enterprise_url: None,
+            network: NetworkConfig::default(),
+            pinned_repos: Vec::new(),
+            local_checkouts: BTreeMap::new(),
+            saved_searches: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// UI language to use, the system's unless one was picked
+    pub fn lang(&self) -> Lang {
+        self.language.unwrap_or_else(Lang::detect)
+    }
+    
+    /// Path of the config file
+    pub fn path() -> PathBuf {
+        paths::config_dir().join("config.toml")
+    }
+    
+    /// Read the config file, falling back to the defaults
+    pub fn load() -> Self {
+        let path = Self::path();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) => return Self::default(),
+        };
+        match toml::from_str(&text) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Ignoring invalid config {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+    
+    /// Write the config file
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create config directory")?;
+        }
+        let text = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        std::fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+    
+    /// REST API root: `api.github.com`, or `<host>/api/v3` on Enterprise Server
+    pub fn api_base(&self) -> String {
+        match self.enterprise_host() {
+            Some(host) => format!("{}/api/v3", host),
+            None => GITHUB_API.to_string(),
+        }
+    }
+    
+    /// GraphQL endpoint matching `api_base`
+    pub fn graphql_url(&self) -> String {
+        match self.enterprise_host() {
+            Some(host) => format!("{}/api/graphql", host),
+            None => format!("{}/graphql", GITHUB_API),
+        }
+    }
+    
+    /// Base URL for raw files on the default branch of `full_name`, ending in `/`
+    pub fn raw_base(&self, full_name: &str) -> String {
+        self.raw_ref_base(full_name, "HEAD")
+    }
+    
+    /// Web UI root: `github.com`, or the Enterprise host
+    pub fn web_base(&self) -> String {
+        self.enterprise_host().unwrap_or("https://github.com").to_string()
+    }
+    
+    /// (HTTPS, SSH) clone URLs of `full_name`
+    pub fn clone_urls(&self, full_name: &str) -> (String, String) {
+        let web = self.web_base();
+        let host = web.split_once("://").map(|(_, host)| host).unwrap_or(&web);
+        (
+            format!("{}/{}.git", web, full_name),
+            format!("git@{}:{}.git", host, full_name),
+        )
+    }
+    
+    /// Raw URL of `path` in `full_name` at `git_ref`
+    pub fn raw_url(&self, full_name: &str, git_ref: &str, path: &str) -> String {
+        format!("{}{}", self.raw_ref_base(full_name, git_ref), encode_path(path))
+    }
+    
+    /// Base URL for raw files of `full_name` at `git_ref`, ending in `/`
+    pub fn raw_ref_base(&self, full_name: &str, git_ref: &str) -> String {
+        match self.enterprise_host() {
+            Some(host) => format!("{}/{}/raw/{}/", host, full_name, git_ref),
+            None => format!("https://raw.githubusercontent.com/{}/{}/", full_name, git_ref),
+        }
+    }
+    
+    /// Web page of `path` in `full_name` at `git_ref`
+    pub fn blob_url(&self, full_name: &str, git_ref: &str, path: &str) -> String {
+        format!("{}/{}/blob/{}/{}", self.web_base(), full_name, git_ref, encode_path(path))
+    }
+    
+    /// Whether `url` is GitHub content that needs the token when it belongs to a
+    /// private repo: raw files, private attachments, anything on the Enterprise host
+    pub fn accepts_token(&self, url: &str) -> bool {
+        let host_of = |url: &str| url.split_once("://").map_or("", |(_, rest)| rest).split(['/', '?', '#']).next().unwrap_or("").to_ascii_lowercase();
+        let host = host_of(url);
+        match self.enterprise_host() {
+            Some(enterprise) => host == host_of(enterprise),
+            None => host == "raw.githubusercontent.com" || host == "private-user-images.githubusercontent.com",
+        }
+    }
+    
+    fn enterprise_host(&self) -> Option<&str> {
+        self.enterprise_url.as_deref()
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+    }
+}
+
+/// Percent-encode each segment of a repository path, keeping the slashes
+pub fn encode_path(path: &str) -> String {
+    path.split('/').map(|segment| urlencoding::encode(segment).into_owned()).collect::<Vec<_>>().join("/")
+}
+
+static CURRENT: OnceLock<RwLock<Config>> = OnceLock::new();
+
+fn cell() -> &'static RwLock<Config> {
+    CURRENT.get_or_init(|| RwLock::new(Config::load()))
+}
+
+/// Load the config file; called once at startup
+pub fn init() -> Config {
+    current()
+}
+
+/// Snapshot of the current settings
+pub fn current() -> Config {
+    cell().read().map(|c| c.clone()).unwrap_or_default()
+}
+
+/// Replace the settings and persist them
+pub fn update(config: Config) -> Result<()> {
+    config.save()?;
+    if let Ok(mut current) = cell().write() {
+        *current = config;
+    }
+    Ok(())
+}
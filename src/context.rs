@@ -1,7 +1,11 @@
-use keyring::Entry;
 use reqwest::Client;
 use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::time::Instant;
 use tokio::sync::RwLock;
+use crate::engine::EngineKind;
+use crate::engine::api_client::RateLimit;
+use crate::ui::avatar_cache::AvatarCache;
 
 /// Application-wide context holding global state
 #[derive(Clone)]
@@ -10,25 +14,31 @@ pub struct AppContext {
     pub http_client: Client,
     /// Current authentication token (if logged in)
     pub auth_token: Arc<RwLock<Option<String>>>,
+    /// Engine selected at startup (None until probing finished)
+    pub engine: Arc<RwLock<Option<EngineKind>>>,
+    /// When the app started, for the uptime display
+    pub started_at: Instant,
+    /// Last known core API quota (None until first checked)
+    pub rate_limit: Arc<RwLock<Option<RateLimit>>>,
+    /// Backend tasks currently holding a permit and running
+    pub active_tasks: Arc<AtomicUsize>,
+    /// User avatar textures by login, shared by every panel
+    pub avatars: AvatarCache,
 }
 
 impl AppContext {
     pub fn new() -> Self {
         Self {
-            http_client: Client::builder()
+            http_client: crate::config::current().network.client_builder()
                 .user_agent("NativeHub/0.1.0")
                 .build()
                 .unwrap_or_default(),
             auth_token: Arc::new(RwLock::new(None)),
-        }
-    }
-
-    /// Try to load token from system keyring on startup
-    pub async fn load_token_from_keyring(&self) {
-        let entry = Entry::new("native_hub", "github_oauth");
-        if let Ok(token) = entry.and_then(|e| e.get_password()) {
-            *self.auth_token.write().await = Some(token);
-            tracing::info!("Token loaded from keyring");
+            engine: Arc::new(RwLock::new(None)),
+            started_at: Instant::now(),
+            rate_limit: Arc::new(RwLock::new(None)),
+            active_tasks: Arc::new(AtomicUsize::new(0)),
+            avatars: AvatarCache::default(),
         }
     }
 }
@@ -4,11 +4,18 @@
 //! This is Android-compatible (no `gh` CLI dependency).
 
 use anyhow::{Context, Result};
-use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
-use serde::Deserialize;
+use async_trait::async_trait;
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, LINK, RANGE, USER_AGENT};
+use serde::de::DeserializeOwned;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::watch;
+use serde::{Deserialize, Serialize};
+use crate::app_event::RepoData;
+use super::Ops;
 
 /// A file or directory node in a repository
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileNode {
     pub name: String,
     pub path: String,
@@ -18,20 +25,377 @@ pub struct FileNode {
     pub download_url: Option<String>,
     #[serde(default)]
     pub size: u64,
+    #[serde(default)]
+    pub sha: String, // Blob sha for files, tree sha for directories
+}
+
+/// The Contents API silently truncates directory listings at this many entries
+const CONTENTS_API_LIMIT: usize = 1000;
+
+/// File name a zipball download of `repo` at `git_ref` is saved under
+pub fn zipball_file_name(repo: &str, git_ref: &str) -> String {
+    let label = if git_ref.is_empty() { "HEAD" } else { git_ref };
+    format!("{}-{}.zip", repo, label.replace('/', "-"))
+}
+
+/// Sibling of `path` a download is written to until it is complete
+pub fn partial_path(path: &std::path::Path) -> std::path::PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Stream a response body to `path` chunk by chunk, reporting (written, total)
+/// 
+/// The data goes to `partial_path(path)` and only replaces `path` once all of
+/// it is written, so a failed or cancelled download leaves an existing file
+/// alone. The partial file is left for the caller to remove.
+async fn stream_to_file(
+    mut response: reqwest::Response,
+    path: &std::path::Path,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+    
+    let partial = partial_path(path);
+    let mut file = tokio::fs::File::create(&partial)
+        .await
+        .with_context(|| format!("Failed to create {}", partial.display()))?;
+    
+    let total = response.content_length();
+    let mut written = 0u64;
+    while let Some(chunk) = response.chunk().await.context("Failed to read download data")? {
+        file.write_all(&chunk).await.context("Failed to write download")?;
+        written += chunk.len() as u64;
+        on_progress(written, total);
+    }
+    file.flush().await.context("Failed to write download")?;
+    drop(file);
+    
+    tokio::fs::rename(&partial, path)
+        .await
+        .with_context(|| format!("Failed to move the download to {}", path.display()))
+}
+
+/// Upper bound on the pages `fetch_all_pages` follows for one list
+const MAX_PAGES: u64 = 10;
+
+/// URL of the `rel` relation in a `Link` pagination header
+fn link_url(header: &str, rel: &str) -> Option<String> {
+    let rel = format!("rel=\"{}\"", rel);
+    header.split(',')
+        .find(|part| part.contains(&rel))
+        .and_then(|part| {
+            let start = part.find('<')? + 1;
+            let end = part.find('>')?;
+            Some(part[start..end].to_string())
+        })
+}
+
+/// Value of the `page` query parameter of a paginated URL
+fn page_param(url: &str) -> Option<u64> {
+    url.split(['?', '&']).find_map(|kv| kv.strip_prefix("page=")?.parse().ok())
+}
+
+/// File node for a git tree entry whose `path` is relative to the repository root;
+/// `raw_base` is `Config::raw_ref_base` of the tree's repository and ref
+fn tree_node(raw_base: &str, name: String, entry: GitTreeEntry) -> FileNode {
+    let is_dir = entry.entry_type == "tree";
+    FileNode {
+        name,
+        node_type: if is_dir { "dir" } else { "file" }.to_string(),
+        download_url: (!is_dir).then(|| format!("{}{}", raw_base, crate::config::encode_path(&entry.path))),
+        size: entry.size.unwrap_or(0),
+        sha: entry.sha,
+        path: entry.path,
+    }
+}
+
+/// Largest file loaded into memory for the code viewer; bigger files must be downloaded
+pub const MAX_INLINE_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Extensions never shown as text, so only their first bytes are fetched
+const BINARY_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "bmp", "ico", "pdf", "zip", "gz", "tgz", "xz", "bz2", "7z", "rar",
+    "jar", "exe", "dll", "so", "dylib", "a", "o", "class", "wasm", "woff", "woff2", "ttf", "otf",
+    "mp3", "mp4", "mov", "wav", "ogg", "flac",
+];
+
+/// Longest rate limit pause waited out before retrying; longer ones fail the request
+const MAX_AUTO_WAIT: u64 = 120;
+/// Pause after a secondary rate limit that didn't say how long to wait
+const SECONDARY_LIMIT_WAIT: u64 = 60;
+/// Times one request is retried after rate limit pauses
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Unix time until which GitHub asked for no more requests, shared by every client
+fn throttle() -> &'static watch::Sender<u64> {
+    static THROTTLE: OnceLock<watch::Sender<u64>> = OnceLock::new();
+    THROTTLE.get_or_init(|| watch::channel(0).0)
+}
+
+/// Follow rate limit pauses: the unix time requests resume (in the past when not paused)
+pub fn watch_throttle() -> watch::Receiver<u64> {
+    throttle().subscribe()
+}
+
+/// Bumped on every 401 response, shared by every client
+fn auth_rejections() -> &'static watch::Sender<u64> {
+    static REJECTIONS: OnceLock<watch::Sender<u64>> = OnceLock::new();
+    REJECTIONS.get_or_init(|| watch::channel(0).0)
+}
+
+/// Notified whenever GitHub rejects the credentials of a request (401),
+/// which is how an expired or revoked token shows up
+pub fn watch_auth_rejections() -> watch::Receiver<u64> {
+    auth_rejections().subscribe()
+}
+
+/// Seconds GitHub asks to wait before retrying a rate limited response:
+/// its `Retry-After`, or until the quota resets once it is used up.
+/// Other 403s (no permission, or a secondary limit that names no time) give
+/// None; see `secondary_limit` for the latter.
+fn rate_limit_wait(response: &reqwest::Response) -> Option<u64> {
+    if !matches!(response.status().as_u16(), 403 | 429) {
+        return None;
+    }
+    let header = |name: &str| response.headers().get(name)?.to_str().ok()?.parse::<u64>().ok();
+    header("retry-after").or_else(|| {
+        (header("x-ratelimit-remaining")? == 0).then(|| header("x-ratelimit-reset").unwrap_or(0).saturating_sub(unix_now()))
+    })
+}
+
+/// Whether a 403 without rate limit headers is a secondary rate limit, told
+/// by its message. Reading the body consumes the response, so an equivalent
+/// one is rebuilt for the caller.
+async fn secondary_limit(response: reqwest::Response) -> reqwest::Result<(reqwest::Response, bool)> {
+    let status = response.status();
+    let version = response.version();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+    let message = String::from_utf8_lossy(&body).to_lowercase();
+    let limited = message.contains("secondary rate limit") || message.contains("abuse detection");
+    
+    let mut rebuilt = http::Response::new(body);
+    *rebuilt.status_mut() = status;
+    *rebuilt.version_mut() = version;
+    *rebuilt.headers_mut() = headers;
+    Ok((rebuilt.into(), limited))
+}
+
+/// `send` that waits out rate limit pauses GitHub asks for and tries again,
+/// so a burst that trips abuse detection slows down instead of failing
+trait SendThrottled {
+    async fn send_throttled(self) -> reqwest::Result<reqwest::Response>;
+}
+
+impl SendThrottled for reqwest::RequestBuilder {
+    async fn send_throttled(self) -> reqwest::Result<reqwest::Response> {
+        let mut retries = 0;
+        loop {
+            // GitHub wants all requests to pause, not just the one that was refused
+            let wait = throttle().borrow().saturating_sub(unix_now());
+            if wait > 0 {
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+            }
+            
+            // Streamed bodies can't be sent twice
+            let Some(request) = self.try_clone() else { return self.send().await };
+            let response = request.send().await?;
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                auth_rejections().send_modify(|count| *count += 1);
+            }
+            let mut wait = rate_limit_wait(&response);
+            let response = if wait.is_none() && response.status() == reqwest::StatusCode::FORBIDDEN {
+                // GitHub's advice when a secondary limit names no time: wait at least a minute
+                let (response, limited) = secondary_limit(response).await?;
+                wait = limited.then_some(SECONDARY_LIMIT_WAIT);
+                response
+            } else {
+                response
+            };
+            match wait {
+                Some(wait) if wait <= MAX_AUTO_WAIT && retries < MAX_RATE_LIMIT_RETRIES => {
+                    retries += 1;
+                    tracing::warn!("Rate limited by GitHub, retrying in {}s ({}/{})", wait, retries, MAX_RATE_LIMIT_RETRIES);
+                    let until = unix_now() + wait.max(1);
+                    throttle().send_if_modified(|current| {
+                        let later = until > *current;
+                        if later {
+                            *current = until;
+                        }
+                        later
+                    });
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+}
+
+/// Failed REST API request, with what GitHub's JSON error body says went wrong
+#[derive(Debug, thiserror::Error)]
+#[error("{status}: {}", self.summary())]
+pub struct GitHubError {
+    pub status: reqwest::StatusCode,
+    pub message: String,
+    pub errors: Vec<String>, // Details from `errors[]`, e.g. which field was invalid
+    pub documentation_url: Option<String>,
+    pub retry_at: Option<u64>, // Unix time a rate limited request may be retried, from the headers
+}
+
+/// Body GitHub sends with a failed request
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+    documentation_url: Option<String>,
+}
+
+impl GitHubError {
+    /// Read the error out of a failed `response`
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_at = rate_limit_wait(&response).map(|wait| unix_now() + wait);
+        let body = response.text().await.unwrap_or_default();
+        Self::parse(status, &body, retry_at)
+    }
+    
+    fn parse(status: reqwest::StatusCode, body: &str, retry_at: Option<u64>) -> Self {
+        match serde_json::from_str::<ErrorBody>(body) {
+            Ok(body) => Self {
+                status,
+                message: body.message,
+                errors: body.errors.iter().map(error_detail).collect(),
+                documentation_url: body.documentation_url,
+                retry_at,
+            },
+            // Not JSON (proxy or raw host pages): the status says as much as the body would
+            Err(_) => Self {
+                status,
+                message: status.canonical_reason().unwrap_or_default().to_string(),
+                errors: Vec::new(),
+                documentation_url: None,
+                retry_at,
+            },
+        }
+    }
+    
+    /// Refused by the primary or a secondary (abuse detection) rate limit
+    /// rather than for lack of permission
+    pub fn is_rate_limit(&self) -> bool {
+        let message = self.message.to_lowercase();
+        matches!(self.status.as_u16(), 403 | 429)
+            && (self.retry_at.is_some() || message.contains("rate limit") || message.contains("abuse"))
+    }
+    
+    /// The message followed by any details, for showing to the user
+    pub fn summary(&self) -> String {
+        if self.errors.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} ({})", self.message, self.errors.join("; "))
+        }
+    }
+}
+
+/// One entry of `errors[]`: a plain string, or a `{resource, field, code}`
+/// object that may carry its own `message`
+fn error_detail(error: &serde_json::Value) -> String {
+    if let Some(text) = error.as_str().or_else(|| error["message"].as_str()) {
+        return text.to_string();
+    }
+    let field = error["field"].as_str().unwrap_or("");
+    match error["code"].as_str().unwrap_or("") {
+        "missing" => format!("{} does not exist", error["resource"].as_str().unwrap_or(field)),
+        "missing_field" => format!("{} is required", field),
+        "invalid" => format!("{} is invalid", field),
+        "already_exists" => format!("{} already exists", field),
+        code => format!("{} {}", field, code).trim().to_string(),
+    }
+}
+
+/// Part of a file's content, see `ApiClient::fetch_file_part`
+pub struct FilePart {
+    pub data: Vec<u8>,
+    pub truncated: bool, // The file goes on past `data`
+    pub content_type: Option<String>,
+}
+
+impl FilePart {
+    /// The bytes as text, lossily decoded, and how many of them it covers.
+    /// A character cut in half at the end of a truncated part is left for the next one.
+    pub fn text(&self) -> (String, usize) {
+        let len = match std::str::from_utf8(&self.data) {
+            Err(e) if self.truncated && e.error_len().is_none() => e.valid_up_to(),
+            _ => self.data.len(),
+        };
+        (String::from_utf8_lossy(&self.data[..len]).into_owned(), len)
+    }
+}
+
+/// Whether `name` has the extension of a binary format
+pub fn has_binary_extension(name: &str) -> bool {
+    name.rsplit_once('.')
+        .is_some_and(|(_, ext)| BINARY_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+impl FilePart {
+    /// Binary rather than text: a binary extension or media type, or a NUL
+    /// byte in the first 8000 bytes (the check git uses)
+    pub fn is_binary(&self, name: &str) -> bool {
+        let media = self.content_type.as_deref()
+            .is_some_and(|kind| ["image/", "audio/", "video/", "font/"].iter().any(|prefix| kind.starts_with(prefix)) && !kind.starts_with("image/svg"));
+        has_binary_extension(name) || media || self.data.iter().take(8000).any(|&byte| byte == 0)
+    }
+}
+
+/// Response of the Git Trees API
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    tree: Vec<GitTreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    entry_type: String, // "blob", "tree" or "commit" (submodule)
+    sha: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 /// HTTP-based GitHub API client
 pub struct ApiClient {
     client: reqwest::Client,
     token: String,
+    api_base: String,    // REST root, differs on GitHub Enterprise Server
+    graphql_url: String,
+    page_size: u32,      // Items per page of issue, PR and commit lists
 }
 
 impl ApiClient {
-    /// Create a new API client with the given OAuth token
+    /// Create a new API client with the given OAuth token, using the endpoint,
+    /// page size and network settings from the current config
     pub fn new(token: String) -> Self {
+        let config = crate::config::current();
         Self {
-            client: reqwest::Client::new(),
+            client: config.network.client(), // Shared, so every ApiClient reuses the same connections
             token,
+            api_base: config.api_base(),
+            graphql_url: config.graphql_url(),
+            page_size: config.page_size.clamp(1, 100),
         }
     }
     
@@ -41,27 +405,30 @@ impl ApiClient {
     /// * `owner` - Repository owner (e.g., "octocat")
     /// * `repo` - Repository name (e.g., "Hello-World")
     /// * `path` - Path within the repo (e.g., "" for root, "src" for src folder)
-    pub async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str) -> Result<Vec<FileNode>> {
-        let url = if path.is_empty() {
-            format!("https://api.github.com/repos/{}/{}/contents", owner, repo)
+    /// * `git_ref` - Branch, tag, or SHA to browse ("" for the default branch)
+    pub async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        let mut url = if path.is_empty() {
+            format!("{}/repos/{}/{}/contents", self.api_base, owner, repo)
         } else {
-            format!("https://api.github.com/repos/{}/{}/contents/{}", owner, repo, path)
+            format!("{}/repos/{}/{}/contents/{}", self.api_base, owner, repo, path)
         };
         
+        if !git_ref.is_empty() {
+            url.push_str(&format!("?ref={}", urlencoding::encode(git_ref)));
+        }
+        
         let response = self.client
             .get(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
             .context("Failed to send request to GitHub API")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("GitHub API returned {}: {}", status, body);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch directory");
         }
         
         let nodes: Vec<FileNode> = response
@@ -69,33 +436,82 @@ impl ApiClient {
             .await
             .context("Failed to parse file tree response")?;
         
+        // Huge directories are cut off by the Contents API; list them from the git tree instead
+        if nodes.len() >= CONTENTS_API_LIMIT {
+            return self.fetch_tree_listing(owner, repo, path, git_ref).await;
+        }
+        
         Ok(nodes)
     }
     
-    /// Fetch raw file content from a download URL
-    pub async fn fetch_file_content(&self, download_url: &str) -> Result<String> {
-        let response = self.client
-            .get(download_url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(USER_AGENT, "NativeHub-Rust-Client")
-            .send()
-            .await
-            .context("Failed to fetch file content")?;
+    /// List a directory via `GET /git/trees/{ref}?recursive=1`, grouping the
+    /// flat recursive listing down to the direct children of `path`
+    async fn fetch_tree_listing(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        let tree_ref = if git_ref.is_empty() { "HEAD" } else { git_ref };
+        let tree = self.fetch_git_tree(owner, repo, tree_ref, true).await?;
         
-        if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to fetch file: {}", status);
+        if tree.truncated {
+            tracing::warn!("Git tree of {}/{} is truncated, walking down to /{} instead", owner, repo, path);
+            return self.walk_tree(owner, repo, path, tree_ref).await;
         }
         
-    response
-            .text()
-            .await
-            .context("Failed to read file content")
+        let raw_base = crate::config::current().raw_ref_base(&format!("{}/{}", owner, repo), tree_ref);
+        let prefix = if path.is_empty() { String::new() } else { format!("{}/", path.trim_end_matches('/')) };
+        let nodes = tree.tree.into_iter()
+            .filter_map(|entry| {
+                // Keep only direct children of `path`
+                let name = entry.path.strip_prefix(&prefix)?;
+                if name.is_empty() || name.contains('/') {
+                    return None;
+                }
+                let name = name.to_string();
+                Some(tree_node(&raw_base, name, entry))
+            })
+            .collect();
+        
+        Ok(nodes)
     }
     
-    /// Fetch repository info (description, stars, forks, topics)
-    pub async fn fetch_repo_info(&self, owner: &str, repo: &str) -> Result<RepoInfo> {
-        let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    /// List `path` by descending one non-recursive tree per path segment,
+    /// for repositories whose recursive tree is too large to be returned whole
+    async fn walk_tree(&self, owner: &str, repo: &str, path: &str, tree_ref: &str) -> Result<Vec<FileNode>> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let total = segments.len() as u64 + 1;
+        
+        let mut tree = self.fetch_git_tree(owner, repo, tree_ref, false).await?;
+        for (depth, segment) in segments.iter().enumerate() {
+            super::report_progress(depth as u64 + 1, Some(total));
+            let sha = tree.tree.iter()
+                .find(|entry| entry.path == *segment && entry.entry_type == "tree")
+                .map(|entry| entry.sha.clone())
+                .with_context(|| format!("Directory not found in git tree: {}", path))?;
+            tree = self.fetch_git_tree(owner, repo, &sha, false).await?;
+        }
+        super::report_progress(total, Some(total));
+        
+        // Entries of a non-recursive tree are relative to it
+        let raw_base = crate::config::current().raw_ref_base(&format!("{}/{}", owner, repo), tree_ref);
+        let prefix = if segments.is_empty() { String::new() } else { format!("{}/", segments.join("/")) };
+        let nodes = tree.tree.into_iter()
+            .map(|mut entry| {
+                let name = std::mem::take(&mut entry.path);
+                entry.path = format!("{}{}", prefix, name);
+                tree_node(&raw_base, name, entry)
+            })
+            .collect();
+        
+        Ok(nodes)
+    }
+    
+    /// Fetch a git tree by ref or sha, optionally with all nested entries
+    async fn fetch_git_tree(&self, owner: &str, repo: &str, tree_ref: &str, recursive: bool) -> Result<GitTree> {
+        let mut url = format!(
+            "{}/repos/{}/{}/git/trees/{}",
+            self.api_base, owner, repo, urlencoding::encode(tree_ref)
+        );
+        if recursive {
+            url.push_str("?recursive=1");
+        }
         
         let response = self.client
             .get(&url)
@@ -103,103 +519,221 @@ impl ApiClient {
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to fetch repo info")?;
+            .context("Failed to fetch git tree")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to fetch repo info: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch git tree");
         }
         
         response
             .json()
             .await
-            .context("Failed to parse repo info")
+            .context("Failed to parse git tree")
     }
-    
-    /// Search repositories on GitHub
-    /// 
-    /// # Arguments
-    /// * `query` - Search query (e.g., "rust async")
-    /// * `sort` - Sort by: "stars", "forks", "help-wanted-issues", "updated" (optional)
-    /// * `per_page` - Results per page (max 100)
-    pub async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
-        let mut url = format!(
-            "https://api.github.com/search/repositories?q={}&per_page={}",
-            urlencoding::encode(query),
-            per_page.min(100)
-        );
         
-        if let Some(s) = sort {
-            url.push_str(&format!("&sort={}", s));
+    /// GET every page of a list endpoint by following `Link: rel="next"`,
+    /// reporting (page, last page) progress
+    async fn fetch_all_pages<T: DeserializeOwned>(&self, url: &str, what: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut next = Some(url.to_string());
+        let mut page = 0;
+        let mut last_page = None;
+        
+        while let Some(url) = next.take() {
+            let response = self.client
+                .get(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.token))
+                .header(ACCEPT, "application/vnd.github+json")
+                .header(USER_AGENT, "NativeHub-Rust-Client")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+                .send_throttled()
+                .await
+                .with_context(|| format!("Failed to fetch {}", what))?;
+            
+            if !response.status().is_success() {
+                return Err(GitHubError::from_response(response).await).with_context(|| format!("Failed to fetch {}", what));
+            }
+            
+            let links = response.headers()
+                .get(LINK)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            
+            let mut batch: Vec<T> = response
+                .json()
+                .await
+                .with_context(|| format!("Failed to parse {}", what))?;
+            items.append(&mut batch);
+            
+            page += 1;
+            if let Some(last) = link_url(&links, "last").as_deref().and_then(page_param) {
+                last_page = Some(last.min(MAX_PAGES));
+            }
+            super::report_progress(page, Some(last_page.unwrap_or(page).max(page)));
+            
+            if page < MAX_PAGES {
+                next = link_url(&links, "next");
+            }
         }
         
+        Ok(items)
+    }
+    
+    /// Fetch the README shown on the repository's page as raw text (None if there is none)
+    pub async fn fetch_readme(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let url = format!("{}/repos/{}/{}/readme", self.api_base, owner, repo);
+        
         let response = self.client
             .get(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
-            .header(ACCEPT, "application/vnd.github+json")
+            .header(ACCEPT, "application/vnd.github.raw")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to search repositories")?;
+            .context("Failed to fetch README")?;
         
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Search failed {}: {}", status, body);
+        match response.status().as_u16() {
+            404 => Ok(None),
+            _ if response.status().is_success() => response.text().await.map(Some).context("Failed to read README"),
+            _ => Err(GitHubError::from_response(response).await).context("Failed to fetch README"),
         }
-        
-        response
-            .json()
-            .await
-            .context("Failed to parse search results")
     }
     
-    // ========================================================================
-    // Issues API
-    // ========================================================================
-    
-    /// Fetch issues for a repository
+    /// Download the zipball of a repository at a ref into `dest_dir`
     /// 
-    /// # Arguments
-    /// * `owner` - Repository owner
-    /// * `repo` - Repository name
-    /// * `state` - "open", "closed", or "all"
-    pub async fn fetch_issues(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<Issue>> {
+    /// The archive is streamed to disk chunk by chunk; `on_progress` is called
+    /// with (bytes written, total bytes if known). Returns the written file path.
+    pub async fn download_zipball(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+        dest_dir: &std::path::Path,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<std::path::PathBuf> {
         let url = format!(
-            "https://api.github.com/repos/{}/{}/issues?state={}&per_page=30",
-            owner, repo, state
+            "{}/repos/{}/{}/zipball/{}",
+            self.api_base, owner, repo, urlencoding::encode(git_ref)
         );
         
         let response = self.client
-            .get(&url)
+            .get(url.trim_end_matches('/'))
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to fetch issues")?;
+            .context("Failed to download archive")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to fetch issues: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Failed to download archive");
         }
         
-        response
-            .json()
+        let path = dest_dir.join(zipball_file_name(repo, git_ref));
+        
+        tokio::fs::create_dir_all(dest_dir)
             .await
-            .context("Failed to parse issues")
+            .context("Failed to create download directory")?;
+        stream_to_file(response, &path, on_progress).await?;
+        
+        Ok(path)
     }
     
-    /// Fetch comments for an issue
-    pub async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/issues/{}/comments",
-            owner, repo, issue_number
-        );
+    /// Download a file's raw bytes to `path`, byte for byte
+    /// 
+    /// Uses the Git Blobs API when the blob sha is known, otherwise the
+    /// contents `download_url`, streaming it to disk without holding it in memory.
+    pub async fn download_file(
+        &self,
+        owner: &str,
+        repo: &str,
+        file: &crate::app_event::FileNode,
+        path: &std::path::Path,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<()> {
+        let request = if !file.sha.is_empty() {
+            let url = format!("{}/repos/{}/{}/git/blobs/{}", self.api_base, owner, repo, file.sha);
+            self.client
+                .get(&url)
+                .header(ACCEPT, "application/vnd.github.raw+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+        } else if let Some(url) = &file.download_url {
+            self.client.get(url)
+        } else {
+            anyhow::bail!("No download source for {}", file.path);
+        };
+        
+        let response = request
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .send_throttled()
+            .await
+            .context("Failed to download file")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to download file");
+        }
+        
+        stream_to_file(response, path, on_progress).await
+    }
+    
+    /// Fetch up to `limit` bytes of a file starting at byte `offset`, by blob
+    /// sha when known, otherwise from its `download_url`. The rest of the
+    /// file is not read.
+    pub async fn fetch_file_part(&self, owner: &str, repo: &str, file: &FileNode, offset: u64, limit: u64) -> Result<FilePart> {
+        let request = if !file.sha.is_empty() {
+            let url = format!("{}/repos/{}/{}/git/blobs/{}", self.api_base, owner, repo, file.sha);
+            self.client
+                .get(&url)
+                .header(ACCEPT, "application/vnd.github.raw+json")
+                .header("X-GitHub-Api-Version", "2022-11-28")
+        } else if let Some(url) = &file.download_url {
+            self.client.get(url)
+        } else {
+            anyhow::bail!("No download source for {}", file.path);
+        };
+        
+        let request = if offset > 0 { request.header(RANGE, format!("bytes={}-", offset)) } else { request };
+        let mut response = request
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .send_throttled()
+            .await
+            .context("Failed to fetch file")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch file");
+        }
+        
+        let content_type = response.headers().get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        // A server that ignores the range sends the whole file; skip to the offset ourselves
+        let mut skip = if response.status() == reqwest::StatusCode::PARTIAL_CONTENT { 0 } else { offset as usize };
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await.context("Failed to read file")? {
+            let skipped = skip.min(chunk.len());
+            skip -= skipped;
+            let chunk = &chunk[skipped..];
+            let room = (limit as usize).saturating_sub(data.len());
+            if chunk.len() > room {
+                data.extend_from_slice(&chunk[..room]);
+                return Ok(FilePart { data, truncated: true, content_type });
+            }
+            data.extend_from_slice(chunk);
+        }
+        
+        Ok(FilePart { data, truncated: false, content_type })
+    }
+    
+    /// Fetch repository info (description, stars, forks, topics)
+    pub async fn fetch_repo_info(&self, owner: &str, repo: &str) -> Result<RepoInfo> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
         
         let response = self.client
             .get(&url)
@@ -207,90 +741,94 @@ impl ApiClient {
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to fetch comments")?;
+            .context("Failed to fetch repo info")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to fetch comments: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch repo info");
         }
         
         response
             .json()
             .await
-            .context("Failed to parse comments")
+            .context("Failed to parse repo info")
     }
     
-    /// Create a comment on an issue
-    pub async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/issues/{}/comments",
-            owner, repo, issue_number
-        );
+    /// Core API quota of the token; querying it doesn't count against the limit
+    pub async fn fetch_rate_limit(&self) -> Result<RateLimit> {
+        let url = format!("{}/rate_limit", self.api_base);
         
         let response = self.client
-            .post(&url)
+            .get(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .json(&serde_json::json!({ "body": body }))
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to create comment")?;
+            .context("Failed to fetch rate limit")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to create comment {}: {}", status, body);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch rate limit");
         }
         
-        response
+        let status: RateLimitStatus = response
             .json()
             .await
-            .context("Failed to parse created comment")
+            .context("Failed to parse rate limit")?;
+        Ok(status.resources.core)
     }
     
-    /// Close or reopen an issue
-    pub async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/issues/{}",
-            owner, repo, issue_number
+    /// Search repositories on GitHub
+    /// 
+    /// # Arguments
+    /// * `query` - Search query (e.g., "rust async")
+    /// * `sort` - Sort by: "stars", "forks", "help-wanted-issues", "updated" (optional)
+    /// * `per_page` - Results per page (max 100)
+    pub async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        let mut url = format!(
+            "{}/search/repositories?q={}&per_page={}",
+            self.api_base, urlencoding::encode(query),
+            per_page.min(100)
         );
         
+        if let Some(s) = sort {
+            url.push_str(&format!("&sort={}", s));
+        }
+        
         let response = self.client
-            .patch(&url)
+            .get(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .json(&serde_json::json!({ "state": state }))
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to update issue")?;
+            .context("Failed to search repositories")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to update issue: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Search failed");
         }
         
         response
             .json()
             .await
-            .context("Failed to parse updated issue")
+            .context("Failed to parse search results")
     }
     
-    // ========================================================================
-    // Pull Request API
-    // ========================================================================
-    
-    /// Fetch pull requests for a repository
-    pub async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+    /// Fetch tags for a repository (most recent first), following pagination
+    pub async fn fetch_tags(&self, owner: &str, repo: &str) -> Result<Vec<Tag>> {
         let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls?state={}&per_page=30",
-            owner, repo, state
+            "{}/repos/{}/{}/tags?per_page=100",
+            self.api_base, owner, repo
         );
+        self.fetch_all_pages(&url, "tags").await
+    }
+    
+    /// Fetch the language breakdown of a repository as (language, bytes), largest first
+    pub async fn fetch_languages(&self, owner: &str, repo: &str) -> Result<Vec<(String, u64)>> {
+        let url = format!("{}/repos/{}/{}/languages", self.api_base, owner, repo);
         
         let response = self.client
             .get(&url)
@@ -298,78 +836,1308 @@ impl ApiClient {
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to fetch pull requests")?;
+            .context("Failed to fetch languages")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to fetch PRs: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch languages");
         }
         
-        response
+        let languages: std::collections::HashMap<String, u64> = response
             .json()
             .await
-            .context("Failed to parse pull requests")
+            .context("Failed to parse languages")?;
+        
+        let mut languages: Vec<(String, u64)> = languages.into_iter().collect();
+        languages.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+        Ok(languages)
     }
     
-    /// Merge a pull request
-    pub async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+    /// Fetch branches of a repository, following pagination
+    pub async fn fetch_branches(&self, owner: &str, repo: &str) -> Result<Vec<Branch>> {
         let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}/merge",
-            owner, repo, pr_number
+            "{}/repos/{}/{}/branches?per_page=100",
+            self.api_base, owner, repo
         );
+        self.fetch_all_pages(&url, "branches").await
+    }
+    
+    /// Fetch the topics of a repository
+    pub async fn fetch_topics(&self, owner: &str, repo: &str) -> Result<Vec<String>> {
+        let url = format!("{}/repos/{}/{}/topics", self.api_base, owner, repo);
         
         let response = self.client
-            .put(&url)
+            .get(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .json(&serde_json::json!({ "merge_method": merge_method }))
-            .send()
+            .send_throttled()
             .await
-            .context("Failed to merge pull request")?;
+            .context("Failed to fetch topics")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to merge PR {}: {}", status, body);
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch topics");
         }
         
-        response
+        let topics: RepoTopics = response
             .json()
             .await
-            .context("Failed to parse merge result")
+            .context("Failed to parse topics")?;
+        
+        Ok(topics.names)
     }
     
-    /// Close a pull request
-    pub async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
-        );
+    /// Replace all topics of a repository, returning the stored set
+    pub async fn replace_topics(&self, owner: &str, repo: &str, names: &[String]) -> Result<Vec<String>> {
+        let url = format!("{}/repos/{}/{}/topics", self.api_base, owner, repo);
         
         let response = self.client
-            .patch(&url)
+            .put(&url)
             .header(AUTHORIZATION, format!("Bearer {}", self.token))
             .header(ACCEPT, "application/vnd.github+json")
             .header(USER_AGENT, "NativeHub-Rust-Client")
             .header("X-GitHub-Api-Version", "2022-11-28")
-            .json(&serde_json::json!({ "state": "closed" }))
-            .send()
+            .json(&serde_json::json!({ "names": names }))
+            .send_throttled()
             .await
-            .context("Failed to close pull request")?;
+            .context("Failed to update topics")?;
         
         if !response.status().is_success() {
-            let status = response.status();
-            anyhow::bail!("Failed to close PR: {}", status);
+            return Err(GitHubError::from_response(response).await).context("Failed to update topics");
         }
         
-        response
+        let topics: RepoTopics = response
             .json()
             .await
-            .context("Failed to parse closed PR")
+            .context("Failed to parse topics")?;
+        
+        Ok(topics.names)
+    }
+    
+    /// Fork a repository into the authenticated user's account
+    /// 
+    /// GitHub creates forks asynchronously (202 Accepted); the returned repo
+    /// may take a few seconds before its contents are browsable.
+    pub async fn fork_repo(&self, owner: &str, repo: &str) -> Result<SearchRepoItem> {
+        let url = format!("{}/repos/{}/{}/forks", self.api_base, owner, repo);
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({}))
+            .send_throttled()
+            .await
+            .context("Failed to fork repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fork repository");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse forked repository")
+    }
+    
+    // ========================================================================
+    // User API
+    // ========================================================================
+    
+    /// Fetch repositories of the authenticated user (most recently updated first)
+    pub async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        let url = format!("{}/user/repos?sort=updated&per_page=100", self.api_base);
+        self.fetch_repo_list(&url).await
+    }
+    
+    /// Fetch repositories the authenticated user has starred (most recently starred first)
+    pub async fn fetch_starred(&self) -> Result<Vec<RepoData>> {
+        let url = format!("{}/user/starred?per_page=100", self.api_base);
+        self.fetch_repo_list(&url).await
+    }
+    
+    /// Fetch repositories owned by an organization
+    pub async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        let url = format!("{}/orgs/{}/repos?sort=updated&per_page=100", self.api_base, org);
+        self.fetch_repo_list(&url).await
+    }
+    
+    async fn fetch_repo_list(&self, url: &str) -> Result<Vec<RepoData>> {
+        let repos: Vec<SearchRepoItem> = self.fetch_all_pages(url, "repositories").await?;
+        Ok(repos.into_iter().map(RepoData::from).collect())
+    }
+    
+    /// OAuth scopes granted to the token, from the `X-OAuth-Scopes` header of `GET /user`.
+    /// None for tokens that don't report scopes (fine-grained PATs, GitHub App tokens).
+    pub async fn fetch_token_scopes(&self) -> Result<Option<Vec<String>>> {
+        let response = self.client
+            .get(format!("{}/user", self.api_base))
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch user")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch user");
+        }
+        
+        Ok(response.headers()
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(|scopes| scopes.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()))
+    }
+    
+    /// Fetch logins of the organizations the authenticated user belongs to
+    pub async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        let response = self.client
+            .get(format!("{}/user/orgs", self.api_base))
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch organizations")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch organizations");
+        }
+        
+        let orgs: Vec<RepoOwner> = response
+            .json()
+            .await
+            .context("Failed to parse organizations")?;
+        
+        Ok(orgs.into_iter().map(|o| o.login).collect())
+    }
+    
+    /// Create a repository for the authenticated user, returning its full name
+    pub async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String> {
+        let response = self.client
+            .post(format!("{}/user/repos", self.api_base))
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "name": name,
+                "description": description,
+                "private": private,
+                "auto_init": auto_init,
+            }))
+            .send_throttled()
+            .await
+            .context("Failed to create repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to create repository");
+        }
+        
+        let repo: SearchRepoItem = response
+            .json()
+            .await
+            .context("Failed to parse created repository")?;
+        
+        Ok(repo.full_name)
+    }
+    
+    // ========================================================================
+    // Repository Administration API
+    // ========================================================================
+    
+    /// Archive or unarchive a repository (requires admin rights)
+    pub async fn set_repo_archived(&self, owner: &str, repo: &str, archived: bool) -> Result<RepoInfo> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        
+        let response = self.client
+            .patch(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "archived": archived }))
+            .send_throttled()
+            .await
+            .context("Failed to update repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update repository");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse updated repository")
+    }
+    
+    /// Permanently delete a repository (requires the delete_repo scope)
+    pub async fn delete_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        
+        let response = self.client
+            .delete(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to delete repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to delete repository");
+        }
+        
+        Ok(())
+    }
+    
+    // ========================================================================
+    // Starring API
+    // ========================================================================
+    
+    /// Check whether the authenticated user has starred a repository
+    pub async fn is_starred(&self, owner: &str, repo: &str) -> Result<bool> {
+        let url = format!("{}/user/starred/{}/{}", self.api_base, owner, repo);
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to check star status")?;
+        
+        // 204 = starred, 404 = not starred
+        match response.status().as_u16() {
+            204 => Ok(true),
+            404 => Ok(false),
+            _ => Err(GitHubError::from_response(response).await).context("Failed to check star status"),
+        }
+    }
+    
+    /// Star state of each of `full_names` from one walk of `/user/starred`
+    /// instead of a request per repository. When the list is cut off at the
+    /// page limit, repositories not found in it are left out (unknown).
+    pub async fn star_states(&self, full_names: &[String]) -> Result<Vec<(String, bool)>> {
+        let url = format!("{}/user/starred?per_page=100", self.api_base);
+        let starred: Vec<SearchRepoItem> = self.fetch_all_pages(&url, "starred repositories").await?;
+        let complete = (starred.len() as u64) < MAX_PAGES * 100;
+        let starred: std::collections::HashSet<String> = starred.into_iter()
+            .map(|repo| repo.full_name.to_lowercase())
+            .collect();
+    
+        Ok(full_names.iter()
+            .map(|name| (name.clone(), starred.contains(&name.to_lowercase())))
+            .filter(|(_, is_starred)| *is_starred || complete)
+            .collect())
+    }
+    
+    /// Star a repository for the authenticated user
+    pub async fn star_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        let url = format!("{}/user/starred/{}/{}", self.api_base, owner, repo);
+        
+        let response = self.client
+            .put(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("Content-Length", "0")
+            .send_throttled()
+            .await
+            .context("Failed to star repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to star repository");
+        }
+        
+        Ok(())
+    }
+    
+    /// Unstar a repository for the authenticated user
+    pub async fn unstar_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        let url = format!("{}/user/starred/{}/{}", self.api_base, owner, repo);
+        
+        let response = self.client
+            .delete(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to unstar repository")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to unstar repository");
+        }
+        
+        Ok(())
+    }
+    
+    // ========================================================================
+    // Subscription (Watch) API
+    // ========================================================================
+    
+    /// Get the authenticated user's watch state for a repository
+    pub async fn get_subscription(&self, owner: &str, repo: &str) -> Result<WatchState> {
+        let url = format!("{}/repos/{}/{}/subscription", self.api_base, owner, repo);
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch subscription")?;
+        
+        // 404 means no explicit subscription: notified only when participating
+        if response.status().as_u16() == 404 {
+            return Ok(WatchState::Participating);
+        }
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch subscription");
+        }
+        
+        let sub: RepoSubscription = response
+            .json()
+            .await
+            .context("Failed to parse subscription")?;
+        
+        Ok(if sub.ignored {
+            WatchState::Ignoring
+        } else if sub.subscribed {
+            WatchState::Watching
+        } else {
+            WatchState::Participating
+        })
+    }
+    
+    /// Set the authenticated user's watch state for a repository
+    pub async fn set_subscription(&self, owner: &str, repo: &str, state: WatchState) -> Result<()> {
+        let url = format!("{}/repos/{}/{}/subscription", self.api_base, owner, repo);
+        
+        let request = match state {
+            // Removing the subscription falls back to participating-only notifications
+            WatchState::Participating => self.client.delete(&url),
+            WatchState::Watching => self.client
+                .put(&url)
+                .json(&serde_json::json!({ "subscribed": true, "ignored": false })),
+            WatchState::Ignoring => self.client
+                .put(&url)
+                .json(&serde_json::json!({ "subscribed": false, "ignored": true })),
+        };
+        
+        let response = request
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to update subscription")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update subscription");
+        }
+        
+        Ok(())
+    }
+    
+    /// Fetch the authenticated user's unread notifications for a repository
+    pub async fn fetch_repo_notifications(&self, owner: &str, repo: &str) -> Result<Vec<Notification>> {
+        let url = format!("{}/repos/{}/{}/notifications", self.api_base, owner, repo);
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch notifications")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch notifications");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse notifications")
+    }
+    
+    // ========================================================================
+    // Issues API
+    // ========================================================================
+    
+    /// Fetch issues for a repository
+    /// 
+    /// # Arguments
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `state` - "open", "closed", or "all"
+    /// * `milestone` - Milestone number, "none", "*", or "" for no filter
+    pub async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        let mut url = format!(
+            "{}/repos/{}/{}/issues?state={}&per_page={}",
+            self.api_base, owner, repo, state, self.page_size
+        );
+        if !milestone.is_empty() {
+            url.push_str(&format!("&milestone={}", milestone));
+        }
+        if !labels.is_empty() {
+            url.push_str(&format!("&labels={}", urlencoding::encode(&labels.join(","))));
+        }
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch issues")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch issues");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse issues")
+    }
+    
+    /// Fetch a single issue (or pull request, which GitHub also serves as an issue)
+    pub async fn fetch_issue(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Issue> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch issue")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch issue");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse issue")
+    }
+    
+    /// Fetch comments for an issue
+    pub async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch comments")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch comments");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse comments")
+    }
+    
+    /// Create a comment on an issue
+    pub async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "body": body }))
+            .send_throttled()
+            .await
+            .context("Failed to create comment")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to create comment");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse created comment")
+    }
+    
+    /// Create a secret gist from text files (name, content)
+    pub async fn create_gist(&self, description: &str, files: &[(String, String)]) -> Result<Gist> {
+        let url = format!("{}/gists", self.api_base);
+        let files: serde_json::Map<String, serde_json::Value> = files.iter()
+            .map(|(name, content)| (name.clone(), serde_json::json!({ "content": content })))
+            .collect();
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "description": description, "public": false, "files": files }))
+            .send_throttled()
+            .await
+            .context("Failed to create gist")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to create gist");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse created gist")
+    }
+    
+    /// Close or reopen an issue
+    pub async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .patch(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "state": state }))
+            .send_throttled()
+            .await
+            .context("Failed to update issue")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update issue");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse updated issue")
+    }
+    
+    /// Add labels to an issue, keeping the ones it already has
+    pub async fn add_issue_labels(&self, owner: &str, repo: &str, issue_number: u32, labels: &[String]) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/labels",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "labels": labels }))
+            .send_throttled()
+            .await
+            .context("Failed to add issue labels")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to add issue labels");
+        }
+        
+        Ok(())
+    }
+    
+    /// Assign a milestone to an issue (None clears it)
+    pub async fn set_issue_milestone(&self, owner: &str, repo: &str, issue_number: u32, milestone: Option<u32>) -> Result<Issue> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            self.api_base, owner, repo, issue_number
+        );
+        
+        let response = self.client
+            .patch(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "milestone": milestone }))
+            .send_throttled()
+            .await
+            .context("Failed to update issue milestone")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update issue milestone");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse updated issue")
+    }
+    
+    /// Fetch open milestones of a repository
+    pub async fn fetch_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>> {
+        let url = format!(
+            "{}/repos/{}/{}/milestones?state=open&per_page=100",
+            self.api_base, owner, repo
+        );
+        
+        self.fetch_all_pages(&url, "milestones").await
+    }
+    
+    /// Users that can be @mentioned in a repo
+    /// 
+    /// Listing collaborators needs push access; everyone else gets the
+    /// assignable users, which the API exposes to any reader.
+    pub async fn fetch_collaborators(&self, owner: &str, repo: &str) -> Result<Vec<IssueUser>> {
+        let url = format!("{}/repos/{}/{}/collaborators?per_page=100", self.api_base, owner, repo);
+        match self.fetch_all_pages(&url, "collaborators").await {
+            Ok(users) => Ok(users),
+            Err(_) => {
+                let url = format!("{}/repos/{}/{}/assignees?per_page=100", self.api_base, owner, repo);
+                self.fetch_all_pages(&url, "assignees").await
+            }
+        }
+    }
+    
+    /// Most recently updated issues and pull requests, for #references
+    pub async fn fetch_recent_issues(&self, owner: &str, repo: &str) -> Result<Vec<Issue>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues?state=all&sort=updated&per_page=50",
+            self.api_base, owner, repo
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch recent issues")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch recent issues");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse recent issues")
+    }
+    
+    // ========================================================================
+    // Labels API
+    // ========================================================================
+    
+    /// Fetch all labels defined in a repository
+    pub async fn fetch_labels(&self, owner: &str, repo: &str) -> Result<Vec<IssueLabel>> {
+        let url = format!(
+            "{}/repos/{}/{}/labels?per_page=100",
+            self.api_base, owner, repo
+        );
+        
+        self.fetch_all_pages(&url, "labels").await
+    }
+    
+    /// Create a new label (color is a 6-digit hex string without '#')
+    pub async fn create_label(&self, owner: &str, repo: &str, label: &IssueLabel) -> Result<IssueLabel> {
+        let url = format!("{}/repos/{}/{}/labels", self.api_base, owner, repo);
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "name": label.name,
+                "color": label.color,
+                "description": label.description.clone().unwrap_or_default(),
+            }))
+            .send_throttled()
+            .await
+            .context("Failed to create label")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to create label");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse created label")
+    }
+    
+    /// Update (and possibly rename) an existing label
+    pub async fn update_label(&self, owner: &str, repo: &str, name: &str, label: &IssueLabel) -> Result<IssueLabel> {
+        let url = format!(
+            "{}/repos/{}/{}/labels/{}",
+            self.api_base, owner, repo, urlencoding::encode(name)
+        );
+        
+        let response = self.client
+            .patch(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "new_name": label.name,
+                "color": label.color,
+                "description": label.description.clone().unwrap_or_default(),
+            }))
+            .send_throttled()
+            .await
+            .context("Failed to update label")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update label");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse updated label")
+    }
+    
+    /// Delete a label from a repository
+    pub async fn delete_label(&self, owner: &str, repo: &str, name: &str) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/{}/labels/{}",
+            self.api_base, owner, repo, urlencoding::encode(name)
+        );
+        
+        let response = self.client
+            .delete(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to delete label")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to delete label");
+        }
+        
+        Ok(())
+    }
+    
+    // ========================================================================
+    // Pull Request API
+    // ========================================================================
+    
+    /// Fetch pull requests for a repository
+    pub async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls?state={}&per_page={}",
+            self.api_base, owner, repo, state, self.page_size
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch pull requests")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch PRs");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse pull requests")
+    }
+    
+    /// Merge a pull request
+    pub async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/merge",
+            self.api_base, owner, repo, pr_number
+        );
+        
+        let response = self.client
+            .put(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "merge_method": merge_method }))
+            .send_throttled()
+            .await
+            .context("Failed to merge pull request")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to merge PR");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse merge result")
+    }
+    
+    /// Merge the base branch into the head branch of a pull request
+    /// 
+    /// GitHub does the update asynchronously (202 Accepted); `expected_head_sha`
+    /// makes it fail instead of clobbering commits pushed in the meantime.
+    pub async fn update_pull_request_branch(&self, owner: &str, repo: &str, pr_number: u32, expected_head_sha: &str) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/update-branch",
+            self.api_base, owner, repo, pr_number
+        );
+        
+        let response = self.client
+            .put(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "expected_head_sha": expected_head_sha }))
+            .send_throttled()
+            .await
+            .context("Failed to update pull request branch")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to update PR branch");
+        }
+        
+        Ok(())
+    }
+    
+    /// Close a pull request
+    pub async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
+        );
+        
+        let response = self.client
+            .patch(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "state": "closed" }))
+            .send_throttled()
+            .await
+            .context("Failed to close pull request")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to close PR");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse closed PR")
+    }
+    
+    /// Fetch a single pull request
+    pub async fn fetch_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch pull request")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch PR");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse PR")
+    }
+    
+    /// Mark a draft pull request as ready for review
+    /// 
+    /// REST has no endpoint for this transition, so it goes through the
+    /// GraphQL `markPullRequestReadyForReview` mutation using the PR's node id.
+    pub async fn mark_ready_for_review(&self, node_id: &str) -> Result<()> {
+        let query = r#"mutation($id: ID!) {
+            markPullRequestReadyForReview(input: { pullRequestId: $id }) {
+                pullRequest { isDraft }
+            }
+        }"#;
+        
+        self.graphql(query, serde_json::json!({ "id": node_id }))
+            .await
+            .context("Failed to mark PR ready for review")?;
+        
+        Ok(())
+    }
+    
+    /// Open a new pull request from `head` into `base`
+    pub async fn create_pull_request(&self, owner: &str, repo: &str, title: &str, body: &str, head: &str, base: &str) -> Result<PullRequest> {
+        let url = format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo);
+        
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "title": title,
+                "body": body,
+                "head": head,
+                "base": base,
+            }))
+            .send_throttled()
+            .await
+            .context("Failed to create pull request")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to create PR");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse created PR")
+    }
+    
+    /// Fetch the files changed by a pull request, including patches
+    pub async fn fetch_pr_files(&self, owner: &str, repo: &str, pr_number: u32) -> Result<Vec<ChangedFile>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/files?per_page=100",
+            self.api_base, owner, repo, pr_number
+        );
+        
+        self.fetch_all_pages(&url, "pull request files").await
+    }
+    
+    // ========================================================================
+    // Commits API
+    // ========================================================================
+    
+    /// Fetch commit history for a repository
+    /// 
+    /// # Arguments
+    /// * `owner` - Repository owner
+    /// * `repo` - Repository name
+    /// * `branch` - Branch, tag, or SHA to list from ("" for the default branch)
+    /// * `page` - 1-based page number
+    pub async fn fetch_commits(&self, owner: &str, repo: &str, branch: &str, page: u32) -> Result<Vec<Commit>> {
+        let mut url = format!(
+            "{}/repos/{}/{}/commits?per_page={}&page={}",
+            self.api_base, owner, repo, self.page_size, page.max(1)
+        );
+        
+        if !branch.is_empty() {
+            url.push_str(&format!("&sha={}", urlencoding::encode(branch)));
+        }
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch commits")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch commits");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse commits")
+    }
+    
+    /// Fetch a single commit including per-file patches
+    pub async fn fetch_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<CommitDetail> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}",
+            self.api_base, owner, repo, sha
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch commit")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch commit");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse commit")
+    }
+    
+    // ========================================================================
+    // Checks & Status API
+    // ========================================================================
+    
+    /// Fetch check runs (GitHub Actions, apps) for a commit ref
+    pub async fn fetch_check_runs(&self, owner: &str, repo: &str, git_ref: &str) -> Result<CheckRunList> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/check-runs?per_page=100",
+            self.api_base, owner, repo, git_ref
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch check runs")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch check runs");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse check runs")
+    }
+    
+    /// Fetch the combined commit status (legacy status API) for a commit ref
+    pub async fn fetch_combined_status(&self, owner: &str, repo: &str, git_ref: &str) -> Result<CombinedStatus> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/status",
+            self.api_base, owner, repo, git_ref
+        );
+        
+        let response = self.client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send_throttled()
+            .await
+            .context("Failed to fetch commit status")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("Failed to fetch commit status");
+        }
+        
+        response
+            .json()
+            .await
+            .context("Failed to parse commit status")
+    }
+    
+    // ========================================================================
+    // Discussions API (GraphQL only)
+    // ========================================================================
+    
+    /// Fetch the discussion categories of a repository
+    pub async fn fetch_discussion_categories(&self, owner: &str, repo: &str) -> Result<Vec<DiscussionCategory>> {
+        let query = r#"query($owner: String!, $repo: String!) {
+            repository(owner: $owner, name: $repo) {
+                discussionCategories(first: 25) { nodes { id name } }
+            }
+        }"#;
+        
+        let data = self.graphql(query, serde_json::json!({ "owner": owner, "repo": repo })).await?;
+        serde_json::from_value(data["repository"]["discussionCategories"]["nodes"].clone())
+            .context("Failed to parse discussion categories")
+    }
+    
+    /// Fetch the most recently updated discussions, optionally within one category
+    pub async fn fetch_discussions(&self, owner: &str, repo: &str, category_id: Option<&str>) -> Result<Vec<Discussion>> {
+        let query = r#"query($owner: String!, $repo: String!, $category: ID) {
+            repository(owner: $owner, name: $repo) {
+                discussions(first: 30, categoryId: $category, orderBy: { field: UPDATED_AT, direction: DESC }) {
+                    nodes {
+                        number title
+                        author { login }
+                        category { name }
+                        comments { totalCount }
+                        answerChosenAt
+                    }
+                }
+            }
+        }"#;
+        
+        let variables = serde_json::json!({ "owner": owner, "repo": repo, "category": category_id });
+        let data = self.graphql(query, variables).await?;
+        serde_json::from_value(data["repository"]["discussions"]["nodes"].clone())
+            .context("Failed to parse discussions")
+    }
+    
+    /// Fetch a discussion thread with its comments and replies
+    pub async fn fetch_discussion(&self, owner: &str, repo: &str, number: u32) -> Result<DiscussionThread> {
+        let query = r#"query($owner: String!, $repo: String!, $number: Int!) {
+            repository(owner: $owner, name: $repo) {
+                discussion(number: $number) {
+                    number title body createdAt url
+                    author { login }
+                    comments(first: 50) {
+                        nodes {
+                            body createdAt isAnswer
+                            author { login }
+                            replies(first: 20) { nodes { body createdAt author { login } } }
+                        }
+                    }
+                }
+            }
+        }"#;
+        
+        let variables = serde_json::json!({ "owner": owner, "repo": repo, "number": number });
+        let data = self.graphql(query, variables).await?;
+        serde_json::from_value(data["repository"]["discussion"].clone())
+            .context("Failed to parse discussion")
+    }
+    
+    /// Run a GraphQL query and return its `data` object
+    async fn graphql(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value> {
+        let response = self.client
+            .post(&self.graphql_url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(USER_AGENT, "NativeHub-Rust-Client")
+            .json(&serde_json::json!({
+                "query": query,
+                "variables": variables,
+            }))
+            .send_throttled()
+            .await
+            .context("Failed to send GraphQL request")?;
+        
+        if !response.status().is_success() {
+            return Err(GitHubError::from_response(response).await).context("GraphQL request failed");
+        }
+        
+        // GraphQL reports errors with a 200 status
+        let mut result: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse GraphQL response")?;
+        if let Some(errors) = result.get("errors").and_then(|errors| errors.as_array()) {
+            let messages: Vec<&str> = errors.iter().filter_map(|error| error["message"].as_str()).collect();
+            anyhow::bail!("GraphQL error: {}", messages.join("; "));
+        }
+        
+        Ok(result["data"].take())
+    }
+}
+
+#[async_trait]
+impl Ops for ApiClient {
+    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        ApiClient::fetch_repos(self).await
+    }
+    
+    async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        ApiClient::fetch_orgs(self).await
+    }
+    
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        ApiClient::fetch_org_repos(self, org).await
+    }
+    
+    async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String> {
+        ApiClient::create_repo(self, name, description, private, auto_init).await
+    }
+    
+    async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        ApiClient::fetch_file_tree(self, owner, repo, path, git_ref).await
+    }
+    
+    async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        ApiClient::search_repos(self, query, sort, per_page).await
+    }
+    
+    async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        ApiClient::fetch_issues(self, owner, repo, state, milestone, labels).await
+    }
+    
+    async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        ApiClient::update_issue_state(self, owner, repo, issue_number, state).await
+    }
+    
+    async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        ApiClient::fetch_issue_comments(self, owner, repo, issue_number).await
+    }
+    
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        ApiClient::create_comment(self, owner, repo, issue_number, body).await
+    }
+    
+    async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        ApiClient::fetch_pull_requests(self, owner, repo, state).await
+    }
+    
+    async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        ApiClient::merge_pull_request(self, owner, repo, pr_number, merge_method).await
+    }
+    
+    async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        ApiClient::close_pull_request(self, owner, repo, pr_number).await
     }
 }
 
@@ -383,35 +2151,65 @@ pub struct RepoInfo {
     #[serde(default)]
     pub forks_count: u32,
     #[serde(default)]
-    pub watchers_count: u32,
-    #[serde(default)]
     pub language: Option<String>,
     #[serde(default)]
     pub topics: Vec<String>,
     #[serde(default)]
-    pub license: Option<LicenseInfo>,
+    pub default_branch: String,
     #[serde(default)]
-    pub open_issues_count: u32,
+    pub archived: bool,
+}
+
+/// A gist as returned on creation
+#[derive(Debug, Clone, Deserialize)]
+pub struct Gist {
+    pub html_url: String,
+    pub git_push_url: String,
     #[serde(default)]
-    pub default_branch: String,
+    pub files: std::collections::HashMap<String, GistFile>,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
-pub struct LicenseInfo {
+#[derive(Debug, Clone, Deserialize)]
+pub struct GistFile {
+    pub raw_url: String,
+}
+
+/// Request quota of one API resource
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: u64, // Unix time the quota refills
+}
+
+/// Response of the rate limit endpoint
+#[derive(Debug, Deserialize)]
+struct RateLimitStatus {
+    resources: RateLimitResources,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResources {
+    core: RateLimit,
+}
+
+/// Response of the repository topics endpoint
+#[derive(Debug, Deserialize)]
+struct RepoTopics {
     #[serde(default)]
-    pub name: String,
+    names: Vec<String>,
 }
 
 /// Search result from GitHub API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub total_count: u32,
     pub incomplete_results: bool,
     pub items: Vec<SearchRepoItem>,
 }
 
-/// A repository item from search results
-#[derive(Debug, Clone, Deserialize)]
+/// A repository item from search results (also the shape of other repo payloads)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchRepoItem {
     pub id: u64,
     pub name: String,
@@ -430,20 +2228,85 @@ pub struct SearchRepoItem {
     pub topics: Vec<String>,
     pub html_url: String,
     pub owner: RepoOwner,
+    #[serde(default)]
+    pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoOwner {
     pub login: String,
     pub avatar_url: String,
 }
 
+/// Repository subscription from the subscription API
+#[derive(Debug, Clone, Deserialize)]
+struct RepoSubscription {
+    #[serde(default)]
+    subscribed: bool,
+    #[serde(default)]
+    ignored: bool,
+}
+
+/// Notification level for a repository.
+///
+/// The REST API only exposes subscribe/ignore; GitHub's per-event "Custom"
+/// level can only be configured on the website, and reads back as `Watching`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchState {
+    Participating, // Only @mentions and threads I take part in
+    Watching,      // All activity
+    Ignoring,      // Never notify
+}
+
+impl WatchState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatchState::Participating => "🔕 Participating",
+            WatchState::Watching => "👁 Watching",
+            WatchState::Ignoring => "🚫 Ignoring",
+        }
+    }
+    
+    pub fn all() -> &'static [WatchState] {
+        &[WatchState::Participating, WatchState::Watching, WatchState::Ignoring]
+    }
+}
+
+/// A notification thread of the authenticated user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub unread: bool,
+    pub reason: String, // "mention", "review_requested", "subscribed", ...
+    pub updated_at: String,
+    pub subject: NotificationSubject,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSubject {
+    pub title: String,
+    #[serde(rename = "type")]
+    pub subject_type: String, // "Issue", "PullRequest", "Release", ...
+}
+
+/// A git tag from the tags API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tag {
+    pub name: String,
+}
+
+/// A branch from the branches API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Branch {
+    pub name: String,
+}
+
 // ============================================================================
 // Issue Types
 // ============================================================================
 
 /// An issue from GitHub API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Issue {
     pub id: u64,
     pub number: u32,
@@ -461,15 +2324,32 @@ pub struct Issue {
     pub html_url: String,
     #[serde(default)]
     pub pull_request: Option<serde_json::Value>, // If present, this is a PR not an issue
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// A repository milestone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub number: u32,
+    pub title: String,
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub open_issues: u32,
+    #[serde(default)]
+    pub closed_issues: u32,
+    #[serde(default)]
+    pub due_on: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueUser {
     pub login: String,
     pub avatar_url: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueLabel {
     pub name: String,
     #[serde(default)]
@@ -479,7 +2359,7 @@ pub struct IssueLabel {
 }
 
 /// A comment on an issue
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueComment {
     pub id: u64,
     pub body: String,
@@ -493,7 +2373,7 @@ pub struct IssueComment {
 // ============================================================================
 
 /// A pull request from GitHub API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequest {
     pub id: u64,
     pub number: u32,
@@ -525,9 +2405,13 @@ pub struct PullRequest {
     pub deletions: u32,
     #[serde(default)]
     pub changed_files: u32,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub node_id: String, // GraphQL id, needed for mutations without a REST equivalent
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequestRef {
     pub label: String,
     #[serde(rename = "ref")]
@@ -536,9 +2420,277 @@ pub struct PullRequestRef {
 }
 
 /// Result of merging a pull request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MergeResult {
     pub sha: String,
     pub merged: bool,
     pub message: String,
 }
+
+// ============================================================================
+// Commit Types
+// ============================================================================
+
+/// A commit from the commits list API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Commit {
+    pub sha: String,
+    pub commit: CommitInfo,
+    #[serde(default)]
+    pub author: Option<IssueUser>, // GitHub account, absent if the email isn't linked
+    pub html_url: String,
+}
+
+impl Commit {
+    /// First 7 characters of the SHA
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+    
+    /// First line of the commit message
+    pub fn summary(&self) -> &str {
+        self.commit.message.lines().next().unwrap_or("")
+    }
+    
+    /// GitHub login if known, otherwise the git author name
+    pub fn author_name(&self) -> &str {
+        match &self.author {
+            Some(user) => &user.login,
+            None => &self.commit.author.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitInfo {
+    pub message: String,
+    pub author: CommitAuthor,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitAuthor {
+    pub name: String,
+    #[serde(default)]
+    pub date: String,
+}
+
+/// A single commit with stats and changed files
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitDetail {
+    pub sha: String,
+    #[serde(default)]
+    pub stats: CommitStats,
+    #[serde(default)]
+    pub files: Vec<ChangedFile>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CommitStats {
+    #[serde(default)]
+    pub additions: u32,
+    #[serde(default)]
+    pub deletions: u32,
+}
+
+/// A file changed by a commit or pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangedFile {
+    pub filename: String,
+    pub status: String, // "added", "removed", "modified", "renamed", ...
+    #[serde(default)]
+    pub additions: u32,
+    #[serde(default)]
+    pub deletions: u32,
+    #[serde(default)]
+    pub changes: u32,
+    #[serde(default)]
+    pub patch: Option<String>, // Absent for binary or very large diffs
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+}
+
+// ============================================================================
+// Checks & Status Types
+// ============================================================================
+
+/// Response wrapper for the check runs endpoint
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CheckRunList {
+    #[serde(default)]
+    pub check_runs: Vec<CheckRun>,
+}
+
+/// A single check run (e.g. a GitHub Actions job)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String, // "queued", "in_progress", "completed"
+    #[serde(default)]
+    pub conclusion: Option<String>, // "success", "failure", "neutral", "cancelled", "skipped", "timed_out", "action_required"
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+/// Combined commit status from the legacy status API
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct CombinedStatus {
+    #[serde(default)]
+    pub statuses: Vec<CommitStatus>,
+}
+
+/// A single commit status reported by an external service
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitStatus {
+    pub state: String,
+    pub context: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub target_url: Option<String>,
+}
+
+// ============================================================================
+// Discussion Types
+// ============================================================================
+
+/// Author of a GraphQL node (None for deleted "ghost" users)
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlActor {
+    pub login: String,
+}
+
+/// A discussion category (e.g. "Q&A", "Ideas")
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscussionCategory {
+    pub id: String,
+    pub name: String,
+}
+
+/// A discussion in the list view
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discussion {
+    pub number: u32,
+    pub title: String,
+    #[serde(default)]
+    pub author: Option<GraphQlActor>,
+    pub category: DiscussionCategoryRef,
+    pub comments: TotalCount,
+    #[serde(default)]
+    pub answer_chosen_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscussionCategoryRef {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotalCount {
+    pub total_count: u32,
+}
+
+/// A discussion with its comment thread
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionThread {
+    pub number: u32,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub created_at: String,
+    pub url: String,
+    #[serde(default)]
+    pub author: Option<GraphQlActor>,
+    pub comments: DiscussionCommentConnection,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscussionCommentConnection {
+    pub nodes: Vec<DiscussionComment>,
+}
+
+/// A top-level comment on a discussion, with its replies
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionComment {
+    #[serde(default)]
+    pub body: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub author: Option<GraphQlActor>,
+    #[serde(default)]
+    pub is_answer: bool,
+    #[serde(default)]
+    pub replies: Option<DiscussionReplyConnection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscussionReplyConnection {
+    pub nodes: Vec<DiscussionReply>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionReply {
+    #[serde(default)]
+    pub body: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub author: Option<GraphQlActor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+    
+    #[test]
+    fn parses_error_body() {
+        let body = r#"{
+            "message": "Validation Failed",
+            "errors": [
+                { "resource": "Issue", "field": "title", "code": "missing_field" },
+                { "resource": "Label", "field": "name", "code": "already_exists" },
+                { "code": "custom", "message": "Body is too long" },
+                "plain text detail"
+            ],
+            "documentation_url": "https://docs.github.com/rest"
+        }"#;
+        let error = GitHubError::parse(StatusCode::UNPROCESSABLE_ENTITY, body, None);
+        assert_eq!(error.message, "Validation Failed");
+        assert_eq!(error.errors, ["title is required", "name already exists", "Body is too long", "plain text detail"]);
+        assert_eq!(error.documentation_url.as_deref(), Some("https://docs.github.com/rest"));
+        assert_eq!(
+            error.summary(),
+            "Validation Failed (title is required; name already exists; Body is too long; plain text detail)"
+        );
+        assert!(!error.is_rate_limit());
+    }
+    
+    #[test]
+    fn falls_back_to_status_for_non_json() {
+        let error = GitHubError::parse(StatusCode::BAD_GATEWAY, "<html>Bad gateway</html>", None);
+        assert_eq!(error.message, "Bad Gateway");
+        assert!(error.errors.is_empty());
+        assert_eq!(error.to_string(), "502 Bad Gateway: Bad Gateway");
+    }
+    
+    #[test]
+    fn recognises_rate_limits() {
+        let primary = GitHubError::parse(StatusCode::FORBIDDEN, r#"{"message": "API rate limit exceeded for user ID 1."}"#, None);
+        assert!(primary.is_rate_limit());
+        
+        let secondary = GitHubError::parse(StatusCode::FORBIDDEN, r#"{"message": "You have exceeded a secondary rate limit."}"#, None);
+        assert!(secondary.is_rate_limit());
+        
+        let throttled = GitHubError::parse(StatusCode::TOO_MANY_REQUESTS, "", Some(1_700_000_000));
+        assert!(throttled.is_rate_limit());
+        assert_eq!(throttled.retry_at, Some(1_700_000_000));
+        
+        // A plain permission error is not worth waiting for
+        let forbidden = GitHubError::parse(StatusCode::FORBIDDEN, r#"{"message": "Resource not accessible by integration"}"#, None);
+        assert!(!forbidden.is_rate_limit());
+    }
+}
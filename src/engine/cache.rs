@@ -0,0 +1,330 @@
+//! Cache Engine - Persistent offline cache backed by SQLite.
+//!
+//! `CachedEngine` wraps another engine: successful reads are stored as JSON
+//! in a local SQLite database, and when a read fails (offline, rate limited)
+//! the last-known copy is served instead. Writes always go to the wrapped
+//! engine, so the app is read-only while offline.
+//!
+//! Slow-changing responses (repo lists, READMEs, images) are additionally
+//! served straight from the cache while younger than their TTL, so a restart
+//! doesn't refetch everything.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use crate::app_event::RepoData;
+use crate::modules::paths;
+use super::Ops;
+use super::api_client::{FileNode, Issue, IssueComment, MergeResult, PullRequest, SearchResult};
+
+/// Repository and organization lists
+pub const LIST_TTL: Duration = Duration::from_secs(5 * 60);
+/// README contents
+pub const README_TTL: Duration = Duration::from_secs(60 * 60);
+/// Listings warmed by hover prefetching; a prefetch of anything younger is skipped
+pub const PREFETCH_TTL: Duration = Duration::from_secs(2 * 60);
+/// Avatars and other images
+pub const IMAGE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Key/value store of JSON documents and binary blobs
+pub struct CacheStore {
+    conn: Mutex<Connection>,
+}
+
+static GLOBAL: OnceLock<Option<CacheStore>> = OnceLock::new();
+
+/// The shared cache, opened on first use (None if the database can't be opened)
+pub fn global() -> Option<&'static CacheStore> {
+    GLOBAL.get_or_init(|| {
+        match CacheStore::open(&default_path()) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                tracing::warn!("Offline cache disabled: {}", e);
+                None
+            }
+        }
+    }).as_ref()
+}
+
+/// `cache.sqlite3` in the platform data directory
+fn default_path() -> PathBuf {
+    paths::data_dir().join("cache.sqlite3")
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Identity of the account a token signs in as: the API host plus a hash of
+/// the token, so the cache never keeps a token itself
+pub fn account_id(api_base: &str, token: &str) -> String {
+    let hash = token.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("{}#{:016x}", api_base, hash)
+}
+
+/// Cache key for a call and its arguments, e.g. `fetch_issues:octocat/hello/open/`
+pub fn key(call: &str, args: &[&str]) -> String {
+    format!("{}:{}", call, args.join("/"))
+}
+
+impl CacheStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create cache directory")?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open cache {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS cache (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS blobs (
+                key TEXT PRIMARY KEY,
+                value BLOB NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )"
+        ).context("Failed to create cache table")?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+    
+    /// Hand the cache to `account` (see `account_id`), wiping it first if it
+    /// was filled by another one so private data never crosses accounts
+    pub fn claim(&self, account: &str) {
+        let Ok(conn) = self.conn.lock() else { return };
+        let owner: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key = 'account'", [], |row| row.get(0))
+            .optional()
+            .ok()
+            .flatten();
+        if owner.as_deref() == Some(account) {
+            return;
+        }
+        if let Err(e) = conn.execute_batch("DELETE FROM cache; DELETE FROM blobs") {
+            tracing::warn!("Failed to clear cache: {}", e);
+            return;
+        }
+        if let Err(e) = conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('account', ?1)",
+            params![account],
+        ) {
+            tracing::warn!("Failed to record cache owner: {}", e);
+        }
+    }
+    
+    /// Drop every stored value and blob, e.g. when the account's token is revoked
+    pub fn clear(&self) {
+        let Ok(conn) = self.conn.lock() else { return };
+        if let Err(e) = conn.execute_batch("DELETE FROM cache; DELETE FROM blobs; DELETE FROM meta") {
+            tracing::warn!("Failed to clear cache: {}", e);
+        }
+    }
+    
+    /// Last stored value for `key`
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get_since(key, i64::MIN)
+    }
+    
+    /// Stored value for `key` if it was written less than `ttl` ago
+    pub fn get_fresh<T: DeserializeOwned>(&self, key: &str, ttl: Duration) -> Option<T> {
+        self.get_since(key, now() - ttl.as_secs() as i64)
+    }
+    
+    /// Whether `key` was written less than `ttl` ago
+    pub fn is_fresh(&self, key: &str, ttl: Duration) -> bool {
+        let Ok(conn) = self.conn.lock() else { return false };
+        conn.query_row(
+            "SELECT 1 FROM cache WHERE key = ?1 AND updated_at >= ?2",
+            params![key, now() - ttl.as_secs() as i64],
+            |_| Ok(()),
+        )
+        .optional()
+        .is_ok_and(|row| row.is_some())
+    }
+    
+    fn get_since<T: DeserializeOwned>(&self, key: &str, since: i64) -> Option<T> {
+        let conn = self.conn.lock().ok()?;
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT value FROM cache WHERE key = ?1 AND updated_at >= ?2",
+                params![key, since],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()?;
+        serde_json::from_str(&json?).ok()
+    }
+    
+    /// Drop the stored value for `key`, e.g. after a write made it stale
+    pub fn remove(&self, key: &str) {
+        if let Ok(conn) = self.conn.lock() {
+            let _ = conn.execute("DELETE FROM cache WHERE key = ?1", params![key]);
+        }
+    }
+    
+    /// Stored blob for `key` if it was written less than `ttl` ago
+    pub fn get_bytes_fresh(&self, key: &str, ttl: Duration) -> Option<Vec<u8>> {
+        let conn = self.conn.lock().ok()?;
+        conn.query_row(
+            "SELECT value FROM blobs WHERE key = ?1 AND updated_at >= ?2",
+            params![key, now() - ttl.as_secs() as i64],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()?
+    }
+    
+    /// Store a blob under `key`, replacing any previous copy. Failures are only logged.
+    pub fn put_bytes(&self, key: &str, value: &[u8]) {
+        let Ok(conn) = self.conn.lock() else { return };
+        if let Err(e) = conn.execute(
+            "INSERT OR REPLACE INTO blobs (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![key, value, now()],
+        ) {
+            tracing::warn!("Failed to write cache blob {}: {}", key, e);
+        }
+    }
+    
+    /// Drop the oldest blobs until all of them together fit in `max_bytes`
+    pub fn trim_blobs(&self, max_bytes: u64) {
+        let Ok(conn) = self.conn.lock() else { return };
+        if let Err(e) = conn.execute(
+            "DELETE FROM blobs WHERE key IN (
+                SELECT key FROM (
+                    SELECT key, SUM(length(value)) OVER (ORDER BY updated_at DESC, key) AS total FROM blobs
+                ) WHERE total > ?1
+            )",
+            params![max_bytes as i64],
+        ) {
+            tracing::warn!("Failed to trim cache blobs: {}", e);
+        }
+    }
+    
+    /// Store `value` under `key`, replacing any previous copy. Failures are only logged.
+    pub fn put<T: Serialize>(&self, key: &str, value: &T) {
+        let Ok(json) = serde_json::to_string(value) else { return };
+        let Ok(conn) = self.conn.lock() else { return };
+        if let Err(e) = conn.execute(
+            "INSERT OR REPLACE INTO cache (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![key, json, now()],
+        ) {
+            tracing::warn!("Failed to write cache entry {}: {}", key, e);
+        }
+    }
+}
+
+/// Engine that caches reads of the wrapped engine and serves them when it fails
+pub struct CachedEngine {
+    inner: Box<dyn Ops>,
+    store: &'static CacheStore,
+}
+
+impl CachedEngine {
+    pub fn new(inner: Box<dyn Ops>, store: &'static CacheStore) -> Self {
+        Self { inner, store }
+    }
+    
+    /// Store a successful result, or fall back to the cached copy on error
+    fn through<T: Serialize + DeserializeOwned>(&self, key: String, result: Result<T>) -> Result<T> {
+        match result {
+            Ok(value) => {
+                self.store.put(&key, &value);
+                Ok(value)
+            }
+            Err(e) => match self.store.get(&key) {
+                Some(cached) => {
+                    tracing::info!("Serving {} from offline cache: {}", key, e);
+                    Ok(cached)
+                }
+                None => Err(e),
+            },
+        }
+    }
+    
+    /// Serve a copy younger than `ttl` without a request, otherwise fetch `through` the cache
+    async fn fresh<T, F>(&self, key: String, ttl: Duration, fetch: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T>>,
+    {
+        if let Some(cached) = self.store.get_fresh(&key, ttl) {
+            return Ok(cached);
+        }
+        self.through(key, fetch.await)
+    }
+}
+
+#[async_trait]
+impl Ops for CachedEngine {
+    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        self.fresh(key("fetch_repos", &[]), LIST_TTL, self.inner.fetch_repos()).await
+    }
+    
+    async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        self.fresh(key("fetch_orgs", &[]), LIST_TTL, self.inner.fetch_orgs()).await
+    }
+    
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        self.fresh(key("fetch_org_repos", &[org]), LIST_TTL, self.inner.fetch_org_repos(org)).await
+    }
+    
+    async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String> {
+        let full_name = self.inner.create_repo(name, description, private, auto_init).await?;
+        self.store.remove(&key("fetch_repos", &[]));
+        Ok(full_name)
+    }
+    
+    async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        let result = self.inner.fetch_file_tree(owner, repo, path, git_ref).await;
+        self.through(key("fetch_file_tree", &[owner, repo, path, git_ref]), result)
+    }
+    
+    async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        self.inner.search_repos(query, sort, per_page).await
+    }
+    
+    async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        let result = self.inner.fetch_issues(owner, repo, state, milestone, labels).await;
+        self.through(key("fetch_issues", &[owner, repo, state, milestone, &labels.join(",")]), result)
+    }
+    
+    async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        self.inner.update_issue_state(owner, repo, issue_number, state).await
+    }
+    
+    async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        let result = self.inner.fetch_issue_comments(owner, repo, issue_number).await;
+        self.through(key("fetch_issue_comments", &[owner, repo, &issue_number.to_string()]), result)
+    }
+    
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        self.inner.create_comment(owner, repo, issue_number, body).await
+    }
+    
+    async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        let result = self.inner.fetch_pull_requests(owner, repo, state).await;
+        self.through(key("fetch_pull_requests", &[owner, repo, state]), result)
+    }
+    
+    async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        self.inner.merge_pull_request(owner, repo, pr_number, merge_method).await
+    }
+    
+    async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        self.inner.close_pull_request(owner, repo, pr_number).await
+    }
+}
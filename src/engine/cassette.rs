@@ -0,0 +1,158 @@
+//! Cassette Engine - Record/replay layer for captured GitHub responses.
+//!
+//! In record mode every `Ops` call is forwarded to a real engine and its
+//! result is written to `<dir>/<call>.json`. In replay mode those files are
+//! served instead, so a captured session can be re-run fully offline.
+//!
+//! Enabled with `NATIVE_HUB_CASSETTE=record:<dir>` or `replay:<dir>`.
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use crate::app_event::RepoData;
+use super::Ops;
+use super::api_client::{FileNode, Issue, IssueComment, MergeResult, PullRequest, SearchResult};
+
+/// Environment variable selecting the cassette mode and directory
+pub const CASSETTE_ENV: &str = "NATIVE_HUB_CASSETTE";
+
+/// Engine that records or replays `Ops` results as JSON fixtures
+pub struct CassetteEngine {
+    dir: PathBuf,
+    inner: Option<Box<dyn Ops>>, // None = replay mode
+}
+
+impl CassetteEngine {
+    /// Forward calls to `inner` and save each result under `dir`
+    pub fn record(inner: Box<dyn Ops>, dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), inner: Some(inner) }
+    }
+    
+    /// Serve results previously recorded under `dir`
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), inner: None }
+    }
+    
+    /// Wrap `inner` according to `NATIVE_HUB_CASSETTE`, or return it unchanged
+    pub fn from_env(inner: Box<dyn Ops>) -> Box<dyn Ops> {
+        match std::env::var(CASSETTE_ENV).ok().as_deref().and_then(|v| v.split_once(':')) {
+            Some(("record", dir)) => Box::new(Self::record(inner, dir)),
+            Some(("replay", dir)) => Box::new(Self::replay(dir)),
+            _ => inner,
+        }
+    }
+    
+    /// Fixture path for a call, e.g. `fetch_issues__octocat__hello__open_.json`
+    fn fixture_path(&self, call: &str, args: &[&str]) -> PathBuf {
+        let mut name = call.to_string();
+        for arg in args {
+            name.push_str("__");
+            name.extend(arg.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' }));
+        }
+        self.dir.join(format!("{}.json", name))
+    }
+    
+    /// Replay mode: the recorded result. Record mode: None, the call must be made
+    async fn load<T: DeserializeOwned>(&self, path: &PathBuf) -> Result<Option<T>> {
+        if self.inner.is_some() {
+            return Ok(None);
+        }
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("No recorded response at {}", path.display()))?;
+        let value = serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to parse fixture {}", path.display()))?;
+        Ok(Some(value))
+    }
+    
+    async fn save<T: Serialize>(&self, path: &PathBuf, value: &T) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .context("Failed to create cassette directory")?;
+        let data = serde_json::to_vec_pretty(value).context("Failed to serialize response")?;
+        tokio::fs::write(path, data)
+            .await
+            .with_context(|| format!("Failed to write fixture {}", path.display()))
+    }
+    
+    fn inner(&self) -> Result<&dyn Ops> {
+        self.inner.as_deref().ok_or_else(|| anyhow!("Cassette engine is in replay mode"))
+    }
+}
+
+/// Replay the fixture for `$call($args)` or run `$inner_call` on the wrapped engine and record it
+macro_rules! cassette {
+    ($self:ident, $call:literal, [$($arg:expr),*], |$inner:ident| $inner_call:expr) => {{
+        let path = $self.fixture_path($call, &[$($arg),*]);
+        if let Some(value) = $self.load(&path).await? {
+            return Ok(value);
+        }
+        let $inner = $self.inner()?;
+        let value = $inner_call.await?;
+        $self.save(&path, &value).await?;
+        Ok(value)
+    }};
+}
+
+#[async_trait]
+impl Ops for CassetteEngine {
+    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        cassette!(self, "fetch_repos", [], |inner| inner.fetch_repos())
+    }
+    
+    async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        cassette!(self, "fetch_orgs", [], |inner| inner.fetch_orgs())
+    }
+    
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        cassette!(self, "fetch_org_repos", [org], |inner| inner.fetch_org_repos(org))
+    }
+    
+    async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String> {
+        cassette!(self, "create_repo", [name], |inner| inner.create_repo(name, description, private, auto_init))
+    }
+    
+    async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        cassette!(self, "fetch_file_tree", [owner, repo, path, git_ref], |inner| inner.fetch_file_tree(owner, repo, path, git_ref))
+    }
+    
+    async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        cassette!(self, "search_repos", [query], |inner| inner.search_repos(query, sort, per_page))
+    }
+    
+    async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        let label_list = labels.join(",");
+        cassette!(self, "fetch_issues", [owner, repo, state, milestone, &label_list], |inner| inner.fetch_issues(owner, repo, state, milestone, labels))
+    }
+    
+    async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        let number = issue_number.to_string();
+        cassette!(self, "update_issue_state", [owner, repo, &number, state], |inner| inner.update_issue_state(owner, repo, issue_number, state))
+    }
+    
+    async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        let number = issue_number.to_string();
+        cassette!(self, "fetch_issue_comments", [owner, repo, &number], |inner| inner.fetch_issue_comments(owner, repo, issue_number))
+    }
+    
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        let number = issue_number.to_string();
+        cassette!(self, "create_comment", [owner, repo, &number], |inner| inner.create_comment(owner, repo, issue_number, body))
+    }
+    
+    async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        cassette!(self, "fetch_pull_requests", [owner, repo, state], |inner| inner.fetch_pull_requests(owner, repo, state))
+    }
+    
+    async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        let number = pr_number.to_string();
+        cassette!(self, "merge_pull_request", [owner, repo, &number], |inner| inner.merge_pull_request(owner, repo, pr_number, merge_method))
+    }
+    
+    async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let number = pr_number.to_string();
+        cassette!(self, "close_pull_request", [owner, repo, &number], |inner| inner.close_pull_request(owner, repo, pr_number))
+    }
+}
@@ -3,9 +3,16 @@
 use anyhow::{Result, Context, anyhow};
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use crate::app_event::RepoData;
 use super::Ops;
+use super::api_client::{
+    FileNode, Issue, IssueComment, IssueLabel, IssueUser, MergeResult, Milestone, PullRequest, PullRequestRef,
+    RepoOwner, SearchRepoItem, SearchResult,
+};
 
 /// Engine that wraps the `gh` CLI tool.
 pub struct GhCliEngine;
@@ -34,15 +41,154 @@ struct GhRepoJson {
     fork_count: u32,
 }
 
-#[async_trait]
-impl Ops for GhCliEngine {
-    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+/// Raw JSON structure from `gh api user/orgs`
+#[derive(Debug, Deserialize)]
+struct GhOrgJson {
+    login: String,
+}
+
+/// Author / owner object in `gh ... --json` output (no avatar URL)
+#[derive(Debug, Deserialize)]
+struct GhActorJson {
+    login: String,
+}
+
+impl GhActorJson {
+    fn into_user(self) -> IssueUser {
+        IssueUser {
+            avatar_url: format!("https://github.com/{}.png", self.login),
+            login: self.login,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhLabelJson {
+    name: String,
+    #[serde(default)]
+    color: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl From<GhLabelJson> for IssueLabel {
+    fn from(l: GhLabelJson) -> Self {
+        IssueLabel { name: l.name, color: l.color, description: l.description }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhMilestoneJson {
+    number: u32,
+    title: String,
+    #[serde(rename = "dueOn", default)]
+    due_on: Option<String>,
+}
+
+/// Raw JSON structure from `gh issue list --json`
+#[derive(Debug, Deserialize)]
+struct GhIssueJson {
+    number: u32,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String, // "OPEN" / "CLOSED"
+    #[serde(default)]
+    author: Option<GhActorJson>, // null for deleted accounts
+    #[serde(default)]
+    labels: Vec<GhLabelJson>,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+    #[serde(default)]
+    comments: Vec<serde::de::IgnoredAny>, // Only the count is used
+    url: String,
+    #[serde(default)]
+    milestone: Option<GhMilestoneJson>,
+}
+
+/// Raw JSON structure from `gh pr list --json`
+#[derive(Debug, Deserialize)]
+struct GhPullRequestJson {
+    id: String, // GraphQL node id
+    number: u32,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String, // "OPEN" / "CLOSED" / "MERGED"
+    #[serde(default)]
+    author: Option<GhActorJson>,
+    #[serde(default)]
+    labels: Vec<GhLabelJson>,
+    #[serde(rename = "createdAt")]
+    created_at: String,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+    url: String,
+    #[serde(rename = "headRefName")]
+    head_ref_name: String,
+    #[serde(rename = "headRefOid")]
+    head_ref_oid: String,
+    #[serde(rename = "headRepositoryOwner", default)]
+    head_repository_owner: Option<GhActorJson>,
+    #[serde(rename = "baseRefName")]
+    base_ref_name: String,
+    #[serde(default)]
+    mergeable: String, // "MERGEABLE" / "CONFLICTING" / "UNKNOWN"
+    #[serde(default)]
+    comments: Vec<serde::de::IgnoredAny>,
+    #[serde(default)]
+    additions: u32,
+    #[serde(default)]
+    deletions: u32,
+    #[serde(rename = "changedFiles", default)]
+    changed_files: u32,
+    #[serde(rename = "isDraft", default)]
+    is_draft: bool,
+}
+
+/// Raw JSON structure from `gh search repos --json`
+#[derive(Debug, Deserialize)]
+struct GhSearchRepoJson {
+    name: String,
+    #[serde(rename = "fullName")]
+    full_name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "isPrivate", default)]
+    is_private: bool,
+    #[serde(rename = "stargazersCount", default)]
+    stargazers_count: u32,
+    #[serde(rename = "forksCount", default)]
+    forks_count: u32,
+    #[serde(default)]
+    language: Option<String>,
+    url: String,
+    owner: GhActorJson,
+    #[serde(rename = "updatedAt", default)]
+    updated_at: String,
+}
+
+const ISSUE_FIELDS: &str = "number,title,body,state,author,labels,createdAt,updatedAt,comments,url,milestone";
+const PR_FIELDS: &str = "id,number,title,body,state,author,labels,createdAt,updatedAt,url,headRefName,headRefOid,\
+headRepositoryOwner,baseRefName,mergeable,comments,additions,deletions,changedFiles,isDraft";
+const SEARCH_REPO_FIELDS: &str = "name,fullName,description,isPrivate,stargazersCount,forksCount,language,url,owner,updatedAt";
+
+impl GhCliEngine {
+    /// Run `gh repo list`, optionally for a specific owner (user or org)
+    async fn list_repos(&self, owner: Option<&str>) -> Result<Vec<RepoData>> {
+        let mut args = vec!["repo", "list"];
+        if let Some(owner) = owner {
+            args.push(owner);
+        }
+        args.extend([
+            "--json", "name,nameWithOwner,description,isPrivate,updatedAt,stargazerCount,forkCount",
+            "--limit", "50"
+        ]);
+        
         let output = Command::new("gh")
-            .args([
-                "repo", "list",
-                "--json", "name,nameWithOwner,description,isPrivate,updatedAt,stargazerCount,forkCount",
-                "--limit", "50"
-            ])
+            .args(&args)
             .output()
             .await
             .context("Failed to run 'gh repo list'. Is GitHub CLI installed?")?;
@@ -63,7 +209,7 @@ impl Ops for GhCliEngine {
                 full_name: r.name_with_owner,
                 description: r.description.unwrap_or_default(),
                 is_private: r.is_private,
-                last_updated: format_relative_time(&r.updated_at),
+                last_updated: r.updated_at,
                 stars_count: r.stargazer_count,
                 forks_count: r.fork_count,
             }
@@ -71,15 +217,282 @@ impl Ops for GhCliEngine {
         
         Ok(repos)
     }
+    
+    /// Run a `gh` subcommand that prints JSON (`--json ...`) and parse its output
+    async fn gh_json<T: DeserializeOwned>(&self, args: &[&str]) -> Result<T> {
+        let command = args.iter().take(2).copied().collect::<Vec<_>>().join(" ");
+        let output = Command::new("gh")
+            .args(args)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run 'gh {}'. Is GitHub CLI installed?", command))?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("gh {} failed: {}", command, stderr.trim()));
+        }
+        
+        serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Failed to parse gh {} output", command))
+    }
+    
+    /// Call a REST endpoint through `gh api`, sending `body` as the JSON request body
+    async fn api<T: DeserializeOwned>(&self, method: &str, endpoint: &str, body: Option<serde_json::Value>) -> Result<T> {
+        let mut cmd = Command::new("gh");
+        cmd.args(["api", "-X", method, endpoint])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if body.is_some() {
+            cmd.args(["--input", "-"]).stdin(Stdio::piped());
+        } else {
+            cmd.stdin(Stdio::null());
+        }
+        
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("Failed to run 'gh api {}'. Is GitHub CLI installed?", endpoint))?;
+        
+        if let (Some(body), Some(mut stdin)) = (body, child.stdin.take()) {
+            stdin.write_all(body.to_string().as_bytes())
+                .await
+                .context("Failed to write gh api request body")?;
+            // Dropping stdin closes it so gh stops reading
+        }
+        
+        let output = child
+            .wait_with_output()
+            .await
+            .with_context(|| format!("Failed to run 'gh api {}'", endpoint))?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("gh api {} failed: {}", endpoint, stderr.trim()));
+        }
+        
+        serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Failed to parse gh api {} output", endpoint))
+    }
 }
 
-/// Convert ISO timestamp to relative time (e.g., "2 hours ago")
-fn format_relative_time(iso: &str) -> String {
-    // Simple implementation - just show the date for now
-    // TODO: Use chrono for proper relative time formatting
-    if let Some(date_part) = iso.split('T').next() {
-        date_part.to_string()
-    } else {
-        iso.to_string()
+#[async_trait]
+impl Ops for GhCliEngine {
+    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        self.list_repos(None).await
+    }
+    
+    async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        let output = Command::new("gh")
+            .args(["api", "user/orgs"])
+            .output()
+            .await
+            .context("Failed to run 'gh api user/orgs'. Is GitHub CLI installed?")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("gh api user/orgs failed: {}", stderr.trim()));
+        }
+        
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let orgs: Vec<GhOrgJson> = serde_json::from_str(&stdout)
+            .context("Failed to parse gh api user/orgs output")?;
+        
+        Ok(orgs.into_iter().map(|o| o.login).collect())
+    }
+    
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        self.list_repos(Some(org)).await
+    }
+    
+    async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String> {
+        let mut args = vec!["repo", "create", name, if private { "--private" } else { "--public" }];
+        if !description.is_empty() {
+            args.extend(["--description", description]);
+        }
+        if auto_init {
+            args.push("--add-readme");
+        }
+        
+        let output = Command::new("gh")
+            .args(&args)
+            .output()
+            .await
+            .context("Failed to run 'gh repo create'. Is GitHub CLI installed?")?;
+        
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("gh repo create failed: {}", stderr.trim()));
+        }
+        
+        // gh prints the URL of the new repo, e.g. https://github.com/owner/name (or an Enterprise host)
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let url = stdout.lines().rev().find(|line| line.contains("://")).unwrap_or_default().trim();
+        repo_from_url(url).with_context(|| format!("Unexpected 'gh repo create' output: {}", stdout.trim()))
+    }
+    
+    async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>> {
+        let mut endpoint = format!("repos/{}/{}/contents/{}", owner, repo, path).trim_end_matches('/').to_string();
+        if !git_ref.is_empty() {
+            endpoint.push_str(&format!("?ref={}", urlencoding::encode(git_ref)));
+        }
+        self.api("GET", &endpoint, None).await
+    }
+    
+    async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        let limit = per_page.min(100).to_string();
+        let mut args = vec!["search", "repos", query, "--json", SEARCH_REPO_FIELDS, "--limit", &limit];
+        if let Some(s) = sort {
+            args.extend(["--sort", s]);
+        }
+        let raw: Vec<GhSearchRepoJson> = self.gh_json(&args).await?;
+        
+        let items: Vec<SearchRepoItem> = raw.into_iter().map(|r| SearchRepoItem {
+            id: 0, // gh only exposes the GraphQL node id
+            name: r.name,
+            full_name: r.full_name,
+            description: r.description,
+            is_private: r.is_private,
+            stargazers_count: r.stargazers_count,
+            forks_count: r.forks_count,
+            language: r.language,
+            topics: Vec::new(),
+            html_url: r.url,
+            owner: {
+                let user = r.owner.into_user();
+                RepoOwner { login: user.login, avatar_url: user.avatar_url }
+            },
+            updated_at: r.updated_at,
+        }).collect();
+        
+        // gh does not report the total hit count; what was returned is all we know
+        Ok(SearchResult {
+            total_count: items.len() as u32,
+            incomplete_results: false,
+            items,
+        })
     }
+    
+    async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        let full_name = format!("{}/{}", owner, repo);
+        let limit = crate::config::current().page_size.to_string();
+        let mut args = vec!["issue", "list", "-R", &full_name, "--state", state, "--json", ISSUE_FIELDS, "--limit", &limit];
+        match milestone {
+            "" => {}
+            "none" => args.extend(["--search", "no:milestone"]),
+            number => args.extend(["--milestone", number]),
+        }
+        for label in labels {
+            args.extend(["--label", label.as_str()]);
+        }
+        let raw: Vec<GhIssueJson> = self.gh_json(&args).await?;
+        
+        Ok(raw.into_iter().map(|i| Issue {
+            id: i.number as u64, // gh only exposes the GraphQL node id
+            number: i.number,
+            title: i.title,
+            body: i.body,
+            state: i.state.to_lowercase(),
+            user: i.author.map(GhActorJson::into_user).unwrap_or_else(ghost_user),
+            labels: i.labels.into_iter().map(Into::into).collect(),
+            created_at: i.created_at,
+            updated_at: i.updated_at,
+            comments: i.comments.len() as u32,
+            html_url: i.url,
+            pull_request: None, // gh issue list never returns PRs
+            milestone: i.milestone.map(|m| Milestone {
+                number: m.number,
+                title: m.title,
+                state: String::new(),
+                open_issues: 0,
+                closed_issues: 0,
+                due_on: m.due_on,
+            }),
+        }).collect())
+    }
+    
+    async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        let endpoint = format!("repos/{}/{}/issues/{}", owner, repo, issue_number);
+        self.api("PATCH", &endpoint, Some(serde_json::json!({ "state": state }))).await
+    }
+    
+    async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        let endpoint = format!("repos/{}/{}/issues/{}/comments", owner, repo, issue_number);
+        self.api("GET", &endpoint, None).await
+    }
+    
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        let endpoint = format!("repos/{}/{}/issues/{}/comments", owner, repo, issue_number);
+        self.api("POST", &endpoint, Some(serde_json::json!({ "body": body }))).await
+    }
+    
+    async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        let full_name = format!("{}/{}", owner, repo);
+        let limit = crate::config::current().page_size.to_string();
+        let args = ["pr", "list", "-R", &full_name, "--state", state, "--json", PR_FIELDS, "--limit", &limit];
+        let raw: Vec<GhPullRequestJson> = self.gh_json(&args).await?;
+        
+        Ok(raw.into_iter().map(|p| {
+            let merged = p.state == "MERGED";
+            let head_owner = p.head_repository_owner.map(|o| o.login).unwrap_or_else(|| owner.to_string());
+            PullRequest {
+                id: p.number as u64,
+                number: p.number,
+                title: p.title,
+                body: p.body,
+                // REST reports merged PRs as closed + merged
+                state: if merged { "closed".to_string() } else { p.state.to_lowercase() },
+                user: p.author.map(GhActorJson::into_user).unwrap_or_else(ghost_user),
+                labels: p.labels.into_iter().map(Into::into).collect(),
+                created_at: p.created_at,
+                updated_at: p.updated_at,
+                html_url: p.url,
+                head: PullRequestRef {
+                    label: format!("{}:{}", head_owner, p.head_ref_name),
+                    ref_name: p.head_ref_name,
+                    sha: p.head_ref_oid,
+                },
+                base: PullRequestRef {
+                    label: format!("{}:{}", owner, p.base_ref_name),
+                    ref_name: p.base_ref_name,
+                    sha: String::new(),
+                },
+                merged,
+                mergeable: match p.mergeable.as_str() {
+                    "MERGEABLE" => Some(true),
+                    "CONFLICTING" => Some(false),
+                    _ => None,
+                },
+                mergeable_state: None,
+                comments: p.comments.len() as u32,
+                commits: 0,
+                additions: p.additions,
+                deletions: p.deletions,
+                changed_files: p.changed_files,
+                draft: p.is_draft,
+                node_id: p.id,
+            }
+        }).collect())
+    }
+    
+    async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        let endpoint = format!("repos/{}/{}/pulls/{}/merge", owner, repo, pr_number);
+        self.api("PUT", &endpoint, Some(serde_json::json!({ "merge_method": merge_method }))).await
+    }
+    
+    async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let endpoint = format!("repos/{}/{}/pulls/{}", owner, repo, pr_number);
+        self.api("PATCH", &endpoint, Some(serde_json::json!({ "state": "closed" }))).await
+    }
+}
+
+/// Stand-in author for deleted accounts, matching what the REST API returns
+fn ghost_user() -> IssueUser {
+    GhActorJson { login: "ghost".to_string() }.into_user()
+}
+
+/// `owner/name` from the web URL of a repository on any host
+fn repo_from_url(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let mut segments = rest.split('/').skip(1).filter(|s| !s.is_empty());
+    let (owner, name) = (segments.next()?, segments.next()?);
+    Some(format!("{}/{}", owner, name.trim_end_matches(".git")))
 }
@@ -0,0 +1,173 @@
+//! Mock Engine - In-memory `Ops` implementation for offline testing.
+//!
+//! Serves canned data for a single repository and applies mutations
+//! (closing issues, merging PRs, commenting) to its in-memory state, so
+//! backend and UI flows can be exercised without touching GitHub.
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use crate::app_event::RepoData;
+use super::Ops;
+use super::api_client::{FileNode, Issue, IssueComment, IssueUser, MergeResult, PullRequest, SearchResult};
+
+/// Data served by the mock engine
+#[derive(Debug, Clone, Default)]
+pub struct MockState {
+    pub repos: Vec<RepoData>,
+    pub orgs: Vec<String>,
+    pub org_repos: HashMap<String, Vec<RepoData>>,
+    pub trees: HashMap<String, Vec<FileNode>>, // Keyed by directory path
+    pub search_results: Vec<crate::app_event::SearchRepoItem>,
+    pub issues: Vec<Issue>,
+    pub comments: HashMap<u32, Vec<IssueComment>>, // Keyed by issue number
+    pub pull_requests: Vec<PullRequest>,
+    pub login: String, // Author of created comments
+}
+
+/// Engine answering every call from a `MockState`
+#[derive(Default)]
+pub struct MockEngine {
+    state: Mutex<MockState>,
+}
+
+impl MockEngine {
+    pub fn new(state: MockState) -> Self {
+        Self { state: Mutex::new(state) }
+    }
+    
+    /// Current state, e.g. to check the effect of a mutation
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Whether an item in `item_state` matches a list filter ("all" matches everything)
+fn matches_state(filter: &str, item_state: &str) -> bool {
+    filter == "all" || filter == item_state
+}
+
+#[async_trait]
+impl Ops for MockEngine {
+    async fn fetch_repos(&self) -> Result<Vec<RepoData>> {
+        Ok(self.state().repos.clone())
+    }
+    
+    async fn fetch_orgs(&self) -> Result<Vec<String>> {
+        Ok(self.state().orgs.clone())
+    }
+    
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>> {
+        Ok(self.state().org_repos.get(org).cloned().unwrap_or_default())
+    }
+    
+    async fn create_repo(&self, name: &str, description: &str, private: bool, _auto_init: bool) -> Result<String> {
+        let mut state = self.state();
+        let full_name = format!("{}/{}", state.login, name);
+        state.repos.insert(0, RepoData {
+            name: name.to_string(),
+            full_name: full_name.clone(),
+            description: description.to_string(),
+            is_private: private,
+            last_updated: String::new(),
+            stars_count: 0,
+            forks_count: 0,
+        });
+        Ok(full_name)
+    }
+    
+    async fn fetch_file_tree(&self, _owner: &str, _repo: &str, path: &str, _git_ref: &str) -> Result<Vec<FileNode>> {
+        self.state().trees.get(path).cloned().ok_or_else(|| anyhow!("Not Found: /{}", path))
+    }
+    
+    async fn search_repos(&self, query: &str, _sort: Option<&str>, per_page: u32) -> Result<SearchResult> {
+        let query = query.to_lowercase();
+        let items: Vec<_> = self.state().search_results.iter()
+            .filter(|r| r.full_name.to_lowercase().contains(&query))
+            .take(per_page as usize)
+            .cloned()
+            .collect();
+        Ok(SearchResult {
+            total_count: items.len() as u32,
+            incomplete_results: false,
+            items,
+        })
+    }
+    
+    async fn fetch_issues(&self, _owner: &str, _repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>> {
+        Ok(self.state().issues.iter()
+            .filter(|i| matches_state(state, &i.state))
+            .filter(|i| match milestone {
+                "" | "*" => milestone.is_empty() || i.milestone.is_some(),
+                "none" => i.milestone.is_none(),
+                number => i.milestone.as_ref().is_some_and(|m| m.number.to_string() == number),
+            })
+            .filter(|i| labels.iter().all(|name| i.labels.iter().any(|l| &l.name == name)))
+            .cloned()
+            .collect())
+    }
+    
+    async fn update_issue_state(&self, _owner: &str, _repo: &str, issue_number: u32, state: &str) -> Result<Issue> {
+        let mut mock = self.state();
+        let issue = mock.issues.iter_mut()
+            .find(|i| i.number == issue_number)
+            .ok_or_else(|| anyhow!("Not Found: issue #{}", issue_number))?;
+        issue.state = state.to_string();
+        Ok(issue.clone())
+    }
+    
+    async fn fetch_issue_comments(&self, _owner: &str, _repo: &str, issue_number: u32) -> Result<Vec<IssueComment>> {
+        Ok(self.state().comments.get(&issue_number).cloned().unwrap_or_default())
+    }
+    
+    async fn create_comment(&self, _owner: &str, _repo: &str, issue_number: u32, body: &str) -> Result<IssueComment> {
+        let mut state = self.state();
+        let id = state.comments.values().map(Vec::len).sum::<usize>() as u64 + 1;
+        let comment = IssueComment {
+            id,
+            body: body.to_string(),
+            user: IssueUser {
+                login: state.login.clone(),
+                avatar_url: String::new(),
+            },
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        state.comments.entry(issue_number).or_default().push(comment.clone());
+        Ok(comment)
+    }
+    
+    async fn fetch_pull_requests(&self, _owner: &str, _repo: &str, state: &str) -> Result<Vec<PullRequest>> {
+        Ok(self.state().pull_requests.iter()
+            .filter(|pr| matches_state(state, &pr.state))
+            .cloned()
+            .collect())
+    }
+    
+    async fn merge_pull_request(&self, _owner: &str, _repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult> {
+        let mut state = self.state();
+        let pr = state.pull_requests.iter_mut()
+            .find(|pr| pr.number == pr_number)
+            .ok_or_else(|| anyhow!("Not Found: pull request #{}", pr_number))?;
+        if pr.merged || pr.state != "open" {
+            return Err(anyhow!("Pull request #{} is not mergeable", pr_number));
+        }
+        pr.merged = true;
+        pr.state = "closed".to_string();
+        Ok(MergeResult {
+            sha: pr.head.sha.clone(),
+            merged: true,
+            message: format!("Pull Request successfully merged ({})", merge_method),
+        })
+    }
+    
+    async fn close_pull_request(&self, _owner: &str, _repo: &str, pr_number: u32) -> Result<PullRequest> {
+        let mut state = self.state();
+        let pr = state.pull_requests.iter_mut()
+            .find(|pr| pr.number == pr_number)
+            .ok_or_else(|| anyhow!("Not Found: pull request #{}", pr_number))?;
+        pr.state = "closed".to_string();
+        Ok(pr.clone())
+    }
+}
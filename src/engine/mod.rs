@@ -1,14 +1,20 @@
 //! Engine Layer - Abstract GitHub Operations
 //! 
 //! This module provides a unified interface for GitHub operations.
-//! The primary implementation uses `gh` CLI, with a future fallback to native HTTP API.
+//! It is implemented by the `gh` CLI engine and by the native HTTP `ApiClient`,
+//! so the backend can dispatch through `Box<dyn Ops>` regardless of which is in use.
 
 pub mod gh_cli;
 pub mod api_client;
+#[cfg(test)]
+pub mod mock;
+pub mod cassette;
+pub mod cache;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use crate::app_event::RepoData;
+use api_client::{FileNode, Issue, IssueComment, MergeResult, PullRequest, SearchResult};
 
 /// Core operations trait - all engines must implement this.
 #[async_trait]
@@ -16,10 +22,116 @@ pub trait Ops: Send + Sync {
     /// Fetch list of repositories for the authenticated user.
     async fn fetch_repos(&self) -> Result<Vec<RepoData>>;
     
-    // Future methods:
-    // async fn fetch_issues(&self, repo: &str) -> Result<Vec<IssueData>>;
-    // async fn fetch_file_tree(&self, repo: &str, path: &str) -> Result<Vec<FileEntry>>;
+    /// Fetch logins of the organizations the authenticated user belongs to.
+    async fn fetch_orgs(&self) -> Result<Vec<String>>;
+    
+    /// Fetch repositories owned by an organization.
+    async fn fetch_org_repos(&self, org: &str) -> Result<Vec<RepoData>>;
+    
+    /// Create a repository for the authenticated user, returning its full name.
+    async fn create_repo(&self, name: &str, description: &str, private: bool, auto_init: bool) -> Result<String>;
+    
+    /// List a directory of a repository at a ref ("" = default branch).
+    async fn fetch_file_tree(&self, owner: &str, repo: &str, path: &str, git_ref: &str) -> Result<Vec<FileNode>>;
+    
+    /// Search public repositories.
+    async fn search_repos(&self, query: &str, sort: Option<&str>, per_page: u32) -> Result<SearchResult>;
+    
+    /// Fetch issues by state ("open"/"closed"/"all"), milestone filter ("" = any)
+    /// and labels (issues must carry all of them).
+    async fn fetch_issues(&self, owner: &str, repo: &str, state: &str, milestone: &str, labels: &[String]) -> Result<Vec<Issue>>;
+    
+    /// Open or close an issue.
+    async fn update_issue_state(&self, owner: &str, repo: &str, issue_number: u32, state: &str) -> Result<Issue>;
+    
+    /// Fetch the comments of an issue or pull request.
+    async fn fetch_issue_comments(&self, owner: &str, repo: &str, issue_number: u32) -> Result<Vec<IssueComment>>;
+    
+    /// Comment on an issue or pull request.
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u32, body: &str) -> Result<IssueComment>;
+    
+    /// Fetch pull requests by state.
+    async fn fetch_pull_requests(&self, owner: &str, repo: &str, state: &str) -> Result<Vec<PullRequest>>;
+    
+    /// Merge a pull request with "merge", "squash" or "rebase".
+    async fn merge_pull_request(&self, owner: &str, repo: &str, pr_number: u32, merge_method: &str) -> Result<MergeResult>;
+    
+    /// Close a pull request without merging.
+    async fn close_pull_request(&self, owner: &str, repo: &str, pr_number: u32) -> Result<PullRequest>;
+}
+
+/// Which engine backs the `Ops` trait for this session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineKind {
+    /// `gh` CLI (desktop with GitHub CLI installed and logged in)
+    GhCli,
+    /// Native HTTP client (always available, the only option on Android)
+    Http,
+}
+
+impl EngineKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EngineKind::GhCli => "GH CLI",
+            EngineKind::Http => "HTTP",
+        }
+    }
+}
+
+/// Pick the engine to use: `gh` if it is installed and authenticated, HTTP otherwise
+pub async fn probe_engine() -> EngineKind {
+    if cfg!(target_os = "android") {
+        return EngineKind::Http;
+    }
+    
+    let status = tokio::process::Command::new("gh")
+        .args(["auth", "status"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await;
+    
+    match status {
+        Ok(status) if status.success() => EngineKind::GhCli,
+        _ => EngineKind::Http,
+    }
+}
+
+/// Create an engine of the given kind; `token` is used by the HTTP engine.
+/// Wrapped in a recording/replaying cassette when `NATIVE_HUB_CASSETTE` is set,
+/// and in the offline cache when it is available. The cache is first claimed
+/// for the token's account, so another account's data is never served.
+pub fn create_engine(kind: EngineKind, token: String) -> Box<dyn Ops> {
+    let account = cache::account_id(&crate::config::current().api_base(), &token);
+    let engine: Box<dyn Ops> = match kind {
+        EngineKind::GhCli => Box::new(gh_cli::GhCliEngine::new()),
+        EngineKind::Http => Box::new(api_client::ApiClient::new(token)),
+    };
+    let engine = cassette::CassetteEngine::from_env(engine);
+    match cache::global() {
+        Some(store) => {
+            store.claim(&account);
+            Box::new(cache::CachedEngine::new(engine, store))
+        }
+        None => engine,
+    }
 }
 
-// Re-export default engine
-pub use gh_cli::GhCliEngine;
+
+/// Receives `(current, total)` progress of a long-running engine call
+pub type ProgressFn = std::sync::Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+tokio::task_local! {
+    static PROGRESS: ProgressFn;
+}
+
+/// Run `fut` with `on_progress` receiving the progress engines report while it runs
+pub async fn with_progress<F: std::future::Future>(on_progress: ProgressFn, fut: F) -> F::Output {
+    PROGRESS.scope(on_progress, fut).await
+}
+
+/// Report progress to the reporter installed by `with_progress`, if any
+pub(crate) fn report_progress(current: u64, total: Option<u64>) {
+    let _ = PROGRESS.try_with(|on_progress| on_progress(current, total));
+}
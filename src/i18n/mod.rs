@@ -1,18 +1,29 @@
 //! Internationalization (i18n) Module
 //! 
 //! Provides multi-language support for the NativeHub UI.
-//! Chinese (zh-CN) is the primary language.
+//! Chinese (zh-CN) is the primary language; keys missing from a language fall back to English.
 
+mod number;
 mod strings;
+mod text;
+mod time;
 
 pub use strings::*;
+pub use text::truncate_chars;
 
 /// Supported languages
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Lang {
     #[default]
     ZhCn, // 简体中文 - Default
     En,   // English
+    ZhTw, // 繁體中文
+    Ja,   // 日本語
+    Ko,   // 한국어
+    De,   // Deutsch
+    Fr,   // Français
+    Es,   // Español
 }
 
 impl Lang {
@@ -20,11 +31,64 @@ impl Lang {
         match self {
             Lang::ZhCn => "简体中文",
             Lang::En => "English",
+            Lang::ZhTw => "繁體中文",
+            Lang::Ja => "日本語",
+            Lang::Ko => "한국어",
+            Lang::De => "Deutsch",
+            Lang::Fr => "Français",
+            Lang::Es => "Español",
+        }
+    }
+    
+    /// Locale file name, e.g. `zh-CN` for `locales/zh-CN.json`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lang::ZhCn => "zh-CN",
+            Lang::En => "en",
+            Lang::ZhTw => "zh-TW",
+            Lang::Ja => "ja",
+            Lang::Ko => "ko",
+            Lang::De => "de",
+            Lang::Fr => "fr",
+            Lang::Es => "es",
+        }
+    }
+
+    /// Language matching a BCP 47 locale like `en-US`, `zh-Hant-TW` or `ja_JP`
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let locale = locale.to_lowercase().replace('_', "-");
+        let mut parts = locale.split(['-', '.']);
+        let lang = match parts.next()? {
+            "zh" if parts.any(|part| matches!(part, "hant" | "tw" | "hk" | "mo")) => Lang::ZhTw,
+            "zh" => Lang::ZhCn,
+            "en" => Lang::En,
+            "ja" => Lang::Ja,
+            "ko" => Lang::Ko,
+            "de" => Lang::De,
+            "fr" => Lang::Fr,
+            "es" => Lang::Es,
+            _ => return None,
+        };
+        Some(lang)
+    }
+    
+    /// Language of the system locale, Chinese if it isn't one we support
+    pub fn detect() -> Lang {
+        sys_locale::get_locale()
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or_default()
+    }
+    
+    /// Written right to left; the layout is mirrored for these.
+    /// None of the shipped packs are, this is here for Arabic/Hebrew.
+    pub fn is_rtl(&self) -> bool {
+        match self {
+            Lang::ZhCn | Lang::En | Lang::ZhTw | Lang::Ja | Lang::Ko | Lang::De | Lang::Fr | Lang::Es => false,
         }
     }
     
     pub fn all() -> &'static [Lang] {
-        &[Lang::ZhCn, Lang::En]
+        &[Lang::ZhCn, Lang::ZhTw, Lang::En, Lang::Ja, Lang::Ko, Lang::De, Lang::Fr, Lang::Es]
     }
 }
 
@@ -49,6 +113,47 @@ impl I18n {
         strings::get(self.lang, key)
     }
     
+    /// Translated string with its `{}` / `{0}` placeholders filled from `args`
+    pub fn tf(&self, key: &str, args: &[&dyn std::fmt::Display]) -> String {
+        strings::interpolate(self.t(key), args, &[])
+    }
+    
+    /// `arg` translated if it is a message key (operation names in log
+    /// messages and the like), otherwise `arg` itself
+    pub fn arg<'a>(&self, arg: &'a str) -> &'a str {
+        strings::lookup(self.lang, arg).unwrap_or(arg)
+    }
+    
+    /// Translated string with its `{name}` placeholders filled from `args`
+    pub fn tf_named(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        strings::interpolate(self.t(key), &[], args)
+    }
+    
+    /// "3 hours ago" for a GitHub timestamp (RFC 3339 or a plain date)
+    pub fn relative_time(&self, timestamp: &str) -> String {
+        time::relative(self.lang, timestamp)
+    }
+    
+    /// Full local date and time of a GitHub timestamp, for tooltips
+    pub fn local_time(&self, timestamp: &str) -> String {
+        time::local(self.lang, timestamp)
+    }
+    
+    /// Shortened star/fork/comment count, e.g. "1.2k" or "1.2万"
+    pub fn count(&self, count: impl Into<u64>) -> String {
+        number::compact(self.lang, count.into())
+    }
+    
+    /// Byte count with a unit, e.g. "1.5 MB"
+    pub fn file_size(&self, bytes: u64) -> String {
+        number::file_size(self.lang, bytes)
+    }
+    
+    /// Whether the layout should be mirrored for the active language
+    pub fn is_rtl(&self) -> bool {
+        self.lang.is_rtl()
+    }
+    
     /// Switch language
     pub fn set_lang(&mut self, lang: Lang) {
         self.lang = lang;
@@ -0,0 +1,74 @@
+//! Numbers
+//!
+//! Star/fork/comment counts are shortened the way each language does it
+//! (`1.2k`, `1.2万`, `1,2 Tsd.`) and byte counts get a unit, both with the
+//! language's decimal separator.
+
+use super::Lang;
+
+/// Decimal comma instead of a decimal point
+fn decimal_comma(lang: Lang) -> bool {
+    matches!(lang, Lang::De | Lang::Fr | Lang::Es)
+}
+
+/// `value` with one decimal, dropped when it is zero: `1.2`, `3`, `1,5`
+fn one_decimal(lang: Lang, value: f64) -> String {
+    let text = format!("{:.1}", value);
+    let text = text.strip_suffix(".0").unwrap_or(&text);
+    if decimal_comma(lang) {
+        text.replace('.', ",")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Shortened count: "987", "1.2k", "3.4M" / "1.2万", "3.4亿"
+pub fn compact(lang: Lang, count: u64) -> String {
+    let n = count as f64;
+    match lang {
+        Lang::ZhCn | Lang::ZhTw | Lang::Ja | Lang::Ko => {
+            let (ten_thousand, hundred_million) = match lang {
+                Lang::ZhCn => ("万", "亿"),
+                Lang::Ko => ("만", "억"),
+                _ => ("万", "億"),
+            };
+            match count {
+                0..10_000 => count.to_string(),
+                10_000..100_000_000 => format!("{}{}", one_decimal(lang, n / 1e4), ten_thousand),
+                _ => format!("{}{}", one_decimal(lang, n / 1e8), hundred_million),
+            }
+        }
+        Lang::En | Lang::De | Lang::Fr | Lang::Es => {
+            let (thousand, million) = match lang {
+                Lang::De => (" Tsd.", " Mio."),
+                Lang::Fr => (" k", " M"),
+                Lang::Es => (" mil", " M"),
+                _ => ("k", "M"),
+            };
+            match count {
+                0..1_000 => count.to_string(),
+                1_000..1_000_000 => format!("{}{}", one_decimal(lang, n / 1e3), thousand),
+                _ => format!("{}{}", one_decimal(lang, n / 1e6), million),
+            }
+        }
+    }
+}
+
+/// Byte count with a binary unit: "512 B", "1.5 MB" ("1,5 Mo" in French)
+pub fn file_size(lang: Lang, bytes: u64) -> String {
+    let units: [&str; 4] = match lang {
+        Lang::Fr => ["o", "Ko", "Mo", "Go"],
+        _ => ["B", "KB", "MB", "GB"],
+    };
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{} {}", one_decimal(lang, value), units[unit])
+    }
+}
@@ -1,121 +1,165 @@
 //! Translation strings for all supported languages
+//!
+//! Each language is a flat JSON table of key -> text under `locales/`, embedded at
+//! build time. A file of the same name in `<config dir>/locales/` is read on first
+//! use and overrides individual keys, so translations can be fixed without recompiling.
+//!
+//! English holds the full key set: other languages fall back to it, and coverage
+//! is measured against it. Set `NATIVE_HUB_I18N_DEBUG=1` to log every key that
+//! is missing from the active language.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock};
 
 use super::Lang;
 
-/// Get translated string by key and language
-pub fn get(lang: Lang, key: &str) -> &'static str {
+type Table = HashMap<String, String>;
+
+/// Embedded locale file of a language
+fn embedded(lang: Lang) -> &'static str {
     match lang {
-        Lang::ZhCn => zh_cn(key),
-        Lang::En => en(key),
+        Lang::ZhCn => include_str!("locales/zh-CN.json"),
+        Lang::En => include_str!("locales/en.json"),
+        Lang::ZhTw => include_str!("locales/zh-TW.json"),
+        Lang::Ja => include_str!("locales/ja.json"),
+        Lang::Ko => include_str!("locales/ko.json"),
+        Lang::De => include_str!("locales/de.json"),
+        Lang::Fr => include_str!("locales/fr.json"),
+        Lang::Es => include_str!("locales/es.json"),
     }
 }
 
-/// Chinese (Simplified) translations
-fn zh_cn(key: &str) -> &'static str {
-    match key {
-        // App
-        "app.title" => "NativeHub",
-        "app.subtitle" => "原生 GitHub 客户端",
-        
-        // Login Screen
-        "login.title" => "连接到 GitHub",
-        "login.button" => "登录 GitHub",
-        "login.button_icon" => "🔐",
-        "login.connecting" => "正在建立连接...",
-        "login.error_prefix" => "错误",
-        
-        // Auth Modal
-        "auth.title" => "安全验证",
-        "auth.instruction" => "请在浏览器中输入以下验证码:",
-        "auth.copy_code" => "复制验证码",
-        "auth.open_browser" => "打开浏览器",
-        "auth.waiting" => "等待验证中...",
-        
-        // Repo Browser
-        "repos.title" => "仓库列表",
-        "repos.refresh" => "刷新",
-        "repos.loading" => "正在加载仓库...",
-        "repos.empty" => "暂无数据，请点击刷新",
-        "repos.private" => "私有",
-        "repos.public" => "公开",
-        
-        // Log Viewer
-        "log.title" => "系统日志",
-        "log.system_online" => "系统已就绪",
-        "log.awaiting" => "等待操作...",
-        "log.scanning_gh" => "正在检测 GH CLI...",
-        "log.gh_found" => "已找到 GH CLI 令牌!",
-        "log.connection_ok" => "安全连接已建立",
-        "log.fetching_repos" => "正在获取仓库列表...",
-        "log.found_repos" => "找到 {} 个仓库",
-        "log.opening_repo" => "正在打开仓库: {}...",
-        "log.browser_launched" => "浏览器已启动",
-        
-        // Settings
-        "settings.language" => "语言",
-        
-        // Common
-        "common.cancel" => "取消",
-        "common.confirm" => "确认",
-        "common.error" => "错误",
-        "common.success" => "成功",
-        
-        // Fallback - return the key itself for debugging (unsafe but works with leaked string)
-        _ => "[MISSING]",
+/// Get translated string by key and language, falling back to English
+pub fn get(lang: Lang, key: &str) -> &'static str {
+    if let Some(text) = table(lang).get(key) {
+        return text;
     }
+    report_missing(lang, key);
+    table(Lang::En).get(key).map(String::as_str).unwrap_or("[MISSING]")
+}
+
+/// Translated string if `key` is a message key of the language or of English
+pub fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    table(lang).get(key).or_else(|| table(Lang::En).get(key)).map(String::as_str)
+}
+
+/// Keys of a language, sorted
+pub fn keys(lang: Lang) -> BTreeSet<&'static str> {
+    table(lang).keys().map(String::as_str).collect()
+}
+
+/// Coverage of a language against the English key set
+pub struct KeyDiff {
+    pub total: usize,               // Keys in English
+    pub missing: Vec<&'static str>, // English keys the language lacks
+    pub extra: Vec<&'static str>,   // Keys only the language has (typos or removed keys)
+}
+
+pub fn diff(lang: Lang) -> KeyDiff {
+    let reference = keys(Lang::En);
+    let keys = keys(lang);
+    KeyDiff {
+        total: reference.len(),
+        missing: reference.difference(&keys).copied().collect(),
+        extra: keys.difference(&reference).copied().collect(),
+    }
+}
+
+/// Whether missing keys are logged (`NATIVE_HUB_I18N_DEBUG` set to anything but 0)
+fn debug_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("NATIVE_HUB_I18N_DEBUG").is_ok_and(|value| value != "0"))
 }
 
-/// English translations
-fn en(key: &str) -> &'static str {
-    match key {
-        // App
-        "app.title" => "NativeHub",
-        "app.subtitle" => "Native GitHub Client",
-        
-        // Login Screen
-        "login.title" => "Connect to GitHub",
-        "login.button" => "LOGIN WITH GITHUB",
-        "login.button_icon" => "🔐",
-        "login.connecting" => "ESTABLISHING UPLINK...",
-        "login.error_prefix" => "ERROR",
-        
-        // Auth Modal
-        "auth.title" => "SECURITY CHECKPOINT",
-        "auth.instruction" => "Enter this code in your browser:",
-        "auth.copy_code" => "COPY CODE",
-        "auth.open_browser" => "OPEN BROWSER",
-        "auth.waiting" => "Waiting for verification...",
-        
-        // Repo Browser
-        "repos.title" => "REPOSITORIES",
-        "repos.refresh" => "REFRESH",
-        "repos.loading" => "Accessing GitHub Uplink...",
-        "repos.empty" => "No Data Stream. Click Refresh.",
-        "repos.private" => "Private",
-        "repos.public" => "Public",
-        
-        // Log Viewer
-        "log.title" => "SYSTEM LOG",
-        "log.system_online" => "SYSTEM LINE ONLINE.",
-        "log.awaiting" => "AWAITING INPUT...",
-        "log.scanning_gh" => "SCANNING FOR GH CLI...",
-        "log.gh_found" => "GH CLI TOKEN FOUND!",
-        "log.connection_ok" => "Secure Connection Established.",
-        "log.fetching_repos" => "FETCHING REPOS VIA GH CLI...",
-        "log.found_repos" => "FOUND {} REPOSITORIES.",
-        "log.opening_repo" => "OPENING REPO: {}...",
-        "log.browser_launched" => "BROWSER LAUNCHED.",
-        
-        // Settings
-        "settings.language" => "Language",
-        
-        // Common
-        "common.cancel" => "Cancel",
-        "common.confirm" => "Confirm",
-        "common.error" => "Error",
-        "common.success" => "Success",
-        
-        // Fallback - return the key itself for debugging
-        _ => "[MISSING]",
+/// Log a missing key in debug mode, once per language and key since the UI asks every frame
+fn report_missing(lang: Lang, key: &str) {
+    static REPORTED: OnceLock<Mutex<HashSet<(Lang, String)>>> = OnceLock::new();
+    if !debug_enabled() {
+        return;
+    }
+    let mut reported = REPORTED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    if reported.insert((lang, key.to_string())) {
+        tracing::warn!("[MISSING] {} has no translation for \"{}\"", lang.code(), key);
+    }
+}
+
+fn table(lang: Lang) -> &'static Table {
+    static TABLES: OnceLock<HashMap<Lang, Table>> = OnceLock::new();
+    &TABLES.get_or_init(|| Lang::all().iter().map(|&lang| (lang, load(lang))).collect())[&lang]
+}
+
+/// Embedded table with the user's override file, if any, applied on top
+fn load(lang: Lang) -> Table {
+    let mut table: Table = serde_json::from_str(embedded(lang)).expect("embedded locale file is valid JSON");
+
+    let path = crate::modules::paths::config_dir().join("locales").join(format!("{}.json", lang.code()));
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return table;
+    };
+    match serde_json::from_str::<Table>(&text) {
+        Ok(overrides) => table.extend(overrides),
+        Err(e) => tracing::warn!("Ignoring invalid locale file {}: {}", path.display(), e),
+    }
+    table
+}
+
+/// Fill the placeholders of a translated string: `{}` takes the next positional
+/// argument, `{0}` a positional one by index and `{name}` a named one.
+/// `{{` and `}}` are literal braces; unknown placeholders are left as they are.
+pub fn interpolate(template: &str, positional: &[&dyn Display], named: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let Some(end) = tail.find('}').filter(|_| tail.starts_with('{')) else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        };
+        let name = &tail[1..end];
+        let value = if name.is_empty() {
+            next += 1;
+            positional.get(next - 1)
+        } else if let Ok(index) = name.parse::<usize>() {
+            positional.get(index)
+        } else {
+            named.iter().find(|(n, _)| *n == name).map(|(_, v)| v)
+        };
+        match value {
+            Some(value) => out.push_str(&value.to_string()),
+            None => out.push_str(&tail[..=end]),
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn fills_placeholders() {
+        assert_eq!(interpolate("{} of {}", &[&1, &3], &[]), "1 of 3");
+        assert_eq!(interpolate("{1} before {0}", &[&"a", &"b"], &[]), "b before a");
+        assert_eq!(interpolate("Hello, {name}!", &[], &[("name", &"octocat")]), "Hello, octocat!");
+        assert_eq!(interpolate("{} {n} {}", &[&1, &2], &[("n", &"x")]), "1 x 2");
+    }
+    
+    #[test]
+    fn keeps_literal_and_unknown_braces() {
+        assert_eq!(interpolate("{{}} and {{x}}", &[&1], &[]), "{} and {x}");
+        assert_eq!(interpolate("{missing} {5} {}", &[], &[]), "{missing} {5} {}");
+        assert_eq!(interpolate("open { and close }", &[], &[]), "open { and close }");
+        assert_eq!(interpolate("无占位符", &[&1], &[]), "无占位符");
     }
 }
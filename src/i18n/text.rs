@@ -0,0 +1,47 @@
+//! Text Truncation
+//!
+//! Cutting a string at a byte index panics inside a multi-byte character
+//! (CJK, accents) and can split an emoji sequence in two. Text is shortened
+//! by whole grapheme clusters instead, the units a reader sees as one
+//! character.
+
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// `text` shortened to at most `max` characters, with "…" in place of the rest
+pub fn truncate_chars(text: &str, max: usize) -> Cow<'_, str> {
+    match text.grapheme_indices(true).nth(max) {
+        Some((end, _)) => Cow::Owned(format!("{}…", text[..end].trim_end())),
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn short_text_is_borrowed() {
+        assert!(matches!(truncate_chars("hello", 5), Cow::Borrowed("hello")));
+        assert_eq!(truncate_chars("", 0), "");
+    }
+    
+    #[test]
+    fn counts_characters_not_bytes() {
+        assert_eq!(truncate_chars("hello world", 5), "hello…");
+        assert_eq!(truncate_chars("你好世界", 2), "你好…");
+        assert_eq!(truncate_chars("héllo", 3), "hél…");
+    }
+    
+    #[test]
+    fn keeps_grapheme_clusters_whole() {
+        // Family emoji (ZWJ sequence) and a flag count as one character each
+        assert_eq!(truncate_chars("👨‍👩‍👧🇯🇵x", 2), "👨‍👩‍👧🇯🇵…");
+        assert_eq!(truncate_chars("e\u{301}tude", 1), "e\u{301}…");
+    }
+    
+    #[test]
+    fn trims_space_before_ellipsis() {
+        assert_eq!(truncate_chars("hello world", 6), "hello…");
+    }
+}
@@ -0,0 +1,175 @@
+//! Timestamps
+//!
+//! GitHub sends RFC 3339 times (`2024-05-01T12:34:56Z`); older saved data
+//! may hold a bare date. Both are shown relative to now, with the full
+//! local time available for tooltips. Unparseable input is shown as is.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use super::Lang;
+
+fn parse(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|time| time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(timestamp, "%Y-%m-%d").ok()?;
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+        })
+}
+
+/// "3 hours ago" / "3 小时前"
+pub fn relative(lang: Lang, timestamp: &str) -> String {
+    let Some(time) = parse(timestamp) else { return timestamp.to_string() };
+    // Clock skew can put fresh timestamps slightly in the future
+    let seconds = (Utc::now() - time).num_seconds().max(0);
+    
+    let (count, unit) = match seconds {
+        0..60 => {
+            return match lang {
+                Lang::ZhCn => "刚刚",
+                Lang::ZhTw => "剛剛",
+                Lang::En => "just now",
+                Lang::Ja => "たった今",
+                Lang::Ko => "방금",
+                Lang::De => "gerade eben",
+                Lang::Fr => "à l'instant",
+                Lang::Es => "justo ahora",
+            }.to_string();
+        }
+        60..3_600 => (seconds / 60, Unit::Minute),
+        3_600..86_400 => (seconds / 3_600, Unit::Hour),
+        86_400..2_592_000 => (seconds / 86_400, Unit::Day),
+        2_592_000..31_536_000 => (seconds / 2_592_000, Unit::Month),
+        _ => (seconds / 31_536_000, Unit::Year),
+    };
+    
+    let one = count == 1;
+    match lang {
+        Lang::ZhCn => format!("{} {}前", count, unit.zh_cn()),
+        Lang::ZhTw => format!("{} {}前", count, unit.zh_tw()),
+        Lang::En => format!("{} {}{} ago", count, unit.en(), if one { "" } else { "s" }),
+        Lang::Ja => format!("{} {}前", count, unit.ja()),
+        Lang::Ko => format!("{}{} 전", count, unit.ko()),
+        Lang::De => format!("vor {} {}", count, unit.de(one)),
+        Lang::Fr => format!("il y a {} {}", count, unit.fr(one)),
+        Lang::Es => format!("hace {} {}", count, unit.es(one)),
+    }
+}
+
+/// Full timestamp in the local time zone
+pub fn local(lang: Lang, timestamp: &str) -> String {
+    let Some(time) = parse(timestamp) else { return timestamp.to_string() };
+    let time = time.with_timezone(&Local);
+    let format = match lang {
+        Lang::ZhCn | Lang::ZhTw | Lang::Ja => "%Y年%m月%d日 %H:%M",
+        Lang::En => "%b %-d, %Y, %H:%M",
+        Lang::Ko => "%Y년 %m월 %d일 %H:%M",
+        Lang::De => "%d.%m.%Y, %H:%M",
+        Lang::Fr | Lang::Es => "%d/%m/%Y %H:%M",
+    };
+    time.format(format).to_string()
+}
+
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl Unit {
+    fn zh_cn(&self) -> &'static str {
+        match self {
+            Unit::Minute => "分钟",
+            Unit::Hour => "小时",
+            Unit::Day => "天",
+            Unit::Month => "个月",
+            Unit::Year => "年",
+        }
+    }
+    
+    fn zh_tw(&self) -> &'static str {
+        match self {
+            Unit::Minute => "分鐘",
+            Unit::Hour => "小時",
+            Unit::Day => "天",
+            Unit::Month => "個月",
+            Unit::Year => "年",
+        }
+    }
+    
+    fn en(&self) -> &'static str {
+        match self {
+            Unit::Minute => "minute",
+            Unit::Hour => "hour",
+            Unit::Day => "day",
+            Unit::Month => "month",
+            Unit::Year => "year",
+        }
+    }
+    
+    fn ja(&self) -> &'static str {
+        match self {
+            Unit::Minute => "分",
+            Unit::Hour => "時間",
+            Unit::Day => "日",
+            Unit::Month => "か月",
+            Unit::Year => "年",
+        }
+    }
+    
+    fn ko(&self) -> &'static str {
+        match self {
+            Unit::Minute => "분",
+            Unit::Hour => "시간",
+            Unit::Day => "일",
+            Unit::Month => "개월",
+            Unit::Year => "년",
+        }
+    }
+    
+    fn de(&self, one: bool) -> &'static str {
+        match (self, one) {
+            (Unit::Minute, true) => "Minute",
+            (Unit::Minute, false) => "Minuten",
+            (Unit::Hour, true) => "Stunde",
+            (Unit::Hour, false) => "Stunden",
+            (Unit::Day, true) => "Tag",
+            (Unit::Day, false) => "Tagen",
+            (Unit::Month, true) => "Monat",
+            (Unit::Month, false) => "Monaten",
+            (Unit::Year, true) => "Jahr",
+            (Unit::Year, false) => "Jahren",
+        }
+    }
+    
+    fn fr(&self, one: bool) -> &'static str {
+        match (self, one) {
+            (Unit::Minute, true) => "minute",
+            (Unit::Minute, false) => "minutes",
+            (Unit::Hour, true) => "heure",
+            (Unit::Hour, false) => "heures",
+            (Unit::Day, true) => "jour",
+            (Unit::Day, false) => "jours",
+            (Unit::Month, _) => "mois",
+            (Unit::Year, true) => "an",
+            (Unit::Year, false) => "ans",
+        }
+    }
+    
+    fn es(&self, one: bool) -> &'static str {
+        match (self, one) {
+            (Unit::Minute, true) => "minuto",
+            (Unit::Minute, false) => "minutos",
+            (Unit::Hour, true) => "hora",
+            (Unit::Hour, false) => "horas",
+            (Unit::Day, true) => "día",
+            (Unit::Day, false) => "días",
+            (Unit::Month, true) => "mes",
+            (Unit::Month, false) => "meses",
+            (Unit::Year, true) => "año",
+            (Unit::Year, false) => "años",
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 mod ui;
 mod context;
+mod config;
 mod modules;
 mod app_event;
 mod backend;
@@ -14,12 +15,21 @@ use ui::NativeHubApp;
 use tokio::runtime::Runtime;
 
 // Shared initialization logic returning the app creation closure
-fn make_app_creator() -> Box<dyn FnOnce(&eframe::CreationContext<'_>) -> eframe::Result<Box<dyn eframe::App>>> {
+fn make_app_creator(link: Option<String>) -> eframe::AppCreator<'static> {
     // 1. Create Channels for Async Bridge
     let (action_tx, action_rx) = tokio::sync::mpsc::channel(100);
-    let (event_tx, event_rx) = std::sync::mpsc::channel();
+    let (event_tx, event_rx) = app_event::EventSender::channel();
+    let ui_waker = event_tx.clone();
     
-    // 2. Initialize Global Context
+    // Link the app was started with; on desktop later launches pass theirs on
+    if let Some(link) = link {
+        let _ = event_tx.send(app_event::AppEvent::OpenLink(link));
+    }
+    #[cfg(not(target_os = "android"))]
+    modules::deep_link::listen(event_tx.clone());
+    
+    // 2. Load settings and initialize Global Context
+    let config = config::init();
     let ctx = context::AppContext::new();
 
     // 3. Spawn Backend Logic on a separate OS thread
@@ -31,15 +41,26 @@ fn make_app_creator() -> Box<dyn FnOnce(&eframe::CreationContext<'_>) -> eframe:
     });
 
     // 4. Return closure
-    Box::new(move |cc| Ok(Box::new(NativeHubApp::new(cc, action_tx, event_rx, ctx))))
+    Box::new(move |cc| {
+        ui_waker.attach(&cc.egui_ctx);
+        Ok(Box::new(NativeHubApp::new(cc, action_tx, event_rx, ctx, config)))
+    })
 }
 
+// Public so the library target, which compiles this file too, sees the app as used
 #[cfg(not(target_os = "android"))]
-fn main() -> eframe::Result<()> {
+pub fn main() -> eframe::Result<()> {
     // Initialize logging
     tracing_subscriber::fmt::init();
 
-    let app_creator = make_app_creator();
+    // Started by a protocol handler while already running: let that instance open it
+    let link = modules::deep_link::from_args();
+    if link.as_deref().is_some_and(modules::deep_link::forward_to_running) {
+        return Ok(());
+    }
+    modules::deep_link::register();
+
+    let app_creator = make_app_creator(link);
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -64,7 +85,15 @@ fn android_main(app: eframe::egui::winit::platform::android::activity::AndroidAp
     std::env::set_var("RUST_BACKTRACE", "1");
     android_logger::init_once(android_logger::Config::default().with_max_level(log::LevelFilter::Info));
 
-    let app_creator = make_app_creator();
+    // App-private storage for the cache and the token store (there is no keyring on Android)
+    if let Some(dir) = app.internal_data_path() {
+        modules::paths::set_data_dir(dir);
+    }
+    modules::token_store::init(&app);
+
+    modules::soft_keyboard::init(&app);
+    let link = modules::deep_link::from_intent(&app);
+    let app_creator = make_app_creator(link);
 
     let options = eframe::NativeOptions {
         android_app: Some(app),
@@ -35,15 +35,12 @@ pub struct DeviceCodeResponse {
     pub device_code: String,
     pub user_code: String,
     pub verification_uri: String,
-    pub expires_in: u64,
     pub interval: u64,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct AccessTokenResponse {
     pub access_token: String,
-    pub token_type: String,
-    pub scope: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -125,3 +122,134 @@ pub async fn poll_access_token(client: &Client, device_code: &str, interval: u64
         }
     }
 }
+
+// ============================================
+// OAuth Web Flow (authorization code + localhost redirect)
+// ============================================
+
+/// How long to wait for the browser to come back to the redirect listener
+const WEB_FLOW_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Get GitHub OAuth Client Secret from environment variable (web flow only).
+fn get_client_secret() -> Result<String> {
+    std::env::var("GITHUB_CLIENT_SECRET")
+        .context("GITHUB_CLIENT_SECRET environment variable not set.")
+}
+
+/// The web flow needs a browser on this machine and both OAuth App credentials
+pub fn web_flow_available() -> bool {
+    !cfg!(target_os = "android") && get_client_id().is_ok() && get_client_secret().is_ok()
+}
+
+/// A pending authorization-code login, listening on localhost for GitHub's redirect
+pub struct WebFlow {
+    listener: tokio::net::TcpListener,
+    redirect_uri: String,
+    state: String,
+    pub authorize_url: String,
+}
+
+/// Bind the redirect listener and build the URL the user must open in the browser.
+/// GitHub accepts any port on a loopback redirect URI, so an ephemeral one is used.
+pub async fn start_web_flow() -> Result<WebFlow> {
+    use rand::Rng;
+    
+    let client_id = get_client_id()?;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await
+        .context("Failed to bind localhost redirect listener")?;
+    let port = listener.local_addr()?.port();
+    
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+    let state = format!("{:032x}", rand::rng().random::<u128>());
+    let authorize_url = format!(
+        "https://github.com/login/oauth/authorize?client_id={}&redirect_uri={}&scope={}&state={}",
+        urlencoding::encode(&client_id),
+        urlencoding::encode(&redirect_uri),
+        urlencoding::encode("repo user read:org"),
+        state
+    );
+    
+    Ok(WebFlow { listener, redirect_uri, state, authorize_url })
+}
+
+impl WebFlow {
+    /// Wait for the redirect, then exchange the code for an access token
+    pub async fn finish(self, client: &Client) -> Result<String> {
+        let code = tokio::time::timeout(WEB_FLOW_TIMEOUT, self.accept_code()).await
+            .map_err(|_| anyhow!("Timed out waiting for browser authorization"))??;
+        
+        let response = client.post("https://github.com/login/oauth/access_token")
+            .header("Accept", "application/json")
+            .form(&[
+                ("client_id", get_client_id()?),
+                ("client_secret", get_client_secret()?),
+                ("code", code),
+                ("redirect_uri", self.redirect_uri.clone()),
+            ])
+            .send()
+            .await?;
+        
+        match response.json::<PollResponse>().await? {
+            PollResponse::Success(token) => Ok(token.access_token),
+            PollResponse::Error { error, error_description } => {
+                Err(anyhow!("Auth error: {}", error_description.unwrap_or(error)))
+            }
+        }
+    }
+    
+    /// Serve requests until the browser hits /callback, skipping favicon and the like
+    async fn accept_code(&self) -> Result<String> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+            
+            // Only the request line matters; it always fits in the first read.
+            // A connection that never sends it must not hold up the real redirect.
+            let mut buf = [0u8; 4096];
+            let Ok(Ok(n)) = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await else {
+                continue;
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let target = request.split_whitespace().nth(1).unwrap_or("");
+            
+            let Some(query) = target.strip_prefix("/callback?") else {
+                let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+                continue;
+            };
+            
+            let params: Vec<(String, String)> = query.split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(k, v)| (k.to_string(), urlencoding::decode(v).map(|v| v.into_owned()).unwrap_or_default()))
+                .collect();
+            let param = |name: &str| params.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+            
+            // Not our redirect (stale tab, another site probing the port): keep waiting for the real one
+            if param("state").as_deref() != Some(self.state.as_str()) {
+                let _ = stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+                continue;
+            }
+            
+            let result = if let Some(error) = param("error") {
+                Err(anyhow!("Auth error: {}", param("error_description").unwrap_or(error)))
+            } else {
+                param("code").ok_or_else(|| anyhow!("Redirect did not include an authorization code"))
+            };
+            
+            let message = if result.is_ok() {
+                "Authorization complete. You can close this tab and return to Native Hub."
+            } else {
+                "Authorization failed. Return to Native Hub for details."
+            };
+            let body = format!("<!DOCTYPE html><html><body style=\"font-family:monospace\"><p>{}</p></body></html>", message);
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(reply.as_bytes()).await;
+            
+            return result;
+        }
+    }
+}
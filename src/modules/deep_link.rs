@@ -0,0 +1,234 @@
+//! Deep Links
+//!
+//! `nativehub://owner/repo[/issues/N]` and `https://github.com/owner/repo[/pull/N]`
+//! links handed to the app by the OS. On desktop the scheme is registered with
+//! the OS on startup and the link arrives as a command line argument; a second
+//! instance started that way passes it to the running one over a loopback
+//! socket and exits. On Android the activity's intent-filter (Cargo.toml)
+//! delivers it in the launch intent. macOS registers the scheme through the
+//! bundle's Info.plist, but winit doesn't pass on the URL of the open event,
+//! so there a link only brings the app up.
+
+use crate::ui::references::{parse_url, Reference};
+
+/// URL scheme of the app's own links
+pub const SCHEME: &str = "nativehub";
+
+/// Loopback port a running instance listens on for links from later launches
+#[cfg(not(target_os = "android"))]
+const FORWARD_PORT: u16 = 47_615;
+
+/// Where a link points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Repo(String),
+    Issue { repo: String, number: u32 },
+    PullRequest { repo: String, number: u32 },
+}
+
+impl DeepLink {
+    /// `owner/name` of the repository the link points into
+    pub fn repo(&self) -> &str {
+        match self {
+            DeepLink::Repo(repo) | DeepLink::Issue { repo, .. } | DeepLink::PullRequest { repo, .. } => repo,
+        }
+    }
+}
+
+/// First path segments of GitHub pages that aren't an owner's repositories
+const RESERVED_PATHS: &[&str] = &[
+    "about", "account", "apps", "codespaces", "collections", "dashboard", "enterprise",
+    "events", "explore", "features", "login", "marketplace", "new", "notifications",
+    "organizations", "orgs", "pricing", "search", "settings", "sponsors", "topics", "trending",
+];
+
+/// Parse a `nativehub://` link or a link into the GitHub web UI at `web_base`
+pub fn parse(url: &str, web_base: &str) -> Option<DeepLink> {
+    let url = url.trim();
+    let web_base = web_base.trim_end_matches('/');
+    let path = url.strip_prefix(&format!("{}://", SCHEME))
+        .or_else(|| strip_web_base(url, web_base))?;
+    let path = path.split(['#', '?']).next().unwrap_or(path).trim_matches('/');
+    let mut parts = path.split('/');
+    let (owner, name) = (parts.next()?, parts.next()?);
+    if owner.is_empty() || name.is_empty() || RESERVED_PATHS.iter().any(|reserved| reserved.eq_ignore_ascii_case(owner)) {
+        return None;
+    }
+    
+    // Issue and pull request links are read the same way as links in markdown
+    match parse_url(&format!("{}/{}", web_base, path), web_base) {
+        Some(Reference::Issue { repo, number }) => Some(DeepLink::Issue { repo, number }),
+        Some(Reference::PullRequest { repo, number }) => Some(DeepLink::PullRequest { repo, number }),
+        // Any other page of the repo (tree, blob, actions...) opens the repo itself
+        None => Some(DeepLink::Repo(format!("{}/{}", owner, name.trim_end_matches(".git")))),
+    }
+}
+
+/// Path after `web_base`, also accepting `http://` and `www.` spellings of it
+fn strip_web_base<'a>(url: &'a str, web_base: &str) -> Option<&'a str> {
+    let host = web_base.split_once("://").map_or(web_base, |(_, host)| host);
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    rest.strip_prefix(host)?.strip_prefix('/')
+}
+
+/// Link passed on the command line, as the desktop protocol handlers do
+#[cfg(not(target_os = "android"))]
+pub fn from_args() -> Option<String> {
+    std::env::args().skip(1).find(|arg| arg.starts_with(&format!("{}:", SCHEME)) || arg.starts_with("https://") || arg.starts_with("http://"))
+}
+
+/// Link the activity was launched with (`Intent.getDataString()`)
+#[cfg(target_os = "android")]
+pub fn from_intent(app: &eframe::egui::winit::platform::android::activity::AndroidApp) -> Option<String> {
+    use jni::objects::{JObject, JString};
+
+    // Safety: both pointers stay valid for the life of the activity
+    let vm = unsafe { jni::JavaVM::from_raw(app.vm_as_ptr() as *mut jni::sys::JavaVM) }.ok()?;
+    let mut env = vm.attach_current_thread().ok()?;
+    let activity = unsafe { JObject::from_raw(app.activity_as_ptr() as jni::sys::jobject) };
+    let intent = env.call_method(&activity, "getIntent", "()Landroid/content/Intent;", &[]).ok()?.l().ok()?;
+    if intent.is_null() {
+        return None;
+    }
+    let data = env.call_method(&intent, "getDataString", "()Ljava/lang/String;", &[]).ok()?.l().ok()?;
+    if data.is_null() {
+        return None;
+    }
+    let data = JString::from(data);
+    let link: String = env.get_string(&data).ok()?.into();
+    Some(link)
+}
+
+/// Hand `link` to an instance that is already running. True if one took it,
+/// in which case this launch has nothing left to do.
+#[cfg(not(target_os = "android"))]
+pub fn forward_to_running(link: &str) -> bool {
+    use std::io::Write;
+
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], FORWARD_PORT));
+    match std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(300)) {
+        Ok(mut stream) => writeln!(stream, "{}", link).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Accept links from later launches for as long as the app runs
+#[cfg(not(target_os = "android"))]
+pub fn listen(event_tx: crate::app_event::EventSender) {
+    use std::io::BufRead;
+
+    let listener = match std::net::TcpListener::bind(("127.0.0.1", FORWARD_PORT)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("Deep link listener unavailable: {}", e);
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut line = String::new();
+            if std::io::BufReader::new(stream).read_line(&mut line).is_ok() && !line.trim().is_empty() {
+                let _ = event_tx.send(crate::app_event::AppEvent::OpenLink(line.trim().to_string()));
+            }
+        }
+    });
+}
+
+/// Register the app as the handler of `nativehub://` links for the current
+/// user. Cheap enough to repeat on every start, which also keeps the
+/// registered executable path current.
+#[cfg(not(target_os = "android"))]
+pub fn register() {
+    let Ok(exe) = std::env::current_exe() else { return };
+    let exe = exe.display().to_string();
+
+    if cfg!(windows) {
+        let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+        let command_key = format!(r"{}\shell\open\command", key);
+        let command = format!("\"{}\" \"%1\"", exe);
+        for args in [
+            ["add", key.as_str(), "/ve", "/d", "URL:NativeHub", "/f"].as_slice(),
+            &["add", key.as_str(), "/v", "URL Protocol", "/d", "", "/f"],
+            &["add", command_key.as_str(), "/ve", "/d", command.as_str(), "/f"],
+        ] {
+            let mut reg = std::process::Command::new("reg");
+            reg.args(args);
+            // No console window flashing up from the windowed release build
+            #[cfg(windows)]
+            {
+                use std::os::windows::process::CommandExt;
+                const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+                reg.creation_flags(CREATE_NO_WINDOW);
+            }
+            if let Err(e) = reg.output() {
+                tracing::warn!("Failed to register {}:// handler: {}", SCHEME, e);
+                return;
+            }
+        }
+    } else if cfg!(target_os = "linux") {
+        let Some(home) = std::env::var_os("HOME") else { return };
+        let applications = std::env::var_os("XDG_DATA_HOME")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::path::PathBuf::from(home).join(".local").join("share"))
+            .join("applications");
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=NativeHub\nExec=\"{}\" %u\nTerminal=false\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+            exe, SCHEME
+        );
+        let file = applications.join("nativehub.desktop");
+        if std::fs::read_to_string(&file).is_ok_and(|current| current == entry) {
+            return;
+        }
+        let written = std::fs::create_dir_all(&applications).and_then(|_| std::fs::write(&file, entry));
+        if let Err(e) = written {
+            tracing::warn!("Failed to register {}:// handler: {}", SCHEME, e);
+            return;
+        }
+        let _ = std::process::Command::new("xdg-mime")
+            .args(["default", "nativehub.desktop", &format!("x-scheme-handler/{}", SCHEME)])
+            .output();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const WEB: &str = "https://github.com";
+    
+    #[test]
+    fn parses_app_links() {
+        assert_eq!(parse("nativehub://octocat/hello", WEB), Some(DeepLink::Repo("octocat/hello".into())));
+        assert_eq!(parse("nativehub://octocat/hello/issues/12", WEB), Some(DeepLink::Issue { repo: "octocat/hello".into(), number: 12 }));
+        assert_eq!(parse("nativehub://octocat/hello/pull/7", WEB), Some(DeepLink::PullRequest { repo: "octocat/hello".into(), number: 7 }));
+        assert_eq!(parse("nativehub://octocat", WEB), None);
+    }
+    
+    #[test]
+    fn parses_web_links() {
+        assert_eq!(parse("https://github.com/octocat/hello.git", WEB), Some(DeepLink::Repo("octocat/hello".into())));
+        assert_eq!(parse("http://www.github.com/octocat/hello/pull/7#discussion", WEB), Some(DeepLink::PullRequest { repo: "octocat/hello".into(), number: 7 }));
+        assert_eq!(parse(" https://github.com/octocat/hello/issues/12?q=1 ", WEB), Some(DeepLink::Issue { repo: "octocat/hello".into(), number: 12 }));
+        // Other pages of the repo open the repo itself
+        assert_eq!(parse("https://github.com/octocat/hello/tree/main/src", WEB), Some(DeepLink::Repo("octocat/hello".into())));
+        assert_eq!(parse("https://github.com/octocat/hello/issues/new", WEB), Some(DeepLink::Repo("octocat/hello".into())));
+    }
+    
+    #[test]
+    fn rejects_other_hosts() {
+        assert_eq!(parse("https://gitlab.com/octocat/hello", WEB), None);
+        assert_eq!(parse("https://github.example.com/octocat/hello", WEB), None);
+        assert_eq!(
+            parse("https://github.example.com/octocat/hello/issues/3", "https://github.example.com"),
+            Some(DeepLink::Issue { repo: "octocat/hello".into(), number: 3 })
+        );
+    }
+    
+    #[test]
+    fn rejects_github_pages() {
+        assert_eq!(parse("https://github.com/features/actions", WEB), None);
+        assert_eq!(parse("https://github.com/orgs/rust-lang/people", WEB), None);
+        assert_eq!(parse("nativehub://settings/tokens", WEB), None);
+    }
+}
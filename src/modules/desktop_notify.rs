@@ -0,0 +1,57 @@
+//! Desktop Notifications
+//!
+//! Native notifications for activity that needs the user: mentions,
+//! assignments and review requests. notify-rust talks to D-Bus on Linux/BSD,
+//! WinRT toasts on Windows and the notification center on macOS. Android has
+//! no equivalent here, so notifications are dropped there.
+
+use eframe::egui;
+
+/// Notification reasons worth interrupting the user for, with the message key describing them
+const ALERT_REASONS: &[(&str, &str)] = &[
+    ("mention", "notify.mention"),
+    ("team_mention", "notify.team_mention"),
+    ("assign", "notify.assign"),
+    ("review_requested", "notify.review_requested"),
+];
+
+/// Message key of what to say about a notification of this reason, None if it should stay quiet
+pub fn alert_key(reason: &str) -> Option<&'static str> {
+    ALERT_REASONS.iter().find(|(r, _)| *r == reason).map(|(_, key)| *key)
+}
+
+/// Show a notification; clicking it brings the window to the front where the platform reports clicks
+#[cfg(not(target_os = "android"))]
+pub fn show(summary: &str, body: &str, open_label: &str, ctx: &egui::Context) {
+    let summary = summary.to_string();
+    let body = body.to_string();
+    let open_label = open_label.to_string();
+    let ctx = ctx.clone();
+    
+    // Showing blocks on D-Bus/WinRT, and waiting for the click blocks until it is dismissed
+    std::thread::spawn(move || {
+        let handle = notify_rust::Notification::new()
+            .appname("NativeHub")
+            .summary(&summary)
+            .body(&body)
+            .action("default", &open_label)
+            .show();
+        
+        match handle {
+            // macOS only delivers clicks on the main run loop; don't park a thread on it
+            Ok(handle) if !cfg!(target_os = "macos") => {
+                handle.wait_for_action(|action| {
+                    if action != "__closed" {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                        ctx.request_repaint();
+                    }
+                });
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Desktop notification failed: {}", e),
+        }
+    });
+}
+
+#[cfg(target_os = "android")]
+pub fn show(_summary: &str, _body: &str, _open_label: &str, _ctx: &egui::Context) {}
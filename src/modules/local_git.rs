@@ -0,0 +1,162 @@
+//! Local Git
+//!
+//! Runs `git` (and `gh repo clone`) against checkouts on disk. Output is
+//! forwarded to the log as it arrives; git's redrawn progress lines are
+//! reduced to one log line per phase plus a progress percentage.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use crate::app_event::{AppEvent, CloneTool, EventSender, LocalGitOp, Operation};
+
+/// Whether `dir` is the root of a git working copy
+pub fn is_checkout(dir: &Path) -> bool {
+    dir.join(".git").exists()
+}
+
+/// Clone `full_name` into `dest` with git (from `url`) or gh
+pub async fn clone(full_name: &str, url: &str, dest: &Path, tool: CloneTool, tx: &EventSender) -> Result<()> {
+    let command = match tool {
+        CloneTool::Git => {
+            let mut command = Command::new("git");
+            command.args(["clone", "--progress", url]).arg(dest);
+            command
+        }
+        CloneTool::Gh => {
+            let mut command = Command::new("gh");
+            command.args(["repo", "clone", full_name]).arg(dest).args(["--", "--progress"]);
+            command
+        }
+    };
+    run(command, Operation::Clone, tx).await
+}
+
+/// Run `op` in the checkout at `dir`
+pub async fn run_op(dir: &Path, op: LocalGitOp, tx: &EventSender) -> Result<()> {
+    if !is_checkout(dir) {
+        anyhow::bail!("{} is not a git repository", dir.display());
+    }
+    
+    let steps: &[&[&str]] = match op {
+        LocalGitOp::Pull => &[&["pull", "--progress"]],
+        LocalGitOp::Push => &[&["push", "--progress"]],
+        // Rebase local commits onto the remote, then publish them
+        LocalGitOp::Sync => &[&["pull", "--rebase", "--progress"], &["push", "--progress"]],
+        LocalGitOp::Status => &[&["status", "--short", "--branch"]],
+    };
+    for args in steps {
+        let mut command = Command::new("git");
+        command.args(*args).current_dir(dir);
+        run(command, Operation::Git, tx).await?;
+    }
+    Ok(())
+}
+
+/// Add a (binary) file to the gist at `push_url`, which the gist API can't do.
+/// Works in a scratch clone under the temp directory that is removed afterwards.
+pub async fn push_to_gist(push_url: &str, token: &str, name: &str, bytes: &[u8], tx: &EventSender) -> Result<()> {
+    use base64::Engine;
+    
+    let gist_id = push_url.rsplit('/').next().unwrap_or_default().trim_end_matches(".git");
+    let dir = std::env::temp_dir().join(format!("native_hub_gist_{}", gist_id));
+    let _ = std::fs::remove_dir_all(&dir);
+    let auth = base64::engine::general_purpose::STANDARD.encode(format!("x-access-token:{}", token));
+    let git = |args: &[&str]| {
+        let mut command = Command::new("git");
+        // The header goes through the environment: arguments are visible to anyone running `ps`
+        command
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env("GIT_CONFIG_VALUE_0", format!("Authorization: Basic {}", auth))
+            .args(["-c", "user.name=NativeHub", "-c", "user.email=native-hub@users.noreply.github.com"])
+            .args(args)
+            .current_dir(&dir);
+        command
+    };
+    
+    let result = async {
+        std::fs::create_dir_all(&dir)?;
+        run(git(&["clone", "--quiet", push_url, "."]), Operation::Upload, tx).await?;
+        std::fs::write(dir.join(name), bytes)?;
+        run(git(&["add", "--", name]), Operation::Upload, tx).await?;
+        run(git(&["commit", "--quiet", "-m", &format!("Add {}", name)]), Operation::Upload, tx).await?;
+        run(git(&["push", "--progress", "origin", "HEAD"]), Operation::Upload, tx).await
+    }.await;
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+/// Run `command`, logging stdout line by line and stderr as progress.
+/// The child is killed if the calling task is cancelled.
+async fn run(mut command: Command, op: Operation, tx: &EventSender) -> Result<()> {
+    let program = command.as_std().get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Never block on a credential prompt nobody can answer
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program))?;
+    
+    let stdout = child.stdout.take().context("no stdout")?;
+    let stderr = child.stderr.take().context("no stderr")?;
+    
+    let read_stdout = async {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if !line.trim().is_empty() {
+                let _ = tx.send(AppEvent::Log(format!("{}: {}", program, line)));
+            }
+        }
+    };
+    let (_, last_line) = tokio::join!(read_stdout, read_progress(stderr, &program, op, tx));
+    
+    let status = child.wait().await?;
+    if !status.success() {
+        anyhow::bail!("{} exited ({}): {}", program, status, last_line?);
+    }
+    Ok(())
+}
+
+/// Forward git's stderr; returns the last line, which explains a failure
+async fn read_progress(mut stderr: impl AsyncReadExt + Unpin, program: &str, op: Operation, tx: &EventSender) -> Result<String> {
+    let mut buf = [0u8; 4096];
+    let mut pending = String::new();
+    let mut phase = String::new();
+    let mut last_line = String::new();
+    
+    loop {
+        let n = stderr.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        pending.push_str(&String::from_utf8_lossy(&buf[..n]));
+        
+        // git redraws progress with \r; log each phase once and report its percentage
+        while let Some(end) = pending.find(['\r', '\n']) {
+            let line = pending[..end].trim().to_string();
+            pending.drain(..=end);
+            if line.is_empty() {
+                continue;
+            }
+            
+            let percent = line.split_once(':')
+                .and_then(|(_, rest)| rest.trim_start().split_once('%'))
+                .and_then(|(number, _)| number.trim().parse::<u64>().ok());
+            let current_phase = line.split(':').next().unwrap_or_default().to_string();
+            if let Some(percent) = percent {
+                let _ = tx.send(AppEvent::Progress { op, current: percent, total: Some(100) });
+            }
+            if percent.is_none() || current_phase != phase || line.ends_with("done.") {
+                let _ = tx.send(AppEvent::Log(format!("{}: {}", program, line)));
+            }
+            phase = current_phase;
+            last_line = line;
+        }
+    }
+    Ok(last_line)
+}
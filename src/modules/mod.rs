@@ -1 +1,7 @@
 pub mod auth;
+pub mod deep_link;
+pub mod desktop_notify;
+pub mod local_git;
+pub mod paths;
+pub mod soft_keyboard;
+pub mod token_store;
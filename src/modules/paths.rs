@@ -0,0 +1,85 @@
+//! Platform Paths
+//!
+//! Where the app keeps its files and settings on each platform. Desktop follows the OS
+//! conventions; Android uses the app's internal storage, which has to be
+//! registered at startup because it is only known to the activity.
+
+use std::path::PathBuf;
+
+const APP_DIR: &str = "native_hub";
+
+#[cfg(target_os = "android")]
+static ANDROID_DATA_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Register the app's internal data directory; must be called before the first lookup
+#[cfg(target_os = "android")]
+pub fn set_data_dir(dir: PathBuf) {
+    let _ = ANDROID_DATA_DIR.set(dir);
+}
+
+/// Directory for persistent app data (caches, the token file on Android).
+/// Not created here; callers create it before writing.
+pub fn data_dir() -> PathBuf {
+    #[cfg(target_os = "android")]
+    let base = ANDROID_DATA_DIR.get().cloned();
+    #[cfg(not(target_os = "android"))]
+    let base = desktop_data_dir().map(|dir| dir.join(APP_DIR));
+    
+    base.unwrap_or_else(|| std::env::temp_dir().join(APP_DIR))
+}
+
+/// Directory for user settings. Same as `data_dir` on Android.
+pub fn config_dir() -> PathBuf {
+    #[cfg(target_os = "android")]
+    let base = ANDROID_DATA_DIR.get().cloned();
+    #[cfg(not(target_os = "android"))]
+    let base = desktop_config_dir().map(|dir| dir.join(APP_DIR));
+    
+    base.unwrap_or_else(|| std::env::temp_dir().join(APP_DIR))
+}
+
+/// Where downloads go by default: `~/Downloads` on desktop (the current
+/// directory without a home), the shared Download folder on Android, or the
+/// app's own storage if that isn't there.
+pub fn download_dir() -> PathBuf {
+    #[cfg(target_os = "android")]
+    {
+        let public = PathBuf::from("/storage/emulated/0/Download");
+        if public.is_dir() { public } else { data_dir().join("Download") }
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join("Downloads"))
+            .unwrap_or_default()
+    }
+}
+
+/// `%LOCALAPPDATA%`, `~/Library/Application Support` or `$XDG_DATA_HOME` (`~/.local/share`)
+#[cfg(not(target_os = "android"))]
+fn desktop_data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+    }
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    if cfg!(target_os = "macos") {
+        Some(home.join("Library").join("Application Support"))
+    } else {
+        Some(std::env::var_os("XDG_DATA_HOME").map(PathBuf::from).unwrap_or_else(|| home.join(".local").join("share")))
+    }
+}
+
+/// `%APPDATA%`, `~/Library/Application Support` or `$XDG_CONFIG_HOME` (`~/.config`)
+#[cfg(not(target_os = "android"))]
+fn desktop_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return std::env::var_os("APPDATA").map(PathBuf::from);
+    }
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    if cfg!(target_os = "macos") {
+        Some(home.join("Library").join("Application Support"))
+    } else {
+        Some(std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from).unwrap_or_else(|| home.join(".config")))
+    }
+}
@@ -0,0 +1,158 @@
+//! Android Soft Keyboard
+//!
+//! winit shows and hides the on-screen keyboard for focused text fields but
+//! leaves the rest to the app: GameActivity's text input, where IME
+//! composition (pinyin, kana, hangul) happens, never reaches egui, and the
+//! keyboard is drawn over the bottom of the window. `SoftKeyboard` polls both
+//! every frame while a field is being edited, turning the IME's text into
+//! egui `Ime`/`Text` events and shrinking the screen to the part above the
+//! keyboard. On other platforms it does nothing.
+
+use eframe::egui;
+
+#[cfg(target_os = "android")]
+use eframe::egui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+static ANDROID_APP: std::sync::OnceLock<AndroidApp> = std::sync::OnceLock::new();
+
+/// Register the activity; must be called before the first frame
+#[cfg(target_os = "android")]
+pub fn init(app: &AndroidApp) {
+    let _ = ANDROID_APP.set(app.clone());
+}
+
+#[derive(Default)]
+pub struct SoftKeyboard {
+    #[cfg_attr(not(target_os = "android"), allow(dead_code))]
+    composing: bool, // A Preedit has been sent and not committed yet
+    inset: f32, // Keyboard height in points, 0 while hidden
+    revealed: Option<egui::Id>, // Field last scrolled into view above the keyboard
+}
+
+impl SoftKeyboard {
+    /// Height of the keyboard in points, 0 while it is hidden
+    pub fn inset(&self) -> f32 {
+        self.inset
+    }
+
+    /// Feed the IME's text to egui and keep the UI above the keyboard.
+    /// Call from `eframe::App::raw_input_hook`.
+    #[cfg_attr(not(target_os = "android"), allow(unused_variables))]
+    pub fn hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        #[cfg(target_os = "android")]
+        {
+            let Some(app) = ANDROID_APP.get() else { return };
+            let editing = ctx.wants_keyboard_input();
+            if editing {
+                self.poll_text(app, &mut raw_input.events);
+                // winit drops GameActivity's text events, so nothing else wakes us up for them
+                ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            } else if self.composing {
+                self.composing = false;
+                app.set_text_input_state(empty_state());
+            }
+
+            let height = if editing { keyboard_height(app).unwrap_or(0) } else { 0 };
+            self.inset = height as f32 / ctx.pixels_per_point();
+            if let Some(screen) = raw_input.screen_rect.as_mut().filter(|_| self.inset > 0.0) {
+                screen.max.y = (screen.max.y - self.inset).max(screen.min.y + 100.0);
+            }
+        }
+    }
+
+    /// Scroll a field that just got focus (or the keyboard) into view. Call from `update`.
+    pub fn reveal_focused(&mut self, ctx: &egui::Context) {
+        let focused = ctx.memory(|m| m.focused()).filter(|_| self.inset > 0.0);
+        if focused != self.revealed {
+            if let Some(response) = focused.and_then(|id| ctx.read_response(id)) {
+                response.scroll_to_me(Some(egui::Align::Center));
+            }
+            self.revealed = focused;
+        }
+    }
+
+    /// Turn the IME's editing buffer into egui events. The buffer is emptied
+    /// after each commit so it only ever holds what is still being composed.
+    #[cfg(target_os = "android")]
+    fn poll_text(&mut self, app: &AndroidApp, events: &mut Vec<egui::Event>) {
+        use eframe::egui::winit::platform::android::activity::input::{TextInputState, TextSpan};
+
+        let state = app.text_input_state();
+        let chars: Vec<char> = state.text.chars().collect();
+        match state.compose_region.filter(|span| span.start < span.end && span.end <= chars.len()) {
+            Some(span) => {
+                let before: String = chars[..span.start].iter().collect();
+                let composing: String = chars[span.start..span.end].iter().collect();
+                if !before.is_empty() {
+                    self.commit(before, events);
+                }
+                if !self.composing {
+                    events.push(egui::Event::Ime(egui::ImeEvent::Enabled));
+                    self.composing = true;
+                }
+                // Anything around the composition has been taken; leave only the composition
+                if span.start > 0 || span.end < chars.len() {
+                    let len = span.end - span.start;
+                    app.set_text_input_state(TextInputState {
+                        text: composing.clone(),
+                        selection: TextSpan { start: len, end: len },
+                        compose_region: Some(TextSpan { start: 0, end: len }),
+                    });
+                }
+                events.push(egui::Event::Ime(egui::ImeEvent::Preedit(composing)));
+            }
+            None if !state.text.is_empty() => {
+                self.commit(state.text, events);
+                app.set_text_input_state(empty_state());
+            }
+            // Composition deleted down to nothing
+            None if self.composing => {
+                events.push(egui::Event::Ime(egui::ImeEvent::Preedit(String::new())));
+                events.push(egui::Event::Ime(egui::ImeEvent::Commit(String::new())));
+                self.composing = false;
+            }
+            None => {}
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    fn commit(&mut self, text: String, events: &mut Vec<egui::Event>) {
+        if self.composing {
+            events.push(egui::Event::Ime(egui::ImeEvent::Commit(text)));
+            self.composing = false;
+        } else {
+            events.push(egui::Event::Text(text));
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+fn empty_state() -> eframe::egui::winit::platform::android::activity::input::TextInputState {
+    use eframe::egui::winit::platform::android::activity::input::{TextInputState, TextSpan};
+    TextInputState { text: String::new(), selection: TextSpan { start: 0, end: 0 }, compose_region: None }
+}
+
+/// Pixels of the window hidden behind the keyboard: the decor view's height
+/// below its visible display frame. Small insets are the navigation bar.
+#[cfg(target_os = "android")]
+fn keyboard_height(app: &AndroidApp) -> Option<i32> {
+    use jni::objects::{JObject, JValue};
+
+    // Safety: both pointers stay valid for the life of the activity
+    let vm = unsafe { jni::JavaVM::from_raw(app.vm_as_ptr() as *mut jni::sys::JavaVM) }.ok()?;
+    let mut env = vm.attach_current_thread().ok()?;
+    let activity = unsafe { JObject::from_raw(app.activity_as_ptr() as jni::sys::jobject) };
+    // Polled every frame, so free the local references each time
+    let (height, visible_bottom) = env.with_local_frame(8, |env| -> jni::errors::Result<(i32, i32)> {
+        let window = env.call_method(&activity, "getWindow", "()Landroid/view/Window;", &[])?.l()?;
+        let decor = env.call_method(&window, "getDecorView", "()Landroid/view/View;", &[])?.l()?;
+        let rect = env.new_object("android/graphics/Rect", "()V", &[])?;
+        env.call_method(&decor, "getWindowVisibleDisplayFrame", "(Landroid/graphics/Rect;)V", &[JValue::Object(&rect)])?;
+        let visible_bottom = env.get_field(&rect, "bottom", "I")?.i()?;
+        let height = env.call_method(&decor, "getHeight", "()I", &[])?.i()?;
+        Ok((height, visible_bottom))
+    }).ok()?;
+    let hidden = height - visible_bottom;
+    (hidden > height / 6).then_some(hidden)
+}
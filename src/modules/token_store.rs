@@ -0,0 +1,225 @@
+//! Token Storage
+//!
+//! Persists the GitHub OAuth token between sessions. Desktop builds use the
+//! system keyring; Android has no keyring backend, so there the token is kept
+//! AES-GCM encrypted in the app's private data directory, with the key held
+//! by the Android Keystore.
+
+use anyhow::Result;
+use std::sync::OnceLock;
+
+#[cfg(target_os = "android")]
+use std::path::PathBuf;
+#[cfg(target_os = "android")]
+use super::paths;
+
+#[cfg(not(target_os = "android"))]
+const SERVICE: &str = "native_hub";
+const ACCOUNT: &str = "github_oauth";
+
+/// Platform-specific storage for the OAuth token
+pub trait TokenStore: Send + Sync {
+    /// The stored token, or None if nothing was saved yet
+    fn load(&self) -> Result<Option<String>>;
+    
+    /// Replace the stored token
+    fn save(&self, token: &str) -> Result<()>;
+    
+    /// Forget the stored token, e.g. once GitHub rejects it
+    fn delete(&self) -> Result<()>;
+}
+
+/// Token store for this platform, created on first use
+pub fn global() -> &'static dyn TokenStore {
+    static STORE: OnceLock<Box<dyn TokenStore>> = OnceLock::new();
+    STORE.get_or_init(platform_store).as_ref()
+}
+
+#[cfg(not(target_os = "android"))]
+fn platform_store() -> Box<dyn TokenStore> {
+    Box::new(KeyringStore)
+}
+
+#[cfg(target_os = "android")]
+fn platform_store() -> Box<dyn TokenStore> {
+    Box::new(EncryptedFileStore { dir: paths::data_dir() })
+}
+
+// ============================================================================
+// Desktop: system keyring
+// ============================================================================
+
+#[cfg(not(target_os = "android"))]
+pub struct KeyringStore;
+
+#[cfg(not(target_os = "android"))]
+impl TokenStore for KeyringStore {
+    fn load(&self) -> Result<Option<String>> {
+        let entry = keyring::Entry::new(SERVICE, ACCOUNT)?;
+        match entry.get_password() {
+            Ok(token) => Ok(Some(token)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    fn save(&self, token: &str) -> Result<()> {
+        keyring::Entry::new(SERVICE, ACCOUNT)?.set_password(token)?;
+        Ok(())
+    }
+    
+    fn delete(&self) -> Result<()> {
+        match keyring::Entry::new(SERVICE, ACCOUNT)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// ============================================================================
+// Android: file encrypted with an Android Keystore key
+// ============================================================================
+
+#[cfg(target_os = "android")]
+static JAVA_VM: OnceLock<jni::JavaVM> = OnceLock::new();
+
+/// Register the VM the Keystore is reached through; must be called before the first lookup
+#[cfg(target_os = "android")]
+pub fn init(app: &eframe::egui::winit::platform::android::activity::AndroidApp) {
+    // Safety: the VM stays valid for the life of the process
+    if let Ok(vm) = unsafe { jni::JavaVM::from_raw(app.vm_as_ptr() as *mut jni::sys::JavaVM) } {
+        let _ = JAVA_VM.set(vm);
+    }
+}
+
+/// Token AES-GCM encrypted in the app's private directory. The key is
+/// generated inside the Android Keystore and never leaves it, so a copy of
+/// the file (a backup, a rooted device dump) can't be decrypted elsewhere.
+#[cfg(target_os = "android")]
+pub struct EncryptedFileStore {
+    dir: PathBuf,
+}
+
+#[cfg(target_os = "android")]
+impl EncryptedFileStore {
+    const NONCE_LEN: usize = 12;
+    const KEY_ALIAS: &str = "native_hub_token";
+    
+    fn token_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.token", ACCOUNT))
+    }
+    
+    /// Run `input` through AES/GCM with the Keystore key: decrypts when `iv`
+    /// is given, otherwise encrypts. Returns the output and the IV used.
+    fn crypt(&self, iv: Option<&[u8]>, input: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let vm = JAVA_VM.get().ok_or_else(|| anyhow::anyhow!("Token store used before init"))?;
+        let mut env = vm.attach_current_thread()?;
+        let result = env.with_local_frame(16, |env| -> jni::errors::Result<_> {
+            let result = keystore_crypt(env, iv, input);
+            if result.is_err() {
+                // Leave no Java exception pending for the next JNI call
+                let _ = env.exception_clear();
+            }
+            result
+        })?;
+        Ok(result)
+    }
+}
+
+/// The AES key stored under `KEY_ALIAS`, generated on first use
+#[cfg(target_os = "android")]
+fn keystore_key<'a>(env: &mut jni::JNIEnv<'a>) -> jni::errors::Result<jni::objects::JObject<'a>> {
+    use jni::objects::{JObject, JValue};
+    
+    let provider = env.new_string("AndroidKeyStore")?;
+    let keystore = env.call_static_method("java/security/KeyStore", "getInstance", "(Ljava/lang/String;)Ljava/security/KeyStore;", &[(&provider).into()])?.l()?;
+    env.call_method(&keystore, "load", "(Ljava/security/KeyStore$LoadStoreParameter;)V", &[JValue::Object(&JObject::null())])?;
+    let alias = env.new_string(EncryptedFileStore::KEY_ALIAS)?;
+    let key = env.call_method(&keystore, "getKey", "(Ljava/lang/String;[C)Ljava/security/Key;", &[(&alias).into(), JValue::Object(&JObject::null())])?.l()?;
+    if !key.is_null() {
+        return Ok(key);
+    }
+    
+    // KeyProperties.PURPOSE_ENCRYPT | PURPOSE_DECRYPT
+    let builder = env.new_object("android/security/keystore/KeyGenParameterSpec$Builder", "(Ljava/lang/String;I)V", &[(&alias).into(), JValue::Int(1 | 2)])?;
+    let gcm = env.new_string("GCM")?;
+    let modes = env.new_object_array(1, "java/lang/String", &gcm)?;
+    env.call_method(&builder, "setBlockModes", "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;", &[(&modes).into()])?;
+    let no_padding = env.new_string("NoPadding")?;
+    let paddings = env.new_object_array(1, "java/lang/String", &no_padding)?;
+    env.call_method(&builder, "setEncryptionPaddings", "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;", &[(&paddings).into()])?;
+    let spec = env.call_method(&builder, "build", "()Landroid/security/keystore/KeyGenParameterSpec;", &[])?.l()?;
+    
+    let algorithm = env.new_string("AES")?;
+    let generator = env.call_static_method("javax/crypto/KeyGenerator", "getInstance", "(Ljava/lang/String;Ljava/lang/String;)Ljavax/crypto/KeyGenerator;", &[(&algorithm).into(), (&provider).into()])?.l()?;
+    env.call_method(&generator, "init", "(Ljava/security/spec/AlgorithmParameterSpec;)V", &[(&spec).into()])?;
+    env.call_method(&generator, "generateKey", "()Ljavax/crypto/SecretKey;", &[])?.l()
+}
+
+/// `Cipher("AES/GCM/NoPadding")` over `input`; see `EncryptedFileStore::crypt`
+#[cfg(target_os = "android")]
+fn keystore_crypt(env: &mut jni::JNIEnv, iv: Option<&[u8]>, input: &[u8]) -> jni::errors::Result<(Vec<u8>, Vec<u8>)> {
+    use jni::objects::{JByteArray, JValue};
+    
+    const ENCRYPT_MODE: i32 = 1;
+    const DECRYPT_MODE: i32 = 2;
+    
+    let key = keystore_key(env)?;
+    let transformation = env.new_string("AES/GCM/NoPadding")?;
+    let cipher = env.call_static_method("javax/crypto/Cipher", "getInstance", "(Ljava/lang/String;)Ljavax/crypto/Cipher;", &[(&transformation).into()])?.l()?;
+    match iv {
+        Some(iv) => {
+            let iv = env.byte_array_from_slice(iv)?;
+            let spec = env.new_object("javax/crypto/spec/GCMParameterSpec", "(I[B)V", &[JValue::Int(128), (&iv).into()])?;
+            env.call_method(&cipher, "init", "(ILjava/security/Key;Ljava/security/spec/AlgorithmParameterSpec;)V", &[JValue::Int(DECRYPT_MODE), (&key).into(), (&spec).into()])?;
+        }
+        // The Keystore insists on picking the IV itself
+        None => {
+            env.call_method(&cipher, "init", "(ILjava/security/Key;)V", &[JValue::Int(ENCRYPT_MODE), (&key).into()])?;
+        }
+    }
+    let input = env.byte_array_from_slice(input)?;
+    let output = JByteArray::from(env.call_method(&cipher, "doFinal", "([B)[B", &[(&input).into()])?.l()?);
+    let iv = JByteArray::from(env.call_method(&cipher, "getIV", "()[B", &[])?.l()?);
+    Ok((env.convert_byte_array(&output)?, env.convert_byte_array(&iv)?))
+}
+
+#[cfg(target_os = "android")]
+impl TokenStore for EncryptedFileStore {
+    fn load(&self) -> Result<Option<String>> {
+        let data = match std::fs::read(self.token_path()) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if data.len() <= Self::NONCE_LEN {
+            anyhow::bail!("Stored token is corrupt");
+        }
+        
+        let (nonce, ciphertext) = data.split_at(Self::NONCE_LEN);
+        let (plaintext, _) = self.crypt(Some(nonce), ciphertext)
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt stored token: {}", e))?;
+        Ok(Some(String::from_utf8(plaintext)?))
+    }
+    
+    fn save(&self, token: &str) -> Result<()> {
+        let (ciphertext, nonce) = self.crypt(None, token.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Failed to encrypt token: {}", e))?;
+        if nonce.len() != Self::NONCE_LEN {
+            anyhow::bail!("Unexpected IV length {}", nonce.len());
+        }
+        
+        let mut data = nonce;
+        data.extend_from_slice(&ciphertext);
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.token_path(), data)?;
+        Ok(())
+    }
+    
+    fn delete(&self) -> Result<()> {
+        match std::fs::remove_file(self.token_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
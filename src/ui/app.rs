@@ -1,23 +1,36 @@
 use eframe::egui;
 use egui_commonmark::CommonMarkCache;
+use std::collections::HashMap;
 use tokio::sync::mpsc::Sender; // UI -> Backend
 use std::sync::mpsc::Receiver; // Backend -> UI
 
 use crate::context::AppContext;
 use crate::modules::auth::DeviceCodeResponse;
-use crate::app_event::{AppAction, AppEvent, FileNode};
-use crate::i18n::I18n;
-use super::sidebar::Sidebar;
-use super::log_viewer::LogViewer;
+use crate::modules::soft_keyboard::SoftKeyboard;
+use crate::app_event::{AppAction, AppError, AppEvent, CloneTool, ErrorMessage, FileNode, Operation, Tag, WatchState};
+use crate::i18n::{I18n, Lang};
+use super::sidebar::{Sidebar, SidebarAction};
+use super::history::{History, Location};
+use super::file_browser::BrowserAction;
+use super::layout::{Dock, Layout, Page};
+use super::log_viewer::{Command, LogViewer};
+use super::command_deck::{CommandDeck, DeckAction};
 use super::repo_browser::RepoBrowser;
+use super::topics_editor::TopicsEditor;
+use super::settings::SettingsModal;
 use super::particles::{ParticleSystem, ClickRipple};
+use crate::config::{Effects, SavedSearch};
 
+#[derive(Clone)]
 pub enum AppState {
     Login,
     RequestingCode,
     DeviceAuth {
         response: DeviceCodeResponse,
     },
+    BrowserAuth {
+        url: String,
+    },
     Main,
     Browsing {
         repo_name: String,      // full_name (owner/repo)
@@ -29,6 +42,22 @@ pub enum AppState {
     },
 }
 
+/// A file that can't be opened in the code viewer, offered for download instead
+#[derive(Clone)]
+struct UnviewableFile {
+    filename: String,
+    size: u64,
+    download_url: Option<String>,
+    preview: Option<Vec<u8>>, // First bytes of a binary file; None if it is text but too big
+}
+
+/// Settings of the open clone dialog
+struct CloneDialog {
+    directory: String, // Parent directory; the clone goes into `<directory>/<repo>`
+    tool: CloneTool,
+    ssh: bool,         // Clone URL for git: SSH instead of HTTPS
+}
+
 pub struct NativeHubApp {
     ctx: AppContext,
     state: AppState,
@@ -41,10 +70,19 @@ pub struct NativeHubApp {
     log_viewer: LogViewer,
     repo_browser: RepoBrowser,
     search_panel: super::search::SearchPanel,
+    trending_panel: super::trending::TrendingPanel,
+    show_trending: bool, // Right column of the main view shows trending instead of search
     issues_panel: super::issues::IssuesPanel,
     pr_panel: super::pull_requests::PullRequestsPanel,
+    commits_panel: super::commits::CommitsPanel,
+    labels_panel: super::labels::LabelsPanel,
+    discussions_panel: super::discussions::DiscussionsPanel,
+    repo_tree: super::repo_tree::RepoTree,
+    command_deck: CommandDeck,
+    code_viewer: super::code_viewer::CodeViewer,
     
     // FX
+    effects: Effects,
     particles: ParticleSystem,
     click_ripples: Vec<ClickRipple>,
     
@@ -53,103 +91,336 @@ pub struct NativeHubApp {
     event_rx: Receiver<AppEvent>,
     
     auth_error: Option<String>,
+    last_error: Option<(AppError, std::time::Instant)>, // Shown with a recovery action outside the login screen
     
     // File browsing state
     selected_repo: Option<String>, // full_name of the repo being browsed
+    selected_ref: String,          // Branch/tag being browsed ("" = default branch)
+    tags: Vec<Tag>,                // Tags of the browsed repo for the ref selector
+    languages: Vec<(String, u64)>, // Language byte counts of the browsed repo
+    starred: HashMap<String, bool>, // Known star state by full_name
+    watch_state: Option<WatchState>, // Subscription of the browsed repo
+    confirm_fork: Option<String>,  // Repo awaiting fork confirmation
+    confirm_delete: Option<(String, String)>, // (full_name, typed confirmation)
+    topics_editor: Option<TopicsEditor>,
+    settings: Option<SettingsModal>,
+    large_file: Option<UnviewableFile>,
+    file_rest: Option<u64>, // Byte offset of the unfetched rest of the open file
+    running_tasks: std::collections::BTreeMap<u64, String>, // Backend tasks by id, for the busy indicator
+    notifications: Vec<crate::app_event::Notification>, // Last polled notifications of the open repo
+    notifications_seeded: bool, // First refresh of the open repo arrived; desktop alerts only for later threads
+    download_dir: Option<String>, // Target directory while the download dialog is open
+    clone_dialog: Option<CloneDialog>,
+    download_progress: Option<(Operation, String, u64, Option<u64>)>, // (download or save, label, bytes written, total)
     
     // Markdown rendering cache
     markdown_cache: CommonMarkCache,
+    
+    restore_repo: Option<String>, // Repo open when the last session ended, reopened after login
+    pending_link: Option<String>, // Deep link that arrived before login, opened once signed in
+    revalidating: u32, // Refreshes running behind cached data, for the "updating" notice
+    throttled_until: u64, // Unix time GitHub's rate limit pause ends; requests wait and retry until then
+    history: History,
+    pending_location: Option<Location>, // History target still waiting for its directory listing
+    layout: Layout,
+    soft_keyboard: SoftKeyboard,
 }
 
+/// eframe storage key of the repo open at exit
+const LAST_REPO_KEY: &str = "last_repo";
+/// eframe storage key of the recently viewed list
+const RECENT_KEY: &str = "recent";
+/// eframe storage key of the browsing layout
+const LAYOUT_KEY: &str = "layout";
+
 impl NativeHubApp {
     pub fn new(
         cc: &eframe::CreationContext<'_>,
         action_tx: Sender<AppAction>,
         event_rx: Receiver<AppEvent>,
-        ctx: AppContext
+        ctx: AppContext,
+        config: crate::config::Config,
     ) -> Self {
-        // Apply Cyberpunk theme
-        super::style::configure_fonts(&cc.egui_ctx);
-        super::style::configure_theme(&cc.egui_ctx);
+        // Apply the configured theme (Cyberpunk by default)
+        super::style::configure_fonts(&cc.egui_ctx, config.lang());
+        super::style::apply_theme(&cc.egui_ctx, config.theme);
         
-        // Install custom HTTP image loader with longer timeout
-        super::image_loader::CustomHttpLoader::install(&cc.egui_ctx);
+        // Image decoders first, then the custom HTTP loader so it takes precedence over ehttp.
+        // It shares the token so README images of private repos load.
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+        super::image_loader::CustomHttpLoader::install(&cc.egui_ctx, ctx.auth_token.clone());
+        ctx.avatars.install(&cc.egui_ctx);
         
         Self {
             ctx,
             state: AppState::Login,
-            i18n: I18n::default(), // Chinese by default
+            i18n: I18n::new(config.lang()), // System locale unless configured
             sidebar: Sidebar::new(),
             log_viewer: LogViewer::new(),
             repo_browser: RepoBrowser::new(action_tx.clone()),
             search_panel: super::search::SearchPanel::new(action_tx.clone()),
+            trending_panel: super::trending::TrendingPanel::new(action_tx.clone()),
+            show_trending: false,
             issues_panel: super::issues::IssuesPanel::new(action_tx.clone()),
             pr_panel: super::pull_requests::PullRequestsPanel::new(action_tx.clone()),
+            commits_panel: super::commits::CommitsPanel::new(action_tx.clone()),
+            labels_panel: super::labels::LabelsPanel::new(action_tx.clone()),
+            discussions_panel: super::discussions::DiscussionsPanel::new(action_tx.clone()),
+            repo_tree: super::repo_tree::RepoTree::new(action_tx.clone()),
+            command_deck: CommandDeck::new(action_tx.clone()),
+            code_viewer: super::code_viewer::CodeViewer::default(),
+            effects: config.effects,
             particles: ParticleSystem::new(100), // Max 100 particles
             click_ripples: Vec::new(),
             action_tx,
             event_rx,
             auth_error: None,
+            last_error: None,
             selected_repo: None,
+            selected_ref: String::new(),
+            tags: Vec::new(),
+            languages: Vec::new(),
+            starred: HashMap::new(),
+            watch_state: None,
+            confirm_fork: None,
+            confirm_delete: None,
+            topics_editor: None,
+            settings: None,
+            large_file: None,
+            file_rest: None,
+            running_tasks: std::collections::BTreeMap::new(),
+            notifications: Vec::new(),
+            notifications_seeded: false,
+            download_dir: None,
+            clone_dialog: None,
+            download_progress: None,
             markdown_cache: CommonMarkCache::default(),
+            restore_repo: cc.storage.and_then(|s| eframe::get_value::<Option<String>>(s, LAST_REPO_KEY)).flatten(),
+            pending_link: None,
+            revalidating: 0,
+            throttled_until: 0,
+            history: History::with_recent(cc.storage.and_then(|s| eframe::get_value(s, RECENT_KEY)).unwrap_or_default()),
+            pending_location: None,
+            layout: cc.storage.and_then(|s| eframe::get_value(s, LAYOUT_KEY)).unwrap_or_default(),
+            soft_keyboard: SoftKeyboard::default(),
         }
     }
 
-    fn process_events(&mut self) {
+    fn process_events(&mut self, ctx: &egui::Context) {
         // ... same as before
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
                 AppEvent::Log(msg) => {
                     self.log_viewer.add_log(msg);
                 }
+                AppEvent::LogKey(key, args) => {
+                    let args: Vec<&str> = args.iter().map(|arg| self.i18n.arg(arg)).collect();
+                    let args: Vec<&dyn std::fmt::Display> = args.iter().map(|arg| arg as &dyn std::fmt::Display).collect();
+                    self.log_viewer.add_log(self.i18n.tf(key, &args));
+                }
+                AppEvent::Revalidating(started) => {
+                    if started {
+                        self.revalidating += 1;
+                    } else {
+                        self.revalidating = self.revalidating.saturating_sub(1);
+                    }
+                }
+                AppEvent::Throttled(until) => {
+                    self.throttled_until = until;
+                }
+                AppEvent::OpenLink(url) => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                    if matches!(self.state, AppState::Main | AppState::Browsing { .. }) {
+                        self.open_link(&url);
+                    } else {
+                        self.pending_link = Some(url);
+                    }
+                }
                 AppEvent::DeviceCode(res) => {
                     self.state = AppState::DeviceAuth { response: res };
                 }
+                AppEvent::BrowserAuth(url) => {
+                    ctx.open_url(egui::OpenUrl::new_tab(&url));
+                    self.state = AppState::BrowserAuth { url };
+                }
                 AppEvent::AuthSuccess(_token) => {
                     tracing::info!("Auth success, token received");
                     self.state = AppState::Main;
                     self.auth_error = None;
+                    self.last_error = None;
                     self.log_viewer.add_log("SYSTEM: Secure Connection Established.".to_string());
                     
                     // Auto-fetch repos immediately after login
                     self.repo_browser.set_loading(true);
                     let _ = self.action_tx.try_send(AppAction::FetchRepos);
+                    let _ = self.action_tx.try_send(AppAction::FetchOrgs);
+                    
+                    // Open the link the app was started with, else reopen the repo from the last session
+                    if let Some(url) = self.pending_link.take() {
+                        self.restore_repo = None;
+                        self.open_link(&url);
+                    } else if let Some(repo) = self.restore_repo.take() {
+                        let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.clone()));
+                        self.open_repo(repo);
+                    }
+                }
+                AppEvent::AuthExpired => {
+                    tracing::warn!("Token expired or revoked, back to login");
+                    self.log_viewer.add_log("SYSTEM: Token rejected by GitHub. Please log in again.".to_string());
+                    // Come back to the open repo once logged in again
+                    self.restore_repo = self.selected_repo.clone().or(self.restore_repo.take());
+                    self.state = AppState::Login;
+                    self.auth_error = Some(self.i18n.t("error.session_expired").to_string());
+                    self.last_error = None;
+                    self.repo_browser.set_loading(false);
+                    // Private images loaded with the revoked token
+                    ctx.forget_all_images();
                 }
+                // Requests still in flight when the token was dropped; the login screen already explains
+                AppEvent::Error(AppError::Auth(_)) if matches!(self.state, AppState::Login) && self.auth_error.is_some() => {}
                 AppEvent::Error(err) => {
-                    self.auth_error = Some(err.clone());
-                    self.log_viewer.add_log(format!("ERROR: {}", err));
+                    self.auth_error = Some(err.message().render(&self.i18n));
+                    self.log_viewer.add_log(format!("ERROR: {}", err.message().render(&self.i18n)));
+                    self.log_viewer.clear_progress();
+                    self.last_error = Some((err, std::time::Instant::now()));
                     
-                    if matches!(self.state, AppState::RequestingCode) {
+                    if matches!(self.state, AppState::RequestingCode | AppState::BrowserAuth { .. }) {
                         self.state = AppState::Login;
                     }
                     self.repo_browser.set_loading(false);
+                    self.repo_browser.finish_create(false);
+                    self.pr_panel.create_failed();
+                    self.pr_panel.branches_failed();
+                    self.download_progress = None;
                 }
                 AppEvent::RepoList(repos) => {
                     self.log_viewer.add_log(format!("SYSTEM: Received {} repositories.", repos.len()));
                     self.repo_browser.set_repos(repos);
                 }
+                AppEvent::StarredList(repos) => {
+                    self.repo_browser.set_starred(repos);
+                }
+                AppEvent::RepoCreated(_full_name) => {
+                    self.repo_browser.finish_create(true);
+                }
+                AppEvent::RepoDeleted(full_name) => {
+                    self.repo_browser.remove_repo(&full_name);
+                    if self.selected_repo.as_deref() == Some(full_name.as_str()) {
+                        self.state = AppState::Main;
+                        self.selected_repo = None;
+                    }
+                }
+                AppEvent::DownloadProgress(op, label, written, total) => {
+                    self.download_progress = Some((op, label, written, total));
+                }
+                AppEvent::TokenScopesMissing(missing) => {
+                    let missing: Vec<String> = missing.iter()
+                        .map(|(scope, needed_for)| format!("{} ({})", scope, self.i18n.t(needed_for)))
+                        .collect();
+                    let message = ErrorMessage::from(("error.token_scopes", vec![missing.join(", ")]));
+                    self.log_viewer.add_log(format!("WARNING: {}", message.render(&self.i18n)));
+                    self.last_error = Some((AppError::Permission(message), std::time::Instant::now()));
+                }
+                AppEvent::TaskStarted(id, name) => {
+                    self.running_tasks.insert(id, name);
+                }
+                AppEvent::TaskFinished(id) => {
+                    self.running_tasks.remove(&id);
+                }
+                AppEvent::Progress { op, current, total } => {
+                    self.log_viewer.set_progress(op, current, total);
+                }
+                AppEvent::Cancelled(op) => {
+                    self.log_viewer.finish_progress(op);
+                    match op {
+                        Operation::Login => {
+                            if matches!(self.state, AppState::RequestingCode | AppState::DeviceAuth { .. } | AppState::BrowserAuth { .. }) {
+                                self.state = AppState::Login;
+                            }
+                        }
+                        Operation::Search => self.search_panel.searching = false,
+                        Operation::Download | Operation::SaveFile => {
+                            if self.download_progress.as_ref().is_some_and(|(progress_op, ..)| *progress_op == op) {
+                                self.download_progress = None;
+                            }
+                        }
+                        Operation::Clone | Operation::Upload => {}
+                        Operation::Bulk => self.issues_panel.bulk_cancelled(),
+                        Operation::Git => self.command_deck.cancelled(),
+                        Operation::LoadDir | Operation::ReadFile => {}
+                        Operation::LoadRepos | Operation::LoadBranches | Operation::LoadTags => {}
+                    }
+                }
+                AppEvent::DownloadFinished(path) => {
+                    self.download_progress = None;
+                    self.log_viewer.add_log(format!("SYSTEM: Saved to {}", path));
+                }
+                AppEvent::RepoForked(repo) => {
+                    self.repo_browser.add_repo(repo);
+                }
+                AppEvent::OrgList(orgs) => {
+                    self.repo_browser.set_orgs(orgs);
+                }
                 AppEvent::FileTree(path, files) => {
                     // Transition to Browsing state
                     if let Some(ref repo_name) = self.selected_repo {
-                        self.log_viewer.add_log(format!("收到 {} 个文件/目录", files.len()));
+                        self.log_viewer.add_log(self.i18n.tf("log.found_files", &[&files.len()]));
+                        
+                        self.repo_tree.set_source(repo_name, &self.selected_ref);
+                        self.repo_tree.insert(repo_name, path.clone(), files.clone());
+                        self.repo_tree.reveal(&path);
+                        
+                        // Keep repo metadata when navigating within the same repo
+                        let repo_info = match &self.state {
+                            AppState::Browsing { repo_name: current, repo_info, .. } if current == repo_name => repo_info.clone(),
+                            _ => None,
+                        };
+                        
                         self.state = AppState::Browsing {
                             repo_name: repo_name.clone(),
                             current_path: path,
                             files,
                             viewing_code: None,
-                            repo_info: None,
+                            repo_info,
                             readme_content: None,
                         };
+                        
+                        // Continue a history jump that had to load the repo or directory first
+                        if let Some(location) = self.pending_location.take()
+                            && self.selected_repo.as_ref() == Some(&location.repo)
+                        {
+                            self.go_to(location);
+                        }
                     }
                 }
-                AppEvent::FileContent(filename, content) => {
+                AppEvent::TreeChildren(full_name, path, files) => {
+                    self.repo_tree.insert(&full_name, path, files);
+                }
+                AppEvent::FileContent(filename, content, rest) => {
                     // Update viewing_code in Browsing state
                     if let AppState::Browsing { ref mut viewing_code, .. } = self.state {
-                        self.log_viewer.add_log(format!("已加载文件: {}", filename));
+                        self.log_viewer.add_log(self.i18n.tf("log.file_loaded", &[&filename]));
                         *viewing_code = Some((filename, content));
+                        self.file_rest = rest;
                     }
                 }
+                AppEvent::FileContentMore(filename, more, rest) => {
+                    match &mut self.state {
+                        AppState::Browsing { viewing_code: Some((open, content)), .. } if *open == filename => {
+                            content.push_str(&more);
+                            self.file_rest = rest;
+                        }
+                        _ => {}
+                    }
+                }
+                AppEvent::FileTooLarge(filename, size, download_url) => {
+                    self.large_file = Some(UnviewableFile { filename, size, download_url, preview: None });
+                }
+                AppEvent::BinaryFile(filename, size, preview, download_url) => {
+                    self.large_file = Some(UnviewableFile { filename, size, download_url, preview: Some(preview) });
+                }
                 AppEvent::RepoInfoLoaded(info) => {
+                    self.pr_panel.default_branch = info.default_branch.clone();
                     // Update repo_info in Browsing state
                     if let AppState::Browsing { ref mut repo_info, .. } = self.state {
                         *repo_info = Some(info);
@@ -164,20 +435,129 @@ impl NativeHubApp {
                 AppEvent::SearchResults(results) => {
                     self.search_panel.set_results(results);
                 }
-                AppEvent::IssueList(issues) => {
-                    self.issues_panel.set_issues(issues);
+                AppEvent::TrendingLoaded(results) => {
+                    self.trending_panel.set_results(results);
+                }
+                AppEvent::TopicsLoaded(full_name, topics) => {
+                    if let Some(editor) = &mut self.topics_editor
+                        && editor.full_name == full_name
+                    {
+                        editor.set_topics(topics.clone());
+                    }
+                    if self.selected_repo.as_deref() == Some(full_name.as_str())
+                        && let AppState::Browsing { repo_info: Some(ref mut info), .. } = self.state
+                    {
+                        info.topics = topics;
+                    }
+                }
+                AppEvent::LanguagesLoaded(full_name, languages) => {
+                    if self.selected_repo.as_deref() == Some(full_name.as_str()) {
+                        self.languages = languages;
+                    }
+                }
+                AppEvent::BranchList(branches) => {
+                    self.pr_panel.set_branches(branches);
+                }
+                AppEvent::TagList(tags) => {
+                    self.tags = tags;
+                }
+                AppEvent::StarState(full_name, starred) => {
+                    if !starred {
+                        self.repo_browser.unstarred(&full_name);
+                    }
+                    self.starred.insert(full_name, starred);
+                }
+                AppEvent::WatchStateLoaded(full_name, state) => {
+                    if self.selected_repo.as_deref() == Some(full_name.as_str()) {
+                        self.watch_state = Some(state);
+                    }
+                }
+                AppEvent::IssueList(request, issues) => {
+                    self.issues_panel.set_issues(request, issues);
                 }
                 AppEvent::IssueComments(issue_number, comments) => {
                     self.issues_panel.set_comments(issue_number, comments);
                 }
+                AppEvent::AttachmentUploaded(id, markdown) => {
+                    self.issues_panel.finish_upload(id, Some(markdown));
+                }
+                AppEvent::AttachmentFailed(id) => {
+                    self.issues_panel.finish_upload(id, None);
+                }
                 AppEvent::CommentCreated(comment) => {
                     self.issues_panel.add_comment(comment);
                 }
                 AppEvent::IssueUpdated(issue) => {
                     self.issues_panel.update_issue(issue);
                 }
-                AppEvent::PullRequestList(prs) => {
-                    self.pr_panel.set_pull_requests(prs);
+                AppEvent::BulkIssuesFinished(full_name, failed) => {
+                    self.issues_panel.bulk_finished(&full_name, failed);
+                }
+                AppEvent::LocalGitFinished(full_name) => {
+                    self.command_deck.finish(&full_name);
+                }
+                AppEvent::Cloned(full_name, directory) => {
+                    self.command_deck.adopt_checkout(&full_name, directory);
+                }
+                AppEvent::IssueLoaded(full_name, issue) => {
+                    // A referenced number can turn out to be a pull request
+                    if full_name == self.issues_panel.current_repo {
+                        if issue.pull_request.is_some() {
+                            self.sidebar.active_tab = 1;
+                            self.pr_panel.open_pr(issue.number);
+                        } else {
+                            self.sidebar.active_tab = 0;
+                            self.issues_panel.select_issue(issue);
+                        }
+                    }
+                }
+                AppEvent::MilestoneList(milestones) => {
+                    self.issues_panel.set_milestones(milestones);
+                }
+                AppEvent::MentionCandidates(full_name, users, issues) => {
+                    self.issues_panel.set_mention_sources(full_name, users, issues);
+                }
+                AppEvent::DiscussionCategories(categories) => {
+                    self.discussions_panel.set_categories(categories);
+                }
+                AppEvent::DiscussionList(request, discussions) => {
+                    self.discussions_panel.set_discussions(request, discussions);
+                }
+                AppEvent::DiscussionLoaded(thread) => {
+                    self.discussions_panel.set_thread(thread);
+                }
+                AppEvent::LabelList(request, labels) => {
+                    self.labels_panel.set_labels(request, labels);
+                }
+                AppEvent::LabelCreated(label) => {
+                    self.labels_panel.add_label(label);
+                }
+                AppEvent::LabelUpdated(previous_name, label) => {
+                    self.labels_panel.update_label(&previous_name, label);
+                }
+                AppEvent::LabelDeleted(name) => {
+                    self.labels_panel.remove_label(&name);
+                }
+                AppEvent::PullRequestList(request, prs) => {
+                    self.pr_panel.set_pull_requests(request, prs);
+                }
+                AppEvent::IssuesUpdated(full_name, issues) => {
+                    self.issues_panel.apply_update(&full_name, issues);
+                }
+                AppEvent::PullRequestsUpdated(full_name, prs) => {
+                    self.pr_panel.apply_update(&full_name, prs);
+                }
+                AppEvent::NotificationsUpdated(full_name, notifications) => {
+                    // Announce threads not seen in the previous refresh
+                    for n in notifications.iter().filter(|n| n.unread && !self.notifications.iter().any(|seen| seen.id == n.id)) {
+                        self.log_viewer.add_log(format!("🔔 {} [{}] {}", full_name, n.subject.subject_type, n.subject.title));
+                        if let Some(key) = crate::modules::desktop_notify::alert_key(&n.reason).filter(|_| self.notifications_seeded) {
+                            let body = format!("{} · {}", full_name, self.i18n.t(key));
+                            crate::modules::desktop_notify::show(&n.subject.title, &body, self.i18n.t("common.open"), ctx);
+                        }
+                    }
+                    self.notifications = notifications;
+                    self.notifications_seeded = true;
                 }
                 AppEvent::PullRequestMerged(result) => {
                     self.pr_panel.on_pr_merged(result);
@@ -185,39 +565,72 @@ impl NativeHubApp {
                 AppEvent::PullRequestClosed(pr) => {
                     self.pr_panel.on_pr_closed(pr);
                 }
+                AppEvent::PullRequestCreated(pr) => {
+                    self.pr_panel.on_pr_created(pr);
+                }
+                AppEvent::PullRequestUpdated(pr) => {
+                    self.pr_panel.update_pr(pr);
+                }
+                AppEvent::PullRequestChecks(sha, check_runs, combined) => {
+                    self.pr_panel.set_checks(sha, check_runs, combined);
+                }
+                AppEvent::PullRequestFiles(pr_number, files) => {
+                    self.pr_panel.set_files(pr_number, files);
+                }
+                AppEvent::CommitList(request, commits) => {
+                    self.commits_panel.set_commits(request, commits);
+                }
+                AppEvent::CommitDetailLoaded(detail) => {
+                    self.commits_panel.set_detail(detail);
+                }
             }
         }
     }
 }
 
 impl eframe::App for NativeHubApp {
+    /// Window geometry and panel sizes are persisted by eframe/egui; the open repo and layout are ours
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let open_repo = match self.state {
+            AppState::Browsing { .. } => self.selected_repo.clone(),
+            _ => None,
+        };
+        eframe::set_value(storage, LAST_REPO_KEY, &open_repo);
+        eframe::set_value(storage, RECENT_KEY, &self.history.recent());
+        eframe::set_value(storage, LAYOUT_KEY, &self.layout);
+    }
+    
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        self.soft_keyboard.hook(ctx, raw_input);
+    }
+    
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.process_events();
+        self.process_events(ctx);
+        self.soft_keyboard.reveal_focused(ctx);
         
         // 0. Handle Click FX Input (Global)
-        if ctx.input(|i| i.pointer.any_click()) {
-            if let Some(pos) = ctx.pointer_interact_pos() {
-                self.click_ripples.push(ClickRipple::new(pos));
-            }
+        if self.effects != Effects::Off && ctx.input(|i| i.pointer.any_click())
+            && let Some(pos) = ctx.pointer_interact_pos()
+        {
+            self.click_ripples.push(ClickRipple::new(pos));
         }
 
-        let screen_rect = ctx.screen_rect();
-        
-        // TEMPORARILY DISABLED: Custom background was blocking UI
-        // TODO: Fix layer ordering issue
-        // let bg_painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("global_bg")));
-        // bg_painter.rect_filled(screen_rect, 0.0, egui::Color32::from_rgba_unmultiplied(5, 8, 15, 200));
-        // let time = ctx.input(|i| i.time);
-        // super::effects::draw_retro_grid(&bg_painter, screen_rect, time);
-        let _ = screen_rect; // Suppress warning
-        
-        // DISABLED FOR CLARITY: Particles
-        // let dt = ctx.input(|i| i.stable_dt).min(0.1);
-        // self.particles.update(dt, screen_rect);
-        // self.particles.draw(&bg_painter);
-
-        // DISABLED FOR CLARITY: Click effects
-        // super::particles::draw_click_effects(&bg_painter, &mut self.click_ripples, dt);
+        let screen_rect = ctx.content_rect();
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        
+        // 1. Background FX, painted into the panels' own layer before any panel
+        // so their fills and widgets always end up on top
+        if self.effects != Effects::Off {
+            let bg_painter = ctx.layer_painter(egui::LayerId::background());
+            // Low keeps the grid still; motion behind text is what hurts readability
+            let time = if self.effects == Effects::Full { ctx.input(|i| i.time) } else { 0.0 };
+            super::effects::draw_retro_grid(&bg_painter, screen_rect, time);
+        
+            if self.effects == Effects::Full {
+                self.particles.update(dt, screen_rect);
+                self.particles.draw(&bg_painter);
+            }
+        }
 
         // 3. UI Layers - These should now be visible with their default dark backgrounds
         match &self.state {
@@ -240,26 +653,56 @@ impl eframe::App for NativeHubApp {
                      self.render_device_auth(ctx, ui, &response);
                 });
             }
+            AppState::BrowserAuth { url } => {
+                let url = url.clone();
+                egui::CentralPanel::default().frame(egui::Frame::NONE).show(ctx, |ui| {
+                     self.render_browser_auth(ctx, ui, &url);
+                });
+            }
             AppState::Main => {
                 self.render_main(ctx);
             }
-            AppState::Browsing { repo_name, current_path, files, viewing_code, repo_info, readme_content } => {
-                let repo_name = repo_name.clone();
-                let current_path = current_path.clone();
-                let files = files.clone();
-                let viewing_code = viewing_code.clone();
-                let repo_info = repo_info.clone();
-                let readme_content = readme_content.clone();
-                self.render_browsing(ctx, &repo_name, &current_path, &files, &viewing_code, &repo_info, &readme_content);
+            AppState::Browsing { .. } => {
+                let state = self.state.clone();
+                self.render_browsing(ctx, &state);
+            }
+        }
+        
+        self.follow_references(ctx);
+        
+        if matches!(self.state, AppState::Main | AppState::Browsing { .. }) && self.last_error.is_some() {
+            self.render_error_banner(ctx);
+        }
+        if self.revalidating > 0 {
+            self.render_updating(ctx);
+        }
+        self.render_throttled(ctx);
+        
+        if let Some(settings) = &mut self.settings
+            && settings.show(ctx, &self.i18n)
+        {
+            if let Some(previous) = settings.previous().cloned() {
+                self.apply_settings(ctx, &previous);
             }
+            self.settings = None;
         }
         
-        // DISABLED FOR CLARITY: CRT overlay makes text blurry
-        // let overlay_painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("crt_overlay")));
-        // super::effects::draw_crt_overlay(&overlay_painter, screen_rect);
+        // 4. Foreground FX: ripples mark clicks on top of the UI, the CRT
+        // overlay only in Full. Painter layers never take input.
+        if self.effects != Effects::Off {
+            let fx_painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("click_fx")));
+            super::particles::draw_click_effects(&fx_painter, &mut self.click_ripples, dt);
+        }
+        if self.effects == Effects::Full {
+            let overlay_painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("crt_overlay")));
+            super::effects::draw_crt_overlay(&overlay_painter, screen_rect);
+        }
         
-        // Force constant repaint for animations
-        ctx.request_repaint();
+        // Input and backend events (see EventSender) trigger repaints on their
+        // own; only keep going every frame while something is moving
+        if self.effects == Effects::Full || !self.click_ripples.is_empty() {
+            ctx.request_repaint();
+        }
     }
 }
 
@@ -267,11 +710,253 @@ impl NativeHubApp {
     fn render_login(&mut self, ui: &mut egui::Ui) {
         use super::login_view::{render_login, LoginAction};
         
+        let lang = self.i18n.lang;
         if let LoginAction::Initiate = render_login(ui, &self.auth_error, &mut self.i18n) {
             self.initiate_login();
         }
+        if self.i18n.lang != lang {
+            self.set_language(ui.ctx(), self.i18n.lang);
+        }
     }
 
+    /// Open links to issues and PRs of the browsed repo in the side panel
+    /// instead of the browser; everything else is left for the browser
+    fn follow_references(&mut self, ctx: &egui::Context) {
+        use super::references::{parse_url, Reference};
+        
+        let AppState::Browsing { repo_name, .. } = &self.state else { return };
+        let web_base = crate::config::current().web_base();
+        let mut targets = Vec::new();
+        ctx.output_mut(|o| o.commands.retain(|command| {
+            let egui::OutputCommand::OpenUrl(open) = command else { return true };
+            match parse_url(&open.url, &web_base) {
+                Some(reference) if reference.repo().eq_ignore_ascii_case(repo_name) => {
+                    targets.push(reference);
+                    false
+                }
+                _ => true,
+            }
+        }));
+        
+        for target in targets {
+            match target {
+                Reference::Issue { repo, number } => {
+                    if self.issues_panel.open_issue(number) {
+                        self.sidebar.active_tab = 0;
+                    } else {
+                        let _ = self.action_tx.try_send(AppAction::FetchIssue(repo, number));
+                    }
+                }
+                Reference::PullRequest { number, .. } => {
+                    self.sidebar.active_tab = 1;
+                    self.pr_panel.open_pr(number);
+                }
+            }
+        }
+    }
+    
+    /// Navigate to a `nativehub://` or github.com link: the repo, then the issue or PR in it
+    fn open_link(&mut self, url: &str) {
+        use crate::modules::deep_link::{self, DeepLink};
+        
+        let Some(link) = deep_link::parse(url, &crate::config::current().web_base()) else {
+            self.log_viewer.add_log(format!("ERROR: {}", self.i18n.tf("log.unknown_link", &[&url])));
+            return;
+        };
+        let repo = link.repo().to_string();
+        if !self.selected_repo.as_ref().is_some_and(|selected| selected.eq_ignore_ascii_case(&repo)) {
+            let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.clone()));
+            self.history.visit(Location::repo(&repo));
+            self.open_repo(repo.clone());
+        }
+        
+        match link {
+            DeepLink::Repo(_) => {}
+            DeepLink::Issue { number, .. } => {
+                self.issues_panel.set_repo(repo.clone());
+                self.sidebar.active_tab = 0;
+                self.layout.page = Page::Panels;
+                if !self.issues_panel.open_issue(number) {
+                    let _ = self.action_tx.try_send(AppAction::FetchIssue(repo, number));
+                }
+            }
+            DeepLink::PullRequest { number, .. } => {
+                self.pr_panel.set_repo(repo);
+                self.sidebar.active_tab = 1;
+                self.layout.page = Page::Panels;
+                self.pr_panel.open_pr(number);
+            }
+        }
+    }
+    
+    /// Small notice at the top while cached data is on screen and its refresh is still running
+    fn render_updating(&self, ctx: &egui::Context) {
+        egui::Area::new(egui::Id::new("updating_notice"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_TOP, egui::Vec2::new(0.0, 6.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(ui.visuals().extreme_bg_color)
+                    .corner_radius(4.0)
+                    .inner_margin(egui::Margin::symmetric(8, 2))
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(self.i18n.t("app.updating")).small().color(super::style::colors::ACCENT_DIM));
+                    });
+            });
+    }
+    
+    /// Countdown while requests wait out a rate limit pause, instead of failing them
+    fn render_throttled(&self, ctx: &egui::Context) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let remaining = self.throttled_until.saturating_sub(now);
+        if remaining == 0 {
+            return;
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        
+        egui::Area::new(egui::Id::new("throttled_notice"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_TOP, egui::Vec2::new(0.0, 30.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(ui.visuals().extreme_bg_color)
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 180, 0)))
+                    .corner_radius(4.0)
+                    .inner_margin(egui::Margin::symmetric(10, 4))
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(self.i18n.tf("app.throttled", &[&remaining]))
+                            .color(egui::Color32::from_rgb(255, 180, 0)));
+                    });
+            });
+    }
+    
+    /// Floating error notice with a recovery action matching the error kind
+    fn render_error_banner(&mut self, ctx: &egui::Context) {
+        /// Wait used when GitHub didn't say when the rate limit resets
+        const DEFAULT_RATE_LIMIT_WAIT: u64 = 60;
+        
+        let Some((err, at)) = &self.last_error else { return };
+        let mut dismiss = false;
+        let mut relogin = false;
+        let mut retry = false;
+        
+        let (icon, key, color) = match err {
+            AppError::Auth(_) => ("🔒", "banner.auth", egui::Color32::from_rgb(255, 180, 0)),
+            AppError::RateLimited { .. } => ("⏳", "banner.rate_limited", egui::Color32::from_rgb(255, 180, 0)),
+            AppError::Network(_) => ("📡", "banner.network", egui::Color32::from_rgb(255, 100, 100)),
+            AppError::NotFound(_) => ("❓", "banner.not_found", egui::Color32::from_rgb(255, 100, 100)),
+            AppError::Permission(_) => ("⛔", "banner.permission", egui::Color32::from_rgb(255, 100, 100)),
+            AppError::Validation(_) => ("⚠", "banner.validation", egui::Color32::from_rgb(255, 100, 100)),
+            AppError::Other(_) => ("⚠", "banner.other", egui::Color32::from_rgb(255, 100, 100)),
+        };
+        let title = format!("{} {}", icon, self.i18n.t(key));
+        
+        egui::Window::new(egui::RichText::new(title).color(color))
+            .id(egui::Id::new("error_banner"))
+            .anchor(egui::Align2::RIGHT_TOP, egui::Vec2::new(-20.0, 20.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                ui.label(err.message().render(&self.i18n));
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    match err {
+                        AppError::Auth(_) => {
+                            relogin = ui.button(self.i18n.t("banner.relogin")).clicked();
+                        }
+                        AppError::RateLimited { reset, .. } => {
+                            let now = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .map(|d| d.as_secs())
+                                .unwrap_or(0);
+                            let remaining = match reset {
+                                Some(reset) => reset.saturating_sub(now),
+                                None => DEFAULT_RATE_LIMIT_WAIT.saturating_sub(at.elapsed().as_secs()),
+                            };
+                            if remaining > 0 {
+                                ui.label(self.i18n.tf("banner.retry_in", &[&remaining]));
+                                ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+                            } else {
+                                retry = ui.button(self.i18n.t("common.retry")).clicked();
+                            }
+                        }
+                        AppError::Network(_) => {
+                            retry = ui.button(self.i18n.t("common.retry")).clicked();
+                        }
+                        _ => {}
+                    }
+                    dismiss = ui.button(self.i18n.t("common.close")).clicked();
+                });
+            });
+        
+        if relogin {
+            self.last_error = None;
+            self.initiate_login();
+        } else if retry {
+            self.last_error = None;
+            self.retry_current_view();
+        } else if dismiss {
+            self.last_error = None;
+        }
+    }
+    
+    /// Reload whatever the main view is showing
+    fn retry_current_view(&mut self) {
+        match (&self.state, &self.selected_repo) {
+            (AppState::Browsing { .. }, Some(repo)) => {
+                let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.clone()));
+            }
+            _ => {
+                self.repo_browser.set_loading(true);
+                let _ = self.action_tx.try_send(AppAction::FetchRepos);
+            }
+        }
+    }
+    
+    /// Switch the UI language and remember it for the next start
+    fn set_language(&mut self, ctx: &egui::Context, lang: Lang) {
+        self.i18n.set_lang(lang);
+        super::style::configure_fonts(ctx, lang);
+        let mut config = crate::config::current();
+        config.language = Some(lang);
+        if let Err(e) = crate::config::update(config) {
+            tracing::warn!("Failed to save language: {}", e);
+        }
+    }
+    
+    /// Apply freshly saved settings, given the ones they replaced
+    fn apply_settings(&mut self, ctx: &egui::Context, previous: &crate::config::Config) {
+        let config = crate::config::current();
+        self.i18n.set_lang(config.lang());
+        if config.lang() != previous.lang() {
+            super::style::configure_fonts(ctx, config.lang());
+        }
+        super::style::apply_theme(ctx, config.theme);
+        self.effects = config.effects;
+        if self.effects == Effects::Off {
+            self.click_ripples.clear();
+        }
+        
+        if config.poll_interval != previous.poll_interval {
+            let _ = self.action_tx.try_send(AppAction::SetPollInterval(config.poll_interval));
+        }
+        if config.engine != previous.engine {
+            let _ = self.action_tx.try_send(AppAction::SetEngine(config.engine));
+        }
+        // A different server means a different set of repos
+        if config.api_base() != previous.api_base() && matches!(self.state, AppState::Main) {
+            self.repo_browser.set_loading(true);
+            let _ = self.action_tx.try_send(AppAction::FetchRepos);
+            let _ = self.action_tx.try_send(AppAction::FetchOrgs);
+        }
+    }
+    
     fn initiate_login(&mut self) {
         self.state = AppState::RequestingCode;
         // Non-blocking send
@@ -321,25 +1006,68 @@ impl NativeHubApp {
         });
     }
 
-    fn render_main(&mut self, ctx: &egui::Context) {
-        egui::SidePanel::left("sidebar_panel")
-            .width_range(200.0..=400.0)
-            .resizable(true)
-            .show(ctx, |ui| {
-                self.sidebar.show(ui);
+    fn render_browser_auth(&mut self, ctx: &egui::Context, _parent_ui: &mut egui::Ui, url: &str) {
+        use super::retro_modal::RetroModal;
+        
+        RetroModal::show(ctx, "🔐 SECURITY CHECKPOINT", |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(10.0);
+                ui.label(egui::RichText::new("AUTHENTICATION REQUIRED").color(egui::Color32::from_rgb(255, 0, 128))); // Magenta
+                ui.separator();
+                ui.add_space(20.0);
+                
+                ui.label("APPROVE ACCESS IN YOUR BROWSER.");
+                ui.label(egui::RichText::new("(IF IT DID NOT OPEN, USE THE LINK BELOW)").size(10.0).color(egui::Color32::GRAY));
+                ui.add_space(5.0);
+                ui.hyperlink_to("OPEN AUTHORIZATION PAGE", url);
+                
+                ui.add_space(30.0);
+                ui.horizontal_centered(|ui| {
+                    ui.spinner();
+                    ui.label(" Awaiting Redirect Signal...");
+                });
+                
+                ui.add_space(30.0);
+                if ui.add(egui::Button::new("ABORT SEQUENCE").min_size(egui::Vec2::new(150.0, 30.0))).clicked() {
+                     let _ = self.action_tx.try_send(AppAction::Cancel);
+                     self.state = AppState::Login;
+                }
             });
+        });
+    }
+
+    fn render_main(&mut self, ctx: &egui::Context) {
+        // Back from the repo list leaves the app, like any other Android app
+        if self.back_pressed(ctx, false) && cfg!(target_os = "android") {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
         
-        egui::TopBottomPanel::bottom("terminal_panel")
-            .min_height(150.0)
-            .resizable(true)
-            .show(ctx, |ui| {
-                 self.log_viewer.show(ui, &self.i18n);
+        let compact = Layout::is_compact(ctx);
+        if compact {
+            self.render_compact_bars(ctx, false);
+        } else {
+            // Right-to-left languages get the sidebar on the right
+            let sidebar_side = if self.i18n.is_rtl() { egui::panel::Side::Right } else { egui::panel::Side::Left };
+            egui::SidePanel::new(sidebar_side, "sidebar_panel")
+                .width_range(200.0..=400.0)
+                .resizable(true)
+                .show(ctx, |ui| self.show_sidebar(ui));
+        
+            egui::TopBottomPanel::bottom("terminal_panel")
+                .min_height(150.0)
+                .resizable(true)
+                .show(ctx, |ui| {
+                     if let Some(command) = self.log_viewer.show(ui, &self.i18n) {
+                         self.run_command(command);
+                     }
                  
-                 ui.separator();
+                     ui.separator();
                  
-                 // HUD Status Bar at the very bottom
-                 super::components::SystemStatusBar::show(ui);
-            });
+                     // HUD Status Bar at the very bottom
+                     let tasks: Vec<&str> = self.running_tasks.values().map(String::as_str).collect();
+                     super::components::SystemStatusBar::show(ui, &self.i18n, &self.ctx, &tasks);
+                });
+        }
 
         // The Central Panel must be added last
         egui::CentralPanel::default()
@@ -347,121 +1075,875 @@ impl NativeHubApp {
             .show(ctx, |ui| {
                 ui.add_space(10.0);
                 
+                // Phones get one column at a time
+                if compact {
+                    match self.layout.current_page(false) {
+                        Page::Discover => self.show_discover(ui),
+                        Page::Menu => self.show_sidebar(ui),
+                        Page::Home | Page::Panels => self.show_repo_list(ui),
+                    }
+                    return;
+                }
+                
                 // Two-column layout: My Repos | Search
                 ui.columns(2, |columns| {
-                    // Left column: My Repositories
-                    columns[0].vertical(|ui| {
-                        if let Some(repo_full_name) = self.repo_browser.show(ui, &self.i18n) {
-                            self.selected_repo = Some(repo_full_name);
-                        }
-                    });
-                    
-                    // Right column: Search
-                    columns[1].vertical(|ui| {
-                        if let Some(repo_full_name) = self.search_panel.show(ui, &self.i18n) {
-                            self.selected_repo = Some(repo_full_name);
-                        }
-                    });
+                    columns[0].vertical(|ui| self.show_repo_list(ui));
+                    columns[1].vertical(|ui| self.show_discover(ui));
                 });
             });
     }
     
-    fn render_browsing(
-        &mut self,
-        ctx: &egui::Context,
-        repo_name: &str,
-        current_path: &str,
-        files: &[FileNode],
-        viewing_code: &Option<(String, String)>,
-        repo_info: &Option<crate::app_event::RepoInfo>,
-        readme_content: &Option<String>,
-    ) {
-        use super::file_browser::{render_file_browser, BrowserAction};
+    /// Sidebar contents: a side panel on desktop, the Menu page on phones
+    fn show_sidebar(&mut self, ui: &mut egui::Ui) {
+        let Some(action) = self.sidebar.show(ui, self.history.recent(), &self.i18n) else {
+            return;
+        };
+        // On phones, whatever is opened from the menu page replaces it
+        match action {
+            SidebarAction::OpenSettings => {
+                self.settings = Some(SettingsModal::open());
+            }
+            SidebarAction::SetLanguage(lang) => {
+                self.set_language(ui.ctx(), lang);
+            }
+            SidebarAction::OpenRecent(location) => {
+                self.history.visit(location.clone());
+                self.layout.page = Page::Home;
+                self.go_to(location);
+            }
+            SidebarAction::RunSaved(SavedSearch::Repos { query, .. }) => {
+                self.show_trending = false;
+                self.layout.page = Page::Discover;
+                self.search_panel.run(query);
+            }
+            SidebarAction::RunSaved(SavedSearch::Issues { repo, state, milestone, labels, .. }) => {
+                let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.clone()));
+                self.history.visit(Location::repo(&repo));
+                self.open_repo(repo.clone());
+                self.sidebar.active_tab = 0;
+                self.layout.page = Page::Panels;
+                self.issues_panel.apply_preset(&repo, &state, &milestone, &labels);
+            }
+        }
+    }
+    
+    /// My Repositories
+    fn show_repo_list(&mut self, ui: &mut egui::Ui) {
+        if let Some(repo_full_name) = self.repo_browser.show(ui, &self.i18n) {
+            self.history.visit(Location::repo(&repo_full_name));
+            self.open_repo(repo_full_name);
+        }
+    }
+    
+    /// Search or Trending
+    fn show_discover(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.show_trending, false, self.i18n.t("app.search_tab"));
+            ui.selectable_value(&mut self.show_trending, true, self.i18n.t("app.trending_tab"));
+        });
+        ui.add_space(5.0);
         
-        // Set current repo for issues and PR panels (triggers load if changed)
-        self.issues_panel.set_repo(repo_name.to_string());
-        self.pr_panel.set_repo(repo_name.to_string());
+        if self.show_trending {
+            let repo_browser = &self.repo_browser;
+            let selected = self.trending_panel.show(ui, &self.i18n, &self.starred, |name| repo_browser.is_pinned(name));
+            if let Some(repo) = self.trending_panel.pin_request.take() {
+                self.repo_browser.toggle_pin(repo);
+            }
+            if let Some(repo_full_name) = selected {
+                self.history.visit(Location::repo(&repo_full_name));
+                self.open_repo(repo_full_name);
+            }
+            return;
+        }
         
-        egui::TopBottomPanel::bottom("terminal_panel_browse")
-            .min_height(100.0)
-            .resizable(true)
-            .show(ctx, |ui| {
-                self.log_viewer.show(ui, &self.i18n);
-            });
+        let repo_browser = &self.repo_browser;
+        let selected = self.search_panel.show(ui, &self.i18n, &self.starred, |name| repo_browser.is_pinned(name));
+        if let Some(repo) = self.search_panel.pin_request.take() {
+            self.repo_browser.toggle_pin(repo);
+        }
+        if let Some(search) = self.search_panel.save_request.take() {
+            self.sidebar.add_saved(search);
+        }
+        if let Some(repo_full_name) = selected {
+            // Leaving the search view - drop any search still in flight
+            let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::Search));
+            self.search_panel.searching = false;
+            self.history.visit(Location::repo(&repo_full_name));
+            self.open_repo(repo_full_name);
+        }
+    }
+    
+    /// Android back button / gesture (BrowserBack to egui). On a compact-layout
+    /// page other than Home it returns there; otherwise it's left to the caller
+    fn back_pressed(&mut self, ctx: &egui::Context, browsing: bool) -> bool {
+        if !ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::BrowserBack)) {
+            return false;
+        }
+        if self.layout.current_page(browsing) != Page::Home {
+            self.layout.page = Page::Home;
+            return false;
+        }
+        true
+    }
+    
+    /// Compact layout: bottom nav bar with the log folded into a bar above it
+    fn render_compact_bars(&mut self, ctx: &egui::Context, browsing: bool) {
+        // While typing, the keyboard needs the bottom of the screen more than the nav and log do
+        if self.soft_keyboard.inset() > 0.0 {
+            return;
+        }
         
-        // Right panel: Issues & PRs with tabs
-        let active_tab = self.sidebar.active_tab;
-        egui::SidePanel::right("issues_pr_panel")
-            .min_width(320.0)
-            .max_width(450.0)
-            .resizable(true)
+        egui::TopBottomPanel::bottom("compact_nav")
+            .show(ctx, |ui| self.layout.bottom_nav(ui, &self.i18n, browsing));
+        
+        egui::TopBottomPanel::bottom("compact_log")
+            .resizable(self.layout.log_expanded)
+            .min_height(if self.layout.log_expanded { 150.0 } else { 0.0 })
             .show(ctx, |ui| {
-                // Tab buttons at the top
                 ui.horizontal(|ui| {
-                    if ui.selectable_label(active_tab == 0, 
-                        egui::RichText::new("📋 Issues").color(if active_tab == 0 { 
-                            super::style::colors::ACCENT 
-                        } else { 
-                            egui::Color32::GRAY 
-                        })
-                    ).clicked() {
-                        self.sidebar.active_tab = 0;
+                    let arrow = if self.layout.log_expanded { "▼" } else { "▲" };
+                    let toggle = egui::RichText::new(format!("{} {}", arrow, self.i18n.t("log.title"))).size(11.0).color(egui::Color32::GRAY);
+                    if ui.add(egui::Button::new(toggle).frame(false)).clicked() {
+                        self.layout.log_expanded = !self.layout.log_expanded;
                     }
-                    if ui.selectable_label(active_tab == 1, 
-                        egui::RichText::new("🔀 PRs").color(if active_tab == 1 { 
-                            super::style::colors::ACCENT 
-                        } else { 
-                            egui::Color32::GRAY 
-                        })
-                    ).clicked() {
-                        self.sidebar.active_tab = 1;
+                    if !self.layout.log_expanded
+                        && let Some(last) = self.log_viewer.last()
+                    {
+                        ui.add(egui::Label::new(egui::RichText::new(last).size(11.0).monospace().color(egui::Color32::from_rgb(0, 255, 136))).truncate());
                     }
                 });
-                
-                ui.separator();
-                
-                // Show the active panel
-                match active_tab {
-                    0 => self.issues_panel.show(ui, &self.i18n),
-                    1 => self.pr_panel.show(ui, &self.i18n),
-                    _ => {}
+                if self.layout.log_expanded
+                    && let Some(command) = self.log_viewer.show(ui, &self.i18n)
+                {
+                    self.run_command(command);
                 }
             });
+    }
+    
+    /// Leave the browsed repo for the repo list, stopping its loads and polling
+    fn back_to_repo_list(&mut self) {
+        let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::LoadDir));
+        let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::ReadFile));
+        let _ = self.action_tx.try_send(AppAction::StopPolling);
+        self.state = AppState::Main;
+        self.selected_repo = None;
+        self.notifications.clear();
+        self.notifications_seeded = false;
+    }
+    
+    /// Carry out a command typed into the log panel
+    fn run_command(&mut self, command: Command) {
+        let browsing = matches!(self.state, AppState::Browsing { .. });
+        match command {
+            Command::Open(repo) => {
+                let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.clone()));
+                self.history.visit(Location::repo(&repo));
+                self.open_repo(repo);
+            }
+            Command::Home => {
+                if browsing {
+                    self.back_to_repo_list();
+                }
+            }
+            Command::Refresh => {
+                self.repo_browser.set_loading(true);
+                let _ = self.action_tx.try_send(AppAction::FetchRepos);
+            }
+            Command::Search(query) => {
+                if browsing {
+                    self.back_to_repo_list();
+                }
+                self.search_panel.run(query);
+            }
+            Command::Issues(_) | Command::PullRequests(_) if !browsing => {
+                self.log_viewer.add_log(format!("ERROR: {}", self.i18n.t("log.open_repo_first")));
+            }
+            Command::Issues(state) => {
+                self.sidebar.active_tab = 0;
+                self.issues_panel.set_filter_state(&state);
+            }
+            Command::PullRequests(state) => {
+                self.sidebar.active_tab = 1;
+                self.pr_panel.set_filter_state(&state);
+            }
+            Command::Translations(lang) => {
+                let langs = lang.map(|lang| vec![lang]).unwrap_or_else(|| Lang::all().to_vec());
+                for lang in langs {
+                    let diff = crate::i18n::diff(lang);
+                    self.log_viewer.add_log(self.i18n.tf(
+                        "log.translation_coverage",
+                        &[&lang.name(), &lang.code(), &(diff.total - diff.missing.len()), &diff.total, &diff.extra.len()]
+                    ));
+                    for key in &diff.missing {
+                        self.log_viewer.add_log(format!("  - {}", key));
+                    }
+                    for key in &diff.extra {
+                        self.log_viewer.add_log(format!("  + {}", key));
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Remember the repo being opened and load its ref list (SelectRepo is sent by the panel)
+    fn open_repo(&mut self, repo_full_name: String) {
+        self.selected_ref.clear();
+        self.tags.clear();
+        self.languages.clear();
+        self.watch_state = None;
+        let _ = self.action_tx.try_send(AppAction::FetchTags(repo_full_name.clone()));
+        let _ = self.action_tx.try_send(AppAction::FetchLanguages(repo_full_name.clone()));
+        let _ = self.action_tx.try_send(AppAction::CheckWatchState(repo_full_name.clone()));
+        let _ = self.action_tx.try_send(AppAction::CheckStarred(repo_full_name.clone()));
+        self.layout.page = Page::Home;
+        self.selected_repo = Some(repo_full_name);
+    }
+    
+    /// Show a history location without recording it: switch repo and directory as
+    /// needed, finishing in the FileTree handler once the listing has arrived
+    fn go_to(&mut self, location: Location) {
+        if self.selected_repo.as_ref() != Some(&location.repo) {
+            let _ = self.action_tx.try_send(AppAction::SelectRepo(location.repo.clone()));
+            self.open_repo(location.repo.clone());
+            self.pending_location = Some(location);
+            return;
+        }
+        
+        let AppState::Browsing { current_path, files, viewing_code, .. } = &mut self.state else {
+            self.pending_location = Some(location);
+            return;
+        };
+        if *current_path != location.path {
+            let _ = self.action_tx.try_send(AppAction::FetchDir(location.repo.clone(), location.path.clone(), self.selected_ref.clone()));
+            self.pending_location = Some(location);
+            return;
+        }
+        
+        match location.file.and_then(|path| files.iter().find(|f| f.path == path).cloned()) {
+            Some(file) => {
+                let _ = self.action_tx.try_send(AppAction::ReadFile(location.repo, file));
+            }
+            None => {
+                let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::ReadFile));
+                *viewing_code = None;
+            }
+        }
+    }
+    
+    fn render_browsing(&mut self, ctx: &egui::Context, state: &AppState) {
+        let AppState::Browsing { repo_name, current_path, files, viewing_code, repo_info, readme_content } = state else { return };
+        use super::file_browser::{render_file_browser, BrowserAction, BrowserView};
+        
+        // Set current repo for the right-hand panels (triggers load if changed)
+        self.issues_panel.set_repo(repo_name.to_string());
+        self.pr_panel.set_repo(repo_name.to_string());
+        self.commits_panel.set_repo(repo_name.to_string());
+        self.labels_panel.set_repo(repo_name.to_string());
+        self.discussions_panel.set_repo(repo_name.to_string());
+        self.command_deck.set_repo(repo_name);
+        self.repo_tree.set_source(repo_name, &self.selected_ref);
+        
+        // Back steps out one level: file → directory → repo list
+        if self.back_pressed(ctx, true) {
+            let action = if viewing_code.is_some() {
+                BrowserAction::CloseViewer
+            } else if !current_path.is_empty() {
+                BrowserAction::NavigateTo(super::file_browser::parent_path(current_path))
+            } else {
+                BrowserAction::BackToRepoList
+            };
+            self.handle_browser_action(action, repo_name, current_path, files);
+        }
+        
+        let compact = Layout::is_compact(ctx);
+        if compact {
+            self.render_compact_bars(ctx, true);
+        } else {
+            egui::TopBottomPanel::bottom("terminal_panel_browse")
+                .min_height(100.0)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    if let Some(command) = self.log_viewer.show(ui, &self.i18n) {
+                        self.run_command(command);
+                    }
+                });
+        
+            // Issues, PRs, Commits, Labels & Discussions with tabs, docked where the user put it
+            let max_width = ctx.content_rect().width() * 0.7;
+            match self.layout.dock {
+                Dock::Right => {
+                    egui::SidePanel::right("issues_pr_panel")
+                        .min_width(320.0)
+                        .max_width(max_width.max(320.0))
+                        .resizable(true)
+                        .show(ctx, |ui| self.render_side_tabs(ui));
+                }
+                Dock::Left => {
+                    egui::SidePanel::left("issues_pr_panel_left")
+                        .min_width(320.0)
+                        .max_width(max_width.max(320.0))
+                        .resizable(true)
+                        .show(ctx, |ui| self.render_side_tabs(ui));
+                }
+                Dock::Bottom => {
+                    egui::TopBottomPanel::bottom("issues_pr_panel_bottom")
+                        .min_height(200.0)
+                        .default_height(320.0)
+                        .resizable(true)
+                        .show(ctx, |ui| self.render_side_tabs(ui));
+                }
+            }
+        
+        }
         
         egui::CentralPanel::default()
             .show(ctx, |ui| {
-                if let Some(action) = render_file_browser(
-                    ui,
-                    &self.i18n,
+                // Phones show the tabs or the menu in place of the browser
+                if compact {
+                    match self.layout.current_page(true) {
+                        Page::Panels => {
+                            self.render_side_tabs(ui);
+                            return;
+                        }
+                        Page::Menu => {
+                            self.show_sidebar(ui);
+                            return;
+                        }
+                        Page::Home | Page::Discover => {}
+                    }
+                }
+                
+                let view = BrowserView {
                     repo_name,
                     current_path,
-                    files,
                     viewing_code,
+                    more: self.file_rest.map(|_| self.running_tasks.values().any(|name| name == "ReadFileMore")),
                     repo_info,
                     readme_content,
-                    &self.action_tx,
+                    tags: &self.tags,
+                    languages: &self.languages,
+                    current_ref: &self.selected_ref,
+                    readme_collapsed: self.layout.readme_collapsed,
+                    compact,
+                    history: (self.history.can_go_back(), self.history.can_go_forward()),
+                    is_starred: self.starred.get(repo_name).copied(),
+                    watch_state: self.watch_state,
+                };
+                if let Some(action) = render_file_browser(
+                    ui,
+                    &self.i18n,
+                    view,
+                    &mut self.repo_tree,
+                    &mut self.code_viewer,
                     &mut self.markdown_cache,
                 ) {
-                    match action {
-                        BrowserAction::BackToRepoList => {
-                            self.state = AppState::Main;
-                            self.selected_repo = None;
-                        }
-                        BrowserAction::NavigateTo(path) => {
-                            if let Some(ref repo) = self.selected_repo {
-                                let _ = self.action_tx.try_send(AppAction::FetchDir(repo.clone(), path));
-                            }
+                    self.handle_browser_action(action, repo_name, current_path, files);
+                }
+            });
+        
+        if let Some(fork_target) = self.confirm_fork.clone() {
+            self.render_fork_confirm(ctx, &fork_target);
+        }
+        
+        if self.confirm_delete.is_some() {
+            self.render_delete_confirm(ctx);
+        }
+        
+        if self.download_dir.is_some() {
+            self.render_download_dialog(ctx, repo_name);
+        }
+        
+        if self.clone_dialog.is_some() {
+            self.render_clone_dialog(ctx, repo_name);
+        }
+        
+        if let Some(UnviewableFile { filename, size, download_url, preview }) = self.large_file.clone() {
+            use super::retro_modal::RetroModal;
+            use super::components::CyberButton;
+            
+            let mut save = false;
+            let title = if preview.is_some() { "⚠ BINARY FILE" } else { "⚠ FILE TOO LARGE" };
+            RetroModal::show(ctx, title, |ui| {
+                ui.label(egui::RichText::new(&filename)
+                    .font(egui::FontId::monospace(16.0))
+                    .color(egui::Color32::from_rgb(0, 240, 255)));
+                ui.add_space(10.0);
+                match &preview {
+                    Some(bytes) => {
+                        ui.label(self.i18n.tf("app.binary_file", &[&self.i18n.file_size(size)]));
+                        ui.add_space(10.0);
+                        egui::ScrollArea::both().max_height(240.0).show(ui, |ui| {
+                            ui.label(egui::RichText::new(super::code_viewer::hex_dump(bytes))
+                                .font(egui::FontId::monospace(12.0))
+                                .color(egui::Color32::from_rgb(150, 150, 170)));
+                        });
+                    }
+                    None => {
+                        ui.label(self.i18n.tf("app.file_too_large", &[&self.i18n.file_size(size)]));
+                    }
+                }
+                ui.add_space(10.0);
+                if let Some(url) = &download_url {
+                    ui.hyperlink_to(self.i18n.t("app.download_in_browser"), url);
+                }
+                ui.add_space(30.0);
+                ui.horizontal(|ui| {
+                    if CyberButton::new(self.i18n.t("common.save_as")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        save = true;
+                    }
+                    ui.add_space(20.0);
+                    if CyberButton::new(self.i18n.t("common.close")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        self.large_file = None;
+                    }
+                });
+            });
+            
+            if save {
+                self.large_file = None;
+                let file = match &self.state {
+                    AppState::Browsing { files, .. } => files.iter().find(|f| f.name == filename).cloned(),
+                    _ => None,
+                };
+                if let (Some(file), Some(full_name)) = (file, self.selected_repo.clone()) {
+                    self.save_file_as(&full_name, file);
+                }
+            }
+        }
+        
+        if let Some((op, label, written, total)) = &self.download_progress {
+            egui::Window::new(self.i18n.t("app.downloading"))
+                .anchor(egui::Align2::RIGHT_BOTTOM, egui::Vec2::new(-20.0, -20.0))
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(label.as_str());
+                        if ui.small_button("✕").on_hover_text(self.i18n.t("app.cancel_download")).clicked() {
+                            let _ = self.action_tx.try_send(AppAction::CancelOperation(*op));
                         }
-                        BrowserAction::OpenFile(_filename, url) => {
-                            let _ = self.action_tx.try_send(AppAction::ReadFile(url));
+                    });
+                    let written_text = self.i18n.file_size(*written);
+                    match total {
+                        Some(total) if *total > 0 => {
+                            ui.add(egui::ProgressBar::new(*written as f32 / *total as f32)
+                                .text(written_text)
+                                .desired_width(220.0));
                         }
-                        BrowserAction::CloseViewer => {
-                            if let AppState::Browsing { ref mut viewing_code, .. } = self.state {
-                                *viewing_code = None;
-                            }
+                        _ => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(written_text);
+                            });
                         }
                     }
+                });
+        }
+        
+        if let Some(editor) = &mut self.topics_editor
+            && editor.show(ctx, &self.i18n, &self.action_tx)
+        {
+            self.topics_editor = None;
+        }
+    }
+    
+    /// Carry out what was clicked in the file browser
+    fn handle_browser_action(&mut self, action: BrowserAction, repo_name: &str, current_path: &str, files: &[FileNode]) {
+        match action {
+            BrowserAction::BackToRepoList => self.back_to_repo_list(),
+            BrowserAction::NavigateTo(path) => {
+                self.history.visit(Location { repo: repo_name.to_string(), path: path.clone(), file: None });
+                if let Some(ref repo) = self.selected_repo {
+                    let _ = self.action_tx.try_send(AppAction::FetchDir(repo.clone(), path, self.selected_ref.clone()));
                 }
+            }
+            BrowserAction::ToggleStar(starred) => {
+                let _ = self.action_tx.try_send(AppAction::SetStarred(repo_name.to_string(), starred));
+            }
+            BrowserAction::Fork => {
+                self.confirm_fork = Some(repo_name.to_string());
+            }
+            BrowserAction::DownloadZip => {
+                self.download_dir = Some(default_download_dir());
+            }
+            BrowserAction::Clone => self.open_clone_dialog(),
+            BrowserAction::SetReadmeCollapsed(collapsed) => self.layout.readme_collapsed = collapsed,
+            BrowserAction::SetArchived(archived) => {
+                let _ = self.action_tx.try_send(AppAction::SetArchived(repo_name.to_string(), archived));
+            }
+            BrowserAction::Delete => {
+                self.confirm_delete = Some((repo_name.to_string(), String::new()));
+            }
+            BrowserAction::EditTopics => {
+                let current = match &self.state {
+                    AppState::Browsing { repo_info: Some(info), .. } => info.topics.clone(),
+                    _ => Vec::new(),
+                };
+                self.topics_editor = Some(TopicsEditor::open(repo_name.to_string(), current, &self.action_tx));
+            }
+            BrowserAction::SetWatch(state) => {
+                let _ = self.action_tx.try_send(AppAction::SetWatchState(repo_name.to_string(), state));
+            }
+            BrowserAction::SwitchRef(git_ref) => {
+                // Reload the root of the tree at the chosen ref
+                self.selected_ref = git_ref;
+                if let Some(ref repo) = self.selected_repo {
+                    let _ = self.action_tx.try_send(AppAction::FetchDir(repo.clone(), String::new(), self.selected_ref.clone()));
+                }
+            }
+            BrowserAction::OpenFile(file) => {
+                // Files opened from the tree may live in another folder; make it current
+                let parent = super::file_browser::parent_path(&file.path);
+                if let Some(listing) = self.repo_tree.listing(&parent).cloned()
+                    && let AppState::Browsing { ref mut current_path, ref mut files, .. } = self.state
+                {
+                    *current_path = parent.clone();
+                    *files = listing;
+                }
+                self.history.visit(Location {
+                    repo: repo_name.to_string(),
+                    path: parent,
+                    file: Some(file.path.clone()),
+                });
+                let _ = self.action_tx.try_send(AppAction::ReadFile(repo_name.to_string(), file));
+            }
+            BrowserAction::SaveFile(filename) => {
+                if let Some(file) = files.iter().find(|f| f.name == filename) {
+                    self.save_file_as(repo_name, file.clone());
+                }
+            }
+            BrowserAction::LoadMore(filename) => {
+                if let (Some(file), Some(offset)) = (files.iter().find(|f| f.name == filename), self.file_rest) {
+                    let _ = self.action_tx.try_send(AppAction::ReadFileMore(repo_name.to_string(), file.clone(), offset));
+                }
+            }
+            BrowserAction::CloseViewer => {
+                self.history.visit(Location { repo: repo_name.to_string(), path: current_path.to_string(), file: None });
+                let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::ReadFile));
+                if let AppState::Browsing { ref mut viewing_code, .. } = self.state {
+                    *viewing_code = None;
+                }
+            }
+            BrowserAction::HistoryBack => {
+                if let Some(location) = self.history.back() {
+                    self.go_to(location);
+                }
+            }
+            BrowserAction::HistoryForward => {
+                if let Some(location) = self.history.forward() {
+                    self.go_to(location);
+                }
+            }
+        }
+    }
+    
+    /// Tab bar and active panel of the Issues/PRs dock
+    fn render_side_tabs(&mut self, ui: &mut egui::Ui) {
+        let active_tab = self.sidebar.active_tab;
+        // Tab buttons at the top
+        ui.horizontal_wrapped(|ui| {
+            if ui.selectable_label(active_tab == 0, 
+                egui::RichText::new("📋 Issues").color(if active_tab == 0 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 0;
+            }
+            if ui.selectable_label(active_tab == 1, 
+                egui::RichText::new("🔀 PRs").color(if active_tab == 1 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 1;
+            }
+            if ui.selectable_label(active_tab == 2, 
+                egui::RichText::new("📜 Commits").color(if active_tab == 2 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 2;
+            }
+            if ui.selectable_label(active_tab == 3, 
+                egui::RichText::new("🏷 Labels").color(if active_tab == 3 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 3;
+            }
+            if ui.selectable_label(active_tab == 4, 
+                egui::RichText::new("💬 Discussions").color(if active_tab == 4 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 4;
+            }
+            if ui.selectable_label(active_tab == 5, 
+                egui::RichText::new("🖥 Local").color(if active_tab == 5 { 
+                    super::style::colors::ACCENT 
+                } else { 
+                    egui::Color32::GRAY 
+                })
+            ).clicked() {
+                self.sidebar.active_tab = 5;
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                self.layout.dock_menu(ui, &self.i18n);
             });
+        });
+        
+        ui.separator();
+        
+        // Show the active panel
+        match active_tab {
+            0 => {
+                self.issues_panel.show(ui, &self.i18n, &mut self.markdown_cache, &self.labels_panel.labels);
+                if let Some(search) = self.issues_panel.save_request.take() {
+                    self.sidebar.add_saved(search);
+                }
+            }
+            1 => self.pr_panel.show(ui, &self.i18n),
+            2 => self.commits_panel.show(ui, &self.i18n),
+            3 => self.labels_panel.show(ui, &self.i18n),
+            4 => self.discussions_panel.show(ui, &self.i18n),
+            5 => match self.command_deck.show(ui, &self.i18n) {
+                Some(DeckAction::ShowIssues) => self.sidebar.active_tab = 0,
+                Some(DeckAction::Clone) => self.open_clone_dialog(),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+    
+    /// Ask where to save `file`, then download its raw bytes there
+    fn save_file_as(&mut self, full_name: &str, file: FileNode) {
+        #[cfg(not(target_os = "android"))]
+        let path = rfd::FileDialog::new()
+            .set_directory(default_download_dir())
+            .set_file_name(&file.name)
+            .save_file();
+        // No native dialog on Android; use the downloads directory
+        #[cfg(target_os = "android")]
+        let path = Some(crate::modules::paths::download_dir().join(&file.name));
+        
+        let Some(path) = path else { return };
+        self.download_progress = Some((Operation::SaveFile, file.name.clone(), 0, Some(file.size)));
+        let _ = self.action_tx.try_send(AppAction::SaveFile(
+            full_name.to_string(),
+            file,
+            path.display().to_string()
+        ));
     }
+    
+    fn render_download_dialog(&mut self, ctx: &egui::Context, full_name: &str) {
+        use super::retro_modal::RetroModal;
+        use super::components::CyberButton;
+        
+        let Some(dir) = &mut self.download_dir else { return };
+        let mut start = false;
+        let mut cancelled = false;
+        let ref_label = if self.selected_ref.is_empty() { self.i18n.t("app.default_branch") } else { self.selected_ref.as_str() };
+        
+        RetroModal::show(ctx, "⬇ DOWNLOAD ZIP", |ui| {
+            ui.label(format!("{} @ {}", full_name, ref_label));
+            ui.add_space(10.0);
+            ui.label(self.i18n.t("app.save_to"));
+            ui.add(egui::TextEdit::singleline(dir).desired_width(f32::INFINITY));
+            
+            ui.add_space(30.0);
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!dir.trim().is_empty(), |ui| {
+                    if CyberButton::new(self.i18n.t("app.start_download")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        start = true;
+                    }
+                });
+                ui.add_space(20.0);
+                if CyberButton::new(self.i18n.t("common.cancel")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+        
+        if start {
+            let _ = self.action_tx.try_send(AppAction::DownloadZipball(
+                full_name.to_string(),
+                self.selected_ref.clone(),
+                dir.trim().to_string()
+            ));
+            self.download_progress = Some((Operation::Download, full_name.to_string(), 0, None));
+        }
+        if start || cancelled {
+            self.download_dir = None;
+        }
+    }
+    
+    fn open_clone_dialog(&mut self) {
+        self.clone_dialog = Some(CloneDialog {
+            directory: default_clone_dir(),
+            tool: CloneTool::Git,
+            ssh: false,
+        });
+    }
+    
+    fn render_clone_dialog(&mut self, ctx: &egui::Context, full_name: &str) {
+        use super::retro_modal::RetroModal;
+        use super::components::CyberButton;
+        
+        let Some(dialog) = &mut self.clone_dialog else { return };
+        let mut start = false;
+        let mut cancelled = false;
+        let (https, ssh) = crate::config::current().clone_urls(full_name);
+        let name = full_name.rsplit('/').next().unwrap_or(full_name);
+        
+        RetroModal::show(ctx, "📥 CLONE REPOSITORY", |ui| {
+            ui.label(full_name);
+            ui.add_space(10.0);
+            ui.label(self.i18n.t("app.clone_to"));
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut dialog.directory).desired_width(ui.available_width() - 70.0));
+                #[cfg(not(target_os = "android"))]
+                if ui.button(self.i18n.t("common.browse")).clicked()
+                    && let Some(dir) = rfd::FileDialog::new().set_directory(&dialog.directory).pick_folder()
+                {
+                    dialog.directory = dir.display().to_string();
+                }
+            });
+            ui.label(egui::RichText::new(format!("→ {}", std::path::Path::new(dialog.directory.trim()).join(name).display()))
+                .size(11.0)
+                .color(egui::Color32::GRAY));
+            
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.label(self.i18n.t("app.clone_with"));
+                ui.radio_value(&mut dialog.tool, CloneTool::Git, "git");
+                ui.radio_value(&mut dialog.tool, CloneTool::Gh, "gh");
+            });
+            if dialog.tool == CloneTool::Git {
+                ui.horizontal(|ui| {
+                    ui.label(self.i18n.t("app.protocol"));
+                    ui.radio_value(&mut dialog.ssh, false, "HTTPS");
+                    ui.radio_value(&mut dialog.ssh, true, "SSH");
+                });
+            } else {
+                ui.label(egui::RichText::new(self.i18n.t("app.gh_clone_hint")).size(11.0).color(egui::Color32::GRAY));
+            }
+            
+            ui.add_space(30.0);
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!dialog.directory.trim().is_empty(), |ui| {
+                    if CyberButton::new(self.i18n.t("app.start_clone")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        start = true;
+                    }
+                });
+                ui.add_space(20.0);
+                if CyberButton::new(self.i18n.t("common.cancel")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+        
+        if start {
+            let url = if dialog.ssh { ssh } else { https };
+            let _ = self.action_tx.try_send(AppAction::CloneRepo(
+                full_name.to_string(),
+                url,
+                dialog.directory.trim().to_string(),
+                dialog.tool
+            ));
+        }
+        if start || cancelled {
+            self.clone_dialog = None;
+        }
+    }
+    
+    /// Deletion requires typing the full repo name, like the GitHub web UI
+    fn render_delete_confirm(&mut self, ctx: &egui::Context) {
+        use super::retro_modal::RetroModal;
+        use super::components::CyberButton;
+        
+        let Some((full_name, typed)) = &mut self.confirm_delete else { return };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        
+        RetroModal::show(ctx, "🗑 DELETE REPOSITORY", |ui| {
+            ui.label(egui::RichText::new(self.i18n.t("app.delete_warning"))
+                .color(egui::Color32::from_rgb(255, 80, 80)));
+            ui.add_space(10.0);
+            ui.label(self.i18n.t("app.delete_type_name"));
+            ui.label(egui::RichText::new(full_name.as_str())
+                .font(egui::FontId::monospace(16.0))
+                .color(egui::Color32::from_rgb(0, 240, 255)));
+            ui.add_space(6.0);
+            ui.add(egui::TextEdit::singleline(typed).desired_width(f32::INFINITY));
+            
+            ui.add_space(20.0);
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(typed == full_name, |ui| {
+                    if CyberButton::new(self.i18n.t("app.delete_forever")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        confirmed = true;
+                    }
+                });
+                ui.add_space(20.0);
+                if CyberButton::new(self.i18n.t("common.cancel")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+        
+        if confirmed {
+            let _ = self.action_tx.try_send(AppAction::DeleteRepo(full_name.clone()));
+        }
+        if confirmed || cancelled {
+            self.confirm_delete = None;
+        }
+    }
+    
+    fn render_fork_confirm(&mut self, ctx: &egui::Context, full_name: &str) {
+        use super::retro_modal::RetroModal;
+        use super::components::CyberButton;
+        
+        RetroModal::show(ctx, "🍴 FORK REPOSITORY", |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(20.0);
+                ui.label(self.i18n.t("app.fork_prompt"));
+                ui.add_space(10.0);
+                ui.label(egui::RichText::new(full_name)
+                    .font(egui::FontId::monospace(18.0))
+                    .color(egui::Color32::from_rgb(0, 240, 255)));
+                
+                ui.add_space(40.0);
+                ui.horizontal(|ui| {
+                    ui.add_space(40.0);
+                    if CyberButton::new(self.i18n.t("app.confirm_fork")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        let _ = self.action_tx.try_send(AppAction::ForkRepo(full_name.to_string()));
+                        self.confirm_fork = None;
+                    }
+                    ui.add_space(20.0);
+                    if CyberButton::new(self.i18n.t("common.cancel")).min_size(egui::Vec2::new(140.0, 40.0)).show(ui).clicked() {
+                        self.confirm_fork = None;
+                    }
+                });
+            });
+        });
+    }
+}
+
+/// Where clones go by default: the home directory
+fn default_clone_dir() -> String {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_default()
+}
+
+/// Default target directory for downloads, as text for the dialog
+fn default_download_dir() -> String {
+    crate::modules::paths::download_dir().display().to_string()
 }
@@ -0,0 +1,61 @@
+//! Avatar Cache
+//!
+//! The same user's avatar turns up in issue lists, comments, pull requests
+//! and search results, often under different URLs (`?v=4`, `login.png`).
+//! Textures are kept here by login, so each avatar is fetched and decoded
+//! once and then shared by every panel for the rest of the session.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use eframe::egui::{self, load::BytesPoll};
+
+/// Avatars are never shown larger than this many pixels; bigger ones are scaled down on decode
+const MAX_AVATAR_PIXELS: u32 = 96;
+
+/// Avatar textures by login, cheap to clone. Lives in `AppContext`.
+#[derive(Clone, Default)]
+pub struct AvatarCache {
+    textures: Arc<Mutex<HashMap<String, Option<egui::TextureHandle>>>>, // None if it couldn't be decoded
+}
+
+impl AvatarCache {
+    /// Make the cache reachable from widgets through `AvatarCache::of`
+    pub fn install(&self, ctx: &egui::Context) {
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new("avatar_cache"), self.clone()));
+    }
+
+    /// The cache installed on `ctx`
+    pub fn of(ctx: &egui::Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp(egui::Id::new("avatar_cache")))
+    }
+
+    /// Texture of `login`'s avatar, downloading it from `url` the first time.
+    /// None while it is still loading or if it couldn't be loaded.
+    pub fn texture(&self, ctx: &egui::Context, login: &str, url: &str) -> Option<egui::TextureHandle> {
+        if let Some(texture) = self.textures.lock().unwrap().get(login) {
+            return texture.clone();
+        }
+
+        // The HTTP loader dedupes the download and caches it on disk
+        let Ok(BytesPoll::Ready { bytes, .. }) = ctx.try_load_bytes(url) else { return None };
+        let texture = decode(&bytes)
+            .map(|image| ctx.load_texture(format!("avatar/{}", login), image, egui::TextureOptions::LINEAR));
+        // Only the texture is kept; drop the encoded copy from the loader's memory
+        ctx.forget_image(url);
+        self.textures.lock().unwrap().insert(login.to_string(), texture.clone());
+        texture
+    }
+}
+
+fn decode(bytes: &[u8]) -> Option<egui::ColorImage> {
+    let image = image::load_from_memory(bytes)
+        .inspect_err(|e| tracing::warn!("Failed to decode avatar: {}", e))
+        .ok()?;
+    let image = if image.width() > MAX_AVATAR_PIXELS || image.height() > MAX_AVATAR_PIXELS {
+        image.thumbnail(MAX_AVATAR_PIXELS, MAX_AVATAR_PIXELS)
+    } else {
+        image
+    };
+    let rgba = image.to_rgba8();
+    Some(egui::ColorImage::from_rgba_unmultiplied([rgba.width() as usize, rgba.height() as usize], rgba.as_raw()))
+}
@@ -0,0 +1,292 @@
+//! Code Viewer UI Component
+//!
+//! Read-only monospace view of an open file with a Ctrl+F find bar that
+//! highlights every match and steps through them. Word wrap, whitespace
+//! markers and tab width are toolbar toggles kept for the session.
+
+use std::ops::Range;
+use eframe::egui::{self, Color32, RichText, ScrollArea, TextFormat};
+
+use super::style::colors;
+use crate::i18n::I18n;
+
+const MATCH_BG: Color32 = Color32::from_rgba_premultiplied(90, 80, 0, 160);
+const CURRENT_BG: Color32 = Color32::from_rgb(255, 140, 0);
+const TAB_WIDTHS: [usize; 3] = [2, 4, 8];
+
+/// Viewer state that outlives a single frame (find bar, permalink lines, display toggles)
+pub struct CodeViewer {
+    wrap: bool,
+    show_whitespace: bool,
+    tab_width: usize,
+    find_open: bool,
+    focus_find: bool,
+    query: String,
+    matches: Vec<Range<usize>>, // Byte ranges into the content
+    current: usize,
+    matched_for: Option<(String, usize, String)>, // (filename, content length, query) of `matches`
+    scroll_to_current: bool,
+    line_range: Option<(usize, usize)>, // 1-based, inclusive; appended to copied permalinks
+}
+
+impl Default for CodeViewer {
+    fn default() -> Self {
+        Self {
+            wrap: false,
+            show_whitespace: false,
+            tab_width: 4,
+            find_open: false,
+            focus_find: false,
+            query: String::new(),
+            matches: Vec::new(),
+            current: 0,
+            matched_for: None,
+            scroll_to_current: false,
+            line_range: None,
+        }
+    }
+}
+
+impl CodeViewer {
+    /// Show the find bar and focus its input
+    pub fn open_find(&mut self) {
+        self.find_open = true;
+        self.focus_find = true;
+    }
+    
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n, filename: &str, content: &str) {
+        if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F)) {
+            self.open_find();
+        }
+        
+        self.show_toolbar(ui, i18n);
+        if self.find_open {
+            self.show_find_bar(ui, i18n);
+        }
+        ui.separator();
+        self.update_matches(filename, content);
+        
+        let scroll = if self.wrap { ScrollArea::vertical() } else { ScrollArea::both() };
+        scroll.id_salt("code_viewer").show(ui, |ui| {
+            let (job, current_char) = self.layout(ui, content);
+            let galley = ui.painter().layout_job(job);
+            let response = ui.label(galley.clone());
+            
+            if std::mem::take(&mut self.scroll_to_current)
+                && let Some(index) = current_char
+            {
+                let rect = galley.pos_from_cursor(egui::text::CCursor::new(index))
+                    .translate(response.rect.min.to_vec2());
+                ui.scroll_to_rect(rect, Some(egui::Align::Center));
+            }
+        });
+    }
+    
+    fn show_toolbar(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.horizontal(|ui| {
+            ui.toggle_value(&mut self.wrap, i18n.t("code.wrap"));
+            ui.toggle_value(&mut self.show_whitespace, i18n.t("code.whitespace"));
+            egui::ComboBox::from_id_salt("tab_width")
+                .selected_text(format!("Tab: {}", self.tab_width))
+                .show_ui(ui, |ui| {
+                    for width in TAB_WIDTHS {
+                        ui.selectable_value(&mut self.tab_width, width, width.to_string());
+                    }
+                });
+        });
+    }
+    
+    /// Header menu copying the content, the raw URL or a permalink
+    pub fn copy_menu(&mut self, ui: &mut egui::Ui, i18n: &I18n, content: &str, raw_url: &str, blob_url: &str) {
+        ui.menu_button(i18n.t("code.copy_menu"), |ui| {
+            if ui.button(i18n.t("code.copy_content")).clicked() {
+                ui.ctx().copy_text(content.to_string());
+                ui.close();
+            }
+            if ui.button(i18n.t("code.copy_raw_url")).clicked() {
+                ui.ctx().copy_text(raw_url.to_string());
+                ui.close();
+            }
+            
+            ui.separator();
+            
+            let line_count = content.lines().count().max(1);
+            let mut with_lines = self.line_range.is_some();
+            if ui.checkbox(&mut with_lines, i18n.t("code.link_lines")).changed() {
+                self.line_range = with_lines.then_some((1, 1));
+            }
+            if let Some((start, end)) = &mut self.line_range {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(start).range(1..=line_count).prefix("L"));
+                    ui.label("–");
+                    ui.add(egui::DragValue::new(end).range(*start..=line_count).prefix("L"));
+                });
+                *end = (*end).max(*start);
+            }
+            if ui.button(i18n.t("code.copy_permalink")).clicked() {
+                let anchor = match self.line_range {
+                    Some((start, end)) if end > start => format!("#L{}-L{}", start, end),
+                    Some((start, _)) => format!("#L{}", start),
+                    None => String::new(),
+                };
+                ui.ctx().copy_text(format!("{}{}", blob_url, anchor));
+                ui.close();
+            }
+        });
+    }
+    
+    fn show_find_bar(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("🔍").color(colors::ACCENT));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text(i18n.t("code.find_hint"))
+                    .desired_width(220.0)
+            );
+            if std::mem::take(&mut self.focus_find) {
+                response.request_focus();
+            }
+            
+            // Enter / Shift+Enter step through matches without leaving the input
+            let (enter, shift, escape) = ui.input(|i| (i.key_pressed(egui::Key::Enter), i.modifiers.shift, i.key_pressed(egui::Key::Escape)));
+            if response.lost_focus() && enter {
+                self.step(if shift { -1 } else { 1 });
+                response.request_focus();
+            }
+            
+            let count = if self.matches.is_empty() {
+                if self.query.is_empty() { String::new() } else { i18n.t("code.no_matches").to_string() }
+            } else {
+                format!("{}/{}", self.current + 1, self.matches.len())
+            };
+            ui.label(RichText::new(count).size(11.0).color(Color32::GRAY));
+            
+            let has_matches = !self.matches.is_empty();
+            if ui.add_enabled(has_matches, egui::Button::new("▲")).on_hover_text(i18n.t("code.prev_match")).clicked() {
+                self.step(-1);
+            }
+            if ui.add_enabled(has_matches, egui::Button::new("▼")).on_hover_text(i18n.t("code.next_match")).clicked() {
+                self.step(1);
+            }
+            if ui.small_button("✕").clicked() || escape {
+                self.find_open = false;
+            }
+        });
+    }
+    
+    /// Recompute matches when the file or the query changed (ASCII case-insensitive)
+    fn update_matches(&mut self, filename: &str, content: &str) {
+        let key = (filename.to_string(), content.len(), self.query.clone());
+        if self.matched_for.as_ref() == Some(&key) {
+            return;
+        }
+        
+        self.matches.clear();
+        self.current = 0;
+        if !self.query.is_empty() {
+            // ASCII lowercasing keeps byte offsets valid for the original text
+            let haystack = content.to_ascii_lowercase();
+            let needle = self.query.to_ascii_lowercase();
+            self.matches = haystack.match_indices(&needle)
+                .map(|(start, m)| start..start + m.len())
+                .collect();
+        }
+        self.scroll_to_current = !self.matches.is_empty();
+        self.matched_for = Some(key);
+    }
+    
+    fn step(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let len = self.matches.len() as isize;
+        self.current = (self.current as isize + delta).rem_euclid(len) as usize;
+        self.scroll_to_current = true;
+    }
+    
+    /// Layout of the content with the display toggles applied and, while the
+    /// find bar is open, every match highlighted. Also returns the char index of
+    /// the current match in the laid-out text, for scrolling.
+    fn layout(&self, ui: &egui::Ui, content: &str) -> (egui::text::LayoutJob, Option<usize>) {
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let plain = TextFormat::simple(font_id.clone(), ui.visuals().text_color());
+        
+        let mut job = egui::text::LayoutJob::default();
+        job.wrap.max_width = if self.wrap { ui.available_width() } else { f32::INFINITY };
+        
+        let mut column = 0;
+        let mut last = 0;
+        let mut current_char = None;
+        let matches = if self.find_open { self.matches.as_slice() } else { &[] };
+        for (i, range) in matches.iter().enumerate() {
+            job.append(&self.display(&content[last..range.start], &mut column), 0.0, plain.clone());
+            if i == self.current {
+                current_char = Some(job.text.chars().count());
+            }
+            let background = if i == self.current { CURRENT_BG } else { MATCH_BG };
+            let color = if i == self.current { Color32::BLACK } else { Color32::WHITE };
+            job.append(&self.display(&content[range.clone()], &mut column), 0.0, TextFormat {
+                background,
+                ..TextFormat::simple(font_id.clone(), color)
+            });
+            last = range.end;
+        }
+        job.append(&self.display(&content[last..], &mut column), 0.0, plain);
+        (job, current_char)
+    }
+    
+    /// Expand tabs to the next tab stop and mark whitespace if enabled.
+    /// `column` carries the position on the current line across segments.
+    fn display(&self, text: &str, column: &mut usize) -> String {
+        let mut out = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match ch {
+                '\n' => {
+                    out.push('\n');
+                    *column = 0;
+                }
+                '\t' => {
+                    let width = self.tab_width - *column % self.tab_width;
+                    if self.show_whitespace {
+                        out.push('→');
+                        out.extend(std::iter::repeat_n(' ', width - 1));
+                    } else {
+                        out.extend(std::iter::repeat_n(' ', width));
+                    }
+                    *column += width;
+                }
+                ' ' if self.show_whitespace => {
+                    out.push('·');
+                    *column += 1;
+                }
+                '\r' => {}
+                _ => {
+                    out.push(ch);
+                    *column += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+/// `hexdump -C` style listing: offset, 16 bytes in hex, then the printable ones
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        out.push_str(&format!("{:08x} ", row * 16));
+        for i in 0..16 {
+            if i % 8 == 0 {
+                out.push(' ');
+            }
+            match chunk.get(i) {
+                Some(byte) => out.push_str(&format!("{:02x} ", byte)),
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str(" |");
+        out.extend(chunk.iter().map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' }));
+        out.push_str("|\n");
+    }
+    out
+}
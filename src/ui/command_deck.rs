@@ -1,47 +1,184 @@
+//! Command Deck UI Component
+//!
+//! Big-button panel for git operations on the browsed repo's local checkout.
+//! The checkout directory is remembered per repo in the config file; output
+//! of the operations streams into the log viewer.
+
 use eframe::egui::{self, Color32, RichText, Stroke, Ui, Vec2};
+use crate::app_event::{AppAction, LocalGitOp};
+use crate::i18n::I18n;
+use crate::modules::local_git;
+use tokio::sync::mpsc::Sender;
+
+/// What the deck asks the app to do besides git operations
+pub enum DeckAction {
+    ShowIssues,
+    Clone, // Open the clone dialog for the repo
+}
 
-pub struct CommandDeck;
+pub struct CommandDeck {
+    repo: String,
+    directory: String,           // Checkout path being edited / in use
+    editing: bool,               // Path editor open
+    busy: Option<LocalGitOp>,    // Operation in flight
+    action_tx: Sender<AppAction>,
+}
 
 impl CommandDeck {
-    pub fn new() -> Self {
-        Self
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            repo: String::new(),
+            directory: String::new(),
+            editing: false,
+            busy: None,
+            action_tx,
+        }
     }
-
-    pub fn show(&self, ui: &mut Ui) {
+    
+    /// Switch to another repo and load its configured checkout
+    pub fn set_repo(&mut self, repo: &str) {
+        if self.repo != repo {
+            self.repo = repo.to_string();
+            self.directory = crate::config::current().local_checkouts.get(repo)
+                .map(|path| path.display().to_string())
+                .unwrap_or_default();
+            self.editing = self.directory.is_empty();
+            self.busy = None;
+        }
+    }
+    
+    /// A git operation ended (or was cancelled)
+    pub fn finish(&mut self, repo: &str) {
+        if self.repo == repo {
+            self.busy = None;
+        }
+    }
+    
+    /// Use a fresh clone as the checkout of its repo unless one is configured already
+    pub fn adopt_checkout(&mut self, repo: &str, directory: String) {
+        let mut config = crate::config::current();
+        if config.local_checkouts.contains_key(repo) {
+            return;
+        }
+        config.local_checkouts.insert(repo.to_string(), directory.clone().into());
+        if let Err(e) = crate::config::update(config) {
+            tracing::warn!("Failed to save local checkout: {}", e);
+        }
+        if self.repo == repo {
+            self.directory = directory;
+            self.editing = false;
+        }
+    }
+    
+    pub fn cancelled(&mut self) {
+        self.busy = None;
+    }
+    
+    pub fn show(&mut self, ui: &mut Ui, i18n: &I18n) -> Option<DeckAction> {
+        let mut action = None;
+        let is_checkout = !self.directory.is_empty() && local_git::is_checkout(std::path::Path::new(&self.directory));
+        
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             
             // Status Header
-            ui.label(RichText::new("STATUS: ONLINE").color(Color32::GREEN));
+            let (status, color) = match (self.busy, is_checkout) {
+                (Some(op), _) => (format!("STATUS: RUNNING {}", op.label().to_uppercase()), Color32::from_rgb(255, 200, 0)),
+                (None, true) => ("STATUS: ONLINE".to_string(), Color32::GREEN),
+                (None, false) => ("STATUS: NO LOCAL CHECKOUT".to_string(), Color32::GRAY),
+            };
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(status).color(color));
+                if self.busy.is_some() {
+                    ui.spinner();
+                }
+            });
+            if !self.editing {
+                ui.label(RichText::new(&self.directory).size(11.0).color(Color32::GRAY).monospace());
+            }
             ui.add_space(10.0);
             
+            if self.editing {
+                self.show_path_editor(ui, i18n, &mut action);
+                ui.add_space(10.0);
+            }
+            
             // Action Grid
+            let ready = is_checkout && self.busy.is_none();
             egui::Grid::new("command_deck_grid")
                 .spacing(Vec2::new(10.0, 10.0))
                 .show(ui, |ui| {
-                    if self.action_btn(ui, "⚡ CONNECT", true).clicked() {
-                        // Action
+                    if self.action_btn(ui, "📥 PULL", ready).clicked() {
+                        self.run(LocalGitOp::Pull);
                     }
-                    if self.action_btn(ui, "📥 PULL", true).clicked() {
-                         // Action
+                    if self.action_btn(ui, "📤 PUSH", ready).clicked() {
+                        self.run(LocalGitOp::Push);
                     }
-                    if self.action_btn(ui, "📤 PUSH", false).clicked() {
-                         // Action
+                    if self.action_btn(ui, "🔄 SYNC", ready).on_hover_text(i18n.t("deck.sync_hint")).clicked() {
+                        self.run(LocalGitOp::Sync);
                     }
                     ui.end_row();
                     
-                    if self.action_btn(ui, "🔄 SYNC", true).clicked() {
-                         // Action
+                    if self.action_btn(ui, "📋 STATUS", ready).clicked() {
+                        self.run(LocalGitOp::Status);
                     }
                     if self.action_btn(ui, "🔎 ISSUES", true).clicked() {
-                         // Action
+                        action = Some(DeckAction::ShowIssues);
                     }
-                    if self.action_btn(ui, "🔧 CONFIG", true).clicked() {
-                         // Action
+                    if self.action_btn(ui, "🔧 CONFIG", self.busy.is_none()).clicked() {
+                        self.editing = !self.editing;
                     }
                     ui.end_row();
                 });
         });
+        
+        action
+    }
+    
+    fn show_path_editor(&mut self, ui: &mut Ui, i18n: &I18n, action: &mut Option<DeckAction>) {
+        ui.label(RichText::new(i18n.t("deck.local_dir")).size(12.0).color(Color32::GRAY));
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.directory)
+                .hint_text("/path/to/checkout")
+                .desired_width(ui.available_width() - 140.0));
+            #[cfg(not(target_os = "android"))]
+            if ui.button(i18n.t("common.browse")).clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.directory = dir.display().to_string();
+            }
+            if ui.button(i18n.t("common.save")).clicked() {
+                self.save_directory();
+            }
+        });
+        
+        let path = std::path::Path::new(self.directory.trim());
+        if !self.directory.trim().is_empty() && !local_git::is_checkout(path) {
+            ui.label(RichText::new(i18n.t("deck.not_checkout")).size(11.0).color(Color32::from_rgb(255, 140, 0)));
+        }
+        if ui.small_button(i18n.t("deck.clone_local")).clicked() {
+            *action = Some(DeckAction::Clone);
+        }
+    }
+    
+    /// Remember the edited directory for this repo (empty = forget it)
+    fn save_directory(&mut self) {
+        self.directory = self.directory.trim().to_string();
+        let mut config = crate::config::current();
+        if self.directory.is_empty() {
+            config.local_checkouts.remove(&self.repo);
+        } else {
+            config.local_checkouts.insert(self.repo.clone(), self.directory.clone().into());
+            self.editing = false;
+        }
+        if let Err(e) = crate::config::update(config) {
+            tracing::warn!("Failed to save local checkout: {}", e);
+        }
+    }
+    
+    fn run(&mut self, op: LocalGitOp) {
+        self.busy = Some(op);
+        let _ = self.action_tx.try_send(AppAction::RunLocalGit(self.repo.clone(), self.directory.clone(), op));
     }
     
     fn action_btn(&self, ui: &mut Ui, text: &str, enabled: bool) -> egui::Response {
@@ -54,7 +191,7 @@ impl CommandDeck {
             .stroke(Stroke::new(1.5, color))
             .fill(Color32::from_black_alpha(150));
             // .rounding(0.0) // Sharp corners for that Sci-Fi look
-            
+        
         ui.add_enabled(enabled, btn)
     }
 }
@@ -0,0 +1,246 @@
+//! Commits UI Component
+//!
+//! Displays the commit history of a repository with paging and a detail view
+//! showing the per-file diffs of a commit.
+
+use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
+use crate::app_event::{AppAction, Commit, CommitDetail, RequestId, next_request_id};
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+
+use super::style::colors;
+use super::components::{CyberButton, timestamp};
+use super::diff_view::render_changed_file;
+
+/// Commits panel - displays commit history for a repository
+pub struct CommitsPanel {
+    pub commits: Vec<Commit>,
+    pub loading: bool,
+    pub current_repo: String,
+    request: RequestId, // Latest list request; other responses are stale
+    pub branch: String, // "" = default branch
+    pub page: u32,
+
+    // Detail view
+    pub selected_commit: Option<Commit>,
+    pub detail: Option<CommitDetail>,
+    pub loading_detail: bool,
+
+    action_tx: Sender<AppAction>,
+}
+
+impl CommitsPanel {
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            commits: Vec::new(),
+            loading: false,
+            current_repo: String::new(),
+            request: 0,
+            branch: String::new(),
+            page: 1,
+            selected_commit: None,
+            detail: None,
+            loading_detail: false,
+            action_tx,
+        }
+    }
+
+    pub fn set_repo(&mut self, repo: String) {
+        if self.current_repo != repo {
+            self.current_repo = repo;
+            self.branch.clear();
+            self.commits.clear();
+            self.selected_commit = None;
+            self.fetch_page(1);
+        }
+    }
+
+    pub fn set_commits(&mut self, request: RequestId, commits: Vec<Commit>) {
+        if request == self.request {
+            self.commits = commits;
+            self.loading = false;
+        }
+    }
+
+    /// Store the loaded detail if it belongs to the selected commit
+    pub fn set_detail(&mut self, detail: CommitDetail) {
+        if let Some(ref commit) = self.selected_commit
+            && commit.sha == detail.sha
+        {
+            self.detail = Some(detail);
+            self.loading_detail = false;
+        }
+    }
+
+    fn fetch_page(&mut self, page: u32) {
+        self.page = page;
+        self.loading = true;
+        self.request = next_request_id();
+        let _ = self.action_tx.try_send(AppAction::FetchCommits(
+            self.request,
+            self.current_repo.clone(),
+            self.branch.clone(),
+            page
+        ));
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        if self.selected_commit.is_some() {
+            self.show_detail(ui, i18n);
+        } else {
+            self.show_list(ui, i18n);
+        }
+    }
+
+    fn show_list(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.vertical(|ui| {
+            // Header
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("📜 Commits").size(18.0).color(colors::ACCENT).strong());
+
+                if self.loading {
+                    ui.spinner();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    // A full page means there may be more history
+                    let has_next = self.commits.len() >= 30;
+                    if ui.add_enabled(has_next && !self.loading, egui::Button::new(i18n.t("common.next_page"))).clicked() {
+                        self.fetch_page(self.page + 1);
+                    }
+                    ui.label(RichText::new(format!("{}", self.page)).size(12.0).color(Color32::GRAY));
+                    if ui.add_enabled(self.page > 1 && !self.loading, egui::Button::new(i18n.t("common.prev_page"))).clicked() {
+                        self.fetch_page(self.page - 1);
+                    }
+                });
+            });
+
+            ui.separator();
+
+            // Commit list
+            ScrollArea::vertical().id_salt("commits_list").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+
+                if self.commits.is_empty() && !self.loading {
+                    ui.colored_label(Color32::GRAY, i18n.t("commits.empty"));
+                }
+
+                for commit in &self.commits {
+                    if self.render_commit_card(ui, commit, i18n) {
+                        self.selected_commit = Some(commit.clone());
+                        self.detail = None;
+                        self.loading_detail = true;
+                        let _ = self.action_tx.try_send(AppAction::FetchCommitDetail(
+                            self.current_repo.clone(),
+                            commit.sha.clone()
+                        ));
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+        });
+    }
+
+    fn render_commit_card(&self, ui: &mut egui::Ui, commit: &Commit, i18n: &I18n) -> bool {
+        let h = 50.0;
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+        super::components::describe(ui, &response, &format!("Commit {}: {}", commit.short_sha(), commit.commit.message.lines().next().unwrap_or_default()));
+
+        let painter = ui.painter();
+        let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+
+        let bg_color = if is_hovered {
+            Color32::from_rgba_unmultiplied(0, 50, 60, 180)
+        } else {
+            Color32::from_rgb(8, 12, 18)
+        };
+
+        // Background
+        painter.rect_filled(rect, 4.0, bg_color);
+
+        // Status strip
+        let strip_rect = egui::Rect::from_min_size(rect.min, Vec2::new(3.0, rect.height()));
+        painter.rect_filled(strip_rect, 0.0, colors::ACCENT_DIM);
+
+        // Border
+        painter.rect_stroke(rect, 4.0, Stroke::new(1.0, if is_hovered { colors::ACCENT } else { Color32::from_rgb(0, 60, 60) }), egui::StrokeKind::Middle);
+
+        // Content
+        let content_rect = rect.shrink2(Vec2::new(12.0, 6.0));
+        ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+            ui.vertical(|ui| {
+                // Message summary
+                ui.label(RichText::new(commit.summary()).size(13.0).color(Color32::WHITE).strong());
+
+                // SHA, author, date
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(commit.short_sha()).size(10.0).color(colors::ACCENT).monospace());
+                    ui.label(RichText::new(commit.author_name()).size(10.0).color(Color32::GRAY));
+                    timestamp(ui, i18n, &commit.commit.author.date);
+                });
+            });
+        });
+
+        response.clicked()
+    }
+
+    fn show_detail(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        let commit = self.selected_commit.clone().unwrap();
+
+        ui.vertical(|ui| {
+            // Back button + title
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.back")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
+                    self.selected_commit = None;
+                    self.detail = None;
+                }
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(commit.short_sha()).size(16.0).color(colors::ACCENT).monospace().strong());
+            });
+
+            ui.separator();
+
+            ScrollArea::vertical().id_salt("commit_detail").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(commit.author_name()).size(12.0).color(colors::ACCENT_DIM));
+                        timestamp(ui, i18n, &commit.commit.author.date);
+                    });
+                    ui.separator();
+                    ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
+                    ui.label(&commit.commit.message);
+                });
+
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(&commit.sha).size(11.0).color(Color32::GRAY).monospace());
+                ui.hyperlink_to(i18n.t("common.view_on_github"), &commit.html_url);
+
+                ui.add_space(10.0);
+
+                // Changed files
+                if self.loading_detail {
+                    ui.spinner();
+                } else if let Some(detail) = &self.detail {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("📁 {} files changed", detail.files.len())).size(12.0));
+                        ui.add_space(20.0);
+                        ui.label(RichText::new(format!("+{}", detail.stats.additions)).size(12.0).color(Color32::from_rgb(100, 200, 100)));
+                        ui.label(RichText::new(format!("-{}", detail.stats.deletions)).size(12.0).color(Color32::from_rgb(200, 100, 100)));
+                    });
+                    ui.separator();
+
+                    for file in &detail.files {
+                        render_changed_file(ui, i18n, file);
+                    }
+                }
+            });
+        });
+    }
+}
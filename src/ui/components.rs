@@ -1,10 +1,16 @@
 //! Custom Cyberpunk UI Components
 //!
 //! CyberButton: A button with "Tactical Corner Brackets" instead of a filled rectangle.
-//! CyberFrame: A container wrapper with corner brackets and semi-transparent background.
-//! SystemStatusBar: HUD-style status bar with fake metrics.
+//! SystemStatusBar: HUD-style status bar with process and API metrics.
+//! avatar: Circular user avatar, decoded once per login and shared by every panel.
+//! row / leading / trailing / column: Layouts mirrored for right-to-left languages.
+//! pull_to_refresh: Touch-screen pull-down-to-reload around a scrolled list.
 
 use eframe::egui::{self, Color32, Pos2, Response, RichText, Sense, Stroke, Ui, Vec2};
+use std::sync::atomic::Ordering;
+use crate::context::AppContext;
+use super::avatar_cache::AvatarCache;
+use crate::i18n::I18n;
 use super::style::colors;
 
 /// A button with tactical corner brackets (sci-fi style)
@@ -29,6 +35,9 @@ impl CyberButton {
     pub fn show(self, ui: &mut Ui) -> Response {
         let desired_size = self.min_size;
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        describe(ui, &response, &self.text);
+        // Keyboard focus is drawn like hover
+        let highlighted = response.hovered() || response.has_focus();
         
         if ui.is_rect_visible(rect) {
             let painter = ui.painter();
@@ -37,9 +46,11 @@ impl CyberButton {
             let (text_color, bg_color, border_color) = if response.is_pointer_button_down_on() {
                 // Active: Black on Cyan
                 (colors::BG_DARK, colors::ACCENT, colors::ACCENT)
-            } else if response.hovered() {
+            } else if highlighted {
                 // Hovered: Cyan glow effect
-                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                if response.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                }
                 (colors::ACCENT, Color32::from_rgba_unmultiplied(0, 60, 80, 150), colors::ACCENT)
             } else {
                 // Inactive: Cyan on transparent
@@ -52,7 +63,7 @@ impl CyberButton {
             }
             
             // Draw tactical corner brackets
-            draw_corner_brackets(painter, rect, border_color, response.hovered());
+            draw_corner_brackets(painter, rect, border_color, highlighted);
             
             // Draw text
             painter.text(
@@ -68,6 +79,160 @@ impl CyberButton {
     }
 }
 
+/// Give a custom-painted clickable widget an accessible name. Without it
+/// AccessKit exposes only an anonymous rect to screen readers.
+pub fn describe(ui: &Ui, response: &Response, label: &str) {
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, ui.is_enabled(), label));
+}
+
+/// Seconds the pointer has to rest on a widget before `hover_intent` fires
+const HOVER_INTENT_DELAY: f64 = 0.3;
+
+/// True once per hover, when the pointer has rested on `response` for a
+/// moment: a good time to prefetch what clicking it would load
+pub fn hover_intent(ui: &Ui, response: &Response) -> bool {
+    let id = response.id.with("hover_intent");
+    if !response.hovered() {
+        ui.data_mut(|d| d.remove::<Option<f64>>(id));
+        return false;
+    }
+    // Some(start of the hover) until it fired, then None
+    let now = ui.input(|i| i.time);
+    match ui.data_mut(|d| *d.get_temp_mut_or(id, Some(now))) {
+        Some(start) if now - start >= HOVER_INTENT_DELAY => {
+            ui.data_mut(|d| d.insert_temp(id, None::<f64>));
+            true
+        }
+        // A resting pointer sends no events, so ask for the frame that fires it
+        Some(start) => {
+            ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(HOVER_INTENT_DELAY - (now - start)));
+            false
+        }
+        None => false,
+    }
+}
+
+/// Relative time ("3 hours ago") of a GitHub timestamp, full local time on hover
+pub fn timestamp(ui: &mut Ui, i18n: &I18n, iso: &str) -> Response {
+    ui.label(RichText::new(i18n.relative_time(iso)).size(10.0).color(Color32::DARK_GRAY))
+        .on_hover_text(i18n.local_time(iso))
+}
+
+/// Row that runs in reading order: left to right, mirrored for RTL languages.
+/// Like `ui.horizontal`, it is only one widget tall.
+pub fn row<R>(ui: &mut Ui, rtl: bool, add_contents: impl FnOnce(&mut Ui) -> R) -> egui::InnerResponse<R> {
+    let size = Vec2::new(ui.available_width(), ui.spacing().interact_size.y);
+    ui.allocate_ui_with_layout(size, leading(rtl), add_contents)
+}
+
+/// Horizontal layout starting at the reading-order start
+pub fn leading(rtl: bool) -> egui::Layout {
+    if rtl {
+        egui::Layout::right_to_left(egui::Align::Center)
+    } else {
+        egui::Layout::left_to_right(egui::Align::Center)
+    }
+}
+
+/// Horizontal layout packed against the reading-order end (counts, ✕ buttons)
+pub fn trailing(rtl: bool) -> egui::Layout {
+    leading(!rtl)
+}
+
+/// Vertical layout with text aligned to the reading-order start
+pub fn column(rtl: bool) -> egui::Layout {
+    egui::Layout::top_down(if rtl { egui::Align::Max } else { egui::Align::Min })
+}
+
+/// `width`-wide strip along the reading-order start edge of a card
+pub fn leading_edge(rect: egui::Rect, rtl: bool, width: f32) -> egui::Rect {
+    if rtl {
+        egui::Rect::from_min_max(Pos2::new(rect.right() - width, rect.top()), rect.max)
+    } else {
+        egui::Rect::from_min_size(rect.min, Vec2::new(width, rect.height()))
+    }
+}
+
+/// How far a list has to be pulled down before letting go reloads it
+const PULL_DISTANCE: f32 = 70.0;
+
+/// Vertically scrolled list that can be pulled down to reload on touch
+/// screens. While pulling, an arc fills up above the list; letting go once it
+/// is full returns true, and a spinner stays there while `refreshing`.
+pub fn pull_to_refresh(ui: &mut Ui, id_salt: &str, refreshing: bool, add_contents: impl FnOnce(&mut Ui)) -> bool {
+    let id = ui.make_persistent_id(id_salt).with("pull_to_refresh");
+    // (distance pulled so far, reload started by a pull)
+    let (mut pulled, mut pulled_reload) = ui.data(|d| d.get_temp::<(f32, bool)>(id)).unwrap_or_default();
+    pulled_reload &= refreshing;
+    
+    let height = if pulled_reload { 32.0 } else { pulled.min(PULL_DISTANCE) * 0.5 };
+    if height > 0.0 {
+        let (rect, _) = ui.allocate_exact_size(Vec2::new(ui.available_width(), height), Sense::hover());
+        let indicator = egui::Rect::from_center_size(rect.center(), Vec2::splat(20.0));
+        if pulled_reload {
+            egui::Spinner::new().color(colors::ACCENT).paint_at(ui, indicator);
+        } else {
+            let progress = (pulled / PULL_DISTANCE).min(1.0);
+            let points: Vec<Pos2> = (0..=32)
+                .map(|k| {
+                    let angle = -std::f32::consts::FRAC_PI_2 + std::f32::consts::TAU * progress * k as f32 / 32.0;
+                    indicator.center() + 8.0 * Vec2::angled(angle)
+                })
+                .collect();
+            let color = colors::ACCENT.gamma_multiply(if progress < 1.0 { 0.5 } else { 1.0 });
+            ui.painter().add(egui::Shape::line(points, Stroke::new(2.0, color)));
+        }
+    }
+    
+    let output = egui::ScrollArea::vertical().id_salt(id_salt).show(ui, add_contents);
+    
+    // Only touch drags count; a mouse drags the scroll bar instead
+    let (touching, delta, pos) = ui.input(|i| (i.any_touches(), i.pointer.delta(), i.pointer.interact_pos()));
+    let at_top = output.state.offset.y <= 0.0;
+    let mut reload = false;
+    if touching && at_top && !refreshing && pos.is_some_and(|pos| output.inner_rect.contains(pos)) {
+        pulled = (pulled + delta.y).max(0.0);
+    } else if !touching {
+        reload = pulled >= PULL_DISTANCE && !refreshing;
+        pulled_reload |= reload;
+        pulled = 0.0;
+    }
+    if pulled > 0.0 {
+        ui.ctx().request_repaint();
+    }
+    ui.data_mut(|d| d.insert_temp(id, (pulled, pulled_reload)));
+    reload
+}
+
+/// "Save" menu asking for a name; returns the name once confirmed
+pub fn save_as_menu(ui: &mut Ui, i18n: &I18n, draft: &mut String, hover: &str) -> Option<String> {
+    let mut saved = None;
+    ui.menu_button("💾", |ui| {
+        ui.label(RichText::new(i18n.t("common.name")).size(11.0).color(Color32::GRAY));
+        let response = ui.add(egui::TextEdit::singleline(draft).desired_width(180.0));
+        let submit = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button(i18n.t("common.save")).clicked() || submit) && !draft.trim().is_empty() {
+            saved = Some(draft.trim().to_string());
+            draft.clear();
+            ui.close();
+        }
+    }).response.on_hover_text(hover);
+    saved
+}
+
+/// Circular avatar of `login`, from the shared `AvatarCache` (empty until loaded)
+pub fn avatar(ui: &mut Ui, login: &str, url: &str, size: f32) -> Response {
+    let texture = AvatarCache::of(ui.ctx()).and_then(|cache| cache.texture(ui.ctx(), login, url));
+    match texture {
+        Some(texture) => ui.add(
+            egui::Image::new(&texture)
+                .fit_to_exact_size(Vec2::splat(size))
+                .corner_radius(size / 2.0)
+        ),
+        None => ui.allocate_response(Vec2::splat(size), Sense::hover()),
+    }
+}
+
 /// Draw "tactical corner brackets" - only the 4 corners, not full border
 pub fn draw_corner_brackets(painter: &egui::Painter, rect: egui::Rect, color: Color32, is_hovered: bool) {
     let stroke_width = if is_hovered { 2.0 } else { 1.5 };
@@ -103,67 +268,19 @@ pub fn draw_corner_brackets(painter: &egui::Painter, rect: egui::Rect, color: Co
 }
 
 // ============================================================================
-// CyberFrame: Container with corner brackets and semi-transparent background
-// ============================================================================
-
-/// A wrapper container with Tactical Corner Brackets and semi-transparent fill
-pub struct CyberFrame {
-    padding: f32,
-    bg_alpha: u8,
-}
-
-impl CyberFrame {
-    pub fn new() -> Self {
-        Self {
-            padding: 12.0,
-            bg_alpha: 200,
-        }
-    }
-    
-    pub fn padding(mut self, padding: f32) -> Self {
-        self.padding = padding;
-        self
-    }
-    
-    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
-        let outer_rect = ui.available_rect_before_wrap();
-        
-        // Draw background
-        let painter = ui.painter();
-        let bg_color = Color32::from_rgba_premultiplied(0, 20, 30, self.bg_alpha);
-        painter.rect_filled(outer_rect, 0.0, bg_color);
-        
-        // Draw corner brackets
-        draw_corner_brackets(painter, outer_rect, colors::ACCENT_DIM, false);
-        
-        // Content with padding
-        let content_rect = outer_rect.shrink(self.padding);
-        let mut child_ui = ui.child_ui(content_rect, egui::Layout::top_down(egui::Align::LEFT), None);
-        let result = add_contents(&mut child_ui);
-        
-        // Consume the space
-        ui.allocate_rect(outer_rect, Sense::hover());
-        
-        result
-    }
-}
-
-impl Default for CyberFrame {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-// ============================================================================
-// SystemStatusBar: HUD-style bottom bar with fake metrics
+// SystemStatusBar: HUD-style bottom bar with live metrics
 // ============================================================================
 
 /// HUD-style status bar displaying system metrics
 pub struct SystemStatusBar;
 
 impl SystemStatusBar {
-    pub fn show(ui: &mut Ui) {
-        let start_time = std::time::Instant::now();
+    /// Metrics come from `ctx`; `tasks` are the names of the running backend
+    /// tasks, listed when hovering the task count.
+    pub fn show(ui: &mut Ui, i18n: &I18n, ctx: &AppContext, tasks: &[&str]) {
+        let engine = ctx.engine.try_read().ok().and_then(|e| e.map(|kind| kind.label()));
+        let rate_limit = ctx.rate_limit.try_read().ok().and_then(|limit| *limit);
+        let active_tasks = ctx.active_tasks.load(Ordering::Relaxed);
         
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 20.0;
@@ -175,18 +292,53 @@ impl SystemStatusBar {
             // Network status
             ui.label(RichText::new("[ NET: SECURE ]").font(mono.clone()).color(dim_cyan));
             
-            // Memory (fake)
-            ui.label(RichText::new("[ MEM: 24MB ]").font(mono.clone()).color(dim_cyan));
+            // Resident memory of the process
+            let memory = match memory_stats::memory_stats() {
+                Some(stats) => format!("{}MB", stats.physical_mem / (1024 * 1024)),
+                None => "N/A".to_string(),
+            };
+            ui.label(RichText::new(format!("[ MEM: {} ]", memory)).font(mono.clone()).color(dim_cyan));
+            
+            // Uptime since app start; ticks without any event, so wake up for it
+            let uptime = ctx.started_at.elapsed().as_secs();
+            ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+            ui.label(RichText::new(format!("[ UPTIME: {:02}:{:02}:{:02} ]", uptime / 3600, uptime / 60 % 60, uptime % 60)).font(mono.clone()).color(dim_cyan));
+            
+            // API quota, red when nearly used up
+            match rate_limit {
+                Some(limit) => {
+                    let color = if limit.remaining * 10 < limit.limit { Color32::from_rgb(255, 80, 80) } else { dim_cyan };
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or_default();
+                    let reset_mins = limit.reset.saturating_sub(now).div_ceil(60);
+                    ui.label(RichText::new(format!("[ API: {}/{} ]", limit.remaining, limit.limit)).font(mono.clone()).color(color))
+                        .on_hover_text(i18n.tf("status.reset_in", &[&reset_mins]));
+                }
+                None => {
+                    ui.label(RichText::new("[ API: ... ]").font(mono.clone()).color(dim_cyan));
+                }
+            }
             
-            // Uptime (real, from app start)
-            let uptime = start_time.elapsed().as_secs();
-            let mins = uptime / 60;
-            let secs = uptime % 60;
-            ui.label(RichText::new(format!("[ UPTIME: {:02}:{:02} ]", mins, secs)).font(mono.clone()).color(dim_cyan));
+            // Active engine
+            ui.label(RichText::new(format!("[ ENGINE: {} ]", engine.unwrap_or("..."))).font(mono.clone()).color(dim_cyan));
             
             // Sync status
             ui.label(RichText::new("[ SYNC: OK ]").font(mono.clone()).color(Color32::from_rgb(0, 200, 100)));
             
+            // Backend activity
+            if active_tasks == 0 {
+                ui.label(RichText::new("[ IDLE ]").font(mono.clone()).color(dim_cyan));
+            } else {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 4.0;
+                    ui.spinner();
+                    ui.label(RichText::new(format!("[ BUSY: {} ]", active_tasks)).font(mono.clone()).color(colors::ACCENT))
+                        .on_hover_text(tasks.join("\n"));
+                });
+            }
+            
             // Separator
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.label(RichText::new("NATIVE_HUB v0.1.0").font(mono).color(Color32::from_rgb(80, 80, 80)));
@@ -0,0 +1,76 @@
+//! Diff View UI Component
+//!
+//! Renders unified diff patches with green/red line coloring.
+//! Shared by the commit detail view and the pull request files view.
+
+use eframe::egui::{self, Color32, RichText, ScrollArea};
+use crate::app_event::ChangedFile;
+use crate::i18n::I18n;
+
+use super::style::colors;
+
+const ADDED: Color32 = Color32::from_rgb(100, 220, 120);
+const REMOVED: Color32 = Color32::from_rgb(255, 110, 110);
+
+/// Render a changed file as a collapsible section with its patch
+pub fn render_changed_file(ui: &mut egui::Ui, i18n: &I18n, file: &ChangedFile) {
+    let (status_icon, status_color) = match file.status.as_str() {
+        "added" => ("A", ADDED),
+        "removed" => ("D", REMOVED),
+        "renamed" => ("R", Color32::from_rgb(230, 180, 0)),
+        _ => ("M", colors::ACCENT),
+    };
+    
+    let title = match &file.previous_filename {
+        Some(prev) if file.status == "renamed" => format!("{} → {}", prev, file.filename),
+        _ => file.filename.clone(),
+    };
+    
+    egui::CollapsingHeader::new(
+        RichText::new(format!("[{}] {}  +{} -{}", status_icon, title, file.additions, file.deletions))
+            .size(12.0)
+            .color(status_color)
+            .monospace()
+    )
+    .id_salt(("changed_file", &file.filename))
+    .default_open(file.changes <= 200)
+    .show(ui, |ui| {
+        match &file.patch {
+            Some(patch) => render_patch(ui, patch),
+            None => {
+                ui.colored_label(Color32::GRAY, i18n.t("diff.unavailable"));
+            }
+        }
+    });
+}
+
+/// Render a unified diff patch in a monospace view
+pub fn render_patch(ui: &mut egui::Ui, patch: &str) {
+    ScrollArea::horizontal()
+        .id_salt(("patch", patch.len(), patch.lines().next().unwrap_or("")))
+        .show(ui, |ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            
+            for line in patch.lines() {
+                let (color, bg) = line_colors(line);
+                let mut text = RichText::new(line).monospace().size(12.0).color(color);
+                if let Some(bg) = bg {
+                    text = text.background_color(bg);
+                }
+                ui.add(egui::Label::new(text).extend());
+            }
+        });
+}
+
+/// Foreground and optional background color for a single diff line
+fn line_colors(line: &str) -> (Color32, Option<Color32>) {
+    if line.starts_with("@@") {
+        (colors::ACCENT_DIM, Some(Color32::from_rgba_unmultiplied(0, 240, 255, 15)))
+    } else if line.starts_with('+') {
+        (ADDED, Some(Color32::from_rgba_unmultiplied(0, 200, 80, 25)))
+    } else if line.starts_with('-') {
+        (REMOVED, Some(Color32::from_rgba_unmultiplied(255, 60, 60, 25)))
+    } else {
+        (colors::TEXT_MUTED, None)
+    }
+}
@@ -0,0 +1,311 @@
+//! Discussions UI Component
+//!
+//! Displays discussion categories and threads, and renders a thread with its
+//! comments and replies. Discussions are only exposed through GraphQL.
+
+use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
+use crate::app_event::{AppAction, Discussion, DiscussionCategory, DiscussionThread, RequestId, next_request_id};
+use crate::engine::api_client::GraphQlActor;
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+
+use super::style::colors;
+use super::components::{CyberButton, timestamp};
+
+/// Discussions panel - displays discussions for a repository
+pub struct DiscussionsPanel {
+    pub discussions: Vec<Discussion>,
+    pub categories: Vec<DiscussionCategory>,
+    pub loading: bool,
+    pub current_repo: String,
+    request: RequestId, // Latest list request; other responses are stale
+    pub category_filter: Option<String>, // Category id, None = all
+    
+    // Detail view
+    pub selected: Option<u32>,
+    pub thread: Option<DiscussionThread>,
+    pub loading_thread: bool,
+    
+    action_tx: Sender<AppAction>,
+}
+
+impl DiscussionsPanel {
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            discussions: Vec::new(),
+            categories: Vec::new(),
+            loading: false,
+            current_repo: String::new(),
+            request: 0,
+            category_filter: None,
+            selected: None,
+            thread: None,
+            loading_thread: false,
+            action_tx,
+        }
+    }
+    
+    pub fn set_repo(&mut self, repo: String) {
+        if self.current_repo != repo {
+            self.current_repo = repo.clone();
+            self.discussions.clear();
+            self.categories.clear();
+            self.category_filter = None;
+            self.selected = None;
+            self.thread = None;
+            let _ = self.action_tx.try_send(AppAction::FetchDiscussionCategories(repo));
+            self.fetch_discussions();
+        }
+    }
+    
+    fn fetch_discussions(&mut self) {
+        self.loading = true;
+        self.request = next_request_id();
+        let _ = self.action_tx.try_send(AppAction::FetchDiscussions(
+            self.request,
+            self.current_repo.clone(),
+            self.category_filter.clone()
+        ));
+    }
+    
+    pub fn set_categories(&mut self, categories: Vec<DiscussionCategory>) {
+        self.categories = categories;
+    }
+    
+    pub fn set_discussions(&mut self, request: RequestId, discussions: Vec<Discussion>) {
+        if request != self.request {
+            return;
+        }
+        self.discussions = discussions;
+        self.loading = false;
+    }
+    
+    pub fn set_thread(&mut self, thread: DiscussionThread) {
+        if self.selected == Some(thread.number) {
+            self.thread = Some(thread);
+            self.loading_thread = false;
+        }
+    }
+    
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        if self.selected.is_some() {
+            self.show_detail(ui, i18n);
+        } else {
+            self.show_list(ui, i18n);
+        }
+    }
+    
+    fn show_list(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.vertical(|ui| {
+            // Header
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("💬 Discussions").size(18.0).color(colors::ACCENT).strong());
+                
+                if self.loading {
+                    ui.spinner();
+                }
+            });
+            
+            // Category filter
+            if !self.categories.is_empty() {
+                let previous = self.category_filter.clone();
+                ui.horizontal_wrapped(|ui| {
+                    let all_selected = self.category_filter.is_none();
+                    if ui.selectable_label(all_selected, i18n.t("discussions.all")).clicked() {
+                        self.category_filter = None;
+                    }
+                    for category in &self.categories {
+                        let is_selected = self.category_filter.as_deref() == Some(category.id.as_str());
+                        if ui.selectable_label(is_selected, &category.name).clicked() {
+                            self.category_filter = Some(category.id.clone());
+                        }
+                    }
+                });
+                if self.category_filter != previous {
+                    self.fetch_discussions();
+                }
+            }
+            
+            ui.separator();
+            
+            // Discussion list
+            ScrollArea::vertical().id_salt("discussions_list").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                
+                if self.discussions.is_empty() && !self.loading {
+                    ui.colored_label(Color32::GRAY, i18n.t("discussions.empty"));
+                }
+                
+                let mut clicked = None;
+                for discussion in &self.discussions {
+                    if self.render_discussion_card(ui, discussion) {
+                        clicked = Some(discussion.number);
+                    }
+                    ui.add_space(4.0);
+                }
+                
+                if let Some(number) = clicked {
+                    self.selected = Some(number);
+                    self.thread = None;
+                    self.loading_thread = true;
+                    let _ = self.action_tx.try_send(AppAction::FetchDiscussion(
+                        self.current_repo.clone(),
+                        number
+                    ));
+                }
+            });
+        });
+    }
+    
+    fn render_discussion_card(&self, ui: &mut egui::Ui, discussion: &Discussion) -> bool {
+        let h = 60.0;
+        let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+        super::components::describe(ui, &response, &format!("Discussion #{}: {}", discussion.number, discussion.title));
+        
+        let painter = ui.painter();
+        let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        
+        let bg_color = if is_hovered {
+            Color32::from_rgba_unmultiplied(0, 50, 60, 180)
+        } else {
+            Color32::from_rgb(8, 12, 18)
+        };
+        
+        // Background
+        painter.rect_filled(rect, 4.0, bg_color);
+        
+        // Status strip - green once an answer was chosen
+        let strip_color = if discussion.answer_chosen_at.is_some() {
+            Color32::from_rgb(0, 200, 100)
+        } else {
+            colors::ACCENT_DIM
+        };
+        let strip_rect = egui::Rect::from_min_size(rect.min, Vec2::new(3.0, rect.height()));
+        painter.rect_filled(strip_rect, 0.0, strip_color);
+        
+        // Border
+        painter.rect_stroke(rect, 4.0, Stroke::new(1.0, if is_hovered { colors::ACCENT } else { Color32::from_rgb(0, 60, 60) }), egui::StrokeKind::Middle);
+        
+        // Content
+        let content_rect = rect.shrink2(Vec2::new(12.0, 6.0));
+        ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    // Title
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("#{}", discussion.number)).size(12.0).color(Color32::GRAY));
+                        ui.label(RichText::new(&discussion.title).size(13.0).color(Color32::WHITE).strong());
+                    });
+                    
+                    // Category + comment count
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(&discussion.category.name).size(10.0).color(colors::ACCENT)
+                            .background_color(Color32::from_rgba_unmultiplied(0, 240, 255, 30)));
+                        if discussion.answer_chosen_at.is_some() {
+                            ui.label(RichText::new("✔ Answered").size(10.0).color(Color32::from_rgb(0, 200, 100)));
+                        }
+                        ui.label(RichText::new(format!("💬 {}", discussion.comments.total_count)).size(10.0).color(Color32::GRAY));
+                    });
+                });
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(RichText::new(author_login(&discussion.author)).size(10.0).color(Color32::DARK_GRAY));
+                });
+            });
+        });
+        
+        response.clicked()
+    }
+    
+    fn show_detail(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.vertical(|ui| {
+            // Back button + title
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.back")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
+                    self.selected = None;
+                    self.thread = None;
+                }
+                
+                if let Some(thread) = &self.thread {
+                    ui.add_space(10.0);
+                    ui.label(RichText::new(format!("#{} {}", thread.number, thread.title))
+                        .size(16.0).color(colors::ACCENT).strong());
+                }
+            });
+            
+            ui.separator();
+            
+            if self.loading_thread {
+                ui.spinner();
+                return;
+            }
+            
+            let Some(thread) = &self.thread else { return };
+            
+            ScrollArea::vertical().id_salt("discussion_detail").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                
+                // Opening post
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(author_login(&thread.author)).size(12.0).color(colors::ACCENT_DIM));
+                        timestamp(ui, i18n, &thread.created_at);
+                    });
+                    ui.separator();
+                    ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
+                    if thread.body.is_empty() {
+                        ui.colored_label(Color32::GRAY, i18n.t("common.no_description"));
+                    } else {
+                        ui.label(&thread.body);
+                    }
+                });
+                ui.hyperlink_to(i18n.t("common.view_on_github"), &thread.url);
+                
+                ui.add_space(10.0);
+                
+                // Comments with nested replies
+                ui.label(RichText::new(i18n.tf("common.comments", &[&thread.comments.nodes.len()])).size(14.0).color(colors::TEXT_MUTED));
+                ui.separator();
+                
+                for comment in &thread.comments.nodes {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(author_login(&comment.author)).size(12.0).color(colors::ACCENT_DIM));
+                            timestamp(ui, i18n, &comment.created_at);
+                            if comment.is_answer {
+                                ui.label(RichText::new("✔ Answer").size(10.0).color(Color32::from_rgb(0, 200, 100)).strong());
+                            }
+                        });
+                        ui.separator();
+                        ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
+                        ui.label(&comment.body);
+                        
+                        let replies = comment.replies.as_ref().map(|r| r.nodes.as_slice()).unwrap_or_default();
+                        for reply in replies {
+                            ui.add_space(4.0);
+                            ui.horizontal(|ui| {
+                                ui.add_space(16.0);
+                                ui.vertical(|ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new(format!("↳ {}", author_login(&reply.author))).size(11.0).color(colors::ACCENT_DIM));
+                                        timestamp(ui, i18n, &reply.created_at);
+                                    });
+                                    ui.label(&reply.body);
+                                });
+                            });
+                        }
+                    });
+                    ui.add_space(5.0);
+                }
+            });
+        });
+    }
+}
+
+/// Login of a GraphQL author, or "ghost" for deleted accounts
+fn author_login(author: &Option<GraphQlActor>) -> &str {
+    author.as_ref().map(|a| a.login.as_str()).unwrap_or("ghost")
+}
@@ -1,4 +1,4 @@
-use eframe::egui::{self, Color32, Painter, Pos2, Rect, Stroke};
+use eframe::egui::{Color32, Painter, Pos2, Rect, Stroke};
 
 /// Draws a retro sci-fi grid background
 pub fn draw_retro_grid(painter: &Painter, rect: Rect, time: f64) {
@@ -44,7 +44,7 @@ pub fn draw_crt_overlay(painter: &eframe::egui::Painter, rect: Rect) {
     // 1. Scanlines
     // Draw horizontal lines every few pixels
     let line_spacing = 4.0;
-    let line_color = Color32::from_black_alpha(50); // Very subtle
+    let line_color = Color32::from_black_alpha(20); // Faint enough not to blur text
     let stroke = Stroke::new(1.0, line_color);
     
     let mut y = rect.top();
@@ -64,7 +64,7 @@ pub fn draw_crt_overlay(painter: &eframe::egui::Painter, rect: Rect) {
     let c = rect.center();
     // Colors
     let color_center = Color32::from_black_alpha(0);
-    let color_edge = Color32::from_black_alpha(150); // Dark corners
+    let color_edge = Color32::from_black_alpha(90); // Dark corners
     
     // Center vertex
     mesh.colored_vertex(c, color_center);
@@ -85,21 +85,3 @@ pub fn draw_crt_overlay(painter: &eframe::egui::Painter, rect: Rect) {
     
     painter.add(mesh);
 }
-
-/// Renders a "Typewriter" text effect
-pub fn typewriter_label(ui: &mut egui::Ui, text: &str, animation_progress: f32) {
-    let visible_chars = (text.len() as f32 * animation_progress) as usize;
-    let visible_text = &text[..visible_chars.min(text.len())];
-    
-    let cursor = if animation_progress < 1.0 && (ui.input(|i| i.time) * 2.0) as i32 % 2 == 0 {
-        "█"
-    } else {
-        ""
-    };
-    
-    ui.label(
-        egui::RichText::new(format!("{}{}", visible_text, cursor))
-            .font(egui::FontId::monospace(14.0))
-            .color(Color32::from_rgb(0, 255, 136))
-    );
-}
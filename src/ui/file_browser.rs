@@ -2,37 +2,61 @@
 //!
 //! Displays a file tree, repo info, and README for browsing repository contents.
 
+use std::sync::Arc;
 use eframe::egui::{self, Color32, RichText, ScrollArea, Vec2};
 use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
-use crate::app_event::{AppAction, FileNode, RepoInfo};
+use crate::app_event::{FileNode, RepoInfo, Tag, WatchState};
 use crate::i18n::I18n;
-use tokio::sync::mpsc::Sender;
 
 use super::style::colors;
 use super::components::CyberButton;
+use super::repo_tree::RepoTree;
+use super::code_viewer::CodeViewer;
+
+/// What the file browser shows for the open repo
+pub struct BrowserView<'a> {
+    pub repo_name: &'a str,
+    pub current_path: &'a str,
+    pub viewing_code: &'a Option<(String, String)>,
+    pub more: Option<bool>, // The open file was only partly fetched; Some(true) while the next part loads
+    pub repo_info: &'a Option<RepoInfo>,
+    pub readme_content: &'a Option<String>,
+    pub tags: &'a [Tag],
+    pub languages: &'a [(String, u64)],
+    pub current_ref: &'a str,
+    pub readme_collapsed: bool,
+    pub compact: bool, // Phone layout: one column, wrapping header
+    pub history: (bool, bool), // (can go back, can go forward)
+    pub is_starred: Option<bool>,
+    pub watch_state: Option<WatchState>,
+}
 
 /// Render the file browser UI
 pub fn render_file_browser(
     ui: &mut egui::Ui,
     i18n: &I18n,
-    repo_name: &str,
-    current_path: &str,
-    files: &[FileNode],
-    viewing_code: &Option<(String, String)>,
-    repo_info: &Option<RepoInfo>,
-    readme_content: &Option<String>,
-    action_tx: &Sender<AppAction>,
+    view: BrowserView,
+    tree: &mut RepoTree,
+    viewer: &mut CodeViewer,
     markdown_cache: &mut CommonMarkCache,
 ) -> Option<BrowserAction> {
+    let BrowserView {
+        repo_name, current_path, viewing_code, more, repo_info, readme_content, tags, languages,
+        current_ref, readme_collapsed, compact, history, is_starred, watch_state,
+    } = view;
     let action = std::cell::RefCell::new(None);
+    let rtl = i18n.is_rtl();
+    // Arrows point the way the reading order runs
+    let (back_arrow, history_back, history_forward) = if rtl { ("→", "▶", "◀") } else { ("←", "◀", "▶") };
     
-    ui.vertical(|ui| {
+    // Rows nested in a right-aligned column run right to left
+    ui.with_layout(super::components::column(rtl), |ui| {
         // ==================
         // HEADER: Repo Info
         // ==================
-        ui.horizontal(|ui| {
+        let header = |ui: &mut egui::Ui| {
             // Back button
-            if CyberButton::new("← 返回").min_size(Vec2::new(80.0, 35.0)).show(ui).clicked() {
+            if CyberButton::new(format!("{} {}", back_arrow, i18n.t("files.back"))).min_size(Vec2::new(80.0, 35.0)).show(ui).clicked() {
                 if current_path.is_empty() {
                     *action.borrow_mut() = Some(BrowserAction::BackToRepoList);
                 } else {
@@ -41,6 +65,14 @@ pub fn render_file_browser(
                 }
             }
             
+            // History navigation
+            if ui.add_enabled(history.0, egui::Button::new(history_back)).on_hover_text(i18n.t("files.history_back")).clicked() {
+                *action.borrow_mut() = Some(BrowserAction::HistoryBack);
+            }
+            if ui.add_enabled(history.1, egui::Button::new(history_forward)).on_hover_text(i18n.t("files.history_forward")).clicked() {
+                *action.borrow_mut() = Some(BrowserAction::HistoryForward);
+            }
+            
             ui.add_space(10.0);
             
             // Repo name and path
@@ -48,49 +80,159 @@ pub fn render_file_browser(
                 .size(16.0)
                 .color(colors::ACCENT));
             
-            // Stats on the right
+            if repo_info.as_ref().is_some_and(|info| info.archived) {
+                ui.label(RichText::new(" ARCHIVED ")
+                    .size(10.0)
+                    .color(Color32::from_rgb(230, 180, 0))
+                    .background_color(Color32::from_rgba_unmultiplied(230, 180, 0, 30)));
+            }
+            
+            // Ref selector: default branch or any tag
+            let default_label = repo_info.as_ref()
+                .map(|info| info.default_branch.as_str())
+                .filter(|b| !b.is_empty())
+                .unwrap_or("HEAD");
+            let selected_text = if current_ref.is_empty() { default_label } else { current_ref };
+            egui::ComboBox::from_id_salt("ref_selector")
+                .selected_text(format!("🏷 {}", selected_text))
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(current_ref.is_empty(), format!("⎇ {}", default_label)).clicked() && !current_ref.is_empty() {
+                        *action.borrow_mut() = Some(BrowserAction::SwitchRef(String::new()));
+                    }
+                    if !tags.is_empty() {
+                        ui.separator();
+                    }
+                    for tag in tags {
+                        if ui.selectable_label(current_ref == tag.name, format!("🏷 {}", tag.name)).clicked() && current_ref != tag.name {
+                            *action.borrow_mut() = Some(BrowserAction::SwitchRef(tag.name.clone()));
+                        }
+                    }
+                });
+            
+            // Stats at the far end
             if let Some(info) = repo_info {
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.label(RichText::new(format!("🍴 {}", info.forks_count))
+                ui.with_layout(super::components::trailing(rtl), |ui| {
+                    // Admin settings: archive / delete
+                    ui.menu_button("⚙", |ui| {
+                        let archive_label = i18n.t(if info.archived { "files.unarchive" } else { "files.archive" });
+                        if ui.button(archive_label).clicked() {
+                            *action.borrow_mut() = Some(BrowserAction::SetArchived(!info.archived));
+                            ui.close();
+                        }
+                        ui.separator();
+                        if ui.button(RichText::new(i18n.t("files.delete_repo")).color(Color32::from_rgb(255, 80, 80))).clicked() {
+                            *action.borrow_mut() = Some(BrowserAction::Delete);
+                            ui.close();
+                        }
+                    });
+                    
+                    // Star toggle (disabled until the star state is known)
+                    let starred = is_starred.unwrap_or(false);
+                    let star_text = if starred {
+                        RichText::new("★ Starred").color(Color32::from_rgb(255, 215, 0))
+                    } else {
+                        RichText::new("☆ Star").color(Color32::GRAY)
+                    };
+                    if ui.add_enabled(is_starred.is_some(), egui::Button::new(star_text)).clicked() {
+                        *action.borrow_mut() = Some(BrowserAction::ToggleStar(!starred));
+                    }
+                    
+                    if ui.button("🍴 Fork").clicked() {
+                        *action.borrow_mut() = Some(BrowserAction::Fork);
+                    }
+                    
+                    if ui.button("⬇ ZIP").on_hover_text(i18n.t("files.download_zip")).clicked() {
+                        *action.borrow_mut() = Some(BrowserAction::DownloadZip);
+                    }
+                    
+                    ui.menu_button("📥 Clone", |ui| {
+                        let (https, ssh) = crate::config::current().clone_urls(repo_name);
+                        for (label, url) in [("HTTPS", https), ("SSH", ssh)] {
+                            ui.label(RichText::new(label).size(10.0).color(Color32::GRAY));
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(&url).monospace().size(11.0));
+                                if ui.small_button("📋").on_hover_text(i18n.t("common.copy")).clicked() {
+                                    ui.ctx().copy_text(url.clone());
+                                    ui.close();
+                                }
+                            });
+                        }
+                        ui.separator();
+                        if ui.button(i18n.t("files.clone_local")).clicked() {
+                            *action.borrow_mut() = Some(BrowserAction::Clone);
+                            ui.close();
+                        }
+                    });
+                    
+                    // Watch / subscription selector
+                    if let Some(current) = watch_state {
+                        egui::ComboBox::from_id_salt("watch_selector")
+                            .selected_text(current.label())
+                            .show_ui(ui, |ui| {
+                                for state in WatchState::all() {
+                                    if ui.selectable_label(current == *state, state.label()).clicked() && current != *state {
+                                        *action.borrow_mut() = Some(BrowserAction::SetWatch(*state));
+                                    }
+                                }
+                            });
+                    }
+                    ui.add_space(15.0);
+                    
+                    ui.label(RichText::new(format!("🍴 {}", i18n.count(info.forks_count)))
                         .size(12.0).color(Color32::GRAY));
                     ui.add_space(15.0);
-                    ui.label(RichText::new(format!("⭐ {}", info.stargazers_count))
+                    ui.label(RichText::new(format!("⭐ {}", i18n.count(info.stargazers_count)))
                         .size(12.0).color(Color32::from_rgb(255, 215, 0)));
                     
-                    if let Some(lang) = &info.language {
+                    // Single primary language until the full breakdown is loaded
+                    if let Some(lang) = info.language.as_ref().filter(|_| languages.is_empty()) {
                         ui.add_space(15.0);
                         ui.label(RichText::new(format!("🔤 {}", lang))
                             .size(12.0).color(colors::ACCENT_DIM));
                     }
                 });
             }
-        });
+        };
+        if compact {
+            ui.horizontal_wrapped(header);
+        } else {
+            ui.horizontal(header);
+        }
+        
+        // Language breakdown bar
+        if !languages.is_empty() {
+            ui.add_space(5.0);
+            render_language_bar(ui, languages);
+        }
         
         // Description
-        if current_path.is_empty() {
-            if let Some(info) = repo_info {
-                if let Some(desc) = &info.description {
-                    if !desc.is_empty() {
-                        ui.add_space(5.0);
-                        ui.label(RichText::new(desc).size(12.0).color(Color32::GRAY).italics());
-                    }
+        if current_path.is_empty()
+            && let Some(info) = repo_info
+        {
+            if let Some(desc) = &info.description
+                && !desc.is_empty()
+            {
+                ui.add_space(5.0);
+                ui.label(RichText::new(desc).size(12.0).color(Color32::GRAY).italics());
+            }
+            
+            // Topics
+            ui.add_space(5.0);
+            ui.horizontal_wrapped(|ui| {
+                for topic in &info.topics {
+                    ui.label(
+                        RichText::new(format!(" {} ", topic))
+                            .size(10.0)
+                            .color(colors::ACCENT)
+                            .background_color(Color32::from_rgba_unmultiplied(0, 240, 255, 30))
+                    );
                 }
                 
-                // Topics
-                if !info.topics.is_empty() {
-                    ui.add_space(5.0);
-                    ui.horizontal_wrapped(|ui| {
-                        for topic in &info.topics {
-                            ui.label(
-                                RichText::new(format!(" {} ", topic))
-                                    .size(10.0)
-                                    .color(colors::ACCENT)
-                                    .background_color(Color32::from_rgba_unmultiplied(0, 240, 255, 30))
-                            );
-                        }
-                    });
+                let edit_label = if info.topics.is_empty() { i18n.t("files.add_topics") } else { "✏" };
+                if ui.small_button(edit_label).clicked() {
+                    *action.borrow_mut() = Some(BrowserAction::EditTopics);
                 }
-            }
+            });
         }
         
         ui.separator();
@@ -98,84 +240,119 @@ pub fn render_file_browser(
         // ==================
         // MAIN CONTENT
         // ==================
+        let open_path = viewing_code.as_ref().map(|(filename, _)| {
+            if current_path.is_empty() { filename.clone() } else { format!("{}/{}", current_path, filename) }
+        });
+        
+        let mut show_tree = |ui: &mut egui::Ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(i18n.t("files.files")).size(12.0).color(colors::TEXT_MUTED));
+                if readme_collapsed && viewing_code.is_none() {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("📝 README ◀").on_hover_text(i18n.t("files.expand_readme")).clicked() {
+                            *action.borrow_mut() = Some(BrowserAction::SetReadmeCollapsed(false));
+                        }
+                    });
+                }
+            });
+            ui.separator();
+            
+            ScrollArea::both().id_salt("file_list").show(ui, |ui| {
+                if let Some(file) = tree.show(ui, i18n, open_path.as_deref()) {
+                    *action.borrow_mut() = Some(BrowserAction::OpenFile(file));
+                }
+            });
+        };
+        
+        // README collapsed: the tree gets the whole column until a file is opened
+        if readme_collapsed && viewing_code.is_none() {
+            show_tree(ui);
+            return;
+        }
+        
+        // LEFT (RIGHT for RTL): Expandable tree, kept while a file is open.
+        // Too narrow on phones, where README and files take turns instead
+        if !compact {
+            let tree_side = if rtl { egui::panel::Side::Right } else { egui::panel::Side::Left };
+            egui::SidePanel::new(tree_side, "repo_tree_panel")
+                .resizable(true)
+                .default_width(240.0)
+                .show_inside(ui, show_tree);
+        }
+        
+        // RIGHT: Code viewer or README
         if let Some((filename, content)) = viewing_code {
-            // Code viewer mode
+            // Links are pinned to the ref being browsed
+            let path = open_path.unwrap_or_default();
+            let git_ref = if current_ref.is_empty() {
+                repo_info.as_ref().map(|info| info.default_branch.as_str()).filter(|b| !b.is_empty()).unwrap_or("HEAD")
+            } else {
+                current_ref
+            };
+            let config = crate::config::current();
+            let raw_url = config.raw_url(repo_name, git_ref, &path);
+            let blob_url = config.blob_url(repo_name, git_ref, &path);
+            
             ui.horizontal(|ui| {
                 ui.label(RichText::new(format!("📄 {}", filename)).size(14.0).color(colors::ACCENT));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("✕ 关闭").clicked() {
+                    if ui.button(i18n.t("files.close")).clicked() {
                         *action.borrow_mut() = Some(BrowserAction::CloseViewer);
                     }
+                    if ui.button(i18n.t("common.save_as")).clicked() {
+                        *action.borrow_mut() = Some(BrowserAction::SaveFile(filename.clone()));
+                    }
+                    if ui.button(i18n.t("files.find")).on_hover_text("Ctrl+F").clicked() {
+                        viewer.open_find();
+                    }
+                    viewer.copy_menu(ui, i18n, content, &raw_url, &blob_url);
+                    if let Some(loading) = more {
+                        let button = ui.add_enabled(!loading, egui::Button::new(i18n.t("files.load_more")))
+                            .on_hover_text(i18n.tf("files.loaded", &[&i18n.file_size(content.len() as u64)]));
+                        if button.clicked() {
+                            *action.borrow_mut() = Some(BrowserAction::LoadMore(filename.clone()));
+                        }
+                    }
                 });
             });
             
             ui.separator();
             
-            ScrollArea::both().show(ui, |ui| {
-                ui.style_mut().wrap = Some(false);
-                ui.monospace(content);
-            });
+            viewer.show(ui, i18n, filename, content);
         } else {
-            // Two-column layout: Files | README
-            ui.columns(2, |columns| {
-                // LEFT: File list
-                columns[0].vertical(|ui| {
-                    ui.label(RichText::new("📂 文件").size(12.0).color(colors::TEXT_MUTED));
-                    ui.separator();
-                    
-                    ScrollArea::vertical().id_salt("file_list").show(ui, |ui| {
-                        ui.set_width(ui.available_width());
-                        
-                        let mut sorted_files = files.to_vec();
-                        sorted_files.sort_by(|a, b| {
-                            match (&a.node_type[..], &b.node_type[..]) {
-                                ("dir", "file") => std::cmp::Ordering::Less,
-                                ("file", "dir") => std::cmp::Ordering::Greater,
-                                _ => a.name.cmp(&b.name),
-                            }
-                        });
-                        
-                        for file in &sorted_files {
-                            let is_dir = file.node_type == "dir";
-                            let icon = if is_dir { "📁" } else { file_icon(&file.name) };
-                            
-                            let response = ui.add(
-                                egui::Button::new(RichText::new(format!("{} {}", icon, file.name)).size(13.0))
-                                    .fill(Color32::TRANSPARENT)
-                                    .min_size(Vec2::new(ui.available_width(), 26.0))
-                            );
-                            
-                            if response.clicked() {
-                                if is_dir {
-                                    *action.borrow_mut() = Some(BrowserAction::NavigateTo(file.path.clone()));
-                                } else if let Some(ref url) = file.download_url {
-                                    *action.borrow_mut() = Some(BrowserAction::OpenFile(file.name.clone(), url.clone()));
-                                }
-                            }
-                            
-                            if response.hovered() {
-                                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                            }
-                        }
-                    });
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("📝 README").size(12.0).color(colors::TEXT_MUTED));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("▶").on_hover_text(i18n.t("files.collapse_readme")).clicked() {
+                        *action.borrow_mut() = Some(BrowserAction::SetReadmeCollapsed(true));
+                    }
                 });
-                
-                // RIGHT: README
-                columns[1].vertical(|ui| {
-                    ui.label(RichText::new("📝 README").size(12.0).color(colors::TEXT_MUTED));
-                    ui.separator();
+            });
+            ui.separator();
                     
-                    ScrollArea::vertical().id_salt("readme_panel").show(ui, |ui| {
-                        if let Some(readme) = readme_content {
-                            // Convert HTML to Markdown for rendering
-                            // (transforms <img> tags to markdown image syntax for fetch)
-                            let converted_readme = html_to_markdown(readme);
-                            CommonMarkViewer::new().show(ui, markdown_cache, &converted_readme);
-                        } else {
-                            ui.colored_label(Color32::GRAY, "无 README 文件");
+            ScrollArea::vertical().id_salt("readme_panel").show(ui, |ui| {
+                if let Some(readme) = readme_content {
+                    // Long READMEs render their start first and the rest a part at a time on request
+                    let shown_id = egui::Id::new(("readme_shown", repo_name));
+                    let shown = ui.data(|d| d.get_temp::<usize>(shown_id)).unwrap_or(README_PART_BYTES);
+                    let visible = readme_prefix(readme, shown);
+                    
+                    // Convert HTML to Markdown for rendering
+                    // (transforms <img> tags to markdown image syntax for fetch)
+                    let raw_base = crate::config::current().raw_base(repo_name);
+                    let converted_readme = ui.memory_mut(|m| m.caches.cache::<ReadmeCache>().get((visible, raw_base.as_str())));
+                    CommonMarkViewer::new().show(ui, markdown_cache, &converted_readme);
+                    
+                    if visible.len() < readme.len() {
+                        ui.add_space(10.0);
+                        let label = i18n.tf("files.show_more", &[&i18n.file_size(visible.len() as u64), &i18n.file_size(readme.len() as u64)]);
+                        if ui.button(label).clicked() {
+                            ui.data_mut(|d| d.insert_temp(shown_id, shown + README_PART_BYTES));
                         }
-                    });
-                });
+                    }
+                } else {
+                    ui.colored_label(Color32::GRAY, i18n.t("files.no_readme"));
+                }
             });
         }
     });
@@ -187,11 +364,81 @@ pub fn render_file_browser(
 pub enum BrowserAction {
     BackToRepoList,
     NavigateTo(String),
-    OpenFile(String, String),
+    OpenFile(FileNode),
     CloseViewer,
+    SetReadmeCollapsed(bool), // Hide the README so the file tree fills the column
+    SaveFile(String),  // Filename of the open file; asks where to save it
+    LoadMore(String),  // Filename of the open file; fetch its next part
+    SwitchRef(String), // Browse the tree at a tag ("" = default branch)
+    ToggleStar(bool),  // New desired star state
+    SetWatch(WatchState),
+    Fork,              // Ask for confirmation before forking
+    DownloadZip,       // Ask for a target directory, then download the zipball
+    Clone,             // Ask for a directory and tool, then clone into it
+    SetArchived(bool), // New desired archive state
+    Delete,            // Ask for typed-name confirmation before deleting
+    EditTopics,        // Open the topic editor
+    HistoryBack,
+    HistoryForward,
 }
 
-fn parent_path(path: &str) -> String {
+/// Render a GitHub-style stacked color bar with a legend of the top languages
+fn render_language_bar(ui: &mut egui::Ui, languages: &[(String, u64)]) {
+    let total: u64 = languages.iter().map(|(_, bytes)| bytes).sum();
+    if total == 0 {
+        return;
+    }
+    
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(ui.available_width(), 6.0), egui::Sense::hover());
+    let painter = ui.painter();
+    let mut x = rect.min.x;
+    for (name, bytes) in languages {
+        let width = rect.width() * (*bytes as f32 / total as f32);
+        let segment = egui::Rect::from_min_size(egui::pos2(x, rect.min.y), Vec2::new(width, rect.height()));
+        painter.rect_filled(segment, 0.0, language_color(name));
+        x += width;
+    }
+    
+    ui.horizontal_wrapped(|ui| {
+        for (name, bytes) in languages.iter().take(6) {
+            let percent = *bytes as f64 * 100.0 / total as f64;
+            ui.label(RichText::new("●").size(10.0).color(language_color(name)));
+            ui.label(RichText::new(format!("{} {:.1}%", name, percent)).size(10.0).color(Color32::GRAY));
+            ui.add_space(6.0);
+        }
+    });
+}
+
+/// Color of a language, matching GitHub's linguist palette for common ones
+fn language_color(name: &str) -> Color32 {
+    match name {
+        "Rust" => Color32::from_rgb(222, 165, 132),
+        "Python" => Color32::from_rgb(53, 114, 165),
+        "JavaScript" => Color32::from_rgb(241, 224, 90),
+        "TypeScript" => Color32::from_rgb(49, 120, 198),
+        "Go" => Color32::from_rgb(0, 173, 216),
+        "C" => Color32::from_rgb(85, 85, 85),
+        "C++" => Color32::from_rgb(243, 75, 125),
+        "C#" => Color32::from_rgb(23, 134, 0),
+        "Java" => Color32::from_rgb(176, 114, 25),
+        "Kotlin" => Color32::from_rgb(169, 123, 255),
+        "Swift" => Color32::from_rgb(240, 81, 56),
+        "Ruby" => Color32::from_rgb(112, 21, 22),
+        "PHP" => Color32::from_rgb(79, 93, 149),
+        "Shell" => Color32::from_rgb(137, 224, 81),
+        "HTML" => Color32::from_rgb(227, 76, 38),
+        "CSS" => Color32::from_rgb(86, 61, 124),
+        "Dart" => Color32::from_rgb(0, 180, 171),
+        "Lua" => Color32::from_rgb(0, 0, 128),
+        _ => {
+            // Stable fallback color derived from the name
+            let hash = name.bytes().fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+            Color32::from_rgb((hash >> 16) as u8 | 0x40, (hash >> 8) as u8 | 0x40, hash as u8 | 0x40)
+        }
+    }
+}
+
+pub(super) fn parent_path(path: &str) -> String {
     if let Some(pos) = path.rfind('/') {
         path[..pos].to_string()
     } else {
@@ -199,7 +446,7 @@ fn parent_path(path: &str) -> String {
     }
 }
 
-fn file_icon(filename: &str) -> &'static str {
+pub(super) fn file_icon(filename: &str) -> &'static str {
     let ext = filename.rsplit('.').next().unwrap_or("");
     match ext.to_lowercase().as_str() {
         "rs" => "🦀",
@@ -214,12 +461,44 @@ fn file_icon(filename: &str) -> &'static str {
     }
 }
 
+/// Bytes of README markdown rendered at a time
+const README_PART_BYTES: usize = 64 * 1024;
+
+/// Start of `readme` up to about `limit` bytes, cut between paragraphs where
+/// possible so a block isn't split mid-line
+fn readme_prefix(readme: &str, limit: usize) -> &str {
+    if readme.len() <= limit {
+        return readme;
+    }
+    let mut end = limit;
+    while !readme.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &readme[..end];
+    let cut = head.rfind("\n\n").or_else(|| head.rfind('\n')).map_or(end, |i| i + 1);
+    &readme[..cut]
+}
+
+/// Runs `html_to_markdown` once per README instead of every frame. egui's
+/// frame cache keys the result by a hash of (README, raw base) and drops it
+/// once the README is no longer shown.
+#[derive(Default)]
+struct ReadmeConverter;
+
+impl egui::cache::ComputerMut<(&str, &str), Arc<str>> for ReadmeConverter {
+    fn compute(&mut self, (content, raw_base): (&str, &str)) -> Arc<str> {
+        html_to_markdown(content, raw_base).into()
+    }
+}
+
+type ReadmeCache = egui::cache::FrameCache<Arc<str>, ReadmeConverter>;
+
 /// Convert HTML in README to clean Markdown for egui_commonmark rendering
 /// Removes HTML tags (especially images) that can't be rendered natively
-fn html_to_markdown(content: &str) -> String {
+fn html_to_markdown(content: &str, raw_base: &str) -> String {
     let mut result = content.to_string();
     
-    // Remove <img> tags completely (they can't be rendered reliably)
+    // Convert <img> tags to Markdown images so the image loader can fetch them
     let mut output = String::new();
     let mut remaining = result.as_str();
     
@@ -227,8 +506,13 @@ fn html_to_markdown(content: &str) -> String {
         // Add content before the tag
         output.push_str(&remaining[..start]);
         
-        // Find the end of the tag and skip it
+        // Find the end of the tag and replace it
         if let Some(end_offset) = remaining[start..].find('>') {
+            let tag = &remaining[start..start + end_offset + 1];
+            if let Some(src) = extract_attr(tag, "src") {
+                let alt = extract_attr(tag, "alt").unwrap_or_default();
+                output.push_str(&format!("![{}]({})", alt, resolve_image_url(&src, raw_base)));
+            }
             remaining = &remaining[start + end_offset + 1..];
         } else {
             remaining = &remaining[start + 4..];
@@ -259,7 +543,7 @@ fn html_to_markdown(content: &str) -> String {
         }
     }
     
-    // Remove Markdown image syntax ![alt](url) since we can't display images
+    // Resolve relative Markdown image URLs ![alt](path) against the raw file URL
     let mut output = String::new();
     let mut remaining = result.as_str();
     while let Some(start) = remaining.find("![") {
@@ -267,8 +551,19 @@ fn html_to_markdown(content: &str) -> String {
         // Find the closing ]
         if let Some(bracket_end) = remaining[start..].find("](") {
             // Find the closing )
-            if let Some(paren_end) = remaining[start + bracket_end..].find(')') {
-                remaining = &remaining[start + bracket_end + paren_end + 1..];
+            let url_start = start + bracket_end + 2;
+            if let Some(paren_end) = remaining[url_start..].find(')') {
+                let target = &remaining[url_start..url_start + paren_end];
+                // Keep an optional title: ![alt](url "title")
+                let (url, title) = target.split_once(' ').unwrap_or((target, ""));
+                output.push_str(&remaining[start..url_start]);
+                output.push_str(&resolve_image_url(url, raw_base));
+                if !title.is_empty() {
+                    output.push(' ');
+                    output.push_str(title);
+                }
+                output.push(')');
+                remaining = &remaining[url_start + paren_end + 1..];
                 continue;
             }
         }
@@ -301,6 +596,18 @@ fn html_to_markdown(content: &str) -> String {
     cleaned_lines.join("\n")
 }
 
+/// Make an image URL absolute; relative paths point into the repository
+fn resolve_image_url(url: &str, raw_base: &str) -> String {
+    let url = url.trim();
+    if url.starts_with("http://") || url.starts_with("https://") || url.starts_with("data:") {
+        url.to_string()
+    } else if let Some(rest) = url.strip_prefix("//") {
+        format!("https://{}", rest)
+    } else {
+        format!("{}{}", raw_base, url.trim_start_matches("./").trim_start_matches('/'))
+    }
+}
+
 /// Extract an attribute value from an HTML tag
 fn extract_attr(tag: &str, attr_name: &str) -> Option<String> {
     let search = format!("{}=\"", attr_name);
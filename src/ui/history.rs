@@ -0,0 +1,105 @@
+//! Navigation History
+//!
+//! Back/forward trail of the places visited in the file browser, plus a
+//! most-recent-first list of repos, directories and files for the sidebar.
+//! The recent list is saved with the window state; the trail is per session.
+
+use serde::{Deserialize, Serialize};
+
+/// Places kept in the back trail
+const MAX_TRAIL: usize = 50;
+/// Entries kept in the recent list
+const MAX_RECENT: usize = 20;
+
+/// A place in a repository: a directory listing, with a file open when `file` is set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub repo: String,         // full_name
+    pub path: String,         // Directory being listed ("" = root)
+    pub file: Option<String>, // Path of the file open in the viewer
+}
+
+impl Location {
+    /// Root of a repository
+    pub fn repo(repo: &str) -> Self {
+        Self { repo: repo.to_string(), path: String::new(), file: None }
+    }
+    
+    /// Short label for lists
+    pub fn label(&self) -> String {
+        match &self.file {
+            Some(file) => format!("📄 {}: {}", self.repo, file),
+            None if self.path.is_empty() => format!("📦 {}", self.repo),
+            None => format!("📁 {}/{}", self.repo, self.path),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct History {
+    back: Vec<Location>,
+    forward: Vec<Location>,
+    current: Option<Location>,
+    recent: Vec<Location>, // Most recent first
+}
+
+impl History {
+    /// Start from a previously saved recent list
+    pub fn with_recent(mut recent: Vec<Location>) -> Self {
+        recent.truncate(MAX_RECENT);
+        Self { recent, ..Default::default() }
+    }
+    
+    /// Record a navigation made by the user; drops the forward trail
+    pub fn visit(&mut self, location: Location) {
+        if self.current.as_ref() == Some(&location) {
+            return;
+        }
+        if let Some(current) = self.current.replace(location.clone()) {
+            self.back.push(current);
+            if self.back.len() > MAX_TRAIL {
+                self.back.remove(0);
+            }
+        }
+        self.forward.clear();
+        self.remember(location);
+    }
+    
+    /// Step back; returns where to go
+    pub fn back(&mut self) -> Option<Location> {
+        let previous = self.back.pop()?;
+        if let Some(current) = self.current.replace(previous.clone()) {
+            self.forward.push(current);
+        }
+        self.remember(previous.clone());
+        Some(previous)
+    }
+    
+    /// Step forward again after going back; returns where to go
+    pub fn forward(&mut self) -> Option<Location> {
+        let next = self.forward.pop()?;
+        if let Some(current) = self.current.replace(next.clone()) {
+            self.back.push(current);
+        }
+        self.remember(next.clone());
+        Some(next)
+    }
+    
+    pub fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+    
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+    
+    pub fn recent(&self) -> &[Location] {
+        &self.recent
+    }
+    
+    fn remember(&mut self, location: Location) {
+        self.recent.retain(|l| *l != location);
+        self.recent.insert(0, location);
+        self.recent.truncate(MAX_RECENT);
+    }
+}
@@ -2,11 +2,23 @@
 //!
 //! Uses reqwest with a longer timeout than egui's default ehttp loader,
 //! which helps when loading images from slow external services.
+//! Downloaded images are kept in the on-disk cache for `IMAGE_TTL`, keyed by
+//! a hash of the URL; in memory only the most recently used ones are kept.
+//! Raw files and attachments of private repos are requested with the token.
 
 use eframe::egui;
 use egui::load::{BytesLoadResult, BytesLoader, BytesPoll, LoadError};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use crate::engine::cache;
+
+/// Bytes of downloaded images kept in memory; the least recently used go first
+const MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+/// Bytes of images kept in the on-disk cache; the oldest downloads go first
+const DISK_BUDGET: u64 = 256 * 1024 * 1024;
+/// Bytes a failed lookup is counted as besides its error text
+const FAILED_ENTRY_SIZE: usize = 256;
 
 /// Loading state for an image
 #[derive(Clone)]
@@ -16,22 +28,111 @@ enum LoadState {
     Failed(String),
 }
 
+impl LoadState {
+    /// Memory the entry accounts for. Failures count a little too, so a long
+    /// session of broken links can't grow the cache without bound.
+    fn byte_size(&self) -> usize {
+        match self {
+            LoadState::Loaded(bytes) => bytes.len(),
+            LoadState::Failed(err) => FAILED_ENTRY_SIZE + err.len(),
+            LoadState::Loading => 0,
+        }
+    }
+}
+
+struct Entry {
+    state: LoadState,
+    last_used: u64, // `MemoryCache::clock` at the last lookup
+}
+
+/// Images by URL, limited to `MEMORY_BUDGET` bytes of loaded images
+#[derive(Default)]
+struct MemoryCache {
+    entries: HashMap<String, Entry>,
+    bytes: usize,
+    clock: u64,
+}
+
+impl MemoryCache {
+    fn get(&mut self, uri: &str) -> Option<LoadState> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(uri)?;
+        entry.last_used = self.clock;
+        Some(entry.state.clone())
+    }
+    
+    /// Store `state` for `uri`, evicting the least recently used entries over
+    /// budget. Returns the evicted URIs; the caller must `forget_image` them
+    /// (after releasing the lock) so egui drops their decoded images and textures too.
+    #[must_use]
+    fn insert(&mut self, uri: String, state: LoadState) -> Vec<String> {
+        self.clock += 1;
+        self.bytes += state.byte_size();
+        let entry = Entry { state, last_used: self.clock };
+        if let Some(old) = self.entries.insert(uri.clone(), entry) {
+            self.bytes -= old.state.byte_size();
+        }
+        
+        let mut evicted = Vec::new();
+        while self.bytes > MEMORY_BUDGET {
+            let oldest = self.entries.iter()
+                .filter(|(key, entry)| **key != uri && !matches!(entry.state, LoadState::Loading))
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                // Still on disk, so a later lookup only costs a read
+                Some(key) => {
+                    self.remove(&key);
+                    evicted.push(key);
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+    
+    fn remove(&mut self, uri: &str) {
+        if let Some(entry) = self.entries.remove(uri) {
+            self.bytes -= entry.state.byte_size();
+        }
+    }
+    
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+}
+
+/// Drop evicted images from every egui loader, decoded images and textures included
+fn forget_all_of(ctx: &egui::Context, evicted: Vec<String>) {
+    for uri in evicted {
+        ctx.forget_image(&uri);
+    }
+}
+
+/// FNV-1a of the URL; unlike `DefaultHasher` it is the same in every build
+fn url_hash(uri: &str) -> u64 {
+    uri.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
 /// Custom HTTP loader with longer timeout (30 seconds)
 pub struct CustomHttpLoader {
-    cache: Arc<Mutex<HashMap<String, LoadState>>>,
+    cache: Arc<Mutex<MemoryCache>>,
+    auth_token: Arc<RwLock<Option<String>>>, // `AppContext::auth_token`
 }
 
 impl CustomHttpLoader {
-    /// Create a new loader
-    pub fn new() -> Self {
+    /// Create a new loader sending `auth_token` to GitHub's content hosts
+    pub fn new(auth_token: Arc<RwLock<Option<String>>>) -> Self {
         Self {
-            cache: Arc::new(Mutex::new(HashMap::new())),
+            cache: Arc::new(Mutex::new(MemoryCache::default())),
+            auth_token,
         }
     }
     
     /// Install this loader into an egui context
-    pub fn install(ctx: &egui::Context) {
-        ctx.add_bytes_loader(Arc::new(Self::new()));
+    pub fn install(ctx: &egui::Context, auth_token: Arc<RwLock<Option<String>>>) {
+        ctx.add_bytes_loader(Arc::new(Self::new(auth_token)));
     }
 }
 
@@ -48,16 +149,16 @@ impl BytesLoader for CustomHttpLoader {
         
         // Check cache first
         {
-            let cache = self.cache.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
             if let Some(state) = cache.get(uri) {
                 return match state {
                     LoadState::Loading => Ok(BytesPoll::Pending { size: None }),
                     LoadState::Loaded(bytes) => Ok(BytesPoll::Ready {
                         size: None, // Size is determined after image decoding
-                        bytes: egui::load::Bytes::Shared(bytes.clone()),
+                        bytes: egui::load::Bytes::Shared(bytes),
                         mime: None,
                     }),
-                    LoadState::Failed(err) => Err(LoadError::Loading(err.clone())),
+                    LoadState::Failed(err) => Err(LoadError::Loading(err)),
                 };
             }
         }
@@ -65,25 +166,48 @@ impl BytesLoader for CustomHttpLoader {
         // Mark as loading
         {
             let mut cache = self.cache.lock().unwrap();
-            cache.insert(uri.to_string(), LoadState::Loading);
+            let _ = cache.insert(uri.to_string(), LoadState::Loading); // Takes no space, so evicts nothing
         }
         
         // Spawn download task
         let uri = uri.to_string();
         let cache = self.cache.clone();
+        let auth_token = self.auth_token.clone();
         let ctx = ctx.clone();
         
         std::thread::spawn(move || {
-            // Use blocking reqwest client with 30s timeout
-            let result = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .and_then(|client| client.get(&uri).send())
+            let config = crate::config::current();
+            let token = auth_token.blocking_read().clone().filter(|_| config.accepts_token(&uri));
+            // Images fetched with the token may be private; keep them in memory only
+            let disk = cache::global().filter(|_| token.is_none());
+            let disk_key = cache::key("image", &[&format!("{:016x}", url_hash(&uri))]);
+            if let Some(bytes) = disk.and_then(|c| c.get_bytes_fresh(&disk_key, cache::IMAGE_TTL)) {
+                let evicted = cache.lock().unwrap().insert(uri, LoadState::Loaded(bytes.into()));
+                forget_all_of(&ctx, evicted);
+                ctx.request_repaint();
+                return;
+            }
+            
+            // Shared blocking reqwest client with the configured timeouts and proxy.
+            // reqwest drops the header again if GitHub redirects to another host.
+            let result = config.network.blocking_client()
+                .and_then(|client| {
+                    let request = client.get(&uri);
+                    match token {
+                        Some(token) => request.bearer_auth(token),
+                        None => request,
+                    }.send()
+                })
+                .and_then(|response| response.error_for_status())
                 .and_then(|response| response.bytes());
             
             let state = match result {
                 Ok(bytes) => {
                     tracing::info!("Loaded image: {} ({} bytes)", uri, bytes.len());
+                    if let Some(disk) = disk {
+                        disk.put_bytes(&disk_key, &bytes);
+                        disk.trim_blobs(DISK_BUDGET);
+                    }
                     LoadState::Loaded(bytes.to_vec().into())
                 }
                 Err(e) => {
@@ -92,11 +216,8 @@ impl BytesLoader for CustomHttpLoader {
                 }
             };
             
-            {
-                let mut cache = cache.lock().unwrap();
-                cache.insert(uri, state);
-            }
-            
+            let evicted = cache.lock().unwrap().insert(uri, state);
+            forget_all_of(&ctx, evicted);
             ctx.request_repaint();
         });
         
@@ -114,12 +235,6 @@ impl BytesLoader for CustomHttpLoader {
     }
 
     fn byte_size(&self) -> usize {
-        let cache = self.cache.lock().unwrap();
-        cache.values().map(|state| {
-            match state {
-                LoadState::Loaded(bytes) => bytes.len(),
-                _ => 0,
-            }
-        }).sum()
+        self.cache.lock().unwrap().bytes
     }
 }
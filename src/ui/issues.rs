@@ -2,26 +2,43 @@
 //!
 //! Displays issues list, issue details, comments, and allows actions.
 
-use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, TextEdit, Vec2};
-use crate::app_event::{AppAction, Issue, IssueComment, IssueLabel};
-use crate::i18n::I18n;
+use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use crate::app_event::{AppAction, BulkIssueOp, Issue, IssueComment, IssueLabel, Milestone, Operation, RequestId, next_request_id};
+use crate::config::SavedSearch;
+use crate::i18n::{I18n, truncate_chars};
 use tokio::sync::mpsc::Sender;
+use std::collections::BTreeSet;
 
 use super::style::colors;
-use super::components::CyberButton;
+use super::components::{CyberButton, avatar, save_as_menu, timestamp};
+use super::mention_input::{MentionInput, MentionSources};
+use super::references::linkify;
 
 /// Issues panel - displays issues for a repository
 pub struct IssuesPanel {
     pub issues: Vec<Issue>,
     pub loading: bool,
     pub current_repo: String,
+    request: RequestId, // Latest list request; other responses are stale
     pub filter_state: String, // "open", "closed", "all"
+    pub milestones: Vec<Milestone>,
+    pub milestone_filter: String, // "" = any, "none", or milestone number
+    pub label_filter: Vec<String>, // Label names; issues must carry all of them
+    selection: BTreeSet<u32>, // Issue numbers ticked for a bulk action
+    pub save_request: Option<SavedSearch>, // Filter to add to the sidebar's saved searches
+    save_name: String,
+    bulk_running: bool,
     
     // Detail view
     pub selected_issue: Option<Issue>,
     pub comments: Vec<IssueComment>,
     pub loading_comments: bool,
     pub new_comment: String,
+    preview_comment: bool, // Composer shows the rendered preview instead of the editor
+    mention_input: MentionInput,
+    mention_sources: MentionSources,
+    uploads: Vec<(RequestId, String)>, // Attachments in flight and their placeholder in the comment
     
     action_tx: Sender<AppAction>,
 }
@@ -32,11 +49,23 @@ impl IssuesPanel {
             issues: Vec::new(),
             loading: false,
             current_repo: String::new(),
+            request: 0,
             filter_state: "open".to_string(),
+            milestones: Vec::new(),
+            milestone_filter: String::new(),
+            label_filter: Vec::new(),
+            selection: BTreeSet::new(),
+            save_request: None,
+            save_name: String::new(),
+            bulk_running: false,
             selected_issue: None,
             comments: Vec::new(),
             loading_comments: false,
             new_comment: String::new(),
+            preview_comment: false,
+            mention_input: MentionInput::default(),
+            mention_sources: MentionSources::default(),
+            uploads: Vec::new(),
             action_tx,
         }
     }
@@ -47,28 +76,152 @@ impl IssuesPanel {
             self.issues.clear();
             self.selected_issue = None;
             self.comments.clear();
-            self.loading = true;
-            let _ = self.action_tx.try_send(AppAction::FetchIssues(repo, self.filter_state.clone()));
+            self.milestones.clear();
+            self.milestone_filter.clear();
+            self.label_filter.clear();
+            self.bulk_running = false;
+            let _ = self.action_tx.try_send(AppAction::FetchMilestones(repo.clone()));
+            let _ = self.action_tx.try_send(AppAction::FetchMentionCandidates(repo));
+            self.fetch_issues();
         }
     }
     
-    pub fn set_issues(&mut self, issues: Vec<Issue>) {
+    /// Show the list with another state filter, leaving any open issue
+    pub fn set_filter_state(&mut self, state: &str) {
+        self.selected_issue = None;
+        if self.filter_state != state {
+            self.filter_state = state.to_string();
+            self.fetch_issues();
+        }
+    }
+    
+    /// Switch to `repo` and show the list with a saved filter combination
+    pub fn apply_preset(&mut self, repo: &str, state: &str, milestone: &str, labels: &[String]) {
+        self.set_repo(repo.to_string());
+        self.selected_issue = None;
+        self.filter_state = state.to_string();
+        self.milestone_filter = milestone.to_string();
+        self.label_filter = labels.to_vec();
+        self.fetch_issues();
+    }
+    
+    fn fetch_issues(&mut self) {
+        self.loading = true;
+        self.selection.clear();
+        self.request = next_request_id();
+        let _ = self.action_tx.try_send(AppAction::FetchIssues(
+            self.request,
+            self.current_repo.clone(),
+            self.filter_state.clone(),
+            self.milestone_filter.clone(),
+            self.label_filter.clone()
+        ));
+    }
+    
+    /// A bulk action ended; the selection is kept if some issues failed so it can be retried
+    pub fn bulk_finished(&mut self, repo: &str, failed: usize) {
+        if self.current_repo == repo {
+            self.bulk_running = false;
+            if failed == 0 {
+                self.selection.clear();
+            }
+        }
+    }
+    
+    pub fn bulk_cancelled(&mut self) {
+        self.bulk_running = false;
+    }
+    
+    fn run_bulk(&mut self, op: BulkIssueOp) {
+        self.bulk_running = true;
+        let numbers = self.selection.iter().copied().collect();
+        let _ = self.action_tx.try_send(AppAction::BulkIssues(self.current_repo.clone(), numbers, op));
+    }
+    
+    pub fn set_milestones(&mut self, milestones: Vec<Milestone>) {
+        self.milestones = milestones;
+    }
+    
+    pub fn set_mention_sources(&mut self, repo: String, users: Vec<String>, issues: Vec<(u32, String)>) {
+        if repo == self.current_repo {
+            self.mention_sources = MentionSources { users, issues };
+        }
+    }
+    
+    /// Apply an issue list unless a newer request has been made since
+    pub fn set_issues(&mut self, request: RequestId, issues: Vec<Issue>) {
+        if request != self.request {
+            return;
+        }
         self.issues = issues;
         self.loading = false;
     }
     
+    /// Apply a polled refresh of the open issues when the panel is showing exactly those
+    pub fn apply_update(&mut self, repo: &str, issues: Vec<Issue>) {
+        if self.current_repo == repo && self.filter_state == "open" && self.milestone_filter.is_empty() && self.label_filter.is_empty() && !self.loading {
+            self.issues = issues;
+        }
+    }
+    
     pub fn set_comments(&mut self, issue_number: u32, comments: Vec<IssueComment>) {
-        if let Some(ref issue) = self.selected_issue {
-            if issue.number == issue_number {
-                self.comments = comments;
-                self.loading_comments = false;
+        if let Some(ref issue) = self.selected_issue
+            && issue.number == issue_number
+        {
+            self.comments = comments;
+            self.loading_comments = false;
+        }
+    }
+    
+    /// Open an issue from the loaded list; false if it isn't in it
+    pub fn open_issue(&mut self, number: u32) -> bool {
+        let Some(issue) = self.issues.iter().find(|i| i.number == number).cloned() else {
+            return false;
+        };
+        self.select_issue(issue);
+        true
+    }
+    
+    /// Show the detail view of `issue` and load its comments
+    pub fn select_issue(&mut self, issue: Issue) {
+        self.comments.clear();
+        self.loading_comments = true;
+        let _ = self.action_tx.try_send(AppAction::FetchIssueComments(
+            self.current_repo.clone(),
+            issue.number
+        ));
+        self.selected_issue = Some(issue);
+    }
+    
+    /// Replace an upload's placeholder with the markdown of the hosted file
+    /// (or just remove it if the upload failed)
+    pub fn finish_upload(&mut self, id: RequestId, markdown: Option<String>) {
+        let Some(pos) = self.uploads.iter().position(|(upload, _)| *upload == id) else { return };
+        let (_, placeholder) = self.uploads.remove(pos);
+        self.new_comment = self.new_comment.replacen(&placeholder, markdown.as_deref().unwrap_or_default(), 1);
+    }
+    
+    /// Start uploading files dropped onto the window, marking each in the comment
+    fn accept_dropped_files(&mut self, files: Vec<egui::DroppedFile>, i18n: &I18n) {
+        for file in files {
+            let Some(path) = file.path else { continue };
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or(file.name);
+            let id = next_request_id();
+            let placeholder = format!("![{}]()", i18n.tf("issues.uploading", &[&name]));
+            if !self.new_comment.is_empty() && !self.new_comment.ends_with('\n') {
+                self.new_comment.push('\n');
             }
+            self.new_comment.push_str(&placeholder);
+            self.new_comment.push('\n');
+            self.uploads.push((id, placeholder));
+            let _ = self.action_tx.try_send(AppAction::UploadAttachment(id, name, path));
         }
     }
     
     pub fn add_comment(&mut self, comment: IssueComment) {
         self.comments.push(comment);
         self.new_comment.clear();
+        self.preview_comment = false;
     }
     
     pub fn update_issue(&mut self, updated: Issue) {
@@ -77,22 +230,23 @@ impl IssuesPanel {
             self.issues[pos] = updated.clone();
         }
         // Update selected
-        if let Some(ref mut selected) = self.selected_issue {
-            if selected.number == updated.number {
-                *selected = updated;
-            }
+        if let Some(ref mut selected) = self.selected_issue
+            && selected.number == updated.number
+        {
+            *selected = updated;
         }
     }
     
-    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+    /// `labels` are the repo's labels offered by the label filter
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n, markdown_cache: &mut CommonMarkCache, labels: &[IssueLabel]) {
         if self.selected_issue.is_some() {
-            self.show_detail(ui, i18n);
+            self.show_detail(ui, i18n, markdown_cache);
         } else {
-            self.show_list(ui, i18n);
+            self.show_list(ui, i18n, labels);
         }
     }
     
-    fn show_list(&mut self, ui: &mut egui::Ui, _i18n: &I18n) {
+    fn show_list(&mut self, ui: &mut egui::Ui, i18n: &I18n, labels: &[IssueLabel]) {
         ui.vertical(|ui| {
             // Header
             ui.horizontal(|ui| {
@@ -109,54 +263,217 @@ impl IssuesPanel {
                         .fill(if is_selected { Color32::from_rgba_unmultiplied(0, 60, 80, 100) } else { Color32::TRANSPARENT })
                     ).clicked() {
                         self.filter_state = state.to_string();
-                        self.loading = true;
-                        let _ = self.action_tx.try_send(AppAction::FetchIssues(
-                            self.current_repo.clone(),
-                            state.to_string()
-                        ));
+                        self.fetch_issues();
+                    }
+                }
+                
+                // Milestone filter
+                if !self.milestones.is_empty() {
+                    let previous = self.milestone_filter.clone();
+                    let selected_text = match self.milestone_filter.as_str() {
+                        "" => format!("🎯 {}", i18n.t("issues.all_milestones")),
+                        "none" => format!("🎯 {}", i18n.t("issues.no_milestone")),
+                        number => self.milestones.iter()
+                            .find(|m| m.number.to_string() == number)
+                            .map(|m| format!("🎯 {}", m.title))
+                            .unwrap_or_else(|| format!("🎯 #{}", number)),
+                    };
+                    egui::ComboBox::from_id_salt("issue_milestone_filter")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.milestone_filter, String::new(), i18n.t("issues.all_milestones"));
+                            ui.selectable_value(&mut self.milestone_filter, "none".to_string(), i18n.t("issues.no_milestone"));
+                            for milestone in &self.milestones {
+                                ui.selectable_value(&mut self.milestone_filter, milestone.number.to_string(), &milestone.title);
+                            }
+                        });
+                    if self.milestone_filter != previous {
+                        self.fetch_issues();
+                    }
+                }
+                
+                // Label filter (multi-select)
+                if !labels.is_empty() {
+                    let mut changed = false;
+                    ui.menu_button(i18n.t("issues.labels"), |ui| {
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            for label in labels {
+                                let mut checked = self.label_filter.contains(&label.name);
+                                let text = RichText::new(&label.name).color(parse_label_color(&label.color));
+                                if ui.checkbox(&mut checked, text).changed() {
+                                    if checked {
+                                        self.label_filter.push(label.name.clone());
+                                    } else {
+                                        self.label_filter.retain(|name| name != &label.name);
+                                    }
+                                    changed = true;
+                                }
+                            }
+                        });
+                    });
+                    if changed {
+                        self.fetch_issues();
                     }
                 }
                 
+                if let Some(name) = save_as_menu(ui, i18n, &mut self.save_name, i18n.t("common.save_filter")) {
+                    self.save_request = Some(SavedSearch::Issues {
+                        name,
+                        repo: self.current_repo.clone(),
+                        state: self.filter_state.clone(),
+                        milestone: self.milestone_filter.clone(),
+                        labels: self.label_filter.clone(),
+                    });
+                }
+                
                 if self.loading {
                     ui.spinner();
                 }
             });
             
+            // Active label filters as removable chips
+            if !self.label_filter.is_empty() {
+                let mut remove = None;
+                ui.horizontal_wrapped(|ui| {
+                    for name in &self.label_filter {
+                        let color = labels.iter()
+                            .find(|l| &l.name == name)
+                            .map(|l| parse_label_color(&l.color))
+                            .unwrap_or(colors::ACCENT);
+                        let chip = RichText::new(format!(" {} ✕", name)).size(11.0).color(color)
+                            .background_color(color.gamma_multiply(0.2));
+                        if ui.add(egui::Label::new(chip).sense(Sense::click())).on_hover_text(i18n.t("common.remove_filter")).clicked() {
+                            remove = Some(name.clone());
+                        }
+                    }
+                    if ui.small_button(i18n.t("common.clear")).clicked() {
+                        self.label_filter.clear();
+                        self.fetch_issues();
+                    }
+                });
+                if let Some(name) = remove {
+                    self.label_filter.retain(|n| n != &name);
+                    self.fetch_issues();
+                }
+            }
+            
+            if !self.selection.is_empty() {
+                self.show_bulk_bar(ui, i18n, labels);
+            }
+            
             ui.separator();
             
             // Issues list
-            ScrollArea::vertical().id_salt("issues_list").show(ui, |ui| {
+            let pulled = super::components::pull_to_refresh(ui, "issues_list", self.loading, |ui| {
                 ui.set_width(ui.available_width());
                 
                 if self.issues.is_empty() && !self.loading {
-                    ui.colored_label(Color32::GRAY, "暂无 Issues");
+                    ui.colored_label(Color32::GRAY, i18n.t("issues.empty"));
                 }
                 
+                let mut clicked = None;
+                let mut toggled = None;
                 for issue in &self.issues {
-                    if self.render_issue_card(ui, issue) {
-                        self.selected_issue = Some(issue.clone());
-                        self.comments.clear();
-                        self.loading_comments = true;
-                        let _ = self.action_tx.try_send(AppAction::FetchIssueComments(
-                            self.current_repo.clone(),
-                            issue.number
-                        ));
-                    }
+                    ui.horizontal(|ui| {
+                        let mut checked = self.selection.contains(&issue.number);
+                        let checkbox = ui.add_enabled(!self.bulk_running, egui::Checkbox::without_text(&mut checked));
+                        if checkbox.on_hover_text(i18n.t("issues.select_for_bulk")).changed() {
+                            toggled = Some(issue.number);
+                        }
+                        if self.render_issue_card(ui, i18n, issue) {
+                            clicked = Some(issue.clone());
+                        }
+                    });
                     ui.add_space(4.0);
                 }
+                if let Some(number) = toggled
+                    && !self.selection.remove(&number)
+                {
+                    self.selection.insert(number);
+                }
+                if let Some(issue) = clicked {
+                    self.select_issue(issue);
+                }
+            });
+            if pulled {
+                self.fetch_issues();
+            }
+        });
+    }
+    
+    /// Actions for the ticked issues
+    fn show_bulk_bar(&mut self, ui: &mut egui::Ui, i18n: &I18n, labels: &[IssueLabel]) {
+        let mut op = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(i18n.tf("issues.selected", &[&self.selection.len()])).size(12.0).color(colors::ACCENT));
+            if self.bulk_running {
+                ui.spinner();
+                if ui.small_button("✕").on_hover_text(i18n.t("issues.stop_bulk")).clicked() {
+                    let _ = self.action_tx.try_send(AppAction::CancelOperation(Operation::Bulk));
+                }
+                return;
+            }
+            if ui.small_button(i18n.t("common.select_all")).clicked() {
+                self.selection = self.issues.iter().map(|i| i.number).collect();
+            }
+            if ui.small_button(i18n.t("common.clear")).clicked() {
+                self.selection.clear();
+            }
+            ui.separator();
+            
+            if ui.button(i18n.t("issues.bulk_close")).clicked() {
+                op = Some(BulkIssueOp::Close);
+            }
+            if !labels.is_empty() {
+                ui.menu_button(i18n.t("issues.add_label"), |ui| {
+                    ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        for label in labels {
+                            let text = RichText::new(&label.name).color(parse_label_color(&label.color));
+                            if ui.button(text).clicked() {
+                                op = Some(BulkIssueOp::AddLabel(label.name.clone()));
+                                ui.close();
+                            }
+                        }
+                    });
+                });
+            }
+            ui.menu_button(i18n.t("issues.milestone"), |ui| {
+                for milestone in &self.milestones {
+                    if ui.button(&milestone.title).clicked() {
+                        op = Some(BulkIssueOp::SetMilestone(Some(milestone.number)));
+                        ui.close();
+                    }
+                }
+                if !self.milestones.is_empty() {
+                    ui.separator();
+                }
+                if ui.button(i18n.t("issues.clear_milestone")).clicked() {
+                    op = Some(BulkIssueOp::SetMilestone(None));
+                    ui.close();
+                }
             });
         });
+        if let Some(op) = op {
+            self.run_bulk(op);
+        }
     }
     
-    fn render_issue_card(&self, ui: &mut egui::Ui, issue: &Issue) -> bool {
+    fn render_issue_card(&self, ui: &mut egui::Ui, i18n: &I18n, issue: &Issue) -> bool {
         let h = 60.0;
         let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+        super::components::describe(ui, &response, &format!("Issue #{}: {} ({})", issue.number, issue.title, issue.state));
         
         let painter = ui.painter();
-        let is_hovered = response.hovered();
+        let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+        // Comments of an issue the pointer rests on, so opening it is instant
+        if super::components::hover_intent(ui, &response) {
+            let _ = self.action_tx.try_send(AppAction::PrefetchComments(self.current_repo.clone(), issue.number));
+        }
         
         let bg_color = if is_hovered {
-            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
             Color32::from_rgba_unmultiplied(0, 50, 60, 180)
         } else {
             Color32::from_rgb(8, 12, 18)
@@ -166,12 +483,13 @@ impl IssuesPanel {
         painter.rect_filled(rect, 4.0, bg_color);
         
         // Status strip
+        let rtl = i18n.is_rtl();
         let strip_color = if issue.state == "open" {
             Color32::from_rgb(0, 200, 100) // Green for open
         } else {
             Color32::from_rgb(150, 80, 150) // Purple for closed
         };
-        let strip_rect = egui::Rect::from_min_size(rect.min, Vec2::new(3.0, rect.height()));
+        let strip_rect = super::components::leading_edge(rect, rtl, 3.0);
         painter.rect_filled(strip_rect, 0.0, strip_color);
         
         // Border
@@ -179,13 +497,16 @@ impl IssuesPanel {
         
         // Content
         let content_rect = rect.shrink2(Vec2::new(12.0, 6.0));
-        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(content_rect), |ui| {
-            ui.horizontal(|ui| {
-                ui.vertical(|ui| {
+        ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+            super::components::row(ui, rtl, |ui| {
+                // Nested rows follow the column's alignment, so they mirror too
+                ui.with_layout(super::components::column(rtl), |ui| {
                     // Title
                     ui.horizontal(|ui| {
                         ui.label(RichText::new(format!("#{}", issue.number)).size(12.0).color(Color32::GRAY));
-                        ui.label(RichText::new(&issue.title).size(13.0).color(Color32::WHITE).strong());
+                        // The card is one row high; long titles are cut and shown whole on hover
+                        ui.label(RichText::new(truncate_chars(&issue.title, 60)).size(13.0).color(Color32::WHITE).strong())
+                            .on_hover_text(&issue.title);
                     });
                     
                     // Labels
@@ -196,15 +517,22 @@ impl IssuesPanel {
                                 .background_color(color.gamma_multiply(0.2)));
                         }
                         
+                        // Milestone
+                        if let Some(milestone) = &issue.milestone {
+                            ui.label(RichText::new(format!("🎯 {}", milestone.title)).size(10.0).color(colors::ACCENT_DIM));
+                        }
+                        
                         // Comment count
                         if issue.comments > 0 {
-                            ui.label(RichText::new(format!("💬 {}", issue.comments)).size(10.0).color(Color32::GRAY));
+                            ui.label(RichText::new(format!("💬 {}", i18n.count(issue.comments))).size(10.0).color(Color32::GRAY));
                         }
                     });
                 });
                 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.with_layout(super::components::trailing(rtl), |ui| {
+                    timestamp(ui, i18n, &issue.updated_at);
                     ui.label(RichText::new(&issue.user.login).size(10.0).color(Color32::DARK_GRAY));
+                    avatar(ui, &issue.user.login, &issue.user.avatar_url, 20.0);
                 });
             });
         });
@@ -212,13 +540,14 @@ impl IssuesPanel {
         response.clicked()
     }
     
-    fn show_detail(&mut self, ui: &mut egui::Ui, _i18n: &I18n) {
+    fn show_detail(&mut self, ui: &mut egui::Ui, i18n: &I18n, markdown_cache: &mut CommonMarkCache) {
         let issue = self.selected_issue.clone().unwrap();
+        let web_base = crate::config::current().web_base();
         
         ui.vertical(|ui| {
             // Back button + title
             ui.horizontal(|ui| {
-                if CyberButton::new("← 返回").min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
+                if CyberButton::new(i18n.t("common.back")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
                     self.selected_issue = None;
                     self.comments.clear();
                 }
@@ -237,22 +566,47 @@ impl IssuesPanel {
                 // Issue body
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
+                        avatar(ui, &issue.user.login, &issue.user.avatar_url, 20.0);
                         ui.label(RichText::new(&issue.user.login).size(12.0).color(colors::ACCENT_DIM));
-                        ui.label(RichText::new(&issue.created_at[..10]).size(10.0).color(Color32::DARK_GRAY));
+                        timestamp(ui, i18n, &issue.created_at);
+                    });
+                    
+                    // Milestone assignment
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(i18n.t("issues.milestone_label")).size(11.0).color(colors::TEXT_MUTED));
+                        let current = issue.milestone.as_ref().map(|m| m.number);
+                        let mut selected = current;
+                        egui::ComboBox::from_id_salt("issue_milestone_assign")
+                            .selected_text(issue.milestone.as_ref().map(|m| m.title.as_str()).unwrap_or(i18n.t("common.none")))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut selected, None, i18n.t("common.none"));
+                                for milestone in &self.milestones {
+                                    ui.selectable_value(&mut selected, Some(milestone.number), &milestone.title);
+                                }
+                            });
+                        if selected != current {
+                            let _ = self.action_tx.try_send(AppAction::SetIssueMilestone(
+                                self.current_repo.clone(),
+                                issue.number,
+                                selected
+                            ));
+                        }
                     });
+                    
                     ui.separator();
-                    if let Some(body) = &issue.body {
-                        ui.style_mut().wrap = Some(true);
-                        ui.label(body);
+                    if let Some(body) = issue.body.as_deref().filter(|b| !b.trim().is_empty()) {
+                        ui.push_id(("issue_body", issue.number), |ui| {
+                            CommonMarkViewer::new().show(ui, markdown_cache, &linkify(body, &self.current_repo, &web_base));
+                        });
                     } else {
-                        ui.colored_label(Color32::GRAY, "(无描述)");
+                        ui.colored_label(Color32::GRAY, i18n.t("common.no_description"));
                     }
                 });
                 
                 ui.add_space(10.0);
                 
                 // Comments
-                ui.label(RichText::new(format!("💬 评论 ({})", self.comments.len())).size(14.0).color(colors::TEXT_MUTED));
+                ui.label(RichText::new(i18n.tf("common.comments", &[&self.comments.len()])).size(14.0).color(colors::TEXT_MUTED));
                 ui.separator();
                 
                 if self.loading_comments {
@@ -262,47 +616,79 @@ impl IssuesPanel {
                 for comment in &self.comments {
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
+                            avatar(ui, &comment.user.login, &comment.user.avatar_url, 20.0);
                             ui.label(RichText::new(&comment.user.login).size(12.0).color(colors::ACCENT_DIM));
-                            ui.label(RichText::new(&comment.created_at[..10]).size(10.0).color(Color32::DARK_GRAY));
+                            timestamp(ui, i18n, &comment.created_at);
                         });
                         ui.separator();
-                        ui.style_mut().wrap = Some(true);
-                        ui.label(&comment.body);
+                        ui.push_id(("issue_comment", comment.id), |ui| {
+                            CommonMarkViewer::new().show(ui, markdown_cache, &linkify(&comment.body, &self.current_repo, &web_base));
+                        });
                     });
                     ui.add_space(5.0);
                 }
                 
                 ui.add_space(20.0);
                 
-                // New comment input
-                ui.label(RichText::new("添加评论:").size(12.0).color(colors::TEXT_MUTED));
-                let input = TextEdit::multiline(&mut self.new_comment)
-                    .desired_width(ui.available_width())
-                    .desired_rows(3)
-                    .hint_text("输入评论内容...");
-                ui.add(input);
-                
+                // New comment input with Write / Preview tabs
                 ui.horizontal(|ui| {
-                    if CyberButton::new("发表评论").min_size(Vec2::new(100.0, 30.0)).show(ui).clicked() {
-                        if !self.new_comment.trim().is_empty() {
-                            let _ = self.action_tx.try_send(AppAction::CreateComment(
-                                self.current_repo.clone(),
-                                issue.number,
-                                self.new_comment.clone()
-                            ));
+                    ui.label(RichText::new(i18n.t("issues.add_comment")).size(12.0).color(colors::TEXT_MUTED));
+                    ui.add_space(10.0);
+                    ui.selectable_value(&mut self.preview_comment, false, i18n.t("common.write"));
+                    ui.selectable_value(&mut self.preview_comment, true, i18n.t("common.preview"));
+                });
+                if self.preview_comment {
+                    ui.group(|ui| {
+                        ui.set_width(ui.available_width());
+                        ui.set_min_height(60.0);
+                        if self.new_comment.trim().is_empty() {
+                            ui.colored_label(Color32::GRAY, i18n.t("issues.nothing_to_preview"));
+                        } else {
+                            ui.push_id(("comment_preview", issue.number), |ui| {
+                                CommonMarkViewer::new().show(ui, markdown_cache, &linkify(&self.new_comment, &self.current_repo, &web_base));
+                            });
                         }
+                    });
+                } else {
+                    // Files dragged over the window are attached to the comment
+                    let (hovering, dropped) = ui.input(|i| (!i.raw.hovered_files.is_empty(), i.raw.dropped_files.clone()));
+                    let editor = self.mention_input.show(ui, "issue_comment_input", &mut self.new_comment, &self.mention_sources, i18n.t("issues.comment_hint"));
+                    if hovering {
+                        ui.painter().rect_stroke(editor.rect.expand(2.0), 4.0, Stroke::new(2.0, colors::ACCENT), egui::StrokeKind::Outside);
+                        ui.label(RichText::new(i18n.t("issues.drop_to_upload")).size(11.0).color(colors::ACCENT));
+                    }
+                    if !dropped.is_empty() {
+                        self.accept_dropped_files(dropped, i18n);
+                    }
+                }
+                if !self.uploads.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(RichText::new(i18n.tf("issues.uploading_count", &[&self.uploads.len()])).size(11.0).color(Color32::GRAY));
+                    });
+                }
+                
+                ui.horizontal(|ui| {
+                    if CyberButton::new(i18n.t("issues.post_comment")).min_size(Vec2::new(100.0, 30.0)).show(ui).clicked()
+                        && !self.new_comment.trim().is_empty()
+                    {
+                        let _ = self.action_tx.try_send(AppAction::CreateComment(
+                            self.current_repo.clone(),
+                            issue.number,
+                            self.new_comment.clone()
+                        ));
                     }
                     
                     ui.add_space(20.0);
                     
                     // Close/Reopen button
                     let (btn_text, new_state) = if issue.state == "open" {
-                        ("关闭 Issue", "closed")
+                        ("issues.close", "closed")
                     } else {
-                        ("重新打开", "open")
+                        ("issues.reopen", "open")
                     };
                     
-                    if CyberButton::new(btn_text).min_size(Vec2::new(100.0, 30.0)).show(ui).clicked() {
+                    if CyberButton::new(i18n.t(btn_text)).min_size(Vec2::new(100.0, 30.0)).show(ui).clicked() {
                         let _ = self.action_tx.try_send(AppAction::UpdateIssueState(
                             self.current_repo.clone(),
                             issue.number,
@@ -315,15 +701,16 @@ impl IssuesPanel {
     }
 }
 
-fn parse_label_color(hex: &str) -> Color32 {
-    if hex.len() == 6 {
-        if let (Ok(r), Ok(g), Ok(b)) = (
+/// Parse a GitHub label color ("rrggbb") into a Color32
+pub fn parse_label_color(hex: &str) -> Color32 {
+    if hex.len() == 6
+        && let (Ok(r), Ok(g), Ok(b)) = (
             u8::from_str_radix(&hex[0..2], 16),
             u8::from_str_radix(&hex[2..4], 16),
             u8::from_str_radix(&hex[4..6], 16),
-        ) {
-            return Color32::from_rgb(r, g, b);
-        }
+        )
+    {
+        return Color32::from_rgb(r, g, b);
     }
     Color32::GRAY
 }
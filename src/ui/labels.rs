@@ -0,0 +1,210 @@
+//! Labels UI Component
+//!
+//! Lists the labels of a repository and allows creating, editing and
+//! deleting them.
+
+use eframe::egui::{self, Color32, RichText, ScrollArea, TextEdit, Vec2};
+use crate::app_event::{AppAction, IssueLabel, RequestId, next_request_id};
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+
+use super::style::colors;
+use super::components::CyberButton;
+use super::issues::parse_label_color;
+
+/// Create / edit form state
+struct LabelForm {
+    original: Option<String>, // None = creating a new label
+    name: String,
+    color: Color32,
+    description: String,
+}
+
+/// Labels panel - manages the labels of a repository
+pub struct LabelsPanel {
+    pub labels: Vec<IssueLabel>,
+    pub loading: bool,
+    pub current_repo: String,
+    request: RequestId, // Latest list request; other responses are stale
+    form: Option<LabelForm>,
+    confirm_delete: Option<String>,
+    action_tx: Sender<AppAction>,
+}
+
+impl LabelsPanel {
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            labels: Vec::new(),
+            loading: false,
+            current_repo: String::new(),
+            request: 0,
+            form: None,
+            confirm_delete: None,
+            action_tx,
+        }
+    }
+    
+    pub fn set_repo(&mut self, repo: String) {
+        if self.current_repo != repo {
+            self.current_repo = repo.clone();
+            self.labels.clear();
+            self.form = None;
+            self.confirm_delete = None;
+            self.loading = true;
+            self.request = next_request_id();
+            let _ = self.action_tx.try_send(AppAction::FetchLabels(self.request, repo));
+        }
+    }
+    
+    pub fn set_labels(&mut self, request: RequestId, labels: Vec<IssueLabel>) {
+        if request != self.request {
+            return;
+        }
+        self.labels = labels;
+        self.loading = false;
+    }
+    
+    pub fn add_label(&mut self, label: IssueLabel) {
+        self.labels.push(label);
+        self.labels.sort_by_key(|l| l.name.to_lowercase());
+    }
+    
+    pub fn update_label(&mut self, previous_name: &str, label: IssueLabel) {
+        if let Some(existing) = self.labels.iter_mut().find(|l| l.name == previous_name) {
+            *existing = label;
+        }
+    }
+    
+    pub fn remove_label(&mut self, name: &str) {
+        self.labels.retain(|l| l.name != name);
+    }
+    
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ui.vertical(|ui| {
+            // Header
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("🏷 Labels").size(18.0).color(colors::ACCENT).strong());
+                
+                if self.loading {
+                    ui.spinner();
+                }
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(i18n.t("common.new")).clicked() {
+                        self.form = Some(LabelForm {
+                            original: None,
+                            name: String::new(),
+                            color: Color32::from_rgb(0, 240, 255),
+                            description: String::new(),
+                        });
+                    }
+                });
+            });
+            
+            ui.separator();
+            
+            if self.form.is_some() {
+                self.show_form(ui, i18n);
+                ui.separator();
+            }
+            
+            ScrollArea::vertical().id_salt("labels_list").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                
+                if self.labels.is_empty() && !self.loading {
+                    ui.colored_label(Color32::GRAY, i18n.t("labels.empty"));
+                }
+                
+                let mut edit = None;
+                for label in &self.labels {
+                    ui.horizontal(|ui| {
+                        let color = parse_label_color(&label.color);
+                        ui.label(RichText::new(format!(" {} ", label.name)).size(12.0).color(color)
+                            .background_color(color.gamma_multiply(0.2)));
+                        
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if self.confirm_delete.as_deref() == Some(label.name.as_str()) {
+                                if ui.small_button(i18n.t("common.cancel")).clicked() {
+                                    self.confirm_delete = None;
+                                }
+                                if ui.small_button(RichText::new(i18n.t("common.confirm_delete")).color(Color32::from_rgb(255, 80, 80))).clicked() {
+                                    let _ = self.action_tx.try_send(AppAction::DeleteLabel(
+                                        self.current_repo.clone(),
+                                        label.name.clone()
+                                    ));
+                                    self.confirm_delete = None;
+                                }
+                            } else {
+                                if ui.small_button("🗑").on_hover_text(i18n.t("common.delete")).clicked() {
+                                    self.confirm_delete = Some(label.name.clone());
+                                }
+                                if ui.small_button("✏").on_hover_text(i18n.t("common.edit")).clicked() {
+                                    edit = Some(label.clone());
+                                }
+                            }
+                        });
+                    });
+                    
+                    if let Some(desc) = label.description.as_ref().filter(|d| !d.is_empty()) {
+                        ui.label(RichText::new(desc).size(10.0).color(Color32::GRAY));
+                    }
+                    ui.add_space(4.0);
+                }
+                
+                if let Some(label) = edit {
+                    self.form = Some(LabelForm {
+                        original: Some(label.name.clone()),
+                        color: parse_label_color(&label.color),
+                        name: label.name,
+                        description: label.description.unwrap_or_default(),
+                    });
+                }
+            });
+        });
+    }
+    
+    fn show_form(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        let Some(form) = &mut self.form else { return };
+        let mut close = false;
+        
+        ui.group(|ui| {
+            let title = i18n.t(if form.original.is_some() { "labels.edit_title" } else { "labels.new_title" });
+            ui.label(RichText::new(title).size(12.0).color(colors::TEXT_MUTED));
+            
+            ui.horizontal(|ui| {
+                ui.color_edit_button_srgba(&mut form.color);
+                ui.add(TextEdit::singleline(&mut form.name).hint_text(i18n.t("labels.name_hint")).desired_width(ui.available_width()));
+            });
+            ui.add(TextEdit::singleline(&mut form.description).hint_text(i18n.t("labels.description_hint")).desired_width(ui.available_width()));
+            
+            // Preview
+            ui.label(RichText::new(format!(" {} ", if form.name.is_empty() { "preview" } else { &form.name }))
+                .size(12.0)
+                .color(form.color)
+                .background_color(form.color.gamma_multiply(0.2)));
+            
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.save")).min_size(Vec2::new(80.0, 28.0)).show(ui).clicked() && !form.name.trim().is_empty() {
+                    let label = IssueLabel {
+                        name: form.name.trim().to_string(),
+                        color: format!("{:02x}{:02x}{:02x}", form.color.r(), form.color.g(), form.color.b()),
+                        description: Some(form.description.trim().to_string()),
+                    };
+                    let action = match &form.original {
+                        Some(original) => AppAction::UpdateLabel(self.current_repo.clone(), original.clone(), label),
+                        None => AppAction::CreateLabel(self.current_repo.clone(), label),
+                    };
+                    let _ = self.action_tx.try_send(action);
+                    close = true;
+                }
+                if CyberButton::new(i18n.t("common.cancel")).min_size(Vec2::new(80.0, 28.0)).show(ui).clicked() {
+                    close = true;
+                }
+            });
+        });
+        
+        if close {
+            self.form = None;
+        }
+    }
+}
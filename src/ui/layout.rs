@@ -0,0 +1,122 @@
+//! Browsing Layout
+//!
+//! Where the Issues/PRs panel is docked and whether the README column is
+//! collapsed. Saved with the window state; panel sizes themselves are
+//! remembered by egui.
+//!
+//! Narrow windows (phones) get a compact layout instead: one view at a
+//! time, picked from a bottom nav bar, with the log folded into a bar.
+
+use eframe::egui::{self, RichText, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::I18n;
+
+/// Screen edge the Issues/PRs panel is docked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Dock {
+    #[default]
+    Right,
+    Left,
+    Bottom,
+}
+
+impl Dock {
+    /// Message key of the dock menu entry
+    pub fn label(&self) -> &'static str {
+        match self {
+            Dock::Right => "layout.dock_right",
+            Dock::Left => "layout.dock_left",
+            Dock::Bottom => "layout.dock_bottom",
+        }
+    }
+    
+    pub fn all() -> &'static [Dock] {
+        &[Dock::Right, Dock::Left, Dock::Bottom]
+    }
+}
+
+/// Below this window width the compact layout is used
+const COMPACT_WIDTH: f32 = 640.0;
+
+/// View shown in the compact layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Page {
+    #[default]
+    Home,     // Repo list, or the file browser inside a repo
+    Discover, // Search / trending
+    Panels,   // Issues, PRs, commits... of the open repo
+    Menu,     // Sidebar contents
+}
+
+impl Page {
+    /// Message key of the nav bar entry
+    pub fn label(&self, browsing: bool) -> &'static str {
+        match self {
+            Page::Home if browsing => "layout.files",
+            Page::Home => "layout.repos",
+            Page::Discover => "layout.discover",
+            Page::Panels => "layout.panels",
+            Page::Menu => "layout.menu",
+        }
+    }
+    
+    /// Nav bar entries on the repo list / inside a repo
+    pub fn all(browsing: bool) -> &'static [Page] {
+        if browsing {
+            &[Page::Home, Page::Panels, Page::Menu]
+        } else {
+            &[Page::Home, Page::Discover, Page::Menu]
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    pub dock: Dock,
+    pub readme_collapsed: bool, // File tree takes the whole column while no file is open
+    pub log_expanded: bool,     // Compact layout: log panel unfolded
+    #[serde(skip)]
+    pub page: Page,
+}
+
+impl Layout {
+    /// Whether the window is narrow enough for the compact (phone) layout
+    pub fn is_compact(ctx: &egui::Context) -> bool {
+        ctx.content_rect().width() < COMPACT_WIDTH
+    }
+    
+    /// Page to show, falling back to Home when it doesn't exist in this view
+    pub fn current_page(&self, browsing: bool) -> Page {
+        if Page::all(browsing).contains(&self.page) { self.page } else { Page::Home }
+    }
+    
+    /// Bottom nav bar of the compact layout
+    pub fn bottom_nav(&mut self, ui: &mut Ui, i18n: &I18n, browsing: bool) {
+        let current = self.current_page(browsing);
+        let pages = Page::all(browsing);
+        ui.columns(pages.len(), |columns| {
+            for (column, page) in columns.iter_mut().zip(pages) {
+                column.vertical_centered_justified(|ui| {
+                    let text = RichText::new(i18n.t(page.label(browsing))).size(14.0);
+                    if ui.add_sized([ui.available_width(), 36.0], egui::Button::selectable(current == *page, text)).clicked() {
+                        self.page = *page;
+                    }
+                });
+            }
+        });
+    }
+
+    /// Dock selector for the panel's tab bar
+    pub fn dock_menu(&mut self, ui: &mut Ui, i18n: &I18n) {
+        ui.menu_button(RichText::new("☰").color(egui::Color32::GRAY), |ui| {
+            for dock in Dock::all() {
+                if ui.selectable_label(self.dock == *dock, i18n.t(dock.label())).clicked() {
+                    self.dock = *dock;
+                    ui.close();
+                }
+            }
+        }).response.on_hover_text(i18n.t("layout.panel_position"));
+    }
+}
@@ -1,10 +1,75 @@
 use eframe::egui::{self, Color32, RichText, ScrollArea};
 use std::collections::VecDeque;
-use crate::i18n::I18n;
+use crate::app_event::{ErrorMessage, Operation};
+use crate::i18n::{I18n, Lang};
+
+/// Terminal commands with the message key of what they do, for `help`
+const HELP: &[(&str, &str)] = &[
+    ("open <owner/repo>", "help.open"),
+    ("home", "help.home"),
+    ("refresh", "help.refresh"),
+    ("search <query>", "help.search"),
+    ("issues [open|closed|all]", "help.issues"),
+    ("prs [open|closed|all]", "help.prs"),
+    ("i18n [lang]", "help.i18n"),
+    ("clear", "help.clear"),
+];
+
+/// A command typed into the log panel's input line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Open(String),
+    Home,
+    Refresh,
+    Search(String),
+    Issues(String), // State filter: "open", "closed" or "all"
+    PullRequests(String),
+    Translations(Option<Lang>), // Coverage of one language, or all of them
+}
+
+/// Parse one input line; `Err` carries the message to log
+fn parse_command(line: &str) -> Result<Option<Command>, ErrorMessage> {
+    let mut words = line.split_whitespace();
+    let Some(name) = words.next() else { return Ok(None) };
+    let rest: Vec<&str> = words.collect();
+    
+    let state = |rest: &[&str]| match rest {
+        [] => Ok("open".to_string()),
+        [state @ ("open" | "closed" | "all")] => Ok(state.to_string()),
+        _ => Err(ErrorMessage::from("cmd.bad_state")),
+    };
+    
+    match name.to_lowercase().as_str() {
+        "open" => match rest.as_slice() {
+            [repo] if repo.split('/').count() == 2 && !repo.starts_with('/') && !repo.ends_with('/') => {
+                Ok(Some(Command::Open(repo.to_string())))
+            }
+            _ => Err(("cmd.usage", vec!["open <owner/repo>".to_string()]).into()),
+        },
+        "home" | "back" => Ok(Some(Command::Home)),
+        "refresh" => Ok(Some(Command::Refresh)),
+        "search" if !rest.is_empty() => Ok(Some(Command::Search(rest.join(" ")))),
+        "search" => Err(("cmd.usage", vec!["search <query>".to_string()]).into()),
+        "issues" => state(&rest).map(|s| Some(Command::Issues(s))),
+        "prs" | "pulls" => state(&rest).map(|s| Some(Command::PullRequests(s))),
+        "i18n" => match rest.as_slice() {
+            [] => Ok(Some(Command::Translations(None))),
+            [code] => Lang::from_locale(code)
+                .map(|lang| Some(Command::Translations(Some(lang))))
+                .ok_or_else(|| ("cmd.unknown_lang", vec![code.to_string()]).into()),
+            _ => Err(("cmd.usage", vec!["i18n [lang]".to_string()]).into()),
+        },
+        _ => Err(("cmd.unknown", vec![name.to_string()]).into()),
+    }
+}
 
 pub struct LogViewer {
     logs: VecDeque<String>,
     max_logs: usize,
+    progress: Vec<(Operation, u64, Option<u64>)>, // (operation, current, total) of running operations
+    input: String,
+    history: Vec<String>,         // Submitted commands, oldest first
+    history_pos: Option<usize>,   // Entry recalled with the arrow keys
 }
 
 impl LogViewer {
@@ -12,6 +77,10 @@ impl LogViewer {
         Self {
             logs: VecDeque::new(),
             max_logs: 100,
+            progress: Vec::new(),
+            input: String::new(),
+            history: Vec::new(),
+            history_pos: None,
         }
     }
     
@@ -29,8 +98,34 @@ impl LogViewer {
         self.logs.push_back(format!("> {}", msg));
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+    /// Most recent log line, shown while the log is folded away
+    pub fn last(&self) -> Option<&str> {
+        self.logs.back().map(String::as_str)
+    }
+
+    /// Update the progress bar of `op`; it disappears once `current` reaches `total`
+    pub fn set_progress(&mut self, op: Operation, current: u64, total: Option<u64>) {
+        if total.is_some_and(|total| current >= total) {
+            self.finish_progress(op);
+        } else if let Some(entry) = self.progress.iter_mut().find(|(o, _, _)| *o == op) {
+            *entry = (op, current, total);
+        } else {
+            self.progress.push((op, current, total));
+        }
+    }
+    
+    pub fn finish_progress(&mut self, op: Operation) {
+        self.progress.retain(|(o, _, _)| *o != op);
+    }
+    
+    pub fn clear_progress(&mut self) {
+        self.progress.clear();
+    }
+
+    /// Render the log with the command line below it; returns a command to run
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) -> Option<Command> {
         self.init_logs(i18n);
+        let mut command = None;
         
         ui.vertical(|ui| {
             ui.label(
@@ -39,6 +134,29 @@ impl LogViewer {
                     .color(Color32::from_gray(100))
             );
             
+            for (op, current, total) in &self.progress {
+                // Downloads count bytes, everything else pages or tree levels
+                let text = match (op, total) {
+                    (Operation::Download | Operation::SaveFile, Some(total)) => format!("{} {}/{}", i18n.t(op.label()), i18n.file_size(*current), i18n.file_size(*total)),
+                    (Operation::Download | Operation::SaveFile, None) => format!("{} {}", i18n.t(op.label()), i18n.file_size(*current)),
+                    (Operation::Clone | Operation::Git | Operation::Upload, _) => format!("{} {}%", i18n.t(op.label()), current),
+                    (_, Some(total)) => format!("{} {}/{}", i18n.t(op.label()), current, total),
+                    (_, None) => format!("{} {}", i18n.t(op.label()), current),
+                };
+                let bar = match total {
+                    Some(total) if *total > 0 => egui::ProgressBar::new(*current as f32 / *total as f32),
+                    _ => egui::ProgressBar::new(0.0).animate(true),
+                };
+                ui.add(bar.desired_height(12.0).text(RichText::new(text).size(10.0).monospace()));
+            }
+            
+            // Command line pinned to the bottom, log fills the space above
+            egui::TopBottomPanel::bottom("log_command_line")
+                .frame(egui::Frame::NONE)
+                .show_inside(ui, |ui| {
+                    command = self.show_input(ui, i18n);
+                });
+            
             ScrollArea::vertical()
                 .stick_to_bottom(true)
                 .show(ui, |ui| {
@@ -53,5 +171,119 @@ impl LogViewer {
                     }
                 });
         });
+        
+        command
+    }
+    
+    fn show_input(&mut self, ui: &mut egui::Ui, i18n: &I18n) -> Option<Command> {
+        let mut command = None;
+        
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("$").color(Color32::from_rgb(0, 255, 136)).monospace());
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.input)
+                    .id_salt("log_command_input")
+                    .hint_text("help")
+                    .font(egui::FontId::monospace(13.0))
+                    .frame(false)
+                    .desired_width(f32::INFINITY)
+            );
+            
+            // Up / Down walk through earlier commands
+            if response.has_focus() && !self.history.is_empty() {
+                let (up, down) = ui.input(|i| (i.key_pressed(egui::Key::ArrowUp), i.key_pressed(egui::Key::ArrowDown)));
+                if up {
+                    let pos = self.history_pos.map_or(self.history.len() - 1, |p| p.saturating_sub(1));
+                    self.history_pos = Some(pos);
+                    self.input = self.history[pos].clone();
+                } else if down {
+                    match self.history_pos {
+                        Some(p) if p + 1 < self.history.len() => {
+                            self.history_pos = Some(p + 1);
+                            self.input = self.history[p + 1].clone();
+                        }
+                        _ => {
+                            self.history_pos = None;
+                            self.input.clear();
+                        }
+                    }
+                }
+            }
+            
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                let line = std::mem::take(&mut self.input);
+                command = self.submit(line.trim(), i18n);
+                response.request_focus();
+            }
+        });
+        
+        command
+    }
+    
+    /// Echo and parse a submitted line; commands handled here return None
+    fn submit(&mut self, line: &str, i18n: &I18n) -> Option<Command> {
+        self.history_pos = None;
+        if line.is_empty() {
+            return None;
+        }
+        if self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+        }
+        self.add_log(format!("$ {}", line));
+        
+        match line {
+            "help" | "?" => {
+                for (command, key) in HELP {
+                    self.add_log(format!("{:<24} {}", command, i18n.t(key)));
+                }
+                None
+            }
+            "clear" => {
+                self.logs.clear();
+                None
+            }
+            _ => match parse_command(line) {
+                Ok(command) => command,
+                Err(message) => {
+                    self.add_log(message.render(i18n));
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse_command("   "), Ok(None));
+        assert_eq!(parse_command("open octocat/hello"), Ok(Some(Command::Open("octocat/hello".into()))));
+        assert_eq!(parse_command("HOME"), Ok(Some(Command::Home)));
+        assert_eq!(parse_command("back"), Ok(Some(Command::Home)));
+        assert_eq!(parse_command("refresh"), Ok(Some(Command::Refresh)));
+        assert_eq!(parse_command("search  rust   gui "), Ok(Some(Command::Search("rust gui".into()))));
+        assert_eq!(parse_command("i18n"), Ok(Some(Command::Translations(None))));
+        assert_eq!(parse_command("i18n ja_JP"), Ok(Some(Command::Translations(Some(Lang::Ja)))));
+    }
+    
+    #[test]
+    fn state_filter_defaults_to_open() {
+        assert_eq!(parse_command("issues"), Ok(Some(Command::Issues("open".into()))));
+        assert_eq!(parse_command("issues closed"), Ok(Some(Command::Issues("closed".into()))));
+        assert_eq!(parse_command("pulls all"), Ok(Some(Command::PullRequests("all".into()))));
+        assert!(parse_command("prs merged").is_err());
+    }
+    
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse_command("open octocat").is_err());
+        assert!(parse_command("open /hello").is_err());
+        assert!(parse_command("open octocat/hello/extra").is_err());
+        assert!(parse_command("search").is_err());
+        assert!(parse_command("i18n xx").is_err());
+        assert!(parse_command("frobnicate").is_err());
     }
 }
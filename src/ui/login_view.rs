@@ -1,4 +1,4 @@
-use eframe::egui::{self, Color32, Rect, Response, RichText, Sense, Stroke, StrokeKind, Ui, Vec2};
+use eframe::egui::{self, Color32, Rect, RichText, Sense, Stroke, StrokeKind, Ui, Vec2};
 use crate::i18n::{I18n, Lang};
 use super::components::CyberButton;
 
@@ -44,7 +44,7 @@ pub fn render_login(ui: &mut Ui, error: &Option<String>, i18n: &mut I18n) -> Log
         draw_tech_border(ui, rect, Color32::from_rgb(0, 240, 255));
         
         // Draw Text centered in rect
-        ui.allocate_new_ui(eframe::egui::UiBuilder::new().max_rect(rect), |ui| {
+        ui.scope_builder(eframe::egui::UiBuilder::new().max_rect(rect), |ui| {
             ui.centered_and_justified(|ui| {
                  ui.label(
                     RichText::new(i18n.t("app.title"))
@@ -107,51 +107,3 @@ fn draw_tech_border(ui: &mut Ui, rect: Rect, color: Color32) {
     let inner_stroke = Stroke::new(1.0, color.gamma_multiply(0.5));
     painter.rect_stroke(inner_rect, 0.0, inner_stroke, StrokeKind::Middle);
 }
-
-fn draw_tech_button(ui: &mut Ui, text: &str) -> Response {
-    let desired_size = Vec2::new(300.0, 60.0);
-    let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
-    
-    // Set cursor to pointer on hover
-    if response.hovered() {
-        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-    }
-    
-    let (color, _bg_alpha) = if response.hovered() {
-        (Color32::from_rgb(0, 255, 255), 1.0)
-    } else {
-        (Color32::from_rgb(0, 200, 220), 1.0)
-    };
-    
-    let painter = ui.painter();
-    
-    // Draw Background FIRST to be behind everything
-    if response.is_pointer_button_down_on() {
-        painter.rect_filled(rect, 4.0, Color32::from_rgb(40, 0, 20));
-    } else {
-        painter.rect_filled(rect, 4.0, Color32::from_rgb(5, 10, 15));
-    }
-    
-    // Glow effect on hover
-    if response.hovered() {
-         painter.rect_stroke(rect.expand(2.0), 2.0, Stroke::new(2.0, color.gamma_multiply(0.3)), StrokeKind::Middle);
-         painter.rect_stroke(rect.expand(4.0), 4.0, Stroke::new(4.0, color.gamma_multiply(0.1)), StrokeKind::Middle);
-    }
-    
-    // Border
-    painter.rect_stroke(rect, 4.0, Stroke::new(1.5, color), StrokeKind::Middle);
-
-    // Draw Icon + Text using painter.text (no nested Ui)
-    let icon = "⚡";
-    let full_text = format!("{} {}", icon, text);
-    
-    painter.text(
-        rect.center(),
-        egui::Align2::CENTER_CENTER,
-        full_text,
-        egui::FontId::proportional(20.0),
-        color,
-    );
-
-    response
-}
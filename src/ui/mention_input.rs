@@ -0,0 +1,187 @@
+//! Mention Input UI Component
+//!
+//! Multiline comment box that completes `@user` from the repo's collaborators
+//! and `#123` from its recent issues. Arrow keys move through the popup,
+//! Tab/Enter insert the highlighted entry, Escape dismisses it.
+
+use eframe::egui::{self, Color32, RichText, TextEdit};
+use egui::text::{CCursor, CCursorRange};
+
+use super::style::colors;
+
+/// Entries shown in the popup
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Completion sources for one repository
+#[derive(Default)]
+pub struct MentionSources {
+    pub users: Vec<String>,          // Logins
+    pub issues: Vec<(u32, String)>, // (number, title), issues and pull requests
+}
+
+/// The reference being typed before the cursor
+struct Query {
+    trigger: char, // '@' or '#'
+    start: usize,  // Byte offset of the trigger
+    end: usize,    // Byte offset of the cursor
+    text: String,  // Typed after the trigger
+}
+
+/// Per-box completion state
+#[derive(Default)]
+pub struct MentionInput {
+    query: Option<Query>,
+    selected: usize,
+    dismissed: Option<usize>, // Trigger offset the user closed the popup for
+}
+
+impl MentionInput {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        id_salt: impl std::hash::Hash,
+        text: &mut String,
+        sources: &MentionSources,
+        hint: &str,
+    ) -> egui::Response {
+        let id = ui.make_persistent_id(id_salt);
+        let suggestions = self.suggestions(sources);
+        
+        // Popup keys have to be taken before the text edit sees them
+        let mut accept = None;
+        if !suggestions.is_empty() && ui.memory(|m| m.has_focus(id)) {
+            ui.input_mut(|i| {
+                if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+                    self.selected = (self.selected + 1) % suggestions.len();
+                }
+                if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+                    self.selected = (self.selected + suggestions.len() - 1) % suggestions.len();
+                }
+                if i.consume_key(egui::Modifiers::NONE, egui::Key::Tab)
+                    || i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)
+                {
+                    accept = Some(self.selected);
+                }
+                if i.consume_key(egui::Modifiers::NONE, egui::Key::Escape) {
+                    self.dismissed = self.query.as_ref().map(|q| q.start);
+                }
+            });
+        }
+        if let Some(index) = accept {
+            self.insert(ui.ctx(), id, text, &suggestions[index].0);
+        }
+        
+        let output = TextEdit::multiline(text)
+            .id(id)
+            .desired_width(ui.available_width())
+            .desired_rows(3)
+            .hint_text(hint)
+            .show(ui);
+        
+        // Find the reference under the cursor
+        let cursor = output.cursor_range.filter(|_| output.response.has_focus()).map(|range| range.primary);
+        let query = cursor.and_then(|cursor| find_query(text, cursor.index));
+        if query.as_ref().map(|q| q.start) != self.query.as_ref().map(|q| q.start) {
+            self.selected = 0;
+        }
+        if self.dismissed.is_some() && self.dismissed != query.as_ref().map(|q| q.start) {
+            self.dismissed = None;
+        }
+        self.query = query;
+        
+        // Popup under the cursor
+        let suggestions = self.suggestions(sources);
+        if let (false, Some(cursor)) = (suggestions.is_empty(), cursor) {
+            let pos = output.galley_pos + output.galley.pos_from_cursor(cursor).left_bottom().to_vec2();
+            let mut clicked = None;
+            egui::Area::new(id.with("mention_popup"))
+                .order(egui::Order::Foreground)
+                .fixed_pos(pos)
+                .show(ui.ctx(), |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        for (i, (insertion, label)) in suggestions.iter().enumerate() {
+                            let text = RichText::new(label).size(12.0).color(if i == self.selected { colors::ACCENT } else { Color32::LIGHT_GRAY });
+                            if ui.selectable_label(i == self.selected, text).clicked() {
+                                clicked = Some(insertion.clone());
+                            }
+                        }
+                    });
+                });
+            if let Some(insertion) = clicked {
+                self.insert(ui.ctx(), id, text, &insertion);
+                ui.memory_mut(|m| m.request_focus(id));
+            }
+        }
+        
+        output.response
+    }
+    
+    /// (text to insert, popup label) for the current query
+    fn suggestions(&self, sources: &MentionSources) -> Vec<(String, String)> {
+        let Some(query) = self.query.as_ref().filter(|q| self.dismissed != Some(q.start)) else {
+            return Vec::new();
+        };
+        let needle = query.text.to_lowercase();
+        
+        match query.trigger {
+            '@' => {
+                let mut users: Vec<&String> = sources.users.iter()
+                    .filter(|login| login.to_lowercase().contains(&needle))
+                    .collect();
+                users.sort_by_key(|login| !login.to_lowercase().starts_with(&needle));
+                users.into_iter()
+                    .take(MAX_SUGGESTIONS)
+                    .map(|login| (format!("@{} ", login), format!("@{}", login)))
+                    .collect()
+            }
+            _ => sources.issues.iter()
+                .filter(|(number, title)| number.to_string().starts_with(&needle) || title.to_lowercase().contains(&needle))
+                .take(MAX_SUGGESTIONS)
+                .map(|(number, title)| (format!("#{} ", number), format!("#{} {}", number, title)))
+                .collect(),
+        }
+    }
+    
+    /// Replace the typed reference with `insertion` and put the cursor after it
+    fn insert(&mut self, ctx: &egui::Context, id: egui::Id, text: &mut String, insertion: &str) {
+        let Some(query) = self.query.take() else { return };
+        if query.end > text.len() {
+            return;
+        }
+        text.replace_range(query.start..query.end, insertion);
+        
+        let cursor = CCursor::new(text[..query.start + insertion.len()].chars().count());
+        if let Some(mut state) = egui::text_edit::TextEditState::load(ctx, id) {
+            state.cursor.set_char_range(Some(CCursorRange::one(cursor)));
+            state.store(ctx, id);
+        }
+        self.selected = 0;
+    }
+}
+
+/// The `@word` / `#word` directly before char index `cursor`, if any
+fn find_query(text: &str, cursor: usize) -> Option<Query> {
+    let end = text.char_indices().nth(cursor).map(|(i, _)| i).unwrap_or(text.len());
+    let before = &text[..end];
+    
+    let (start, trigger) = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '-' || *c == '_'))?;
+    if trigger != '@' && trigger != '#' {
+        return None;
+    }
+    
+    // Only at the start of a word, so e-mail addresses and URLs don't trigger
+    let preceded_ok = before[..start].chars().next_back().is_none_or(|c| c.is_whitespace() || c == '(');
+    if !preceded_ok {
+        return None;
+    }
+    
+    Some(Query {
+        trigger,
+        start,
+        end,
+        text: before[start + 1..].to_string(),
+    })
+}
@@ -10,46 +10,30 @@ pub mod effects;
 pub mod style;
 pub mod components;
 pub mod file_browser;
+pub mod code_viewer;
+pub mod repo_tree;
 pub mod search;
+pub mod trending;
 pub mod issues;
+pub mod mention_input;
+pub mod references;
+pub mod labels;
 pub mod pull_requests;
+pub mod commits;
+pub mod diff_view;
+pub mod discussions;
+pub mod topics_editor;
+pub mod settings;
+pub mod history;
+pub mod layout;
 pub mod image_loader;
+pub mod avatar_cache;
 
-use eframe::egui::{self, Color32, FontData, FontDefinitions, FontFamily};
+use eframe::egui::{self, Color32};
 pub use app::NativeHubApp;
 
-/// Configure fonts to include CJK support for Chinese language
-pub fn configure_fonts(ctx: &egui::Context) {
-    let mut fonts = FontDefinitions::default();
-    
-    // Try to load Microsoft YaHei from Windows fonts folder
-    // This is pre-installed on all Windows systems
-    let font_path = std::path::Path::new("C:/Windows/Fonts/msyh.ttc");
-    
-    if let Ok(font_data) = std::fs::read(font_path) {
-        fonts.font_data.insert(
-            "Microsoft YaHei".to_owned(),
-            FontData::from_owned(font_data).into(),
-        );
-        
-        // Add to proportional fonts (for UI text)
-        fonts.families
-            .entry(FontFamily::Proportional)
-            .or_default()
-            .push("Microsoft YaHei".to_owned());
-            
-        tracing::info!("Loaded Microsoft YaHei font for CJK support");
-    } else {
-        tracing::warn!("Could not load CJK font - Chinese may display as tofu");
-    }
-    
-    ctx.set_fonts(fonts);
-}
-
 /// Configure the application style for a geek/terminal aesthetic
 pub fn configure_style(ctx: &egui::Context) {
-    configure_fonts(ctx);
-
     let mut style = (*ctx.style()).clone();
     
     // Darker, more "terminal" background colors
@@ -1,24 +1,51 @@
 //! Pull Requests UI Component
 //!
-//! Displays pull requests list and allows merge/close actions.
+//! Displays pull requests list, changed files, and allows merge/close actions.
 
-use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
-use crate::app_event::{AppAction, PullRequest, MergeResult};
+use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, TextEdit, Vec2};
+use crate::app_event::{AppAction, Branch, PullRequest, MergeResult, CheckRun, CombinedStatus, ChangedFile, RequestId, next_request_id};
 use crate::i18n::I18n;
 use tokio::sync::mpsc::Sender;
 
 use super::style::colors;
-use super::components::CyberButton;
+use super::components::{CyberButton, avatar};
+use super::diff_view::render_changed_file;
+
+/// "New PR" form state
+#[derive(Default)]
+pub struct NewPrForm {
+    pub base: String,
+    pub head: String,
+    pub title: String,
+    pub body: String,
+    pub submitting: bool,
+}
 
 /// Pull Requests panel
 pub struct PullRequestsPanel {
     pub pull_requests: Vec<PullRequest>,
     pub loading: bool,
     pub current_repo: String,
+    request: RequestId, // Latest list request; other responses are stale
     pub filter_state: String, // "open", "closed", "all"
     
     // Detail view
     pub selected_pr: Option<PullRequest>,
+    pub check_runs: Vec<CheckRun>,
+    pub combined_status: CombinedStatus,
+    pub loading_checks: bool,
+    pub detail_tab: u8, // 0 = Overview, 1 = Files changed
+    pub files: Vec<ChangedFile>,
+    pub loading_files: bool,
+    files_loaded_for: Option<u32>, // PR number the files list belongs to
+    updating_branch: Option<u32>,  // PR whose branch is being updated from base
+    pending_open: Option<u32>,     // Referenced PR to select once it has been fetched
+    
+    // Create view
+    pub branches: Vec<Branch>,
+    loading_branches: bool,
+    pub default_branch: String, // Of the open repo; preselected as base
+    pub new_pr: Option<NewPrForm>,
     
     action_tx: Sender<AppAction>,
 }
@@ -29,8 +56,22 @@ impl PullRequestsPanel {
             pull_requests: Vec::new(),
             loading: false,
             current_repo: String::new(),
+            request: 0,
             filter_state: "open".to_string(),
             selected_pr: None,
+            check_runs: Vec::new(),
+            combined_status: CombinedStatus::default(),
+            loading_checks: false,
+            detail_tab: 0,
+            files: Vec::new(),
+            loading_files: false,
+            files_loaded_for: None,
+            updating_branch: None,
+            pending_open: None,
+            branches: Vec::new(),
+            loading_branches: false,
+            default_branch: String::new(),
+            new_pr: None,
             action_tx,
         }
     }
@@ -40,26 +81,194 @@ impl PullRequestsPanel {
             self.current_repo = repo.clone();
             self.pull_requests.clear();
             self.selected_pr = None;
-            self.loading = true;
-            let _ = self.action_tx.try_send(AppAction::FetchPullRequests(repo, self.filter_state.clone()));
+            self.pending_open = None;
+            self.branches.clear();
+            self.new_pr = None;
+            self.fetch_pull_requests();
         }
     }
     
-    pub fn set_pull_requests(&mut self, prs: Vec<PullRequest>) {
-        self.pull_requests = prs;
-        self.loading = false;
+    /// Show the list with another state filter, leaving any open PR
+    pub fn set_filter_state(&mut self, state: &str) {
+        self.selected_pr = None;
+        self.new_pr = None;
+        if self.filter_state != state {
+            self.filter_state = state.to_string();
+            self.fetch_pull_requests();
+        }
     }
     
-    pub fn on_pr_merged(&mut self, _result: MergeResult) {
-        // Refresh the list after merge
+    fn fetch_pull_requests(&mut self) {
         self.loading = true;
+        self.request = next_request_id();
         let _ = self.action_tx.try_send(AppAction::FetchPullRequests(
+            self.request,
             self.current_repo.clone(),
             self.filter_state.clone()
         ));
+    }
+    
+    /// Apply a PR list unless a newer request has been made since
+    pub fn set_pull_requests(&mut self, request: RequestId, prs: Vec<PullRequest>) {
+        if request != self.request {
+            return;
+        }
+        self.pull_requests = prs;
+        self.loading = false;
+    }
+    
+    /// Apply a polled refresh of the open PRs when the panel is showing exactly those
+    pub fn apply_update(&mut self, repo: &str, prs: Vec<PullRequest>) {
+        if self.current_repo == repo && self.filter_state == "open" && !self.loading {
+            self.pull_requests = prs;
+        }
+    }
+    
+    pub fn on_pr_merged(&mut self, _result: MergeResult) {
+        // Refresh the list after merge
+        self.fetch_pull_requests();
         self.selected_pr = None;
     }
     
+    /// Store check results if they belong to the currently selected PR head
+    pub fn set_checks(&mut self, sha: String, check_runs: Vec<CheckRun>, combined: CombinedStatus) {
+        if let Some(ref pr) = self.selected_pr
+            && pr.head.sha == sha
+        {
+            self.check_runs = check_runs;
+            self.combined_status = combined;
+            self.loading_checks = false;
+        }
+    }
+    
+    /// Store changed files if they belong to the currently selected PR
+    pub fn set_files(&mut self, pr_number: u32, files: Vec<ChangedFile>) {
+        if self.selected_pr.as_ref().map(|p| p.number) == Some(pr_number) {
+            self.files = files;
+            self.loading_files = false;
+        }
+    }
+    
+    /// Request the changed files of the selected PR once per selection
+    fn ensure_files_loaded(&mut self, pr_number: u32) {
+        if self.files_loaded_for != Some(pr_number) {
+            self.files_loaded_for = Some(pr_number);
+            self.files.clear();
+            self.loading_files = true;
+            let _ = self.action_tx.try_send(AppAction::FetchPullRequestFiles(
+                self.current_repo.clone(),
+                pr_number
+            ));
+        }
+    }
+    
+    /// Whether any check run or commit status reports a failure
+    fn has_failing_checks(&self) -> bool {
+        self.check_runs.iter().any(|c| matches!(
+            c.conclusion.as_deref(),
+            Some("failure") | Some("timed_out") | Some("cancelled") | Some("action_required")
+        )) || self.combined_status.statuses.iter().any(|s| s.state == "failure" || s.state == "error")
+    }
+    
+    /// Whether any check run or commit status is still running
+    fn has_pending_checks(&self) -> bool {
+        self.check_runs.iter().any(|c| c.status != "completed")
+            || self.combined_status.statuses.iter().any(|s| s.state == "pending")
+    }
+    
+    pub fn set_branches(&mut self, branches: Vec<Branch>) {
+        // Preselect the repo's default branch as base, or the usual names for it
+        if let Some(form) = &mut self.new_pr
+            && form.base.is_empty()
+            && let Some(b) = [self.default_branch.as_str(), "main", "master"].iter()
+                .find_map(|name| branches.iter().find(|b| !name.is_empty() && b.name == *name))
+        {
+            form.base = b.name.clone();
+        }
+        self.branches = branches;
+        self.loading_branches = false;
+    }
+    
+    /// The branch list request failed; stop waiting for it
+    pub fn branches_failed(&mut self) {
+        self.loading_branches = false;
+    }
+    
+    fn fetch_branches(&mut self) {
+        self.loading_branches = true;
+        let _ = self.action_tx.try_send(AppAction::FetchBranches(self.current_repo.clone()));
+    }
+    
+    /// Show the newly opened PR in the list and open its detail view
+    pub fn on_pr_created(&mut self, pr: PullRequest) {
+        self.new_pr = None;
+        self.pull_requests.insert(0, pr.clone());
+        self.select_pr(pr);
+    }
+    
+    /// Re-enable the create form after a failed request
+    pub fn create_failed(&mut self) {
+        if let Some(form) = &mut self.new_pr {
+            form.submitting = false;
+        }
+    }
+    
+    /// Open a PR by number, fetching it first if it isn't in the list
+    pub fn open_pr(&mut self, number: u32) {
+        self.new_pr = None;
+        if let Some(pr) = self.pull_requests.iter().find(|p| p.number == number).cloned() {
+            self.select_pr(pr);
+        } else {
+            self.pending_open = Some(number);
+            let _ = self.action_tx.try_send(AppAction::FetchPullRequest(self.current_repo.clone(), number));
+        }
+    }
+    
+    fn select_pr(&mut self, pr: PullRequest) {
+        self.detail_tab = 0;
+        self.check_runs.clear();
+        self.combined_status = CombinedStatus::default();
+        self.loading_checks = true;
+        let _ = self.action_tx.try_send(AppAction::FetchPullRequestChecks(
+            self.current_repo.clone(),
+            pr.head.sha.clone()
+        ));
+        // List entries lack mergeability; the single-PR endpoint computes it
+        let _ = self.action_tx.try_send(AppAction::FetchPullRequest(self.current_repo.clone(), pr.number));
+        self.selected_pr = Some(pr);
+    }
+    
+    /// Replace a PR in the list and in the detail view
+    pub fn update_pr(&mut self, pr: PullRequest) {
+        if let Some(pos) = self.pull_requests.iter().position(|p| p.number == pr.number) {
+            self.pull_requests[pos] = pr.clone();
+        }
+        if self.updating_branch == Some(pr.number) {
+            self.updating_branch = None;
+        }
+        if self.pending_open == Some(pr.number) {
+            self.pending_open = None;
+            self.select_pr(pr);
+            return;
+        }
+        if let Some(ref mut selected) = self.selected_pr
+            && selected.number == pr.number
+        {
+            // New head commit (e.g. after updating the branch): its checks are different
+            let head_changed = selected.head.sha != pr.head.sha;
+            *selected = pr;
+            if head_changed {
+                self.check_runs.clear();
+                self.combined_status = CombinedStatus::default();
+                self.loading_checks = true;
+                let _ = self.action_tx.try_send(AppAction::FetchPullRequestChecks(
+                    self.current_repo.clone(),
+                    selected.head.sha.clone()
+                ));
+            }
+        }
+    }
+    
     pub fn on_pr_closed(&mut self, pr: PullRequest) {
         // Update in list
         if let Some(pos) = self.pull_requests.iter().position(|p| p.number == pr.number) {
@@ -68,15 +277,17 @@ impl PullRequestsPanel {
         self.selected_pr = None;
     }
     
-    pub fn show(&mut self, ui: &mut egui::Ui, _i18n: &I18n) {
-        if self.selected_pr.is_some() {
-            self.show_detail(ui);
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        if self.new_pr.is_some() {
+            self.show_create_form(ui, i18n);
+        } else if self.selected_pr.is_some() {
+            self.show_detail(ui, i18n);
         } else {
-            self.show_list(ui);
+            self.show_list(ui, i18n);
         }
     }
     
-    fn show_list(&mut self, ui: &mut egui::Ui) {
+    fn show_list(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
         ui.vertical(|ui| {
             // Header
             ui.horizontal(|ui| {
@@ -93,48 +304,61 @@ impl PullRequestsPanel {
                         .fill(if is_selected { Color32::from_rgba_unmultiplied(0, 60, 80, 100) } else { Color32::TRANSPARENT })
                     ).clicked() {
                         self.filter_state = state.to_string();
-                        self.loading = true;
-                        let _ = self.action_tx.try_send(AppAction::FetchPullRequests(
-                            self.current_repo.clone(),
-                            state.to_string()
-                        ));
+                        self.fetch_pull_requests();
                     }
                 }
                 
                 if self.loading {
                     ui.spinner();
                 }
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(i18n.t("pulls.new")).clicked() {
+                        self.new_pr = Some(NewPrForm::default());
+                        self.fetch_branches();
+                    }
+                });
             });
             
             ui.separator();
             
             // PR list
-            ScrollArea::vertical().id_salt("pr_list").show(ui, |ui| {
+            let pulled = super::components::pull_to_refresh(ui, "pr_list", self.loading, |ui| {
                 ui.set_width(ui.available_width());
                 
                 if self.pull_requests.is_empty() && !self.loading {
-                    ui.colored_label(Color32::GRAY, "暂无 Pull Requests");
+                    ui.colored_label(Color32::GRAY, i18n.t("pulls.empty"));
                 }
                 
+                let mut clicked = None;
                 for pr in &self.pull_requests {
                     if self.render_pr_card(ui, pr) {
-                        self.selected_pr = Some(pr.clone());
+                        clicked = Some(pr.clone());
                     }
                     ui.add_space(4.0);
                 }
+                if let Some(pr) = clicked {
+                    self.select_pr(pr);
+                }
             });
+            if pulled {
+                self.fetch_pull_requests();
+            }
         });
     }
     
     fn render_pr_card(&self, ui: &mut egui::Ui, pr: &PullRequest) -> bool {
         let h = 65.0;
         let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+        super::components::describe(ui, &response, &format!("Pull request #{}: {} ({})", pr.number, pr.title, pr.state));
         
         let painter = ui.painter();
-        let is_hovered = response.hovered();
+        let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
         
         let bg_color = if is_hovered {
-            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
             Color32::from_rgba_unmultiplied(50, 30, 60, 180)
         } else {
             Color32::from_rgb(12, 8, 18)
@@ -159,7 +383,7 @@ impl PullRequestsPanel {
         
         // Content
         let content_rect = rect.shrink2(Vec2::new(12.0, 6.0));
-        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+        ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
                     // Title
@@ -183,12 +407,16 @@ impl PullRequestsPanel {
                     // Status badge
                     let (status_text, status_color) = if pr.merged {
                         ("MERGED", Color32::from_rgb(150, 80, 200))
+                    } else if pr.draft && pr.state == "open" {
+                        ("DRAFT", Color32::GRAY)
                     } else if pr.state == "open" {
                         ("OPEN", Color32::from_rgb(0, 200, 100))
                     } else {
                         ("CLOSED", Color32::from_rgb(200, 80, 80))
                     };
                     ui.label(RichText::new(status_text).size(10.0).color(status_color).strong());
+                    ui.add_space(8.0);
+                    avatar(ui, &pr.user.login, &pr.user.avatar_url, 20.0).on_hover_text(&pr.user.login);
                 });
             });
         });
@@ -196,29 +424,51 @@ impl PullRequestsPanel {
         response.clicked()
     }
     
-    fn show_detail(&mut self, ui: &mut egui::Ui) {
+    fn show_detail(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
         let pr = self.selected_pr.clone().unwrap();
         
         ui.vertical(|ui| {
             // Back button + title
             ui.horizontal(|ui| {
-                if CyberButton::new("← 返回").min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
+                if CyberButton::new(i18n.t("common.back")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
                     self.selected_pr = None;
                 }
                 
                 ui.add_space(10.0);
                 ui.label(RichText::new(format!("PR #{} {}", pr.number, pr.title))
                     .size(16.0).color(Color32::from_rgb(200, 100, 200)).strong());
+                if pr.draft {
+                    ui.label(RichText::new(" DRAFT ").size(10.0).color(Color32::LIGHT_GRAY)
+                        .background_color(Color32::from_rgba_unmultiplied(150, 150, 150, 40)));
+                }
+            });
+            
+            ui.separator();
+            
+            // Detail tabs: Overview | Files changed
+            ui.horizontal(|ui| {
+                if ui.selectable_label(self.detail_tab == 0, i18n.t("pulls.overview")).clicked() {
+                    self.detail_tab = 0;
+                }
+                if ui.selectable_label(self.detail_tab == 1, format!("📁 Files ({})", pr.changed_files)).clicked() {
+                    self.detail_tab = 1;
+                    self.ensure_files_loaded(pr.number);
+                }
             });
             
             ui.separator();
             
+            if self.detail_tab == 1 {
+                self.show_files(ui, i18n);
+                return;
+            }
+            
             ScrollArea::vertical().id_salt("pr_detail").show(ui, |ui| {
                 ui.set_width(ui.available_width());
                 
                 // Branch info
                 ui.group(|ui| {
-                    ui.label(RichText::new("分支信息").size(14.0).color(colors::ACCENT_DIM));
+                    ui.label(RichText::new(i18n.t("pulls.branch_info")).size(14.0).color(colors::ACCENT_DIM));
                     ui.separator();
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("Base:").color(Color32::GRAY));
@@ -233,7 +483,7 @@ impl PullRequestsPanel {
                 
                 // Stats
                 ui.group(|ui| {
-                    ui.label(RichText::new("统计").size(14.0).color(colors::ACCENT_DIM));
+                    ui.label(RichText::new(i18n.t("pulls.stats")).size(14.0).color(colors::ACCENT_DIM));
                     ui.separator();
                     ui.horizontal(|ui| {
                         ui.label(RichText::new(format!("📝 {} commits", pr.commits)).size(12.0));
@@ -247,22 +497,118 @@ impl PullRequestsPanel {
                 
                 ui.add_space(10.0);
                 
+                // Checks
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(i18n.t("pulls.checks")).size(14.0).color(colors::ACCENT_DIM));
+                        if self.loading_checks {
+                            ui.spinner();
+                        }
+                    });
+                    ui.separator();
+                    
+                    if !self.loading_checks && self.check_runs.is_empty() && self.combined_status.statuses.is_empty() {
+                        ui.colored_label(Color32::GRAY, i18n.t("pulls.no_checks"));
+                    }
+                    
+                    for run in &self.check_runs {
+                        let (icon, color) = check_icon(&run.status, run.conclusion.as_deref());
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(icon).size(12.0).color(color));
+                            match &run.html_url {
+                                Some(url) => { ui.hyperlink_to(RichText::new(&run.name).size(12.0), url); }
+                                None => { ui.label(RichText::new(&run.name).size(12.0)); }
+                            }
+                            if let Some(conclusion) = &run.conclusion {
+                                ui.label(RichText::new(conclusion).size(10.0).color(Color32::GRAY));
+                            }
+                        });
+                    }
+                    
+                    for status in &self.combined_status.statuses {
+                        let (icon, color) = status_icon(&status.state);
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(icon).size(12.0).color(color));
+                            match &status.target_url {
+                                Some(url) => { ui.hyperlink_to(RichText::new(&status.context).size(12.0), url); }
+                                None => { ui.label(RichText::new(&status.context).size(12.0)); }
+                            }
+                            if let Some(desc) = &status.description {
+                                ui.label(RichText::new(desc).size(10.0).color(Color32::GRAY));
+                            }
+                        });
+                    }
+                });
+                
+                ui.add_space(10.0);
+                
                 // Body
                 ui.group(|ui| {
-                    ui.label(RichText::new("描述").size(14.0).color(colors::ACCENT_DIM));
+                    ui.label(RichText::new(i18n.t("common.description")).size(14.0).color(colors::ACCENT_DIM));
                     ui.separator();
                     if let Some(body) = &pr.body {
-                        ui.style_mut().wrap = Some(true);
+                        ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
                         ui.label(body);
                     } else {
-                        ui.colored_label(Color32::GRAY, "(无描述)");
+                        ui.colored_label(Color32::GRAY, i18n.t("common.no_description"));
                     }
                 });
                 
                 ui.add_space(20.0);
                 
                 // Actions
-                if pr.state == "open" && !pr.merged {
+                if pr.state == "open" && !pr.merged && pr.draft {
+                    // Drafts cannot be merged until marked ready
+                    ui.label(RichText::new(i18n.t("pulls.draft_notice")).size(12.0).color(Color32::GRAY));
+                    ui.horizontal(|ui| {
+                        if CyberButton::new(i18n.t("pulls.mark_ready")).min_size(Vec2::new(140.0, 35.0)).show(ui).clicked() {
+                            let _ = self.action_tx.try_send(AppAction::MarkPullRequestReady(
+                                self.current_repo.clone(),
+                                pr.number,
+                                pr.node_id.clone()
+                            ));
+                        }
+                        
+                        ui.add_space(30.0);
+                        
+                        if CyberButton::new(i18n.t("pulls.close")).min_size(Vec2::new(100.0, 35.0)).show(ui).clicked() {
+                            let _ = self.action_tx.try_send(AppAction::ClosePullRequest(
+                                self.current_repo.clone(),
+                                pr.number
+                            ));
+                        }
+                    });
+                } else if pr.state == "open" && !pr.merged {
+                    // Mergeability as computed by GitHub
+                    let (text, color) = mergeable_label(pr.mergeable_state.as_deref());
+                    ui.label(RichText::new(i18n.t(text)).size(12.0).color(color));
+                    
+                    if pr.mergeable_state.as_deref() == Some("behind") || self.updating_branch == Some(pr.number) {
+                        ui.horizontal(|ui| {
+                            let updating = self.updating_branch == Some(pr.number);
+                            ui.add_enabled_ui(!updating, |ui| {
+                                if CyberButton::new(i18n.t("pulls.update_branch")).min_size(Vec2::new(120.0, 30.0)).show(ui).clicked() {
+                                    self.updating_branch = Some(pr.number);
+                                    let _ = self.action_tx.try_send(AppAction::UpdatePullRequestBranch(
+                                        self.current_repo.clone(),
+                                        pr.number,
+                                        pr.head.sha.clone()
+                                    ));
+                                }
+                            });
+                            if updating {
+                                ui.spinner();
+                                ui.label(RichText::new(i18n.t("pulls.updating_branch")).size(11.0).color(Color32::GRAY));
+                            }
+                        });
+                    }
+                    
+                    if self.has_failing_checks() {
+                        ui.label(RichText::new(i18n.t("pulls.checks_failing")).size(12.0).color(Color32::from_rgb(255, 80, 80)));
+                    } else if self.has_pending_checks() {
+                        ui.label(RichText::new(i18n.t("pulls.checks_pending")).size(12.0).color(Color32::from_rgb(230, 180, 0)));
+                    }
+                    
                     ui.horizontal(|ui| {
                         if CyberButton::new("🔀 Merge (merge)").min_size(Vec2::new(120.0, 35.0)).show(ui).clicked() {
                             let _ = self.action_tx.try_send(AppAction::MergePullRequest(
@@ -294,7 +640,7 @@ impl PullRequestsPanel {
                         
                         ui.add_space(30.0);
                         
-                        if CyberButton::new("❌ 关闭 PR").min_size(Vec2::new(100.0, 35.0)).show(ui).clicked() {
+                        if CyberButton::new(i18n.t("pulls.close")).min_size(Vec2::new(100.0, 35.0)).show(ui).clicked() {
                             let _ = self.action_tx.try_send(AppAction::ClosePullRequest(
                                 self.current_repo.clone(),
                                 pr.number
@@ -302,10 +648,167 @@ impl PullRequestsPanel {
                         }
                     });
                 } else {
-                    let status = if pr.merged { "已合并" } else { "已关闭" };
-                    ui.label(RichText::new(format!("此 PR {}", status)).size(14.0).color(Color32::GRAY));
+                    let status = if pr.merged { "pulls.merged_notice" } else { "pulls.closed_notice" };
+                    ui.label(RichText::new(i18n.t(status)).size(14.0).color(Color32::GRAY));
+                }
+            });
+        });
+    }
+    
+    fn show_create_form(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        let Some(form) = &mut self.new_pr else { return };
+        let mut cancel = false;
+        let mut submit = false;
+        let mut retry = false;
+        
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.back")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
+                    cancel = true;
+                }
+                ui.add_space(10.0);
+                ui.label(RichText::new(i18n.t("pulls.new_title")).size(16.0).color(Color32::from_rgb(200, 100, 200)).strong());
+            });
+            
+            ui.separator();
+            
+            if self.loading_branches {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(RichText::new(i18n.t("common.loading_branches")).color(Color32::GRAY));
+                });
+                return;
+            }
+            if self.branches.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(i18n.t("pulls.branches_failed")).color(Color32::GRAY));
+                    if ui.button(i18n.t("common.retry")).clicked() {
+                        retry = true;
+                    }
+                });
+                return;
+            }
+            
+            // Branch pickers
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Base:").color(Color32::GRAY));
+                branch_combo(ui, "new_pr_base", &mut form.base, &self.branches, i18n.t("common.pick_branch"));
+                ui.label(RichText::new("←").color(Color32::GRAY));
+                ui.label(RichText::new("Head:").color(Color32::GRAY));
+                branch_combo(ui, "new_pr_head", &mut form.head, &self.branches, i18n.t("common.pick_branch"));
+            });
+            
+            let same_branch = !form.base.is_empty() && form.base == form.head;
+            if same_branch {
+                ui.label(RichText::new(i18n.t("pulls.same_branch")).size(12.0).color(Color32::from_rgb(255, 80, 80)));
+            }
+            
+            ui.add_space(8.0);
+            ui.label(RichText::new(i18n.t("common.title")).size(12.0).color(colors::TEXT_MUTED));
+            ui.add(TextEdit::singleline(&mut form.title).desired_width(ui.available_width()));
+            
+            ui.add_space(8.0);
+            ui.label(RichText::new(i18n.t("common.description")).size(12.0).color(colors::TEXT_MUTED));
+            ui.add(TextEdit::multiline(&mut form.body)
+                .desired_width(ui.available_width())
+                .desired_rows(8)
+                .hint_text(i18n.t("pulls.body_hint")));
+            
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                let ready = !form.base.is_empty() && !form.head.is_empty() && !same_branch
+                    && !form.title.trim().is_empty() && !form.submitting;
+                ui.add_enabled_ui(ready, |ui| {
+                    if CyberButton::new(i18n.t("pulls.create")).min_size(Vec2::new(120.0, 35.0)).show(ui).clicked() {
+                        submit = true;
+                    }
+                });
+                if form.submitting {
+                    ui.spinner();
                 }
             });
         });
+        
+        if submit {
+            form.submitting = true;
+            let _ = self.action_tx.try_send(AppAction::CreatePullRequest(
+                self.current_repo.clone(),
+                form.title.trim().to_string(),
+                form.body.clone(),
+                form.head.clone(),
+                form.base.clone()
+            ));
+        }
+        if cancel {
+            self.new_pr = None;
+        }
+        if retry {
+            self.fetch_branches();
+        }
+    }
+    
+    fn show_files(&mut self, ui: &mut egui::Ui, i18n: &I18n) {
+        ScrollArea::vertical().id_salt("pr_files").show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            
+            if self.loading_files {
+                ui.spinner();
+                return;
+            }
+            
+            if self.files.is_empty() {
+                ui.colored_label(Color32::GRAY, i18n.t("pulls.no_files"));
+            }
+            
+            for file in &self.files {
+                render_changed_file(ui, i18n, file);
+            }
+        });
+    }
+}
+
+/// Icon and color for a check run based on its status/conclusion
+fn check_icon(status: &str, conclusion: Option<&str>) -> (&'static str, Color32) {
+    if status != "completed" {
+        return ("⏳", Color32::from_rgb(230, 180, 0));
     }
+    match conclusion {
+        Some("success") => ("✔", Color32::from_rgb(0, 200, 100)),
+        Some("neutral") | Some("skipped") => ("○", Color32::GRAY),
+        _ => ("✖", Color32::from_rgb(255, 80, 80)),
+    }
+}
+
+/// Message key and color for a PR's `mergeable_state`
+fn mergeable_label(state: Option<&str>) -> (&'static str, Color32) {
+    match state {
+        Some("clean") => ("pulls.mergeable_clean", Color32::from_rgb(0, 200, 100)),
+        Some("behind") => ("pulls.mergeable_behind", Color32::from_rgb(230, 180, 0)),
+        Some("dirty") => ("pulls.mergeable_dirty", Color32::from_rgb(255, 80, 80)),
+        Some("blocked") => ("pulls.mergeable_blocked", Color32::from_rgb(255, 80, 80)),
+        Some("unstable") => ("pulls.mergeable_unstable", Color32::from_rgb(230, 180, 0)),
+        Some("has_hooks") => ("pulls.mergeable_has_hooks", Color32::from_rgb(0, 200, 100)),
+        _ => ("pulls.mergeable_unknown", Color32::GRAY),
+    }
+}
+
+/// Icon and color for a legacy commit status state
+fn status_icon(state: &str) -> (&'static str, Color32) {
+    match state {
+        "success" => ("✔", Color32::from_rgb(0, 200, 100)),
+        "pending" => ("⏳", Color32::from_rgb(230, 180, 0)),
+        _ => ("✖", Color32::from_rgb(255, 80, 80)),
+    }
+}
+
+/// Branch selector used by the "New PR" form
+fn branch_combo(ui: &mut egui::Ui, id: &str, selected: &mut String, branches: &[Branch], placeholder: &str) {
+    let text = if selected.is_empty() { placeholder } else { selected.as_str() };
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text.to_string())
+        .show_ui(ui, |ui| {
+            for branch in branches {
+                ui.selectable_value(selected, branch.name.clone(), &branch.name);
+            }
+        });
 }
@@ -0,0 +1,216 @@
+//! Cross-References
+//!
+//! Turns `#123`, `owner/repo#123`, commit SHAs and bare URLs in issue and
+//! comment markdown into links, and recognises links that point back into a
+//! repository so the app can open them itself instead of the browser.
+
+/// Issue or pull request a link points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    Issue { repo: String, number: u32 }, // May still turn out to be a pull request
+    PullRequest { repo: String, number: u32 },
+}
+
+impl Reference {
+    /// `owner/name` of the repository the reference points into
+    pub fn repo(&self) -> &str {
+        match self {
+            Reference::Issue { repo, .. } | Reference::PullRequest { repo, .. } => repo,
+        }
+    }
+}
+
+/// Rewrite `markdown` so references become links. `repo` is the `owner/name`
+/// that bare `#123` and SHAs refer to. Code blocks, inline code and existing
+/// links are left untouched.
+pub fn linkify(markdown: &str, repo: &str, web_base: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut fence: Option<&str> = None;
+    
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            (None, None) => {
+                linkify_line(line, repo, web_base, &mut out);
+                continue;
+            }
+            _ => {}
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+fn linkify_line(line: &str, repo: &str, web_base: &str, out: &mut String) {
+    let mut rest = line;
+    let mut prev: Option<char> = None;
+    
+    while let Some(ch) = rest.chars().next() {
+        // Spans copied verbatim: inline code, [links](...), <autolinks> and html tags
+        if let Some(len) = verbatim_len(rest) {
+            out.push_str(&rest[..len]);
+            prev = rest[..len].chars().next_back();
+            rest = &rest[len..];
+            continue;
+        }
+        
+        if prev.is_none_or(|c| !is_word_char(c) && !"/#&@.\\".contains(c))
+            && let Some((len, link)) = reference_at(rest, repo, web_base)
+        {
+            out.push_str(&link);
+            prev = rest[..len].chars().next_back();
+            rest = &rest[len..];
+            continue;
+        }
+        
+        out.push(ch);
+        prev = Some(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+}
+
+/// Length of a span at the start of `text` that must not be rewritten
+fn verbatim_len(text: &str) -> Option<usize> {
+    if text.starts_with('`') {
+        let ticks = text.len() - text.trim_start_matches('`').len();
+        let closing = &text[..ticks];
+        return Some(text[ticks..].find(closing).map(|i| ticks + i + ticks).unwrap_or(ticks));
+    }
+    if text.starts_with("![") || text.starts_with('[') {
+        let label_end = text.find(']')?;
+        if !text[label_end..].starts_with("](") {
+            return None;
+        }
+        let close = text[label_end..].find(')')?;
+        return Some(label_end + close + 1);
+    }
+    if text.starts_with('<') && text[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+        return text.find('>').map(|i| i + 1);
+    }
+    None
+}
+
+/// Reference starting exactly at `text`: (bytes consumed, markdown link)
+fn reference_at(text: &str, repo: &str, web_base: &str) -> Option<(usize, String)> {
+    // Bare URL, minus trailing punctuation
+    if text.starts_with("https://") || text.starts_with("http://") {
+        let end = text.find(|c: char| c.is_whitespace() || c == '<').unwrap_or(text.len());
+        let url = text[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '\'', '"']);
+        return Some((url.len(), format!("<{}>", url)));
+    }
+    
+    // #123
+    if let Some(digits) = text.strip_prefix('#') {
+        let len = word_len(digits);
+        let number = digits[..len].parse::<u32>().ok()?;
+        return Some((len + 1, format!("[#{}]({}/{}/issues/{})", number, web_base, repo, number)));
+    }
+    
+    // owner/repo#123
+    let word = word_len(text);
+    if text[word..].starts_with('/') && word > 0 {
+        let name = &text[word + 1..];
+        let name_len = name.find(|c: char| !(c.is_ascii_alphanumeric() || "-_.".contains(c))).unwrap_or(name.len());
+        let after = &name[name_len..];
+        if name_len > 0 && after.starts_with('#') {
+            let digits = &after[1..];
+            let len = word_len(digits);
+            if let Ok(number) = digits[..len].parse::<u32>() {
+                let target = &text[..word + 1 + name_len];
+                let consumed = word + 1 + name_len + 1 + len;
+                return Some((consumed, format!("[{}#{}]({}/{}/issues/{})", target, number, web_base, target, number)));
+            }
+        }
+    }
+    
+    // Commit SHA: 7-40 hex digits with at least one letter and one digit
+    let sha = &text[..word];
+    let is_sha = (7..=40).contains(&sha.len())
+        && sha.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+        && sha.chars().any(|c| c.is_ascii_digit())
+        && sha.chars().any(|c| c.is_ascii_alphabetic());
+    if is_sha {
+        return Some((word, format!("[`{}`]({}/{}/commit/{})", &sha[..7], web_base, repo, sha)));
+    }
+    
+    None
+}
+
+/// Length of the leading run of word characters
+fn word_len(text: &str) -> usize {
+    text.find(|c: char| !is_word_char(c)).unwrap_or(text.len())
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Issue or pull request behind a web URL under `web_base`, if it is one
+pub fn parse_url(url: &str, web_base: &str) -> Option<Reference> {
+    let path = url.strip_prefix(web_base)?.strip_prefix('/')?;
+    let path = path.split(['#', '?']).next().unwrap_or(path);
+    let mut parts = path.split('/');
+    let (owner, name, kind, number) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    let number = number.parse::<u32>().ok()?;
+    let repo = format!("{}/{}", owner, name);
+    
+    match kind {
+        "issues" => Some(Reference::Issue { repo, number }),
+        "pull" => Some(Reference::PullRequest { repo, number }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const WEB: &str = "https://github.com";
+    
+    fn link(markdown: &str) -> String {
+        linkify(markdown, "octocat/hello", WEB)
+    }
+    
+    #[test]
+    fn links_issue_references() {
+        assert_eq!(link("Fixes #12."), "Fixes [#12](https://github.com/octocat/hello/issues/12).");
+        assert_eq!(link("See rust-lang/rust#100"), "See [rust-lang/rust#100](https://github.com/rust-lang/rust/issues/100)");
+        // Not references: anchors, entities, words ending in #
+        assert_eq!(link("a#1 &#39; C#"), "a#1 &#39; C#");
+    }
+    
+    #[test]
+    fn links_commits_and_urls() {
+        assert_eq!(
+            link("in 1a2b3c4d5e"),
+            "in [`1a2b3c4`](https://github.com/octocat/hello/commit/1a2b3c4d5e)"
+        );
+        // All digits or all letters is a number or a word, not a SHA
+        assert_eq!(link("1234567 deadbeef"), "1234567 deadbeef");
+        assert_eq!(link("see https://example.com/x."), "see <https://example.com/x>.");
+    }
+    
+    #[test]
+    fn leaves_code_and_links_alone() {
+        assert_eq!(link("`#12` and [#3](url)"), "`#12` and [#3](url)");
+        assert_eq!(link("<a href=\"#12\">x</a>"), "<a href=\"#12\">x</a>");
+        assert_eq!(link("```\n#12\n```\n#4\n"), "```\n#12\n```\n[#4](https://github.com/octocat/hello/issues/4)\n");
+    }
+    
+    #[test]
+    fn parses_web_urls() {
+        assert_eq!(
+            parse_url("https://github.com/octocat/hello/pull/7#issuecomment-1", WEB),
+            Some(Reference::PullRequest { repo: "octocat/hello".into(), number: 7 })
+        );
+        assert_eq!(
+            parse_url("https://github.com/octocat/hello/issues/12", WEB).map(|r| r.repo().to_string()),
+            Some("octocat/hello".into())
+        );
+        assert_eq!(parse_url("https://github.com/octocat/hello/commit/abc", WEB), None);
+        assert_eq!(parse_url("https://example.com/octocat/hello/issues/1", WEB), None);
+    }
+}
@@ -1,11 +1,77 @@
 use eframe::egui::{self, Color32, RichText, Sense, Stroke, Vec2};
 use crate::app_event::{AppAction, RepoData};
-use crate::i18n::I18n;
+use crate::i18n::{I18n, truncate_chars};
 use tokio::sync::mpsc::Sender;
 
+use super::components::CyberButton;
+use super::retro_modal::RetroModal;
+
+/// State of the "New Repo" form
+#[derive(Default)]
+pub struct NewRepoForm {
+    pub name: String,
+    pub description: String,
+    pub private: bool,
+    pub auto_init: bool,
+    pub submitting: bool,
+}
+
+/// Order of the repo list
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RepoSort {
+    Name,
+    Stars,
+    Updated,
+    PrivateFirst,
+}
+
+impl RepoSort {
+    const ALL: [RepoSort; 4] = [RepoSort::Updated, RepoSort::Name, RepoSort::Stars, RepoSort::PrivateFirst];
+    
+    fn label_key(&self) -> &'static str {
+        match self {
+            RepoSort::Name => "repos.sort_name",
+            RepoSort::Stars => "repos.sort_stars",
+            RepoSort::Updated => "repos.sort_updated",
+            RepoSort::PrivateFirst => "repos.sort_private",
+        }
+    }
+    
+    fn compare(&self, a: &RepoData, b: &RepoData) -> std::cmp::Ordering {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        match self {
+            RepoSort::Name => by_name(),
+            RepoSort::Stars => b.stars_count.cmp(&a.stars_count).then_with(by_name),
+            RepoSort::Updated => b.last_updated.cmp(&a.last_updated).then_with(by_name),
+            RepoSort::PrivateFirst => b.is_private.cmp(&a.is_private).then_with(by_name),
+        }
+    }
+}
+
+/// Which repositories the list shows
+#[derive(Clone, PartialEq, Eq)]
+pub enum RepoSource {
+    Mine,
+    Org(String),
+    Starred, // Repos I've starred, with unstar buttons on the cards
+}
+
+/// What was clicked on a repo card
+pub(super) enum CardAction {
+    Open,
+    TogglePin,
+    Unstar,
+}
+
 pub struct RepoBrowser {
     pub repos: Vec<RepoData>,
+    pub pinned: Vec<RepoData>, // Persisted in the config file
     pub loading: bool,
+    pub orgs: Vec<String>,
+    pub source: RepoSource,
+    pub new_repo: Option<NewRepoForm>,
+    pub filter: String, // Case-insensitive match on name and description
+    pub sort: RepoSort,
     action_tx: Sender<AppAction>,
 }
 
@@ -13,7 +79,13 @@ impl RepoBrowser {
     pub fn new(action_tx: Sender<AppAction>) -> Self {
         Self {
             repos: Vec::new(),
+            pinned: crate::config::current().pinned_repos,
             loading: false,
+            orgs: Vec::new(),
+            source: RepoSource::Mine,
+            new_repo: None,
+            filter: String::new(),
+            sort: RepoSort::Updated,
             action_tx,
         }
     }
@@ -23,10 +95,98 @@ impl RepoBrowser {
     }
     
     pub fn set_repos(&mut self, repos: Vec<RepoData>) {
+        if self.source == RepoSource::Starred {
+            return; // Late answer for the list we switched away from
+        }
+        // Keep pinned cards current with what the list reports
+        for pinned in &mut self.pinned {
+            if let Some(fresh) = repos.iter().find(|r| r.full_name == pinned.full_name) {
+                *pinned = fresh.clone();
+            }
+        }
         self.repos = repos;
         self.loading = false;
     }
 
+    pub fn set_starred(&mut self, repos: Vec<RepoData>) {
+        if self.source == RepoSource::Starred {
+            self.repos = repos;
+            self.loading = false;
+        }
+    }
+    
+    /// Drop a repo from the starred list once it is unstarred
+    pub fn unstarred(&mut self, full_name: &str) {
+        if self.source == RepoSource::Starred {
+            self.repos.retain(|r| r.full_name != full_name);
+        }
+    }
+    
+    pub fn is_pinned(&self, full_name: &str) -> bool {
+        self.pinned.iter().any(|r| r.full_name == full_name)
+    }
+    
+    /// Pin or unpin a repo and save the pin list
+    pub fn toggle_pin(&mut self, repo: RepoData) {
+        if self.is_pinned(&repo.full_name) {
+            self.pinned.retain(|r| r.full_name != repo.full_name);
+        } else {
+            self.pinned.push(repo);
+        }
+        
+        let mut config = crate::config::current();
+        config.pinned_repos = self.pinned.clone();
+        if let Err(e) = crate::config::update(config) {
+            tracing::warn!("Failed to save pinned repos: {}", e);
+        }
+    }
+
+    /// Insert a newly created repo (e.g. a fork) at the top of my repo list
+    pub fn add_repo(&mut self, repo: RepoData) {
+        if self.source == RepoSource::Mine && !self.repos.iter().any(|r| r.full_name == repo.full_name) {
+            self.repos.insert(0, repo);
+        }
+    }
+    
+    /// Drop a deleted repo from the list
+    pub fn remove_repo(&mut self, full_name: &str) {
+        self.repos.retain(|r| r.full_name != full_name);
+        if let Some(repo) = self.pinned.iter().find(|r| r.full_name == full_name).cloned() {
+            self.toggle_pin(repo);
+        }
+    }
+    
+    /// Close the create form on success, or re-enable it after a failure
+    pub fn finish_create(&mut self, success: bool) {
+        if success {
+            self.new_repo = None;
+        } else if let Some(form) = &mut self.new_repo {
+            form.submitting = false;
+        }
+    }
+    
+    pub fn set_orgs(&mut self, orgs: Vec<String>) {
+        self.orgs = orgs;
+    }
+    
+    fn matches_filter(&self, repo: &RepoData) -> bool {
+        let needle = self.filter.trim().to_lowercase();
+        needle.is_empty()
+            || repo.full_name.to_lowercase().contains(&needle)
+            || repo.description.to_lowercase().contains(&needle)
+    }
+    
+    /// Fetch repos for the currently selected owner
+    fn refresh(&mut self) {
+        self.loading = true;
+        let action = match &self.source {
+            RepoSource::Mine => AppAction::FetchRepos,
+            RepoSource::Org(org) => AppAction::FetchOrgRepos(org.clone()),
+            RepoSource::Starred => AppAction::FetchStarred,
+        };
+        let _ = self.action_tx.try_send(action);
+    }
+
     /// Returns Some(full_name) if a repo was clicked
     pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) -> Option<String> {
         let mut selected = None;
@@ -37,6 +197,10 @@ impl RepoBrowser {
             selected = self.render_list(ui, i18n);
         });
         
+        if self.new_repo.is_some() {
+            self.render_new_repo_form(ui.ctx(), i18n);
+        }
+        
         selected
     }
 
@@ -54,57 +218,197 @@ impl RepoBrowser {
                 };
                 
                 if refresh_btn.clicked() {
-                    self.loading = true;
                     // Trigger backend fetch
-                     let _ = self.action_tx.try_send(AppAction::FetchRepos);
+                    self.refresh();
+                }
+                
+                if ui.button(format!("＋ {}", i18n.t("repos.new"))).clicked() {
+                    self.new_repo = Some(NewRepoForm { auto_init: true, ..Default::default() });
+                }
+                
+                // Source selector: personal repos, starred repos or one of my organizations
+                let previous = self.source.clone();
+                let selected_text = match &self.source {
+                    RepoSource::Mine => format!("👤 {}", i18n.t("repos.mine")),
+                    RepoSource::Org(org) => format!("🏢 {}", org),
+                    RepoSource::Starred => format!("⭐ {}", i18n.t("repos.starred")),
+                };
+                egui::ComboBox::from_id_salt("repo_owner_selector")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.source, RepoSource::Mine, format!("👤 {}", i18n.t("repos.mine")));
+                        ui.selectable_value(&mut self.source, RepoSource::Starred, format!("⭐ {}", i18n.t("repos.starred")));
+                        for org in &self.orgs {
+                            ui.selectable_value(&mut self.source, RepoSource::Org(org.clone()), format!("🏢 {}", org));
+                        }
+                    });
+                    
+                if self.source != previous {
+                    self.repos.clear();
+                    self.refresh();
                 }
             });
         });
         
+        // Filter and sort, applied to the loaded list
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.filter)
+                .hint_text(i18n.t("repos.filter"))
+                .desired_width(240.0));
+            if !self.filter.is_empty() && ui.small_button("✕").clicked() {
+                self.filter.clear();
+            }
+            
+            ui.add_space(10.0);
+            egui::ComboBox::from_id_salt("repo_sort_selector")
+                .selected_text(format!("⇅ {}", i18n.t(self.sort.label_key())))
+                .show_ui(ui, |ui| {
+                    for sort in RepoSort::ALL {
+                        ui.selectable_value(&mut self.sort, sort, i18n.t(sort.label_key()));
+                    }
+                });
+        });
+        
         ui.separator();
     }
 
+    fn render_new_repo_form(&mut self, ctx: &egui::Context, i18n: &I18n) {
+        let Some(form) = &mut self.new_repo else { return };
+        let mut close = false;
+        let mut submit = false;
+        
+        RetroModal::show(ctx, &format!("＋ {}", i18n.t("repos.new")), |ui| {
+            ui.label(RichText::new(i18n.t("repos.new_name")).size(12.0).color(Color32::GRAY));
+            ui.add(egui::TextEdit::singleline(&mut form.name).desired_width(f32::INFINITY));
+            ui.add_space(6.0);
+            
+            ui.label(RichText::new(i18n.t("repos.new_description")).size(12.0).color(Color32::GRAY));
+            ui.add(egui::TextEdit::singleline(&mut form.description).desired_width(f32::INFINITY));
+            ui.add_space(6.0);
+            
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut form.private, false, format!("🌐 {}", i18n.t("repos.public")));
+                ui.radio_value(&mut form.private, true, format!("🔒 {}", i18n.t("repos.private")));
+            });
+            ui.checkbox(&mut form.auto_init, i18n.t("repos.new_readme"));
+            
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                let can_submit = !form.name.trim().is_empty() && !form.submitting;
+                ui.add_enabled_ui(can_submit, |ui| {
+                    if CyberButton::new(i18n.t("repos.create")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                        submit = true;
+                    }
+                });
+                ui.add_space(10.0);
+                if CyberButton::new(i18n.t("common.cancel")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                    close = true;
+                }
+                if form.submitting {
+                    ui.spinner();
+                }
+            });
+        });
+        
+        if submit {
+            form.submitting = true;
+            let _ = self.action_tx.try_send(AppAction::CreateRepo(
+                form.name.trim().to_string(),
+                form.description.trim().to_string(),
+                form.private,
+                form.auto_init
+            ));
+        }
+        if close {
+            self.new_repo = None;
+        }
+    }
+
     fn render_list(&mut self, ui: &mut egui::Ui, i18n: &I18n) -> Option<String> {
-        if self.loading && self.repos.is_empty() {
+        if self.loading && self.repos.is_empty() && self.pinned.is_empty() {
             ui.centered_and_justified(|ui| {
                 ui.label(i18n.t("repos.loading"));
             });
             return None;
         }
 
-        if self.repos.is_empty() {
+        if self.repos.is_empty() && self.pinned.is_empty() {
              ui.centered_and_justified(|ui| {
                 ui.label(i18n.t("repos.empty"));
             });
             return None;
         }
         
-        let mut clicked_repo = None;
+        let mut clicked = None;
         
-        egui::ScrollArea::vertical().show(ui, |ui| {
+        let pulled = super::components::pull_to_refresh(ui, "repo_list", self.loading, |ui| {
             ui.set_width(ui.available_width());
             
-            for repo in &self.repos {
-                if let Some(full_name) = self.render_repo_card(ui, repo) {
-                    clicked_repo = Some(full_name);
+            let pinned: Vec<&RepoData> = self.pinned.iter().filter(|r| self.matches_filter(r)).collect();
+            let mut rest: Vec<&RepoData> = self.repos.iter()
+                .filter(|r| !self.is_pinned(&r.full_name) && self.matches_filter(r))
+                .collect();
+            rest.sort_by(|a, b| self.sort.compare(a, b));
+            
+            if pinned.is_empty() && rest.is_empty() {
+                ui.label(RichText::new(i18n.t("repos.no_match")).color(Color32::GRAY));
+            }
+            
+            // Pinned repos first, then the rest of the list without them
+            if !pinned.is_empty() {
+                ui.label(RichText::new(format!("📌 {}", i18n.t("repos.pinned"))).size(12.0).color(Color32::GRAY));
+                for repo in pinned {
+                    if let Some(action) = self.render_repo_card(ui, repo, true, i18n) {
+                        clicked = Some((action, repo.clone()));
+                    }
+                    ui.add_space(8.0);
+                }
+                ui.separator();
+                ui.add_space(4.0);
+            }
+            
+            for repo in rest {
+                if let Some(action) = self.render_repo_card(ui, repo, false, i18n) {
+                    clicked = Some((action, repo.clone()));
                 }
                 ui.add_space(8.0);
             }
         });
+        if pulled {
+            self.refresh();
+        }
         
-        clicked_repo
+        match clicked {
+            Some((CardAction::Open, repo)) => {
+                let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.full_name.clone()));
+                Some(repo.full_name)
+            }
+            Some((CardAction::TogglePin, repo)) => {
+                self.toggle_pin(repo);
+                None
+            }
+            Some((CardAction::Unstar, repo)) => {
+                let _ = self.action_tx.try_send(AppAction::SetStarred(repo.full_name, false));
+                None
+            }
+            None => None,
+        }
     }
 
-    fn render_repo_card(&self, ui: &mut egui::Ui, repo: &RepoData) -> Option<String> {
+    fn render_repo_card(&self, ui: &mut egui::Ui, repo: &RepoData, pinned: bool, i18n: &I18n) -> Option<CardAction> {
         let h = 80.0;
         let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+        super::components::describe(ui, &response, &format!("{}{}", repo.full_name, if repo.is_private { " (private)" } else { "" }));
         
         let painter = ui.painter();
-        let is_hovered = response.hovered();
+        let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+        if response.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
         
         // Hover Effect - Cyan glow background
         let bg_color = if is_hovered {
-            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
             Color32::from_rgba_unmultiplied(0, 40, 50, 180) // Faint cyan glow
         } else {
             Color32::from_rgb(5, 8, 12) // Dark background
@@ -120,59 +424,70 @@ impl RepoBrowser {
         painter.rect_filled(rect, 4.0, bg_color);
         painter.rect_stroke(rect, 4.0, Stroke::new(1.0, border_color), eframe::egui::StrokeKind::Middle);
         
-        // Status Strip (2px vertical line on left)
+        // Status Strip (2px vertical line on the leading edge)
+        let rtl = i18n.is_rtl();
         let strip_color = if repo.is_private {
             Color32::from_rgb(255, 140, 0) // Orange for private
         } else {
             Color32::from_rgb(0, 240, 255) // Cyan for public
         };
         let strip_brightness = if is_hovered { 1.0 } else { 0.6 };
-        let strip_rect = egui::Rect::from_min_size(
-            rect.min,
-            Vec2::new(3.0, rect.height())
-        );
+        let strip_rect = super::components::leading_edge(rect, rtl, 3.0);
         painter.rect_filled(strip_rect, 0.0, strip_color.gamma_multiply(strip_brightness));
         
         // Content
         let content_rect = rect.shrink2(Vec2::new(12.0, 10.0));
-        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(content_rect), |ui| {
-            ui.horizontal(|ui| {
+        let mut pin_clicked = false;
+        let mut unstar_clicked = false;
+        ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+            super::components::row(ui, rtl, |ui| {
                 // Icon
                 let icon = if repo.is_private { "🔒" } else { "🌐" };
                 ui.label(RichText::new(icon).size(24.0));
                 
-                ui.vertical(|ui| {
+                ui.with_layout(super::components::column(rtl), |ui| {
                     // Repo name
                     ui.label(RichText::new(&repo.name).size(16.0).color(Color32::WHITE).strong());
                     
                     // Description (truncated)
-                    let desc = if repo.description.len() > 60 {
-                        format!("{}...", &repo.description[..60])
-                    } else {
-                        repo.description.clone()
-                    };
-                    ui.label(RichText::new(desc).size(11.0).color(Color32::GRAY));
+                    ui.label(RichText::new(truncate_chars(&repo.description, 60)).size(11.0).color(Color32::GRAY));
                 });
                 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.with_layout(super::components::trailing(rtl), |ui| {
+                    pin_clicked = pin_button(ui, pinned, i18n).clicked();
+                    if self.source == RepoSource::Starred {
+                        let unstar = egui::Button::new(RichText::new("★").color(Color32::from_rgb(255, 215, 0))).frame(false);
+                        unstar_clicked = ui.add(unstar).on_hover_text(i18n.t("repos.unstar")).clicked();
+                    }
+                    
                     // Updated time
-                    ui.label(RichText::new(&repo.last_updated).size(10.0).color(Color32::from_rgb(0, 180, 200)).italics());
+                    ui.label(RichText::new(i18n.relative_time(&repo.last_updated)).size(10.0).color(Color32::from_rgb(0, 180, 200)).italics())
+                        .on_hover_text(i18n.local_time(&repo.last_updated));
                     
                     ui.add_space(10.0);
                     
                     // Stars & Forks
-                    ui.label(RichText::new(format!("⭐ {}", repo.stars_count)).size(10.0).color(Color32::from_rgb(255, 215, 0)));
-                    ui.label(RichText::new(format!("🍴 {}", repo.forks_count)).size(10.0).color(Color32::GRAY));
+                    ui.label(RichText::new(format!("⭐ {}", i18n.count(repo.stars_count))).size(10.0).color(Color32::from_rgb(255, 215, 0)));
+                    ui.label(RichText::new(format!("🍴 {}", i18n.count(repo.forks_count))).size(10.0).color(Color32::GRAY));
                 });
             });
         });
         
-        // Handle click - return the full_name for file browsing
-        if response.clicked() {
-            let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.full_name.clone()));
-            return Some(repo.full_name.clone());
+        if pin_clicked {
+            Some(CardAction::TogglePin)
+        } else if unstar_clicked {
+            Some(CardAction::Unstar)
+        } else if response.clicked() {
+            Some(CardAction::Open)
+        } else {
+            None
         }
-        
-        None
     }
 }
+
+/// Pin toggle shown on repo cards; bright when pinned
+pub(super) fn pin_button(ui: &mut egui::Ui, pinned: bool, i18n: &I18n) -> egui::Response {
+    let color = if pinned { Color32::from_rgb(0, 240, 255) } else { Color32::from_rgb(70, 70, 70) };
+    ui.add(egui::Button::new(RichText::new("📌").color(color)).frame(false))
+        .on_hover_text(i18n.t(if pinned { "repos.unpin" } else { "repos.pin" }))
+}
@@ -0,0 +1,180 @@
+//! Repository Tree UI Component
+//!
+//! Expandable file tree shown next to the file viewer. Folders load their
+//! children on first expand and keep them until the repo or ref changes, so
+//! the whole structure stays visible while reading a file.
+
+use std::collections::{HashMap, HashSet};
+use eframe::egui::{self, RichText};
+use crate::app_event::{AppAction, FileNode};
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+
+use super::file_browser::{file_icon, parent_path};
+
+/// Indentation per tree level
+const INDENT: f32 = 14.0;
+
+/// Lazily loaded tree of one repository at one ref
+pub struct RepoTree {
+    repo: String,
+    git_ref: String,
+    children: HashMap<String, Vec<FileNode>>, // Directory path ("" = root) -> sorted listing
+    expanded: HashSet<String>,
+    loading: HashSet<String>,
+    prefetched: HashSet<String>, // Folders whose listing was requested on hover
+    action_tx: Sender<AppAction>,
+}
+
+/// What the user did to the tree during one frame
+#[derive(Default)]
+struct TreeInput {
+    clicked: Option<FileNode>, // File to open
+    toggled: Option<String>, // Folder to expand or collapse
+    hovered: Option<String>, // Folder the pointer rests on
+}
+
+impl RepoTree {
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            repo: String::new(),
+            git_ref: String::new(),
+            children: HashMap::new(),
+            expanded: HashSet::new(),
+            loading: HashSet::new(),
+            prefetched: HashSet::new(),
+            action_tx,
+        }
+    }
+    
+    /// Switch to another repo or ref, dropping everything cached for the old one
+    pub fn set_source(&mut self, repo: &str, git_ref: &str) {
+        if self.repo != repo || self.git_ref != git_ref {
+            self.repo = repo.to_string();
+            self.git_ref = git_ref.to_string();
+            self.children.clear();
+            self.expanded.clear();
+            self.loading.clear();
+            self.prefetched.clear();
+        }
+    }
+    
+    /// Cache the listing of `path`; responses for another repo are ignored
+    pub fn insert(&mut self, repo: &str, path: String, mut files: Vec<FileNode>) {
+        if repo != self.repo {
+            return;
+        }
+        files.sort_by(|a, b| {
+            match (&a.node_type[..], &b.node_type[..]) {
+                ("dir", "file") => std::cmp::Ordering::Less,
+                ("file", "dir") => std::cmp::Ordering::Greater,
+                _ => a.name.cmp(&b.name),
+            }
+        });
+        self.loading.remove(&path);
+        self.children.insert(path, files);
+    }
+    
+    /// Listing of `path` if it has been loaded
+    pub fn listing(&self, path: &str) -> Option<&Vec<FileNode>> {
+        self.children.get(path)
+    }
+    
+    /// Expand every folder on the way to `path` so it is visible
+    pub fn reveal(&mut self, path: &str) {
+        let mut current = path.to_string();
+        while !current.is_empty() {
+            self.expanded.insert(current.clone());
+            current = parent_path(&current);
+        }
+    }
+    
+    /// Render the tree; returns a file the user clicked
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n, open_file: Option<&str>) -> Option<FileNode> {
+        let mut input = TreeInput::default();
+        
+        if self.children.contains_key("") {
+            self.show_dir(ui, i18n, "", 0, open_file, &mut input);
+        } else {
+            ui.spinner();
+        }
+        let TreeInput { clicked, toggled, hovered } = input;
+        
+        // Load a folder the pointer rests on, so expanding it is instant
+        if let Some(path) = hovered.filter(|path| !self.children.contains_key(path) && !self.prefetched.contains(path)) {
+            self.prefetched.insert(path.clone());
+            let _ = self.action_tx.try_send(AppAction::PrefetchTreeDir(self.repo.clone(), path, self.git_ref.clone()));
+        }
+        
+        if let Some(path) = toggled {
+            if !self.expanded.remove(&path) {
+                self.expanded.insert(path);
+            } else {
+                self.loading.remove(&path);
+            }
+        }
+        
+        // Fetch any expanded folder that has no listing yet
+        let missing: Vec<String> = self.expanded.iter()
+            .filter(|path| !self.children.contains_key(*path) && !self.loading.contains(*path))
+            .cloned()
+            .collect();
+        for path in missing {
+            self.loading.insert(path.clone());
+            let _ = self.action_tx.try_send(AppAction::FetchTreeDir(self.repo.clone(), path, self.git_ref.clone()));
+        }
+        
+        clicked
+    }
+    
+    fn show_dir(
+        &self,
+        ui: &mut egui::Ui,
+        i18n: &I18n,
+        path: &str,
+        depth: usize,
+        open_file: Option<&str>,
+        input: &mut TreeInput,
+    ) {
+        let Some(entries) = self.children.get(path) else { return };
+        
+        for entry in entries {
+            let is_dir = entry.node_type == "dir";
+            let is_expanded = is_dir && self.expanded.contains(&entry.path);
+            let label = if is_dir {
+                format!("{} 📁 {}", if is_expanded { "▾" } else { "▸" }, entry.name)
+            } else {
+                format!("   {} {}", file_icon(&entry.name), entry.name)
+            };
+            let selected = open_file == Some(entry.path.as_str());
+            
+            let response = ui.horizontal(|ui| {
+                ui.add_space(depth as f32 * INDENT);
+                let response = ui.selectable_label(selected, RichText::new(label).size(12.0));
+                if is_dir { response } else { response.on_hover_text(i18n.file_size(entry.size)) }
+            }).inner;
+            
+            if is_dir && !is_expanded && super::components::hover_intent(ui, &response) {
+                input.hovered = Some(entry.path.clone());
+            }
+            if response.clicked() {
+                if is_dir {
+                    input.toggled = Some(entry.path.clone());
+                } else {
+                    input.clicked = Some(entry.clone());
+                }
+            }
+            
+            if is_expanded {
+                if self.children.contains_key(&entry.path) {
+                    self.show_dir(ui, i18n, &entry.path, depth + 1, open_file, input);
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.add_space((depth + 1) as f32 * INDENT);
+                        ui.spinner();
+                    });
+                }
+            }
+        }
+    }
+}
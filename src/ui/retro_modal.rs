@@ -15,7 +15,7 @@ impl RetroModal {
             .interactable(true) // Block interaction with below layers
             .show(ctx, |ui| {
                 ui.painter().rect_filled(
-                    ctx.screen_rect(),
+                    ctx.content_rect(),
                     0.0,
                     Color32::from_black_alpha(200)
                 );
@@ -73,7 +73,7 @@ impl RetroModal {
                 );
                 
                 // Title Text
-                ui.allocate_new_ui(eframe::egui::UiBuilder::new().max_rect(title_rect), |ui| {
+                ui.scope_builder(eframe::egui::UiBuilder::new().max_rect(title_rect), |ui| {
                     ui.centered_and_justified(|ui| {
                         ui.label(
                             RichText::new(title)
@@ -90,7 +90,7 @@ impl RetroModal {
                     rect.max - Vec2::new(20.0, 20.0)
                 );
                 
-                ui.allocate_new_ui(eframe::egui::UiBuilder::new().max_rect(content_rect), |ui| {
+                ui.scope_builder(eframe::egui::UiBuilder::new().max_rect(content_rect), |ui| {
                     ui.vertical(|ui| {
                         content(ui);
                     });
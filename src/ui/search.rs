@@ -3,18 +3,24 @@
 //! Provides search functionality for finding repositories on GitHub.
 
 use eframe::egui::{self, Color32, RichText, ScrollArea, Sense, Stroke, Vec2};
-use crate::app_event::{AppAction, SearchRepoItem};
-use crate::i18n::I18n;
+use crate::app_event::{AppAction, RepoData, SearchRepoItem};
+use crate::config::SavedSearch;
+use crate::i18n::{I18n, truncate_chars};
 use tokio::sync::mpsc::Sender;
+use std::collections::HashMap;
 
 use super::style::colors;
-use super::components::CyberButton;
+use super::components::{CyberButton, avatar, save_as_menu};
+use super::repo_browser::{CardAction, pin_button};
 
 /// Search panel state
 pub struct SearchPanel {
     pub query: String,
     pub results: Vec<SearchRepoItem>,
     pub searching: bool,
+    pub pin_request: Option<RepoData>, // Result whose pin was toggled, handled by the repo browser
+    pub save_request: Option<SavedSearch>, // Query to add to the sidebar's saved searches
+    save_name: String,
     action_tx: Sender<AppAction>,
 }
 
@@ -24,23 +30,33 @@ impl SearchPanel {
             query: String::new(),
             results: Vec::new(),
             searching: false,
+            pin_request: None,
+            save_request: None,
+            save_name: String::new(),
             action_tx,
         }
     }
     
+    /// Search for `query` as if it had been typed in
+    pub fn run(&mut self, query: String) {
+        self.query = query.clone();
+        self.searching = true;
+        let _ = self.action_tx.try_send(AppAction::SearchRepos(query));
+    }
+    
     pub fn set_results(&mut self, results: Vec<SearchRepoItem>) {
         self.results = results;
         self.searching = false;
     }
     
     /// Show the search panel. Returns Some(full_name) if a repo was clicked.
-    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n) -> Option<String> {
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n, starred: &HashMap<String, bool>, is_pinned: impl Fn(&str) -> bool) -> Option<String> {
         let mut selected = None;
         
         ui.vertical(|ui| {
             // Search Header
             ui.horizontal(|ui| {
-                ui.label(RichText::new("🔍 搜索仓库").size(18.0).color(colors::ACCENT).strong());
+                ui.label(RichText::new(i18n.t("search.title")).size(18.0).color(colors::ACCENT).strong());
             });
             
             ui.add_space(10.0);
@@ -49,38 +65,44 @@ impl SearchPanel {
             ui.horizontal(|ui| {
                 // Input field with cyberpunk styling
                 let input = egui::TextEdit::singleline(&mut self.query)
-                    .hint_text("输入关键词搜索 (如: rust async)")
+                    .hint_text(i18n.t("search.hint"))
                     .desired_width(ui.available_width() - 100.0)
                     .font(egui::FontId::proportional(14.0));
                 
                 let response = ui.add(input);
                 
                 // Search on Enter key
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if !self.query.trim().is_empty() {
-                        self.searching = true;
-                        let _ = self.action_tx.try_send(AppAction::SearchRepos(self.query.clone()));
-                    }
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && !self.query.trim().is_empty()
+                {
+                    self.searching = true;
+                    let _ = self.action_tx.try_send(AppAction::SearchRepos(self.query.clone()));
                 }
                 
                 // Search button
                 if self.searching {
                     ui.spinner();
                 } else {
-                    if CyberButton::new("搜索").min_size(Vec2::new(80.0, 30.0)).show(ui).clicked() {
-                        if !self.query.trim().is_empty() {
-                            self.searching = true;
-                            let _ = self.action_tx.try_send(AppAction::SearchRepos(self.query.clone()));
-                        }
+                    if CyberButton::new(i18n.t("search.button")).min_size(Vec2::new(80.0, 30.0)).show(ui).clicked()
+                        && !self.query.trim().is_empty()
+                    {
+                        self.searching = true;
+                        let _ = self.action_tx.try_send(AppAction::SearchRepos(self.query.clone()));
                     }
                 }
+                
+                if !self.query.trim().is_empty()
+                    && let Some(name) = save_as_menu(ui, i18n, &mut self.save_name, i18n.t("search.save"))
+                {
+                    self.save_request = Some(SavedSearch::Repos { name, query: self.query.trim().to_string() });
+                }
             });
             
             ui.separator();
             
             // Results count
             if !self.results.is_empty() {
-                ui.label(RichText::new(format!("找到 {} 个结果", self.results.len()))
+                ui.label(RichText::new(i18n.tf("search.found", &[&self.results.len()]))
                     .size(12.0).color(colors::TEXT_MUTED));
                 ui.add_space(5.0);
             }
@@ -89,95 +111,119 @@ impl SearchPanel {
             ScrollArea::vertical().id_salt("search_results").show(ui, |ui| {
                 ui.set_width(ui.available_width());
                 
+                let mut clicked = None;
                 for repo in &self.results {
-                    if let Some(full_name) = self.render_search_result(ui, repo) {
-                        selected = Some(full_name);
+                    let pinned = is_pinned(&repo.full_name);
+                    if let Some(action) = render_repo_result(ui, repo, starred.get(&repo.full_name).copied(), pinned, i18n) {
+                        clicked = Some((action, repo.clone()));
                     }
                     ui.add_space(6.0);
                 }
                 
+                match clicked {
+                    Some((CardAction::Open, repo)) => {
+                        let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.full_name.clone()));
+                        selected = Some(repo.full_name);
+                    }
+                    Some((CardAction::TogglePin, repo)) => {
+                        self.pin_request = Some(repo.into());
+                    }
+                    Some((CardAction::Unstar, _)) | None => {}
+                }
+                
                 if self.results.is_empty() && !self.searching && !self.query.is_empty() {
-                    ui.colored_label(Color32::GRAY, "无搜索结果");
+                    ui.colored_label(Color32::GRAY, i18n.t("search.empty"));
                 }
             });
         });
         
         selected
     }
+}
     
-    fn render_search_result(&self, ui: &mut egui::Ui, repo: &SearchRepoItem) -> Option<String> {
-        let h = 70.0;
-        let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+/// Repo card used for search and trending results
+pub(super) fn render_repo_result(ui: &mut egui::Ui, repo: &SearchRepoItem, is_starred: Option<bool>, pinned: bool, i18n: &I18n) -> Option<CardAction> {
+    let h = 70.0;
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), h), Sense::click());
+    super::components::describe(ui, &response, &repo.full_name);
         
-        let painter = ui.painter();
-        let is_hovered = response.hovered();
+    let painter = ui.painter();
+    let is_hovered = response.hovered() || response.has_focus(); // Keyboard focus looks like hover
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
         
-        // Hover effect
-        let bg_color = if is_hovered {
-            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-            Color32::from_rgba_unmultiplied(0, 50, 60, 180)
-        } else {
-            Color32::from_rgb(8, 12, 18)
-        };
+    // Hover effect
+    let bg_color = if is_hovered {
+        Color32::from_rgba_unmultiplied(0, 50, 60, 180)
+    } else {
+        Color32::from_rgb(8, 12, 18)
+    };
         
-        let border_color = if is_hovered {
-            colors::ACCENT
-        } else {
-            Color32::from_rgb(0, 60, 60)
-        };
+    let border_color = if is_hovered {
+        colors::ACCENT
+    } else {
+        Color32::from_rgb(0, 60, 60)
+    };
         
-        // Background
-        painter.rect_filled(rect, 4.0, bg_color);
-        painter.rect_stroke(rect, 4.0, Stroke::new(1.0, border_color), egui::StrokeKind::Middle);
+    // Background
+    painter.rect_filled(rect, 4.0, bg_color);
+    painter.rect_stroke(rect, 4.0, Stroke::new(1.0, border_color), egui::StrokeKind::Middle);
         
-        // Status strip
-        let strip_color = if repo.is_private {
-            Color32::from_rgb(255, 140, 0)
-        } else {
-            colors::ACCENT
-        };
-        let strip_rect = egui::Rect::from_min_size(rect.min, Vec2::new(3.0, rect.height()));
-        painter.rect_filled(strip_rect, 0.0, strip_color.gamma_multiply(if is_hovered { 1.0 } else { 0.6 }));
+    // Status strip
+    let rtl = i18n.is_rtl();
+    let strip_color = if repo.is_private {
+        Color32::from_rgb(255, 140, 0)
+    } else {
+        colors::ACCENT
+    };
+    let strip_rect = super::components::leading_edge(rect, rtl, 3.0);
+    painter.rect_filled(strip_rect, 0.0, strip_color.gamma_multiply(if is_hovered { 1.0 } else { 0.6 }));
         
-        // Content
-        let content_rect = rect.shrink2(Vec2::new(12.0, 8.0));
-        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(content_rect), |ui| {
-            ui.horizontal(|ui| {
-                ui.vertical(|ui| {
-                    // Repo full name
-                    ui.label(RichText::new(&repo.full_name).size(14.0).color(Color32::WHITE).strong());
+    // Content
+    let content_rect = rect.shrink2(Vec2::new(12.0, 8.0));
+    let mut pin_clicked = false;
+    ui.scope_builder(egui::UiBuilder::new().max_rect(content_rect), |ui| {
+        super::components::row(ui, rtl, |ui| {
+            avatar(ui, &repo.owner.login, &repo.owner.avatar_url, 32.0);
+                
+            ui.with_layout(super::components::column(rtl), |ui| {
+                // Repo full name
+                ui.label(RichText::new(&repo.full_name).size(14.0).color(Color32::WHITE).strong());
                     
-                    // Description (truncated)
-                    if let Some(desc) = &repo.description {
-                        let desc_text = if desc.len() > 80 {
-                            format!("{}...", &desc[..80])
-                        } else {
-                            desc.clone()
-                        };
-                        ui.label(RichText::new(desc_text).size(11.0).color(Color32::GRAY));
-                    }
-                });
+                // Description (truncated)
+                if let Some(desc) = &repo.description {
+                    ui.label(RichText::new(truncate_chars(desc, 80)).size(11.0).color(Color32::GRAY));
+                }
+            });
                 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    // Stats
-                    ui.label(RichText::new(format!("🍴 {}", repo.forks_count)).size(10.0).color(Color32::GRAY));
-                    ui.add_space(10.0);
-                    ui.label(RichText::new(format!("⭐ {}", repo.stargazers_count)).size(10.0).color(Color32::from_rgb(255, 215, 0)));
+            ui.with_layout(super::components::trailing(rtl), |ui| {
+                pin_clicked = pin_button(ui, pinned, i18n).clicked();
                     
-                    // Language
-                    if let Some(lang) = &repo.language {
-                        ui.add_space(10.0);
-                        ui.label(RichText::new(lang).size(10.0).color(colors::ACCENT_DIM));
-                    }
-                });
+                // Stats
+                ui.label(RichText::new(format!("🍴 {}", i18n.count(repo.forks_count))).size(10.0).color(Color32::GRAY));
+                ui.add_space(10.0);
+                ui.label(RichText::new(format!("⭐ {}", i18n.count(repo.stargazers_count))).size(10.0).color(Color32::from_rgb(255, 215, 0)));
+                
+                // Whether I've starred it
+                if is_starred == Some(true) {
+                    ui.label(RichText::new("★").size(12.0).color(Color32::from_rgb(255, 215, 0)));
+                }
+                
+                // Language
+                if let Some(lang) = &repo.language {
+                    ui.add_space(10.0);
+                    ui.label(RichText::new(lang).size(10.0).color(colors::ACCENT_DIM));
+                }
             });
         });
+    });
         
-        if response.clicked() {
-            let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.full_name.clone()));
-            return Some(repo.full_name.clone());
-        }
-        
+    if pin_clicked {
+        Some(CardAction::TogglePin)
+    } else if response.clicked() {
+        Some(CardAction::Open)
+    } else {
         None
     }
 }
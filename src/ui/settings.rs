@@ -0,0 +1,149 @@
+//! Settings UI Component
+//!
+//! Modal for editing the settings persisted in the config file. Everything
+//! applies as soon as it is saved: language, theme, engine and refresh
+//! interval right away, the API endpoint and page size to new requests.
+
+use eframe::egui::{self, Color32, RichText, Vec2};
+use crate::config::{self, Config, Effects, Theme};
+use crate::engine::EngineKind;
+use crate::i18n::{I18n, Lang};
+
+use super::components::CyberButton;
+use super::retro_modal::RetroModal;
+
+/// Editable copy of the settings
+pub struct SettingsModal {
+    draft: Config,
+    enterprise_url: String,
+    error: Option<String>,
+    previous: Option<Config>, // Settings replaced by a successful save
+}
+
+impl SettingsModal {
+    pub fn open() -> Self {
+        let draft = config::current();
+        Self {
+            enterprise_url: draft.enterprise_url.clone().unwrap_or_default(),
+            draft,
+            error: None,
+            previous: None,
+        }
+    }
+    
+    /// The settings in effect before the modal saved, None if it was cancelled
+    pub fn previous(&self) -> Option<&Config> {
+        self.previous.as_ref()
+    }
+    
+    /// Returns true when the modal should be closed
+    pub fn show(&mut self, ctx: &egui::Context, i18n: &I18n) -> bool {
+        let mut close = false;
+        
+        RetroModal::show(ctx, "⚙ SETTINGS", |ui| {
+            egui::Grid::new("settings_grid").num_columns(2).spacing([16.0, 10.0]).show(ui, |ui| {
+                // Also in English, so a wrong language can still be found and undone
+                if i18n.lang == Lang::En {
+                    ui.label(i18n.t("settings.language"));
+                } else {
+                    ui.label(format!("{} / Language", i18n.t("settings.language")));
+                }
+                egui::ComboBox::from_id_salt("settings_lang")
+                    .selected_text(self.draft.lang().name())
+                    .show_ui(ui, |ui| {
+                        for lang in Lang::all() {
+                            ui.selectable_value(&mut self.draft.language, Some(*lang), lang.name());
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(i18n.t("settings.theme"));
+                egui::ComboBox::from_id_salt("settings_theme")
+                    .selected_text(self.draft.theme.name())
+                    .show_ui(ui, |ui| {
+                        for theme in Theme::all() {
+                            ui.selectable_value(&mut self.draft.theme, *theme, theme.name());
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(i18n.t("settings.effects"));
+                egui::ComboBox::from_id_salt("settings_effects")
+                    .selected_text(self.draft.effects.name(i18n))
+                    .show_ui(ui, |ui| {
+                        for effects in Effects::all() {
+                            ui.selectable_value(&mut self.draft.effects, *effects, effects.name(i18n));
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(i18n.t("settings.engine"));
+                egui::ComboBox::from_id_salt("settings_engine")
+                    .selected_text(self.draft.engine.map(|e| e.label()).unwrap_or(i18n.t("settings.engine_auto")))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.draft.engine, None, i18n.t("settings.engine_auto"));
+                        for kind in [EngineKind::GhCli, EngineKind::Http] {
+                            ui.selectable_value(&mut self.draft.engine, Some(kind), kind.label());
+                        }
+                    });
+                ui.end_row();
+                
+                ui.label(i18n.t("settings.page_size"));
+                ui.add(egui::DragValue::new(&mut self.draft.page_size).range(1..=100));
+                ui.end_row();
+                
+                ui.label(i18n.t("settings.poll_interval"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.draft.poll_interval).range(0..=3600).suffix(" s"));
+                    if self.draft.poll_interval == 0 {
+                        ui.label(RichText::new(i18n.t("settings.poll_off")).size(10.0).color(Color32::GRAY));
+                    }
+                });
+                ui.end_row();
+                
+                ui.label("Enterprise URL");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.enterprise_url)
+                        .hint_text("https://github.example.com")
+                        .desired_width(260.0)
+                );
+                ui.end_row();
+            });
+            
+            ui.add_space(6.0);
+            ui.label(RichText::new(i18n.t("settings.enterprise_hint")).size(10.0).color(Color32::GRAY));
+            
+            if let Some(err) = &self.error {
+                ui.colored_label(Color32::from_rgb(255, 100, 100), err);
+            }
+            
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.save")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                    match self.save(i18n) {
+                        Ok(()) => close = true,
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+                ui.add_space(10.0);
+                if CyberButton::new(i18n.t("common.cancel")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                    close = true;
+                }
+            });
+        });
+        
+        close
+    }
+    
+    fn save(&mut self, i18n: &I18n) -> Result<(), String> {
+        let url = self.enterprise_url.trim();
+        if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(i18n.t("settings.enterprise_invalid").to_string());
+        }
+        self.draft.enterprise_url = (!url.is_empty()).then(|| url.to_string());
+        let previous = config::current();
+        config::update(self.draft.clone()).map_err(|e| i18n.tf("settings.save_failed", &[&e]))?;
+        self.previous = Some(previous);
+        Ok(())
+    }
+}
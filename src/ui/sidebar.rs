@@ -1,42 +1,138 @@
 use eframe::egui::{self, Color32, RichText};
+use crate::config::SavedSearch;
+use crate::i18n::{I18n, Lang};
+use super::components;
+use super::history::Location;
+
+/// Recent entries listed in the sidebar
+const SIDEBAR_RECENT: usize = 8;
+
+/// What was clicked in the sidebar
+pub enum SidebarAction {
+    OpenSettings,
+    OpenRecent(Location),
+    RunSaved(SavedSearch),
+    SetLanguage(Lang),
+}
 
 pub struct Sidebar {
-    pub active_tab: u8, // 0 = Issues, 1 = PRs (used in Browsing view)
+    pub active_tab: u8, // 0 = Issues, 1 = PRs, 2 = Commits, 3 = Labels, 4 = Discussions, 5 = Local git (used in Browsing view)
+    saved: Vec<SavedSearch>, // Persisted in the config file
 }
 
 impl Sidebar {
     pub fn new() -> Self {
-        Self { active_tab: 0 }
+        Self { active_tab: 0, saved: crate::config::current().saved_searches }
+    }
+    
+    /// Add a saved search, replacing one of the same name
+    pub fn add_saved(&mut self, search: SavedSearch) {
+        match self.saved.iter_mut().find(|s| s.name() == search.name()) {
+            Some(existing) => *existing = search,
+            None => self.saved.push(search),
+        }
+        self.save();
+    }
+    
+    fn save(&self) {
+        let mut config = crate::config::current();
+        config.saved_searches = self.saved.clone();
+        if let Err(e) = crate::config::update(config) {
+            tracing::warn!("Failed to save saved searches: {}", e);
+        }
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui) {
-        ui.vertical(|ui| {
+    pub fn show(&mut self, ui: &mut egui::Ui, recent: &[Location], i18n: &I18n) -> Option<SidebarAction> {
+        let mut action = None;
+        let lang = i18n.lang;
+        let rtl = lang.is_rtl();
+        ui.with_layout(components::column(rtl), |ui| {
             // App logo/title
             ui.add_space(10.0);
             ui.label(RichText::new("⚡ NativeHub").size(22.0).color(Color32::from_rgb(0, 240, 255)).strong());
-            ui.label(RichText::new("GitHub 原生客户端").size(11.0).color(Color32::GRAY));
+            ui.label(RichText::new(i18n.t("app.subtitle")).size(11.0).color(Color32::GRAY));
             ui.add_space(20.0);
             
             ui.separator();
             
             // Navigation hints
             ui.add_space(10.0);
-            ui.label(RichText::new("📂 导航").size(14.0).color(Color32::from_rgb(0, 180, 200)));
+            ui.label(RichText::new(i18n.t("sidebar.navigation")).size(14.0).color(Color32::from_rgb(0, 180, 200)));
             ui.add_space(5.0);
             
-            ui.label(RichText::new("• 主页 - 查看您的仓库").size(11.0).color(Color32::LIGHT_GRAY));
-            ui.label(RichText::new("• 搜索 - 搜索 GitHub 仓库").size(11.0).color(Color32::LIGHT_GRAY));
+            ui.label(RichText::new(i18n.t("sidebar.nav_home")).size(11.0).color(Color32::LIGHT_GRAY));
+            ui.label(RichText::new(i18n.t("sidebar.nav_search")).size(11.0).color(Color32::LIGHT_GRAY));
+            ui.label(RichText::new(i18n.t("sidebar.nav_trending")).size(11.0).color(Color32::LIGHT_GRAY));
             
             ui.add_space(20.0);
             ui.separator();
             
+            // Recently viewed repos, directories and files
+            if !recent.is_empty() {
+                ui.add_space(10.0);
+                ui.label(RichText::new(i18n.t("sidebar.recent")).size(14.0).color(Color32::from_rgb(0, 180, 200)));
+                ui.add_space(5.0);
+                
+                for location in recent.iter().take(SIDEBAR_RECENT) {
+                    let label = location.label();
+                    let response = ui.add(
+                        egui::Label::new(RichText::new(&label).size(11.0).color(Color32::LIGHT_GRAY))
+                            .truncate()
+                            .sense(egui::Sense::click())
+                    );
+                    if response.on_hover_text(&label).clicked() {
+                        action = Some(SidebarAction::OpenRecent(location.clone()));
+                    }
+                }
+                
+                ui.add_space(20.0);
+                ui.separator();
+            }
+            
+            // Saved repo searches and issue filters
+            if !self.saved.is_empty() {
+                ui.add_space(10.0);
+                ui.label(RichText::new(i18n.t("sidebar.saved")).size(14.0).color(Color32::from_rgb(0, 180, 200)));
+                ui.add_space(5.0);
+                
+                let mut remove = None;
+                for (index, search) in self.saved.iter().enumerate() {
+                    let (icon, hover) = match search {
+                        SavedSearch::Repos { query, .. } => ("🔍", query.clone()),
+                        SavedSearch::Issues { repo, state, .. } => ("📋", format!("{} · {}", repo, state)),
+                    };
+                    components::row(ui, rtl, |ui| {
+                        let response = ui.add(
+                            egui::Label::new(RichText::new(format!("{} {}", icon, search.name())).size(11.0).color(Color32::LIGHT_GRAY))
+                                .truncate()
+                                .sense(egui::Sense::click())
+                        );
+                        if response.on_hover_text(hover).clicked() {
+                            action = Some(SidebarAction::RunSaved(search.clone()));
+                        }
+                        ui.with_layout(components::trailing(rtl), |ui| {
+                            if ui.small_button("✕").on_hover_text(i18n.t("common.delete")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+                    });
+                }
+                if let Some(index) = remove {
+                    self.saved.remove(index);
+                    self.save();
+                }
+                
+                ui.add_space(20.0);
+                ui.separator();
+            }
+            
             // Quick tips
             ui.add_space(10.0);
-            ui.label(RichText::new("💡 提示").size(14.0).color(Color32::from_rgb(0, 180, 200)));
+            ui.label(RichText::new(i18n.t("sidebar.tips")).size(14.0).color(Color32::from_rgb(0, 180, 200)));
             ui.add_space(5.0);
             
-            ui.label(RichText::new("点击仓库卡片进入浏览模式").size(10.0).color(Color32::DARK_GRAY));
-            ui.label(RichText::new("右侧面板可切换 Issues/PRs").size(10.0).color(Color32::DARK_GRAY));
+            ui.label(RichText::new(i18n.t("sidebar.tip_open")).size(10.0).color(Color32::DARK_GRAY));
+            ui.label(RichText::new(i18n.t("sidebar.tip_panels")).size(10.0).color(Color32::DARK_GRAY));
             
             ui.add_space(20.0);
             ui.separator();
@@ -46,7 +142,25 @@ impl Sidebar {
                 ui.add_space(10.0);
                 ui.label(RichText::new("v0.1.0").size(10.0).color(Color32::DARK_GRAY));
                 ui.label(RichText::new("Made with Rust + egui").size(9.0).color(Color32::from_rgba_unmultiplied(100, 100, 100, 150)));
+                ui.add_space(10.0);
+                components::row(ui, rtl, |ui| {
+                    if ui.button(i18n.t("sidebar.settings")).clicked() {
+                        action = Some(SidebarAction::OpenSettings);
+                    }
+                    let mut selected = lang;
+                    egui::ComboBox::from_id_salt("sidebar_lang")
+                        .selected_text(format!("🌐 {}", lang.name()))
+                        .show_ui(ui, |ui| {
+                            for lang in Lang::all() {
+                                ui.selectable_value(&mut selected, *lang, lang.name());
+                            }
+                        });
+                    if selected != lang {
+                        action = Some(SidebarAction::SetLanguage(selected));
+                    }
+                });
             });
         });
+        action
     }
 }
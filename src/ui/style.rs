@@ -6,6 +6,10 @@
 //! - Secondary: #FF003C (Neon Red)
 
 use eframe::egui::{self, Color32, Stroke};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use crate::config::Theme;
+use crate::i18n::Lang;
 
 /// Core theme colors
 pub mod colors {
@@ -19,14 +23,21 @@ pub mod colors {
     pub const ACCENT: Color32 = Color32::from_rgb(0, 240, 255);
     /// Dimmed accent for inactive states
     pub const ACCENT_DIM: Color32 = Color32::from_rgb(0, 120, 128);
-    /// Neon red secondary
-    pub const SECONDARY: Color32 = Color32::from_rgb(255, 0, 60);
     /// Text color
     pub const TEXT: Color32 = Color32::from_rgb(220, 240, 255);
     /// Muted text
     pub const TEXT_MUTED: Color32 = Color32::from_rgb(100, 120, 140);
 }
 
+/// Apply a theme from the settings, replacing the previous one
+pub fn apply_theme(ctx: &egui::Context, theme: Theme) {
+    ctx.set_visuals(egui::Visuals::dark());
+    match theme {
+        Theme::Cyberpunk => configure_theme(ctx),
+        Theme::Terminal => super::configure_style(ctx),
+    }
+}
+
 /// Configure the full Cyberpunk theme
 pub fn configure_theme(ctx: &egui::Context) {
     let mut style = (*ctx.style()).clone();
@@ -102,50 +113,101 @@ pub fn configure_theme(ctx: &egui::Context) {
     ctx.set_style(style);
 }
 
-/// Configure fonts (called separately because it needs FontDefinitions)
-pub fn configure_fonts(ctx: &egui::Context) {
+/// CJK glyph variant a font is designed for; Han characters look different in each
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CjkScript {
+    Sc, // Simplified Chinese
+    Tc, // Traditional Chinese
+    Jp,
+    Kr, // Also the only fonts with Hangul
+}
+
+impl CjkScript {
+    fn preferred(lang: Lang) -> Self {
+        match lang {
+            Lang::ZhTw => CjkScript::Tc,
+            Lang::Ja => CjkScript::Jp,
+            Lang::Ko => CjkScript::Kr,
+            Lang::ZhCn | Lang::En | Lang::De | Lang::Fr | Lang::Es => CjkScript::Sc,
+        }
+    }
+}
+
+/// Platform-specific CJK fonts: (name, path, face index in a collection, script)
+#[cfg(target_os = "macos")]
+const CJK_FONTS: &[(&str, &str, u32, CjkScript)] = &[
+    // PingFang SC - Modern macOS Chinese font (best quality)
+    ("PingFang SC", "/System/Library/Fonts/PingFang.ttc", 0, CjkScript::Sc),
+    // Hiragino Sans GB - Available on older macOS
+    ("Hiragino Sans GB", "/System/Library/Fonts/Hiragino Sans GB.ttc", 0, CjkScript::Sc),
+    // STHeiti - Fallback Chinese font
+    ("STHeiti", "/System/Library/Fonts/STHeiti Medium.ttc", 0, CjkScript::Sc),
+    ("Heiti TC", "/System/Library/Fonts/STHeiti Light.ttc", 0, CjkScript::Tc),
+    ("Hiragino Sans", "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc", 0, CjkScript::Jp),
+    ("Apple SD Gothic Neo", "/System/Library/Fonts/AppleSDGothicNeo.ttc", 0, CjkScript::Kr),
+];
+
+#[cfg(target_os = "windows")]
+const CJK_FONTS: &[(&str, &str, u32, CjkScript)] = &[
+    ("Microsoft YaHei", "C:/Windows/Fonts/msyh.ttc", 0, CjkScript::Sc),
+    ("SimHei", "C:/Windows/Fonts/simhei.ttf", 0, CjkScript::Sc),
+    ("Microsoft JhengHei", "C:/Windows/Fonts/msjh.ttc", 0, CjkScript::Tc),
+    ("Yu Gothic", "C:/Windows/Fonts/YuGothM.ttc", 0, CjkScript::Jp),
+    ("Meiryo", "C:/Windows/Fonts/meiryo.ttc", 0, CjkScript::Jp),
+    ("Malgun Gothic", "C:/Windows/Fonts/malgun.ttf", 0, CjkScript::Kr),
+];
+
+// Noto Sans CJK collections hold the JP, KR, SC, TC and HK faces in that order
+#[cfg(target_os = "linux")]
+const CJK_FONTS: &[(&str, &str, u32, CjkScript)] = &[
+    ("Noto Sans CJK SC", "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc", 2, CjkScript::Sc),
+    ("Noto Sans CJK TC", "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc", 3, CjkScript::Tc),
+    ("Noto Sans CJK JP", "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc", 0, CjkScript::Jp),
+    ("Noto Sans CJK KR", "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc", 1, CjkScript::Kr),
+    ("WenQuanYi Micro Hei", "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc", 0, CjkScript::Sc),
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+const CJK_FONTS: &[(&str, &str, u32, CjkScript)] = &[];
+
+/// Contents of the CJK font files found on this system, read once and shared by every
+/// face and language switch
+fn cjk_font_files() -> &'static HashMap<&'static str, Vec<u8>> {
+    static FILES: OnceLock<HashMap<&'static str, Vec<u8>>> = OnceLock::new();
+    FILES.get_or_init(|| {
+        let mut files = HashMap::new();
+        for (_, font_path, _, _) in CJK_FONTS {
+            if files.contains_key(font_path) {
+                continue;
+            }
+            if let Ok(font_data) = std::fs::read(font_path) {
+                tracing::info!("Loaded CJK font file: {}", font_path);
+                files.insert(*font_path, font_data);
+            }
+        }
+        files
+    })
+}
+
+/// Configure fonts (called separately because it needs FontDefinitions), preferring
+/// the CJK fonts drawn for `lang` and keeping the others as fallbacks
+pub fn configure_fonts(ctx: &egui::Context, lang: Lang) {
     use egui::{FontData, FontDefinitions, FontFamily};
     
     let mut fonts = FontDefinitions::default();
+    let files = cjk_font_files();
+    let preferred = CjkScript::preferred(lang);
     
-    // Platform-specific CJK font paths
-    #[cfg(target_os = "macos")]
-    let cjk_font_paths: &[(&str, &str)] = &[
-        // PingFang SC - Modern macOS Chinese font (best quality)
-        ("PingFang SC", "/System/Library/Fonts/PingFang.ttc"),
-        // Hiragino Sans GB - Available on older macOS
-        ("Hiragino Sans GB", "/System/Library/Fonts/Hiragino Sans GB.ttc"),
-        // STHeiti - Fallback Chinese font
-        ("STHeiti", "/System/Library/Fonts/STHeiti Medium.ttc"),
-    ];
-    
-    #[cfg(target_os = "windows")]
-    let cjk_font_paths: &[(&str, &str)] = &[
-        ("Microsoft YaHei", "C:/Windows/Fonts/msyh.ttc"),
-        ("SimHei", "C:/Windows/Fonts/simhei.ttf"),
-    ];
-    
-    #[cfg(target_os = "linux")]
-    let cjk_font_paths: &[(&str, &str)] = &[
-        ("Noto Sans CJK SC", "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc"),
-        ("WenQuanYi Micro Hei", "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc"),
-    ];
-    
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-    let cjk_font_paths: &[(&str, &str)] = &[];
-    
-    // Collect successfully loaded CJK fonts
+    // Fonts for the language's script first, the rest in platform order
     let mut loaded_cjk_fonts: Vec<String> = Vec::new();
-    
-    for (font_name, font_path) in cjk_font_paths {
-        let path = std::path::Path::new(font_path);
-        if let Ok(font_data) = std::fs::read(path) {
-            fonts.font_data.insert(
-                font_name.to_string(),
-                FontData::from_owned(font_data).into(),
-            );
+    let ordered = CJK_FONTS.iter().filter(|font| font.3 == preferred)
+        .chain(CJK_FONTS.iter().filter(|font| font.3 != preferred));
+    for (font_name, font_path, index, _) in ordered {
+        if let Some(font_data) = files.get(font_path) {
+            let mut data = FontData::from_static(font_data);
+            data.index = *index;
+            fonts.font_data.insert(font_name.to_string(), data.into());
             loaded_cjk_fonts.push(font_name.to_string());
-            tracing::info!("Loaded CJK font: {} from {}", font_name, font_path);
         }
     }
     
@@ -156,9 +218,11 @@ pub fn configure_fonts(ctx: &egui::Context) {
         for family in [FontFamily::Proportional, FontFamily::Monospace] {
             let existing = fonts.families.entry(family).or_default();
             let mut new_list = loaded_cjk_fonts.clone();
-            new_list.extend(existing.drain(..));
+            new_list.append(existing);
             *existing = new_list;
         }
+    } else {
+        tracing::warn!("Could not load a CJK font - CJK text may display as tofu");
     }
     
     ctx.set_fonts(fonts);
@@ -0,0 +1,150 @@
+//! Topics Editor UI Component
+//!
+//! Modal for editing the topic chips of a repository. The edited set is
+//! sent back as a whole and replaces the existing topics.
+
+use eframe::egui::{self, Color32, RichText, Vec2};
+use crate::app_event::{AppAction, ErrorMessage};
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+
+use super::style::colors;
+use super::components::CyberButton;
+use super::retro_modal::RetroModal;
+
+/// GitHub allows at most 20 topics per repository
+const MAX_TOPICS: usize = 20;
+/// GitHub limits each topic to 50 characters
+const MAX_TOPIC_LEN: usize = 50;
+
+/// Editable topic list for one repository
+pub struct TopicsEditor {
+    pub full_name: String,
+    pub topics: Vec<String>,
+    pub loading: bool,
+    input: String,
+    error: Option<ErrorMessage>,
+}
+
+impl TopicsEditor {
+    /// Open the editor with the topics currently shown, and refresh them from GitHub
+    pub fn open(full_name: String, topics: Vec<String>, action_tx: &Sender<AppAction>) -> Self {
+        let _ = action_tx.try_send(AppAction::FetchTopics(full_name.clone()));
+        Self {
+            full_name,
+            topics,
+            loading: true,
+            input: String::new(),
+            error: None,
+        }
+    }
+    
+    /// Apply the fetched topics unless the user has already started editing
+    pub fn set_topics(&mut self, topics: Vec<String>) {
+        if self.loading {
+            self.topics = topics;
+            self.loading = false;
+        }
+    }
+    
+    /// Returns true when the editor should be closed
+    pub fn show(&mut self, ctx: &egui::Context, i18n: &I18n, action_tx: &Sender<AppAction>) -> bool {
+        let mut close = false;
+        
+        RetroModal::show(ctx, "🏷 EDIT TOPICS", |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&self.full_name).size(12.0).color(Color32::GRAY).monospace());
+                if self.loading {
+                    ui.spinner();
+                }
+            });
+            ui.add_space(6.0);
+            
+            // Current topics as removable chips
+            egui::ScrollArea::vertical().max_height(110.0).show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    let mut remove = None;
+                    for (i, topic) in self.topics.iter().enumerate() {
+                        let chip = RichText::new(format!(" {} ✕", topic))
+                            .size(11.0)
+                            .color(colors::ACCENT)
+                            .background_color(Color32::from_rgba_unmultiplied(0, 240, 255, 30));
+                        if ui.add(egui::Label::new(chip).sense(egui::Sense::click()))
+                            .on_hover_text(i18n.t("common.remove"))
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    }
+                    if let Some(i) = remove {
+                        self.topics.remove(i);
+                        self.loading = false;
+                    }
+                    if self.topics.is_empty() {
+                        ui.colored_label(Color32::GRAY, i18n.t("topics.empty"));
+                    }
+                });
+            });
+            
+            ui.add_space(6.0);
+            
+            // New topic input
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.input)
+                        .hint_text("new-topic")
+                        .desired_width(250.0)
+                );
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button(i18n.t("topics.add")).clicked() || submitted {
+                    self.add_input();
+                    response.request_focus();
+                }
+            });
+            
+            if let Some(err) = &self.error {
+                ui.colored_label(Color32::from_rgb(255, 100, 100), err.render(i18n));
+            }
+            
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                if CyberButton::new(i18n.t("common.save")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                    let _ = action_tx.try_send(AppAction::SetTopics(self.full_name.clone(), self.topics.clone()));
+                    close = true;
+                }
+                ui.add_space(10.0);
+                if CyberButton::new(i18n.t("common.cancel")).min_size(Vec2::new(120.0, 36.0)).show(ui).clicked() {
+                    close = true;
+                }
+            });
+        });
+        
+        close
+    }
+    
+    /// Validate and append the typed topic (GitHub: lowercase letters, digits and hyphens)
+    fn add_input(&mut self) {
+        let topic = self.input.trim().to_lowercase().replace(' ', "-");
+        if topic.is_empty() {
+            return;
+        }
+        
+        self.error = if self.topics.len() >= MAX_TOPICS {
+            Some(("topics.too_many", vec![MAX_TOPICS.to_string()]).into())
+        } else if topic.len() > MAX_TOPIC_LEN {
+            Some(("topics.too_long", vec![MAX_TOPIC_LEN.to_string()]).into())
+        } else if topic.starts_with('-') || !topic.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+            Some("topics.invalid".into())
+        } else {
+            None
+        };
+        
+        if self.error.is_none() {
+            if !self.topics.contains(&topic) {
+                self.topics.push(topic);
+            }
+            self.input.clear();
+            self.loading = false;
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Trending UI Component
+//!
+//! Most starred repositories created in the last day, week or month,
+//! optionally limited to one language. Loaded when first shown.
+
+use eframe::egui::{self, Color32, RichText, ScrollArea};
+use crate::app_event::{AppAction, RepoData, SearchRepoItem, TrendingRange};
+use crate::i18n::I18n;
+use tokio::sync::mpsc::Sender;
+use std::collections::HashMap;
+
+use super::style::colors;
+use super::repo_browser::CardAction;
+use super::search::render_repo_result;
+
+pub struct TrendingPanel {
+    range: TrendingRange,
+    language: String,
+    results: Vec<SearchRepoItem>,
+    loading: bool,
+    loaded: bool, // Fetched at least once
+    pub pin_request: Option<RepoData>, // Result whose pin was toggled, handled by the repo browser
+    action_tx: Sender<AppAction>,
+}
+
+impl TrendingPanel {
+    pub fn new(action_tx: Sender<AppAction>) -> Self {
+        Self {
+            range: TrendingRange::default(),
+            language: String::new(),
+            results: Vec::new(),
+            loading: false,
+            loaded: false,
+            pin_request: None,
+            action_tx,
+        }
+    }
+    
+    pub fn set_results(&mut self, results: Vec<SearchRepoItem>) {
+        self.results = results;
+        self.loading = false;
+    }
+    
+    fn refresh(&mut self) {
+        self.loading = true;
+        self.loaded = true;
+        let _ = self.action_tx.try_send(AppAction::FetchTrending(self.range, self.language.trim().to_string()));
+    }
+    
+    /// Show the trending list. Returns Some(full_name) if a repo was clicked.
+    pub fn show(&mut self, ui: &mut egui::Ui, i18n: &I18n, starred: &HashMap<String, bool>, is_pinned: impl Fn(&str) -> bool) -> Option<String> {
+        if !self.loaded {
+            self.refresh();
+        }
+        let mut selected = None;
+        
+        ui.vertical(|ui| {
+            ui.label(RichText::new(i18n.t("trending.title")).size(18.0).color(colors::ACCENT).strong());
+            ui.add_space(10.0);
+            
+            ui.horizontal(|ui| {
+                let mut changed = false;
+                for range in TrendingRange::all() {
+                    changed |= ui.selectable_value(&mut self.range, *range, i18n.t(range.label())).changed();
+                }
+                ui.add_space(10.0);
+                let response = ui.add(egui::TextEdit::singleline(&mut self.language)
+                    .hint_text(i18n.t("trending.language_hint"))
+                    .desired_width(120.0));
+                changed |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                
+                if self.loading {
+                    ui.spinner();
+                } else if ui.button("🔄").on_hover_text(i18n.t("common.refresh")).clicked() {
+                    changed = true;
+                }
+                if changed && !self.loading {
+                    self.refresh();
+                }
+            });
+            
+            ui.separator();
+            
+            ScrollArea::vertical().id_salt("trending_results").show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                
+                let mut clicked = None;
+                for repo in &self.results {
+                    let pinned = is_pinned(&repo.full_name);
+                    if let Some(action) = render_repo_result(ui, repo, starred.get(&repo.full_name).copied(), pinned, i18n) {
+                        clicked = Some((action, repo.clone()));
+                    }
+                    ui.add_space(6.0);
+                }
+                
+                match clicked {
+                    Some((CardAction::Open, repo)) => {
+                        let _ = self.action_tx.try_send(AppAction::SelectRepo(repo.full_name.clone()));
+                        selected = Some(repo.full_name);
+                    }
+                    Some((CardAction::TogglePin, repo)) => {
+                        self.pin_request = Some(repo.into());
+                    }
+                    Some((CardAction::Unstar, _)) | None => {}
+                }
+                
+                if self.results.is_empty() && !self.loading {
+                    ui.colored_label(Color32::GRAY, i18n.t("trending.empty"));
+                }
+            });
+        });
+        
+        selected
+    }
+}